@@ -48,6 +48,7 @@ use crate::exec::SandboxType;
 use crate::exec::process_exec_tool_call;
 use crate::exec_env::create_env;
 use crate::flags::OPENAI_STREAM_MAX_RETRIES;
+use crate::hooks::manager::GateDecision;
 use crate::hooks::manager::HookManager;
 use crate::hooks::types::LifecycleEvent;
 use crate::hooks::protocol_integration::{ProtocolEventConverter, ProtocolEventEmitter};
@@ -213,6 +214,10 @@ pub(crate) struct Session {
     rollout: Mutex<Option<crate::rollout::RolloutRecorder>>,
     state: Mutex<State>,
     codex_linux_sandbox_exe: Option<PathBuf>,
+
+    /// Lifecycle hooks for this session. `None` when the hook manager failed to
+    /// start.
+    hook_manager: Option<Arc<HookManager>>,
 }
 
 impl Session {
@@ -221,6 +226,27 @@ impl Session {
             .map(PathBuf::from)
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
+
+    /// Run the exec-before hooks for a command. Returns why the command must not
+    /// run when a required hook failed.
+    async fn exec_hook_veto(&self, sub_id: &str, call_id: &str, params: &ExecParams) -> Option<String> {
+        let hook_manager = self.hook_manager.as_ref()?;
+        let event = LifecycleEvent::ExecBefore {
+            call_id: call_id.to_string(),
+            task_id: sub_id.to_string(),
+            command: params.command.clone(),
+            cwd: params.cwd.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        match hook_manager.dispatch_event_gated(event).await {
+            Ok(GateDecision::Proceed) => None,
+            Ok(GateDecision::Abort { reason }) => Some(reason),
+            Err(e) => {
+                warn!("Failed to execute exec hooks: {}", e);
+                None
+            }
+        }
+    }
 }
 
 /// Mutable state of the agent
@@ -681,6 +707,7 @@ async fn submission_loop(
                     state: Mutex::new(state),
                     rollout: Mutex::new(rollout_recorder),
                     codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+                    hook_manager: hook_manager.clone(),
                 }));
 
                 // Trigger session start lifecycle event
@@ -1398,6 +1425,16 @@ async fn handle_container_exec_with_params(
         }
     };
 
+    if let Some(reason) = sess.exec_hook_veto(&sub_id, &call_id, &params).await {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id,
+            output: FunctionCallOutputPayload {
+                content: format!("exec command rejected by hook: {reason}"),
+                success: None,
+            },
+        };
+    }
+
     sess.notify_exec_command_begin(&sub_id, &call_id, &params)
         .await;
 
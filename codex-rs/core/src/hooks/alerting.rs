@@ -1,13 +1,25 @@
 //! Hook error reporting and alerting system.
 
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
-use std::time::{Duration, SystemTime};
-
+use std::time::Duration;
+
+use async_trait::async_trait;
+#[cfg(feature = "email")]
+use lettre::message::{Mailbox, MultiPart};
+#[cfg(feature = "email")]
+use lettre::transport::smtp::authentication::Credentials;
+#[cfg(feature = "email")]
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
+use crate::hooks::clock::{Clock, SystemClock};
+use crate::hooks::proxy::ProxySettings;
+#[cfg(feature = "email")]
+use crate::hooks::secrets::{EnvSecretProvider, SecretProvider};
 use crate::hooks::types::{HookError, LifecycleEventType};
 
 /// Comprehensive error reporting and alerting system.
@@ -21,12 +33,50 @@ pub struct AlertingSystem {
     active_alerts: Arc<RwLock<HashMap<String, ActiveAlert>>>,
     /// Alert history.
     alert_history: Arc<RwLock<VecDeque<AlertRecord>>>,
-    /// Notification channels.
-    notification_channels: Vec<Box<dyn NotificationChannel>>,
-    /// Alert sender for async processing.
-    alert_sender: mpsc::UnboundedSender<AlertEvent>,
+    /// Recent event values per rule and group, for evaluating conditions over a window.
+    rule_windows: RuleWindows,
+    /// When recent alerts were allowed to notify, per suppression key.
+    suppression_windows: SuppressionWindows,
+    /// Notification channels, shared with the alert processor.
+    notification_channels: NotificationChannels,
+    /// Alert sender for async processing, `None` once closed.
+    alert_sender: RwLock<Option<mpsc::UnboundedSender<AlertEvent>>>,
+    /// Whether the alert processing task is running.
+    processor_running: Arc<AtomicBool>,
+    /// Set when the processor is being stopped deliberately.
+    processor_shutdown: Arc<AtomicBool>,
+    /// Number of events accepted by `trigger_alert_event`.
+    events_sent: AtomicU64,
+    /// Number of events the processor has handled, for [`AlertingSystem::flush`].
+    events_processed: Arc<watch::Sender<u64>>,
+    /// Time source for alert timestamps and escalation.
+    clock: Arc<dyn Clock>,
 }
 
+/// Rule ID used for alerts raised by the alerting system about itself.
+pub const ALERT_PROCESSOR_RULE_ID: &str = "alert_processor";
+
+/// Events are tracked per rule, hook and lifecycle event type.
+type RuleWindowKey = (String, Option<String>, Option<LifecycleEventType>);
+
+/// Timestamped event values observed for each rule group.
+type RuleWindows = Arc<RwLock<HashMap<RuleWindowKey, VecDeque<(DateTime<Utc>, f64)>>>>;
+
+/// Notification times of recent alerts for each suppression key.
+type SuppressionWindows = Arc<RwLock<HashMap<RuleWindowKey, VecDeque<DateTime<Utc>>>>>;
+
+/// Registered notification channels, looked up by `channel_name()`.
+type NotificationChannels = Arc<RwLock<Vec<Arc<dyn NotificationChannel>>>>;
+
+/// Request timeout for HTTP-based notification channels.
+const NOTIFICATION_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delivery attempts for webhook notifications, including the first.
+const WEBHOOK_NOTIFICATION_MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first webhook notification retry; doubled on each further retry.
+const WEBHOOK_NOTIFICATION_RETRY_DELAY: Duration = Duration::from_millis(200);
+
 /// Configuration for the alerting system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertingConfig {
@@ -36,7 +86,7 @@ pub struct AlertingConfig {
     pub default_severity_threshold: AlertSeverity,
     /// Maximum number of alerts to keep in history.
     pub max_alert_history: usize,
-    /// Alert aggregation window.
+    /// Default window in which repeated events for a rule are grouped into one alert.
     pub aggregation_window: Duration,
     /// Whether to enable alert suppression.
     pub enable_suppression: bool,
@@ -46,6 +96,9 @@ pub struct AlertingConfig {
     pub enable_escalation: bool,
     /// Escalation timeout.
     pub escalation_timeout: Duration,
+    /// Whether to restart the alert processor after it panics.
+    #[serde(default)]
+    pub restart_processor: bool,
 }
 
 impl Default for AlertingConfig {
@@ -54,11 +107,12 @@ impl Default for AlertingConfig {
             enabled: true,
             default_severity_threshold: AlertSeverity::Warning,
             max_alert_history: 10000,
-            aggregation_window: Duration::from_minutes(5),
+            aggregation_window: Duration::from_secs(5 * 60),
             enable_suppression: true,
-            suppression_duration: Duration::from_minutes(30),
+            suppression_duration: Duration::from_secs(30 * 60),
             enable_escalation: true,
             escalation_timeout: Duration::from_hours(1),
+            restart_processor: false,
         }
     }
 }
@@ -86,6 +140,16 @@ pub struct AlertRule {
     pub escalation: Option<EscalationSettings>,
     /// Rule tags for categorization.
     pub tags: Vec<String>,
+    /// Grouping window for this rule, overriding `AlertingConfig::aggregation_window`.
+    #[serde(default)]
+    pub aggregation_window: Option<Duration>,
+}
+
+impl AlertRule {
+    /// Window in which repeated events are grouped into one alert.
+    pub fn aggregation_window(&self, config: &AlertingConfig) -> Duration {
+        self.aggregation_window.unwrap_or(config.aggregation_window)
+    }
 }
 
 /// Alert condition definition.
@@ -105,6 +169,47 @@ pub struct AlertCondition {
     pub event_type_filter: Option<LifecycleEventType>,
 }
 
+impl AlertCondition {
+    /// Whether an alert event falls under this condition.
+    pub fn matches(&self, event: &AlertEvent) -> bool {
+        let type_matches = match (&self.condition_type, &event.event_type) {
+            (AlertConditionType::ErrorRate, AlertEventType::ErrorRateExceeded) => true,
+            (AlertConditionType::ErrorRate | AlertConditionType::FailureCount, AlertEventType::HookExecutionFailed) => true,
+            (AlertConditionType::ExecutionTime, AlertEventType::HookExecutionSlow) => true,
+            (AlertConditionType::ResourceUsage, AlertEventType::ResourceUsageHigh) => true,
+            (AlertConditionType::Custom(name), AlertEventType::Custom(event_name)) => name == event_name,
+            _ => false,
+        };
+
+        type_matches
+            && self.hook_id_filter.as_ref().map_or(true, |id| event.data.hook_id.as_ref() == Some(id))
+            && self.event_type_filter.as_ref().map_or(true, |t| event.data.event_type.as_ref() == Some(t))
+    }
+
+    /// Whether the event values observed within the evaluation window trip this condition.
+    ///
+    /// Failure counts compare the number of events, error rates their mean value and
+    /// throughput its minimum; the remaining conditions compare the peak value.
+    pub fn is_tripped(&self, values: &[f64]) -> bool {
+        if values.is_empty() || values.len() < self.min_occurrences as usize {
+            return false;
+        }
+
+        let count = values.len() as f64;
+        match self.condition_type {
+            AlertConditionType::FailureCount => count >= self.threshold,
+            AlertConditionType::ErrorRate => values.iter().sum::<f64>() / count >= self.threshold,
+            AlertConditionType::LowThroughput => values.iter().copied().fold(f64::INFINITY, f64::min) < self.threshold,
+            AlertConditionType::ExecutionTime
+            | AlertConditionType::ResourceUsage
+            | AlertConditionType::QueueDepth
+            | AlertConditionType::Custom(_) => {
+                values.iter().copied().fold(f64::NEG_INFINITY, f64::max) >= self.threshold
+            }
+        }
+    }
+}
+
 /// Types of alert conditions.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AlertConditionType {
@@ -137,17 +242,40 @@ pub enum AlertSeverity {
     Critical,
 }
 
+impl AlertSeverity {
+    /// Stable name used in notifications.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Error => "error",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
 /// Alert suppression settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuppressionSettings {
-    /// Suppression duration.
+    /// Suppression duration; zero uses `AlertingConfig::suppression_duration`.
     pub duration: Duration,
     /// Maximum alerts per suppression window.
     pub max_alerts_per_window: u32,
-    /// Whether to suppress similar alerts.
+    /// Whether alerts of the rule for other hooks and event types share the limit.
     pub suppress_similar: bool,
 }
 
+impl SuppressionSettings {
+    /// Window in which at most `max_alerts_per_window` alerts send notifications.
+    pub fn window(&self, config: &AlertingConfig) -> Duration {
+        if self.duration.is_zero() {
+            config.suppression_duration
+        } else {
+            self.duration
+        }
+    }
+}
+
 /// Alert escalation settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EscalationSettings {
@@ -162,7 +290,8 @@ pub struct EscalationSettings {
 pub struct EscalationLevel {
     /// Level number.
     pub level: u32,
-    /// Escalation timeout.
+    /// Time since the alert was raised or last escalated before escalating to this level;
+    /// zero uses `AlertingConfig::escalation_timeout`.
     pub timeout: Duration,
     /// Notification channels for this level.
     pub notification_channels: Vec<String>,
@@ -170,6 +299,17 @@ pub struct EscalationLevel {
     pub severity: AlertSeverity,
 }
 
+impl EscalationLevel {
+    /// How long an unacknowledged alert waits before escalating to this level.
+    pub fn delay(&self, config: &AlertingConfig) -> Duration {
+        if self.timeout.is_zero() {
+            config.escalation_timeout
+        } else {
+            self.timeout
+        }
+    }
+}
+
 /// Active alert information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveAlert {
@@ -285,9 +425,13 @@ pub struct AlertEventData {
 }
 
 /// Notification channel trait.
+///
+/// Sending is async so HTTP-backed channels deliver on the caller's runtime
+/// instead of blocking it or spawning detached tasks whose failures are lost.
+#[async_trait]
 pub trait NotificationChannel: Send + Sync + std::fmt::Debug {
     /// Send a notification.
-    fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError>;
+    async fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError>;
     
     /// Get channel name.
     fn channel_name(&self) -> &str;
@@ -310,18 +454,47 @@ pub struct EmailNotificationChannel {
 }
 
 /// SMTP configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SmtpConfig {
     /// SMTP server host.
     pub host: String,
     /// SMTP server port.
     pub port: u16,
-    /// Username.
+    /// Username; no authentication is attempted when empty.
     pub username: String,
-    /// Password.
+    /// Password, or a secret reference such as `env:SMTP_PASSWORD`.
     pub password: String,
-    /// Whether to use TLS.
+    /// Whether to use TLS (implicit TLS on port 465, STARTTLS otherwise).
     pub use_tls: bool,
+    /// Sender address; defaults to `username`.
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Send credentials without TLS to hosts other than localhost.
+    #[serde(default)]
+    pub allow_plaintext_auth: bool,
+}
+
+impl std::fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("use_tls", &self.use_tls)
+            .field("from", &self.from)
+            .field("allow_plaintext_auth", &self.allow_plaintext_auth)
+            .finish()
+    }
+}
+
+/// Escape text for inclusion in an HTML body.
+#[cfg(feature = "email")]
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 /// Slack notification channel.
@@ -333,6 +506,8 @@ pub struct SlackNotificationChannel {
     pub webhook_url: String,
     /// Default channel.
     pub default_channel: String,
+    /// Proxy used to reach Slack.
+    pub proxy: ProxySettings,
     /// Whether enabled.
     pub enabled: bool,
 }
@@ -346,6 +521,8 @@ pub struct WebhookNotificationChannel {
     pub url: String,
     /// HTTP headers.
     pub headers: HashMap<String, String>,
+    /// Proxy used to reach the webhook.
+    pub proxy: ProxySettings,
     /// Whether enabled.
     pub enabled: bool,
 }
@@ -353,31 +530,152 @@ pub struct WebhookNotificationChannel {
 impl AlertingSystem {
     /// Create a new alerting system.
     pub fn new(config: AlertingConfig) -> Self {
-        let (alert_sender, mut alert_receiver) = mpsc::unbounded_channel();
+        Self::new_with_clock(config, SystemClock::shared())
+    }
+
+    /// Create a new alerting system that reads time from `clock`.
+    pub fn new_with_clock(config: AlertingConfig, clock: Arc<dyn Clock>) -> Self {
+        let (alert_sender, alert_receiver) = mpsc::unbounded_channel();
         
         let system = Self {
             config,
             alert_rules: Arc::new(RwLock::new(Vec::new())),
             active_alerts: Arc::new(RwLock::new(HashMap::new())),
             alert_history: Arc::new(RwLock::new(VecDeque::new())),
-            notification_channels: Vec::new(),
-            alert_sender,
+            rule_windows: Arc::new(RwLock::new(HashMap::new())),
+            suppression_windows: Arc::new(RwLock::new(HashMap::new())),
+            notification_channels: Arc::new(RwLock::new(Vec::new())),
+            alert_sender: RwLock::new(Some(alert_sender)),
+            processor_running: Arc::new(AtomicBool::new(true)),
+            processor_shutdown: Arc::new(AtomicBool::new(false)),
+            events_sent: AtomicU64::new(0),
+            events_processed: Arc::new(watch::channel(0).0),
+            clock,
         };
 
-        // Start alert processing task
-        let active_alerts = system.active_alerts.clone();
-        let alert_history = system.alert_history.clone();
-        let config = system.config.clone();
-        
+        // Start alert processing task under supervision
+        system.spawn_supervised_processor(Arc::new(tokio::sync::Mutex::new(alert_receiver)));
+
+        system
+    }
+
+    /// Run the alert processor, raising a self-alert if it stops unexpectedly and
+    /// restarting it after a panic when `restart_processor` is set.
+    fn spawn_supervised_processor(&self, receiver: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<AlertEvent>>>) {
+        let alert_rules = self.alert_rules.clone();
+        let active_alerts = self.active_alerts.clone();
+        let rule_windows = self.rule_windows.clone();
+        let suppression_windows = self.suppression_windows.clone();
+        let notification_channels = self.notification_channels.clone();
+        let config = self.config.clone();
+        let running = self.processor_running.clone();
+        let shutdown = self.processor_shutdown.clone();
+        let events_processed = self.events_processed.clone();
+        let clock = self.clock.clone();
+
         tokio::spawn(async move {
-            while let Some(event) = alert_receiver.recv().await {
-                if let Err(e) = Self::process_alert_event(event, &active_alerts, &alert_history, &config).await {
-                    tracing::error!("Failed to process alert event: {}", e);
+            loop {
+                let processor = tokio::spawn(Self::run_processor(
+                    receiver.clone(),
+                    events_processed.clone(),
+                    alert_rules.clone(),
+                    active_alerts.clone(),
+                    rule_windows.clone(),
+                    suppression_windows.clone(),
+                    notification_channels.clone(),
+                    config.clone(),
+                    clock.clone(),
+                ));
+                let outcome = processor.await;
+
+                if shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let (reason, restartable) = match &outcome {
+                    Ok(()) => ("event channel closed".to_string(), false),
+                    Err(e) if e.is_panic() => ("processor panicked".to_string(), true),
+                    Err(e) => (format!("processor task failed: {}", e), true),
+                };
+                tracing::error!("Alert processor stopped unexpectedly: {}", reason);
+                if let Err(e) = Self::insert_alert(
+                    &active_alerts,
+                    ALERT_PROCESSOR_RULE_ID,
+                    AlertSeverity::Critical,
+                    format!("Alert processor stopped unexpectedly: {}", reason),
+                    None,
+                    None,
+                    clock.now(),
+                ) {
+                    tracing::error!("Failed to raise alert processor self-alert: {}", e);
+                }
+
+                if !(restartable && config.restart_processor) {
+                    break;
                 }
+                tracing::warn!("Restarting alert processor");
             }
+
+            running.store(false, Ordering::SeqCst);
         });
+    }
 
-        system
+    /// Process alert events until the channel closes.
+    async fn run_processor(
+        receiver: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<AlertEvent>>>,
+        events_processed: Arc<watch::Sender<u64>>,
+        alert_rules: Arc<RwLock<Vec<AlertRule>>>,
+        active_alerts: Arc<RwLock<HashMap<String, ActiveAlert>>>,
+        rule_windows: RuleWindows,
+        suppression_windows: SuppressionWindows,
+        notification_channels: NotificationChannels,
+        config: AlertingConfig,
+        clock: Arc<dyn Clock>,
+    ) {
+        let mut receiver = receiver.lock().await;
+        while let Some(event) = receiver.recv().await {
+            if let Err(e) = Self::process_alert_event(
+                event,
+                &alert_rules,
+                &active_alerts,
+                &rule_windows,
+                &suppression_windows,
+                &notification_channels,
+                &config,
+                &clock,
+            ).await {
+                tracing::error!("Failed to process alert event: {}", e);
+            }
+            events_processed.send_modify(|processed| *processed += 1);
+        }
+    }
+
+    /// Whether the alert processing task is still running.
+    pub fn is_processor_running(&self) -> bool {
+        self.processor_running.load(Ordering::SeqCst)
+    }
+
+    /// Wait until every event triggered so far has been processed.
+    ///
+    /// Returns at once if the processor is not running.
+    pub async fn flush(&self) {
+        let target = self.events_sent.load(Ordering::SeqCst);
+        let mut processed = self.events_processed.subscribe();
+        while *processed.borrow_and_update() < target {
+            if !self.is_processor_running() {
+                return;
+            }
+            // Recheck periodically in case the processor stops while we wait
+            let _ = tokio::time::timeout(Duration::from_millis(50), processed.changed()).await;
+        }
+    }
+
+    /// Stop accepting alert events; the processor exits once queued events are handled.
+    pub fn close(&self) {
+        self.processor_shutdown.store(true, Ordering::SeqCst);
+        if let Ok(mut sender) = self.alert_sender.write() {
+            sender.take();
+        }
     }
 
     /// Add an alert rule.
@@ -396,9 +694,11 @@ impl AlertingSystem {
         Ok(())
     }
 
-    /// Add a notification channel.
-    pub fn add_notification_channel(&mut self, channel: Box<dyn NotificationChannel>) {
-        self.notification_channels.push(channel);
+    /// Add a notification channel; rules refer to it by its `channel_name()`.
+    pub fn add_notification_channel(&self, channel: Box<dyn NotificationChannel>) {
+        if let Ok(mut channels) = self.notification_channels.write() {
+            channels.push(Arc::from(channel));
+        }
     }
 
     /// Trigger an alert event.
@@ -407,47 +707,290 @@ impl AlertingSystem {
             return Ok(());
         }
 
-        self.alert_sender.send(event)
+        let sender = self.alert_sender.read()
+            .map_err(|e| HookError::Execution(format!("Failed to read alert sender: {}", e)))?;
+        let sender = sender.as_ref()
+            .ok_or_else(|| HookError::Execution("Alert processor is not accepting events".to_string()))?;
+        sender.send(event)
             .map_err(|e| HookError::Execution(format!("Failed to send alert event: {}", e)))?;
+        self.events_sent.fetch_add(1, Ordering::SeqCst);
 
         Ok(())
     }
 
     /// Process an alert event.
+    ///
+    /// The event is recorded against every enabled rule it matches; a rule raises an
+    /// alert only once its condition trips over the rule's aggregation window, and
+    /// further tripping events are counted on the existing alert for that group.
+    /// Newly created alerts are sent to the channels named by their rule unless the
+    /// rule's suppression limit was reached, in which case they are marked suppressed.
     async fn process_alert_event(
         event: AlertEvent,
+        alert_rules: &Arc<RwLock<Vec<AlertRule>>>,
         active_alerts: &Arc<RwLock<HashMap<String, ActiveAlert>>>,
-        alert_history: &Arc<RwLock<VecDeque<AlertRecord>>>,
+        rule_windows: &RuleWindows,
+        suppression_windows: &SuppressionWindows,
+        notification_channels: &NotificationChannels,
         config: &AlertingConfig,
+        clock: &Arc<dyn Clock>,
     ) -> Result<(), HookError> {
-        // This is a simplified implementation
-        // In a real system, you would evaluate alert rules and trigger notifications
-        
         tracing::info!("Processing alert event: {:?}", event.event_type);
-        
-        // Create a sample alert for demonstration
+
+        let matched: Vec<AlertRule> = alert_rules.read()
+            .map_err(|e| HookError::Execution(format!("Failed to read alert rules: {}", e)))?
+            .iter()
+            .filter(|rule| rule.enabled && rule.condition.matches(&event))
+            .cloned()
+            .collect();
+
+        let mut tripped = Vec::new();
+        {
+            let mut windows = rule_windows.write()
+                .map_err(|e| HookError::Execution(format!("Failed to write rule windows: {}", e)))?;
+            for rule in matched {
+                let window = rule.aggregation_window(config);
+                let key = (rule.id.clone(), event.data.hook_id.clone(), event.data.event_type);
+                let samples = windows.entry(key).or_default();
+                samples.push_back((event.timestamp, event.data.value));
+
+                let latest = samples.iter().map(|(timestamp, _)| *timestamp).max().unwrap_or(event.timestamp);
+                samples.retain(|(timestamp, _)| {
+                    latest.signed_duration_since(*timestamp).to_std().map_or(true, |age| age <= window)
+                });
+
+                let values: Vec<f64> = samples.iter().map(|(_, value)| *value).collect();
+                if rule.condition.is_tripped(&values) {
+                    tripped.push(rule);
+                }
+            }
+        }
+
+        let created = {
+            let mut created = Vec::new();
+            let mut alerts = active_alerts.write()
+                .map_err(|e| HookError::Execution(format!("Failed to write active alerts: {}", e)))?;
+            for rule in tripped {
+                let window = rule.aggregation_window(config);
+                let group = alerts.values_mut().find(|alert| {
+                    alert.rule_id == rule.id
+                        && alert.hook_id == event.data.hook_id
+                        && alert.event_type == event.data.event_type
+                        && event.timestamp.signed_duration_since(alert.triggered_at).to_std().map_or(true, |age| age <= window)
+                });
+                if let Some(alert) = group {
+                    alert.occurrence_count += 1;
+                    alert.last_updated = alert.last_updated.max(event.timestamp);
+                    continue;
+                }
+
+                let suppressed = !Self::admit_notification(suppression_windows, &rule, &event, config)?;
+                let alert_id = uuid::Uuid::new_v4().to_string();
+                let alert = ActiveAlert {
+                    id: alert_id.clone(),
+                    rule_id: rule.id.clone(),
+                    severity: rule.severity.clone(),
+                    message: format!("Alert triggered by {:?}", event.event_type),
+                    triggered_at: event.timestamp,
+                    last_updated: event.timestamp,
+                    occurrence_count: 1,
+                    status: if suppressed { AlertStatus::Suppressed } else { AlertStatus::Active },
+                    hook_id: event.data.hook_id.clone(),
+                    event_type: event.data.event_type,
+                    metadata: HashMap::new(),
+                    escalation_level: 0,
+                };
+                alerts.insert(alert_id, alert.clone());
+                if suppressed {
+                    tracing::debug!("Suppressed notifications for alert {} of rule {}", alert.id, rule.id);
+                } else {
+                    created.push((alert, rule));
+                }
+            }
+            created
+        };
+
+        for (alert, rule) in created {
+            Self::notify_channels(notification_channels, &alert, &rule.notification_channels).await;
+
+            let escalation = rule.escalation.filter(|settings| {
+                config.enable_escalation && settings.auto_escalate && !settings.levels.is_empty()
+            });
+            if let Some(settings) = escalation {
+                tokio::spawn(Self::run_escalation(
+                    alert.id,
+                    settings.levels,
+                    active_alerts.clone(),
+                    notification_channels.clone(),
+                    config.clone(),
+                    clock.clone(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Escalate an alert through `levels` while it stays unacknowledged.
+    ///
+    /// Each level waits for its delay, then raises the alert's escalation level and
+    /// severity and notifies the level's channels. Stops once the alert is
+    /// acknowledged or resolved.
+    async fn run_escalation(
+        alert_id: String,
+        levels: Vec<EscalationLevel>,
+        active_alerts: Arc<RwLock<HashMap<String, ActiveAlert>>>,
+        notification_channels: NotificationChannels,
+        config: AlertingConfig,
+        clock: Arc<dyn Clock>,
+    ) {
+        for level in levels {
+            tokio::time::sleep(level.delay(&config)).await;
+
+            let escalated = {
+                let mut alerts = match active_alerts.write() {
+                    Ok(alerts) => alerts,
+                    Err(e) => {
+                        tracing::error!("Failed to write active alerts: {}", e);
+                        return;
+                    }
+                };
+                let Some(alert) = alerts.get_mut(&alert_id) else {
+                    return;
+                };
+                if matches!(alert.status, AlertStatus::Acknowledged | AlertStatus::Resolved) {
+                    return;
+                }
+
+                alert.escalation_level = level.level;
+                alert.status = AlertStatus::Escalated;
+                if level.severity > alert.severity {
+                    alert.severity = level.severity.clone();
+                }
+                alert.last_updated = clock.now();
+                alert.clone()
+            };
+
+            tracing::warn!("Escalated alert {} to level {}", alert_id, level.level);
+            Self::notify_channels(&notification_channels, &escalated, &level.notification_channels).await;
+        }
+    }
+
+    /// Whether a new alert of `rule` may notify, recording it against the rule's
+    /// suppression limit if so.
+    fn admit_notification(
+        suppression_windows: &SuppressionWindows,
+        rule: &AlertRule,
+        event: &AlertEvent,
+        config: &AlertingConfig,
+    ) -> Result<bool, HookError> {
+        let Some(settings) = rule.suppression.as_ref().filter(|_| config.enable_suppression) else {
+            return Ok(true);
+        };
+
+        let key = if settings.suppress_similar {
+            (rule.id.clone(), None, None)
+        } else {
+            (rule.id.clone(), event.data.hook_id.clone(), event.data.event_type)
+        };
+        let window = settings.window(config);
+
+        let mut windows = suppression_windows.write()
+            .map_err(|e| HookError::Execution(format!("Failed to write suppression windows: {}", e)))?;
+        let notified = windows.entry(key).or_default();
+        notified.retain(|timestamp| {
+            event.timestamp.signed_duration_since(*timestamp).to_std().map_or(true, |age| age < window)
+        });
+
+        if notified.len() >= settings.max_alerts_per_window as usize {
+            return Ok(false);
+        }
+        notified.push_back(event.timestamp);
+        Ok(true)
+    }
+
+    /// Send an alert to each enabled registered channel named in `channel_names`.
+    ///
+    /// Delivery failures are logged rather than returned so one broken channel does
+    /// not keep the others from being notified.
+    async fn notify_channels(notification_channels: &NotificationChannels, alert: &ActiveAlert, channel_names: &[String]) {
+        if channel_names.is_empty() {
+            return;
+        }
+
+        let targets: Vec<Arc<dyn NotificationChannel>> = match notification_channels.read() {
+            Ok(channels) => channels
+                .iter()
+                .filter(|channel| channel.is_enabled() && channel_names.iter().any(|name| name == channel.channel_name()))
+                .cloned()
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to read notification channels: {}", e);
+                return;
+            }
+        };
+
+        for name in channel_names {
+            if !targets.iter().any(|channel| channel.channel_name() == name) {
+                tracing::warn!("Alert rule {} names unknown or disabled notification channel {}", alert.rule_id, name);
+            }
+        }
+
+        for channel in targets {
+            if let Err(e) = channel.send_notification(alert).await {
+                tracing::warn!(
+                    "Failed to send alert {} to notification channel {}: {}",
+                    alert.id,
+                    channel.channel_name(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Raise an alert directly, bypassing rule evaluation.
+    pub fn raise_alert(
+        &self,
+        rule_id: &str,
+        severity: AlertSeverity,
+        message: String,
+        hook_id: Option<String>,
+        event_type: Option<LifecycleEventType>,
+    ) -> Result<String, HookError> {
+        Self::insert_alert(&self.active_alerts, rule_id, severity, message, hook_id, event_type, self.clock.now())
+    }
+
+    /// Insert a new active alert and return its ID.
+    fn insert_alert(
+        active_alerts: &RwLock<HashMap<String, ActiveAlert>>,
+        rule_id: &str,
+        severity: AlertSeverity,
+        message: String,
+        hook_id: Option<String>,
+        event_type: Option<LifecycleEventType>,
+        now: DateTime<Utc>,
+    ) -> Result<String, HookError> {
         let alert_id = uuid::Uuid::new_v4().to_string();
         let alert = ActiveAlert {
             id: alert_id.clone(),
-            rule_id: "default_rule".to_string(),
-            severity: AlertSeverity::Warning,
-            message: format!("Alert triggered by {:?}", event.event_type),
-            triggered_at: event.timestamp,
-            last_updated: event.timestamp,
+            rule_id: rule_id.to_string(),
+            severity,
+            message,
+            triggered_at: now,
+            last_updated: now,
             occurrence_count: 1,
             status: AlertStatus::Active,
-            hook_id: event.data.hook_id,
-            event_type: event.data.event_type,
+            hook_id,
+            event_type,
             metadata: HashMap::new(),
             escalation_level: 0,
         };
 
-        // Add to active alerts
-        if let Ok(mut alerts) = active_alerts.write() {
-            alerts.insert(alert_id, alert);
-        }
+        active_alerts.write()
+            .map_err(|e| HookError::Execution(format!("Failed to write active alerts: {}", e)))?
+            .insert(alert_id.clone(), alert);
 
-        Ok(())
+        Ok(alert_id)
     }
 
     /// Get active alerts.
@@ -472,7 +1015,7 @@ impl AlertingSystem {
         if let Ok(mut alerts) = self.active_alerts.write() {
             if let Some(alert) = alerts.get_mut(alert_id) {
                 alert.status = AlertStatus::Acknowledged;
-                alert.last_updated = Utc::now();
+                alert.last_updated = self.clock.now();
                 alert.metadata.insert(
                     "acknowledged_by".to_string(),
                     serde_json::Value::String(acknowledged_by.to_string()),
@@ -486,6 +1029,8 @@ impl AlertingSystem {
     pub fn resolve_alert(&self, alert_id: &str, resolved_by: &str) -> Result<(), HookError> {
         if let Ok(mut alerts) = self.active_alerts.write() {
             if let Some(alert) = alerts.remove(alert_id) {
+                tracing::info!("Alert {} resolved by {}", alert_id, resolved_by);
+                let now = self.clock.now();
                 // Move to history
                 let record = AlertRecord {
                     id: alert.id,
@@ -493,8 +1038,8 @@ impl AlertingSystem {
                     severity: alert.severity,
                     message: alert.message,
                     triggered_at: alert.triggered_at,
-                    resolved_at: Some(Utc::now()),
-                    duration: Some(Utc::now().signed_duration_since(alert.triggered_at).to_std().unwrap_or(Duration::ZERO)),
+                    resolved_at: Some(now),
+                    duration: Some(now.signed_duration_since(alert.triggered_at).to_std().unwrap_or(Duration::ZERO)),
                     final_status: AlertStatus::Resolved,
                     total_occurrences: alert.occurrence_count,
                     hook_id: alert.hook_id,
@@ -552,7 +1097,7 @@ impl AlertingSystem {
             "active_alerts": active_alerts,
             "alert_history": alert_history,
             "statistics": self.get_alert_statistics()?,
-            "exported_at": Utc::now()
+            "exported_at": self.clock.now()
         });
 
         serde_json::to_string_pretty(&export_data)
@@ -560,6 +1105,13 @@ impl AlertingSystem {
     }
 }
 
+impl Drop for AlertingSystem {
+    fn drop(&mut self) {
+        // Dropping the sender closes the channel; that is an orderly stop, not a failure
+        self.processor_shutdown.store(true, Ordering::SeqCst);
+    }
+}
+
 /// Alert statistics summary.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertStatistics {
@@ -578,15 +1130,156 @@ pub struct AlertStatistics {
 }
 
 // Notification channel implementations
+impl EmailNotificationChannel {
+    /// SMTP transport for the configured server, authenticating when a username is set.
+    #[cfg(feature = "email")]
+    pub fn smtp_transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, HookError> {
+        let config = &self.smtp_config;
+        let builder = if !config.use_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        } else if config.port == 465 {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| HookError::Configuration(format!("Invalid SMTP host {}: {}", config.host, e)))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| HookError::Configuration(format!("Invalid SMTP host {}: {}", config.host, e)))?
+        };
+        let mut builder = builder.port(config.port).timeout(Some(NOTIFICATION_HTTP_TIMEOUT));
+
+        if !config.username.is_empty() {
+            if !config.use_tls {
+                let local = matches!(config.host.as_str(), "localhost" | "127.0.0.1" | "::1");
+                if !local && !config.allow_plaintext_auth {
+                    return Err(HookError::Configuration(format!(
+                        "Refusing to send SMTP credentials to {} without TLS; enable use_tls or set allow_plaintext_auth",
+                        config.host
+                    )));
+                }
+                tracing::warn!("Sending SMTP credentials to {} without TLS", config.host);
+            }
+            let password = EnvSecretProvider.resolve(&config.password)?;
+            builder = builder.credentials(Credentials::new(config.username.clone(), password));
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Send the alert over SMTP.
+    #[cfg(feature = "email")]
+    async fn deliver(&self, alert: &ActiveAlert) -> Result<(), HookError> {
+        let transport = self.smtp_transport()?;
+        self.send_with(&transport, alert).await
+    }
+
+    /// Fail the send: this build has no SMTP client.
+    #[cfg(not(feature = "email"))]
+    async fn deliver(&self, _alert: &ActiveAlert) -> Result<(), HookError> {
+        Err(HookError::Configuration(format!(
+            "Email channel {} cannot send: email support is not enabled in this build; rebuild codex-core with the `email` feature",
+            self.name
+        )))
+    }
+
+    /// Subject line naming the severity and the hook that raised the alert.
+    pub fn subject(alert: &ActiveAlert) -> String {
+        match &alert.hook_id {
+            Some(hook_id) => format!(
+                "[{}] Codex hook alert for {}: {}",
+                alert.severity.as_str(),
+                hook_id,
+                alert.message
+            ),
+            None => format!("[{}] Codex alert: {}", alert.severity.as_str(), alert.message),
+        }
+    }
+
+    /// Build the multipart (plaintext and HTML) message for an alert.
+    #[cfg(feature = "email")]
+    pub fn build_message(&self, alert: &ActiveAlert) -> Result<Message, HookError> {
+        let parse_mailbox = |address: &str| {
+            address
+                .parse::<Mailbox>()
+                .map_err(|e| HookError::Configuration(format!("Invalid email address {}: {}", address, e)))
+        };
+
+        if self.recipients.is_empty() {
+            return Err(HookError::Configuration(format!(
+                "Email channel {} has no recipients",
+                self.name
+            )));
+        }
+
+        let from = self.smtp_config.from.as_deref().unwrap_or(&self.smtp_config.username);
+        let mut builder = Message::builder().from(parse_mailbox(from)?).subject(Self::subject(alert));
+        for recipient in &self.recipients {
+            builder = builder.to(parse_mailbox(recipient)?);
+        }
+
+        let hook = alert.hook_id.as_deref().unwrap_or("-");
+        let text = format!(
+            "{}\n\nSeverity: {}\nRule: {}\nHook: {}\nOccurrences: {}\nTriggered at: {}\nAlert ID: {}\n",
+            alert.message,
+            alert.severity.as_str(),
+            alert.rule_id,
+            hook,
+            alert.occurrence_count,
+            alert.triggered_at.to_rfc3339(),
+            alert.id
+        );
+        let html = format!(
+            "<p>{}</p><table>\
+             <tr><th align=\"left\">Severity</th><td>{}</td></tr>\
+             <tr><th align=\"left\">Rule</th><td>{}</td></tr>\
+             <tr><th align=\"left\">Hook</th><td>{}</td></tr>\
+             <tr><th align=\"left\">Occurrences</th><td>{}</td></tr>\
+             <tr><th align=\"left\">Triggered at</th><td>{}</td></tr>\
+             <tr><th align=\"left\">Alert ID</th><td>{}</td></tr>\
+             </table>",
+            escape_html(&alert.message),
+            alert.severity.as_str(),
+            escape_html(&alert.rule_id),
+            escape_html(hook),
+            alert.occurrence_count,
+            alert.triggered_at.to_rfc3339(),
+            escape_html(&alert.id)
+        );
+
+        builder
+            .multipart(MultiPart::alternative_plain_html(text, html))
+            .map_err(|e| HookError::Execution(format!("Failed to build email notification: {}", e)))
+    }
+
+    /// Send the alert email through `transport`.
+    #[cfg(feature = "email")]
+    pub async fn send_with<T>(&self, transport: &T, alert: &ActiveAlert) -> Result<(), HookError>
+    where
+        T: AsyncTransport + Sync,
+        T::Error: std::fmt::Display,
+    {
+        let message = self.build_message(alert)?;
+        transport.send(message).await.map_err(|e| {
+            HookError::Execution(format!(
+                "Failed to send email notification via {}:{}: {}",
+                self.smtp_config.host, self.smtp_config.port, e
+            ))
+        })?;
+        Ok(())
+    }
+}
+
+#[async_trait]
 impl NotificationChannel for EmailNotificationChannel {
-    fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
+    async fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
         if !self.enabled {
             return Ok(());
         }
 
-        tracing::info!("Sending email notification for alert: {}", alert.id);
-        // TODO: Implement actual email sending
-        Ok(())
+        tracing::info!(
+            "Sending email notification for alert {} to {} recipients",
+            alert.id,
+            self.recipients.len()
+        );
+        self.deliver(alert).await
     }
 
     fn channel_name(&self) -> &str {
@@ -598,14 +1291,63 @@ impl NotificationChannel for EmailNotificationChannel {
     }
 }
 
+impl SlackNotificationChannel {
+    /// HTTP client for posting to Slack, routed through the channel's proxy.
+    pub fn http_client(&self) -> Result<reqwest::Client, HookError> {
+        self.proxy.build_client(NOTIFICATION_HTTP_TIMEOUT, "Codex-Hooks/1.0")
+    }
+
+    /// Incoming-webhook message posted for an alert.
+    pub fn payload(&self, alert: &ActiveAlert) -> serde_json::Value {
+        let mut fields = vec![
+            serde_json::json!({ "title": "Rule", "value": alert.rule_id, "short": true }),
+            serde_json::json!({ "title": "Occurrences", "value": alert.occurrence_count.to_string(), "short": true }),
+        ];
+        if let Some(hook_id) = &alert.hook_id {
+            fields.push(serde_json::json!({ "title": "Hook", "value": hook_id, "short": true }));
+        }
+
+        let color = match alert.severity {
+            AlertSeverity::Info => "#439fe0",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Error | AlertSeverity::Critical => "danger",
+        };
+        let mut payload = serde_json::json!({
+            "text": format!("[{}] {}", alert.severity.as_str().to_uppercase(), alert.message),
+            "attachments": [{
+                "color": color,
+                "fields": fields,
+                "footer": format!("Alert {}", alert.id),
+                "ts": alert.triggered_at.timestamp(),
+            }],
+        });
+        if !self.default_channel.is_empty() {
+            payload["channel"] = serde_json::json!(self.default_channel);
+        }
+        payload
+    }
+}
+
+#[async_trait]
 impl NotificationChannel for SlackNotificationChannel {
-    fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
+    async fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
         if !self.enabled {
             return Ok(());
         }
 
         tracing::info!("Sending Slack notification for alert: {}", alert.id);
-        // TODO: Implement actual Slack webhook sending
+        let response = self
+            .http_client()?
+            .post(&self.webhook_url)
+            .json(&self.payload(alert))
+            .send()
+            .await
+            .map_err(|e| HookError::Execution(format!("Failed to send Slack notification: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(HookError::Execution(format!("Slack notification returned status {}", status)));
+        }
         Ok(())
     }
 
@@ -618,15 +1360,76 @@ impl NotificationChannel for SlackNotificationChannel {
     }
 }
 
+impl WebhookNotificationChannel {
+    /// HTTP client for posting to the webhook, routed through the channel's proxy.
+    pub fn http_client(&self) -> Result<reqwest::Client, HookError> {
+        self.proxy.build_client(NOTIFICATION_HTTP_TIMEOUT, "Codex-Hooks/1.0")
+    }
+
+    /// JSON body posted for an alert.
+    pub fn payload(alert: &ActiveAlert) -> serde_json::Value {
+        serde_json::json!({
+            "alert": alert,
+            "severity": alert.severity,
+            "timestamp": Utc::now(),
+        })
+    }
+
+    /// Post the payload once; the error flags whether the attempt may be retried.
+    async fn post(&self, client: &reqwest::Client, payload: &serde_json::Value) -> Result<(), (HookError, bool)> {
+        let mut request = client.post(&self.url).json(payload);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                Err((
+                    HookError::Execution(format!("Webhook notification returned status {}", status)),
+                    status.is_server_error(),
+                ))
+            }
+            Err(e) => Err((
+                HookError::Execution(format!("Failed to send webhook notification: {}", e)),
+                e.is_connect() || e.is_timeout(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
 impl NotificationChannel for WebhookNotificationChannel {
-    fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
+    async fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
         if !self.enabled {
             return Ok(());
         }
 
         tracing::info!("Sending webhook notification for alert: {}", alert.id);
-        // TODO: Implement actual webhook HTTP request
-        Ok(())
+        let client = self.http_client()?;
+        let payload = Self::payload(alert);
+
+        let mut delay = WEBHOOK_NOTIFICATION_RETRY_DELAY;
+        let mut attempt = 1;
+        loop {
+            match self.post(&client, &payload).await {
+                Ok(()) => return Ok(()),
+                Err((e, true)) if attempt < WEBHOOK_NOTIFICATION_MAX_ATTEMPTS => {
+                    tracing::warn!(
+                        "Webhook notification attempt {} for alert {} failed, retrying in {:?}: {}",
+                        attempt,
+                        alert.id,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err((e, _)) => return Err(e),
+            }
+        }
     }
 
     fn channel_name(&self) -> &str {
@@ -640,7 +1443,9 @@ impl NotificationChannel for WebhookNotificationChannel {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
     use super::*;
+    use crate::hooks::clock::MockClock;
 
     #[tokio::test]
     async fn test_alerting_system_creation() {
@@ -660,7 +1465,7 @@ mod tests {
             condition: AlertCondition {
                 condition_type: AlertConditionType::ErrorRate,
                 threshold: 0.1,
-                time_window: Duration::from_minutes(5),
+                time_window: Duration::from_secs(5 * 60),
                 min_occurrences: 3,
                 hook_id_filter: None,
                 event_type_filter: None,
@@ -670,6 +1475,7 @@ mod tests {
             suppression: None,
             escalation: None,
             tags: vec!["test".to_string()],
+            aggregation_window: None,
         };
 
         system.add_alert_rule(rule).unwrap();
@@ -679,7 +1485,10 @@ mod tests {
     #[tokio::test]
     async fn test_alert_event_triggering() {
         let system = AlertingSystem::new(AlertingConfig::default());
-        
+        let mut rule = failure_rule("test_failures", None);
+        rule.condition.hook_id_filter = Some("test_hook".to_string());
+        system.add_alert_rule(rule).unwrap();
+
         let event = AlertEvent {
             event_type: AlertEventType::HookExecutionFailed,
             data: AlertEventData {
@@ -693,14 +1502,178 @@ mod tests {
         };
 
         system.trigger_alert_event(event).unwrap();
-        
-        // Give some time for async processing
-        tokio::time::sleep(Duration::from_millis(100)).await;
+        system.flush().await;
         
         let active_alerts = system.get_active_alerts().unwrap();
         assert!(!active_alerts.is_empty());
     }
 
+    fn failure_rule(id: &str, aggregation_window: Option<Duration>) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            enabled: true,
+            condition: AlertCondition {
+                condition_type: AlertConditionType::FailureCount,
+                threshold: 1.0,
+                time_window: Duration::from_secs(5 * 60),
+                min_occurrences: 1,
+                hook_id_filter: Some("flaky_hook".to_string()),
+                event_type_filter: None,
+            },
+            severity: AlertSeverity::Error,
+            notification_channels: Vec::new(),
+            suppression: None,
+            escalation: None,
+            tags: Vec::new(),
+            aggregation_window,
+        }
+    }
+
+    fn failure_event(timestamp: DateTime<Utc>) -> AlertEvent {
+        AlertEvent {
+            event_type: AlertEventType::HookExecutionFailed,
+            data: AlertEventData {
+                hook_id: Some("flaky_hook".to_string()),
+                event_type: Some(LifecycleEventType::TaskStart),
+                value: 1.0,
+                error_message: Some("boom".to_string()),
+                context: HashMap::new(),
+            },
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rules_group_events_on_their_own_window() {
+        let system = AlertingSystem::new(AlertingConfig {
+            aggregation_window: Duration::from_secs(5 * 60),
+            ..AlertingConfig::default()
+        });
+        system.add_alert_rule(failure_rule("blips", Some(Duration::from_secs(30)))).unwrap();
+        system.add_alert_rule(failure_rule("sustained", Some(Duration::from_hours(1)))).unwrap();
+        system.add_alert_rule(failure_rule("inherits_default", None)).unwrap();
+
+        // Four failures over ten minutes: 0s, 10s, 2m and 10m
+        let start = Utc::now();
+        for offset in [0, 10, 120, 600] {
+            system.trigger_alert_event(failure_event(start + chrono::Duration::seconds(offset))).unwrap();
+        }
+        system.flush().await;
+
+        let alerts = system.get_active_alerts().unwrap();
+        let occurrences = |rule_id: &str| {
+            let mut counts: Vec<u32> = alerts.iter()
+                .filter(|a| a.rule_id == rule_id)
+                .map(|a| a.occurrence_count)
+                .collect();
+            counts.sort();
+            counts
+        };
+
+        // Short window: only the back-to-back failures are grouped
+        assert_eq!(occurrences("blips"), vec![1, 1, 2]);
+        // Long window: everything lands in one group
+        assert_eq!(occurrences("sustained"), vec![4]);
+        // No override: the global five-minute window applies
+        assert_eq!(occurrences("inherits_default"), vec![1, 3]);
+        assert_eq!(alerts.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_events_raise_no_alert() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        system.add_alert_rule(failure_rule("flaky", None)).unwrap();
+
+        let mut event = failure_event(Utc::now());
+        event.data.hook_id = Some("stable_hook".to_string());
+        system.trigger_alert_event(event).unwrap();
+        system.flush().await;
+
+        assert!(system.get_active_alerts().unwrap().is_empty());
+    }
+
+    fn error_rate_event(timestamp: DateTime<Utc>, rate: f64) -> AlertEvent {
+        AlertEvent {
+            event_type: AlertEventType::ErrorRateExceeded,
+            data: AlertEventData {
+                hook_id: Some("flaky_hook".to_string()),
+                event_type: Some(LifecycleEventType::TaskStart),
+                value: rate,
+                error_message: None,
+                context: HashMap::new(),
+            },
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_rule_fires_after_crossing_threshold_within_window() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let mut rule = failure_rule("error_rate", Some(Duration::from_secs(60)));
+        rule.condition.condition_type = AlertConditionType::ErrorRate;
+        rule.condition.threshold = 0.5;
+        rule.condition.min_occurrences = 3;
+        system.add_alert_rule(rule).unwrap();
+
+        let start = Utc::now();
+        let at = |seconds: i64| start + chrono::Duration::seconds(seconds);
+        let trigger = |seconds: i64, rate: f64| system.trigger_alert_event(error_rate_event(at(seconds), rate)).unwrap();
+
+        // High rates, but fewer than three samples in the window
+        trigger(0, 0.9);
+        trigger(10, 0.9);
+        system.flush().await;
+        assert!(system.get_active_alerts().unwrap().is_empty());
+
+        // The earlier samples fall out of the window; three samples average 0.4
+        trigger(120, 0.9);
+        trigger(130, 0.1);
+        trigger(140, 0.2);
+        system.flush().await;
+        assert!(system.get_active_alerts().unwrap().is_empty());
+
+        // Four samples now average above 0.5
+        trigger(150, 0.9);
+        system.flush().await;
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule_id, "error_rate");
+        assert_eq!(alerts[0].occurrence_count, 1);
+
+        // Still tripped: counted on the existing alert rather than duplicated
+        trigger(160, 0.9);
+        system.flush().await;
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].occurrence_count, 2);
+        assert_eq!(alerts[0].last_updated, at(160));
+    }
+
+    #[test]
+    fn test_condition_trip_rules() {
+        let mut condition = failure_rule("rule", None).condition;
+        condition.threshold = 2.0;
+        condition.min_occurrences = 1;
+
+        assert!(!condition.is_tripped(&[]));
+        assert!(!condition.is_tripped(&[1.0]));
+        assert!(condition.is_tripped(&[1.0, 1.0]));
+
+        condition.condition_type = AlertConditionType::ExecutionTime;
+        assert!(!condition.is_tripped(&[0.5, 1.5]));
+        assert!(condition.is_tripped(&[0.5, 2.5]));
+
+        condition.condition_type = AlertConditionType::LowThroughput;
+        assert!(condition.is_tripped(&[5.0, 1.0]));
+        assert!(!condition.is_tripped(&[5.0, 3.0]));
+
+        condition.min_occurrences = 3;
+        condition.condition_type = AlertConditionType::ExecutionTime;
+        assert!(!condition.is_tripped(&[5.0, 5.0]));
+    }
+
     #[test]
     fn test_alert_severity_ordering() {
         assert!(AlertSeverity::Critical > AlertSeverity::Error);
@@ -713,7 +1686,7 @@ mod tests {
         let condition = AlertCondition {
             condition_type: AlertConditionType::ErrorRate,
             threshold: 0.1,
-            time_window: Duration::from_minutes(5),
+            time_window: Duration::from_secs(5 * 60),
             min_occurrences: 3,
             hook_id_filter: None,
             event_type_filter: None,
@@ -723,4 +1696,566 @@ mod tests {
         let deserialized: AlertCondition = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized.condition_type, AlertConditionType::ErrorRate);
     }
+
+    async fn wait_for_processor_stop(system: &AlertingSystem) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while system.is_processor_running() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("alert processor should report stopped");
+    }
+
+    #[tokio::test]
+    async fn test_processor_reports_stopped_when_sender_dropped() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        assert!(system.is_processor_running());
+
+        // Dropping the sender without closing is an unexpected stop
+        system.alert_sender.write().unwrap().take();
+        wait_for_processor_stop(&system).await;
+
+        let alerts = system.get_active_alerts().unwrap();
+        let self_alert = alerts.iter().find(|a| a.rule_id == ALERT_PROCESSOR_RULE_ID).unwrap();
+        assert_eq!(self_alert.severity, AlertSeverity::Critical);
+
+        let event = AlertEvent {
+            event_type: AlertEventType::HookExecutionFailed,
+            data: AlertEventData {
+                hook_id: None,
+                event_type: None,
+                value: 1.0,
+                error_message: None,
+                context: HashMap::new(),
+            },
+            timestamp: Utc::now(),
+        };
+        assert!(system.trigger_alert_event(event).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_close_stops_processor_without_self_alert() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        system.close();
+        wait_for_processor_stop(&system).await;
+
+        let alerts = system.get_active_alerts().unwrap();
+        assert!(alerts.iter().all(|a| a.rule_id != ALERT_PROCESSOR_RULE_ID));
+    }
+
+    fn webhook_channel(url: String) -> WebhookNotificationChannel {
+        WebhookNotificationChannel {
+            name: "ops-webhook".to_string(),
+            url,
+            headers: HashMap::from([("X-Alert-Token".to_string(), "secret".to_string())]),
+            proxy: ProxySettings::Disabled,
+            enabled: true,
+        }
+    }
+
+    fn webhook_alert() -> ActiveAlert {
+        let now = Utc::now();
+        ActiveAlert {
+            id: "alert-1".to_string(),
+            rule_id: "hook_failures".to_string(),
+            severity: AlertSeverity::Critical,
+            message: "Hook failed".to_string(),
+            triggered_at: now,
+            last_updated: now,
+            occurrence_count: 2,
+            status: AlertStatus::Active,
+            hook_id: Some("hook-1".to_string()),
+            event_type: Some(LifecycleEventType::TaskComplete),
+            metadata: HashMap::new(),
+            escalation_level: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slack_notification_posts_message() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let channel = SlackNotificationChannel {
+            name: "ops-slack".to_string(),
+            webhook_url: server.uri(),
+            default_channel: "#alerts".to_string(),
+            proxy: ProxySettings::Disabled,
+            enabled: true,
+        };
+        channel.send_notification(&webhook_alert()).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["channel"], "#alerts");
+        assert_eq!(body["text"], "[CRITICAL] Hook failed");
+        assert_eq!(body["attachments"][0]["color"], "danger");
+        assert_eq!(body["attachments"][0]["fields"][2]["value"], "hook-1");
+
+        let failing = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&failing)
+            .await;
+        let channel = SlackNotificationChannel { webhook_url: failing.uri(), ..channel };
+        let error = channel.send_notification(&webhook_alert()).await.unwrap_err();
+        assert!(error.to_string().contains("404"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notification_posts_alert_payload() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("X-Alert-Token", "secret"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let channel = webhook_channel(server.uri());
+        channel.send_notification(&webhook_alert()).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["alert"]["id"], "alert-1");
+        assert_eq!(body["alert"]["occurrence_count"], 2);
+        assert_eq!(body["severity"], serde_json::json!(AlertSeverity::Critical));
+        assert!(body["timestamp"].as_str().unwrap().parse::<DateTime<Utc>>().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notification_retries_server_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let channel = webhook_channel(server.uri());
+        channel.send_notification(&webhook_alert()).await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notification_gives_up_after_max_attempts() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(u64::from(WEBHOOK_NOTIFICATION_MAX_ATTEMPTS))
+            .mount(&server)
+            .await;
+
+        let channel = webhook_channel(server.uri());
+        let error = channel.send_notification(&webhook_alert()).await.unwrap_err();
+        assert!(error.to_string().contains("503"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notification_does_not_retry_client_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let channel = webhook_channel(server.uri());
+        assert!(channel.send_notification(&webhook_alert()).await.is_err());
+    }
+
+    fn email_channel(recipients: &[&str]) -> EmailNotificationChannel {
+        EmailNotificationChannel {
+            name: "ops-email".to_string(),
+            smtp_config: SmtpConfig {
+                host: "smtp.example.com".to_string(),
+                port: 587,
+                username: "alerts@example.com".to_string(),
+                password: "hunter2".to_string(),
+                use_tls: true,
+                from: None,
+                allow_plaintext_auth: false,
+            },
+            recipients: recipients.iter().map(|r| r.to_string()).collect(),
+            enabled: true,
+        }
+    }
+
+    #[cfg(feature = "email")]
+    #[tokio::test]
+    async fn test_email_notification_subject_and_bodies() {
+        use lettre::transport::stub::AsyncStubTransport;
+
+        let channel = email_channel(&["oncall@example.com", "lead@example.com"]);
+        let transport = AsyncStubTransport::new_ok();
+        let mut alert = webhook_alert();
+        alert.message = "Hook <deploy> failed".to_string();
+        channel.send_with(&transport, &alert).await.unwrap();
+
+        let messages = transport.messages().await;
+        assert_eq!(messages.len(), 1);
+        let (envelope, raw) = &messages[0];
+        assert_eq!(envelope.to().len(), 2);
+        assert_eq!(envelope.from().unwrap().to_string(), "alerts@example.com");
+
+        let subject = raw.lines().find(|line| line.starts_with("Subject:")).unwrap();
+        assert!(subject.contains("critical"), "{}", subject);
+        assert!(subject.contains("hook-1"), "{}", subject);
+        assert!(raw.contains("Content-Type: text/plain"));
+        assert!(raw.contains("Content-Type: text/html"));
+        assert!(raw.contains("Hook &lt;deploy&gt; failed"));
+    }
+
+    #[cfg(feature = "email")]
+    #[tokio::test]
+    async fn test_email_notification_surfaces_transport_errors() {
+        use lettre::transport::stub::AsyncStubTransport;
+
+        let channel = email_channel(&["oncall@example.com"]);
+        let result = channel.send_with(&AsyncStubTransport::new_error(), &webhook_alert()).await;
+        assert!(matches!(result, Err(HookError::Execution(_))));
+    }
+
+    #[cfg(feature = "email")]
+    #[tokio::test]
+    async fn test_email_notification_rejects_invalid_recipients() {
+        let channel = email_channel(&["not an address"]);
+        assert!(matches!(channel.build_message(&webhook_alert()), Err(HookError::Configuration(_))));
+
+        let channel = email_channel(&[]);
+        assert!(matches!(channel.build_message(&webhook_alert()), Err(HookError::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_email_channel_sends_nothing() {
+        let mut channel = email_channel(&["oncall@example.com"]);
+        channel.enabled = false;
+        // Would fail to connect if it tried to send
+        assert!(channel.send_notification(&webhook_alert()).await.is_ok());
+    }
+
+    #[cfg(feature = "email")]
+    #[tokio::test]
+    async fn test_smtp_refuses_plaintext_credentials_to_remote_hosts() {
+        let mut channel = email_channel(&["oncall@example.com"]);
+        channel.smtp_config.use_tls = false;
+        let error = channel.smtp_transport().unwrap_err();
+        assert!(error.to_string().contains("without TLS"), "{}", error);
+
+        channel.smtp_config.allow_plaintext_auth = true;
+        assert!(channel.smtp_transport().is_ok());
+
+        // Local relays and unauthenticated sends need no opt-in
+        channel.smtp_config.allow_plaintext_auth = false;
+        channel.smtp_config.host = "localhost".to_string();
+        assert!(channel.smtp_transport().is_ok());
+        channel.smtp_config.host = "smtp.example.com".to_string();
+        channel.smtp_config.username.clear();
+        assert!(channel.smtp_transport().is_ok());
+    }
+
+    #[test]
+    fn test_smtp_config_debug_redacts_password() {
+        let channel = email_channel(&["oncall@example.com"]);
+        let debug = format!("{:?}", channel);
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    /// Channel that records the alerts it is asked to send.
+    #[derive(Debug)]
+    struct RecordingChannel {
+        name: String,
+        enabled: bool,
+        sent: Arc<std::sync::Mutex<Vec<ActiveAlert>>>,
+    }
+
+    impl RecordingChannel {
+        fn new(name: &str, enabled: bool) -> (Self, Arc<std::sync::Mutex<Vec<ActiveAlert>>>) {
+            let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let channel = Self {
+                name: name.to_string(),
+                enabled,
+                sent: sent.clone(),
+            };
+            (channel, sent)
+        }
+    }
+
+    #[async_trait]
+    impl NotificationChannel for RecordingChannel {
+        async fn send_notification(&self, alert: &ActiveAlert) -> Result<(), HookError> {
+            self.sent.lock().unwrap().push(alert.clone());
+            Ok(())
+        }
+
+        fn channel_name(&self) -> &str {
+            &self.name
+        }
+
+        fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alerts_notify_channels_named_by_their_rule() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (pager, paged) = RecordingChannel::new("pager", true);
+        let (chat, chatted) = RecordingChannel::new("chat", true);
+        let (muted, muted_sent) = RecordingChannel::new("muted", false);
+        system.add_notification_channel(Box::new(pager));
+        system.add_notification_channel(Box::new(chat));
+        system.add_notification_channel(Box::new(muted));
+
+        let mut paging = failure_rule("paging", None);
+        paging.notification_channels = vec!["pager".to_string(), "muted".to_string(), "missing".to_string()];
+        system.add_alert_rule(paging).unwrap();
+        system.add_alert_rule(failure_rule("quiet", None)).unwrap();
+        let mut other_hook = failure_rule("other_hook", None);
+        other_hook.condition.hook_id_filter = Some("other".to_string());
+        other_hook.notification_channels = vec!["chat".to_string()];
+        system.add_alert_rule(other_hook).unwrap();
+
+        // Two failures of the same hook: one alert per matching rule, the second is only counted
+        let start = Utc::now();
+        system.trigger_alert_event(failure_event(start)).unwrap();
+        system.trigger_alert_event(failure_event(start + chrono::Duration::seconds(1))).unwrap();
+        system.flush().await;
+
+        let paged = paged.lock().unwrap();
+        assert_eq!(paged.len(), 1);
+        assert_eq!(paged[0].rule_id, "paging");
+        assert_eq!(paged[0].hook_id.as_deref(), Some("flaky_hook"));
+        assert!(chatted.lock().unwrap().is_empty());
+        assert!(muted_sent.lock().unwrap().is_empty());
+        assert_eq!(system.get_active_alerts().unwrap().len(), 2);
+    }
+
+    fn suppressed_rule(max_alerts_per_window: u32, suppress_similar: bool) -> AlertRule {
+        let mut rule = failure_rule("suppressed", Some(Duration::ZERO));
+        rule.condition.hook_id_filter = None;
+        rule.notification_channels = vec!["pager".to_string()];
+        rule.suppression = Some(SuppressionSettings {
+            duration: Duration::from_secs(10 * 60),
+            max_alerts_per_window,
+            suppress_similar,
+        });
+        rule
+    }
+
+    #[tokio::test]
+    async fn test_suppression_limits_notifications_per_group() {
+        let clock = Arc::new(MockClock::new());
+        let system = AlertingSystem::new_with_clock(AlertingConfig::default(), clock.clone());
+        let (pager, paged) = RecordingChannel::new("pager", true);
+        system.add_notification_channel(Box::new(pager));
+        system.add_alert_rule(suppressed_rule(3, false)).unwrap();
+
+        // Ten rapid failures of one hook; a zero aggregation window makes each its own alert
+        for _ in 0..10 {
+            system.trigger_alert_event(failure_event(clock.now())).unwrap();
+            clock.advance(Duration::from_secs(1));
+        }
+        system.flush().await;
+
+        assert_eq!(paged.lock().unwrap().len(), 3);
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts.len(), 10);
+        assert_eq!(alerts.iter().filter(|a| a.status == AlertStatus::Suppressed).count(), 7);
+
+        // Just inside the suppression window, alerts stay suppressed
+        clock.advance(Duration::from_secs(8 * 60));
+        system.trigger_alert_event(failure_event(clock.now())).unwrap();
+        system.flush().await;
+        assert_eq!(paged.lock().unwrap().len(), 3);
+
+        // Once the suppression window has passed, alerts notify again
+        clock.advance(Duration::from_secs(3 * 60));
+        system.trigger_alert_event(failure_event(clock.now())).unwrap();
+        system.flush().await;
+        assert_eq!(paged.lock().unwrap().len(), 4);
+
+        // Resolution time is measured on the same clock
+        let first = alerts.iter().min_by_key(|alert| alert.triggered_at).unwrap();
+        clock.advance(Duration::from_secs(30));
+        system.resolve_alert(&first.id, "oncall").unwrap();
+        let history = system.get_alert_history(None).unwrap();
+        assert_eq!(
+            history[0].duration,
+            clock.now().signed_duration_since(first.triggered_at).to_std().ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suppress_similar_shares_limit_across_hooks() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (pager, paged) = RecordingChannel::new("pager", true);
+        system.add_notification_channel(Box::new(pager));
+        system.add_alert_rule(suppressed_rule(2, true)).unwrap();
+
+        let now = Utc::now();
+        for i in 0..10 {
+            let mut event = failure_event(now);
+            event.data.hook_id = Some(format!("hook_{}", i));
+            system.trigger_alert_event(event).unwrap();
+        }
+        system.flush().await;
+
+        assert_eq!(paged.lock().unwrap().len(), 2);
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts.len(), 10);
+        assert_eq!(alerts.iter().filter(|a| a.status == AlertStatus::Suppressed).count(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_suppressed_alerts_keep_counting_occurrences() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (pager, paged) = RecordingChannel::new("pager", true);
+        system.add_notification_channel(Box::new(pager));
+        let mut rule = suppressed_rule(0, false);
+        rule.aggregation_window = Some(Duration::from_secs(5 * 60));
+        system.add_alert_rule(rule).unwrap();
+
+        let start = Utc::now();
+        for offset in 0..10 {
+            system.trigger_alert_event(failure_event(start + chrono::Duration::seconds(offset))).unwrap();
+        }
+        system.flush().await;
+
+        assert!(paged.lock().unwrap().is_empty());
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].status, AlertStatus::Suppressed);
+        assert_eq!(alerts[0].occurrence_count, 10);
+    }
+
+    #[tokio::test]
+    async fn test_suppression_disabled_in_config_notifies_every_alert() {
+        let system = AlertingSystem::new(AlertingConfig {
+            enable_suppression: false,
+            ..AlertingConfig::default()
+        });
+        let (pager, paged) = RecordingChannel::new("pager", true);
+        system.add_notification_channel(Box::new(pager));
+        system.add_alert_rule(suppressed_rule(3, false)).unwrap();
+
+        let start = Utc::now();
+        for offset in 0..10 {
+            system.trigger_alert_event(failure_event(start + chrono::Duration::seconds(offset))).unwrap();
+        }
+        system.flush().await;
+
+        assert_eq!(paged.lock().unwrap().len(), 10);
+    }
+
+    fn escalating_rule(levels: Vec<EscalationLevel>) -> AlertRule {
+        let mut rule = failure_rule("escalating", None);
+        rule.notification_channels = vec!["pager".to_string()];
+        rule.escalation = Some(EscalationSettings {
+            levels,
+            auto_escalate: true,
+        });
+        rule
+    }
+
+    fn escalation_level(level: u32, channel: &str, severity: AlertSeverity) -> EscalationLevel {
+        EscalationLevel {
+            level,
+            timeout: Duration::from_secs(60),
+            notification_channels: vec![channel.to_string()],
+            severity,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unacknowledged_alert_escalates_after_timeout() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (manager, managed) = RecordingChannel::new("manager", true);
+        system.add_notification_channel(Box::new(manager));
+        system.add_alert_rule(escalating_rule(vec![escalation_level(1, "manager", AlertSeverity::Critical)])).unwrap();
+
+        system.trigger_alert_event(failure_event(Utc::now())).unwrap();
+        tokio::time::sleep(Duration::from_secs(59)).await;
+        assert!(managed.lock().unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let escalated = managed.lock().unwrap().clone();
+        assert_eq!(escalated.len(), 1);
+        assert_eq!(escalated[0].escalation_level, 1);
+        assert_eq!(escalated[0].status, AlertStatus::Escalated);
+        assert_eq!(escalated[0].severity, AlertSeverity::Critical);
+
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts[0].status, AlertStatus::Escalated);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acknowledgement_stops_escalation() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (manager, managed) = RecordingChannel::new("manager", true);
+        let (director, directed) = RecordingChannel::new("director", true);
+        system.add_notification_channel(Box::new(manager));
+        system.add_notification_channel(Box::new(director));
+        system.add_alert_rule(escalating_rule(vec![
+            escalation_level(1, "manager", AlertSeverity::Error),
+            escalation_level(2, "director", AlertSeverity::Critical),
+        ])).unwrap();
+
+        system.trigger_alert_event(failure_event(Utc::now())).unwrap();
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(managed.lock().unwrap().len(), 1);
+
+        let alert_id = system.get_active_alerts().unwrap()[0].id.clone();
+        system.acknowledge_alert(&alert_id, "oncall").unwrap();
+        tokio::time::sleep(Duration::from_secs(300)).await;
+
+        assert_eq!(managed.lock().unwrap().len(), 1);
+        assert!(directed.lock().unwrap().is_empty());
+        let alerts = system.get_active_alerts().unwrap();
+        assert_eq!(alerts[0].escalation_level, 1);
+        assert_eq!(alerts[0].status, AlertStatus::Acknowledged);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_resolved_alert_does_not_escalate() {
+        let system = AlertingSystem::new(AlertingConfig::default());
+        let (manager, managed) = RecordingChannel::new("manager", true);
+        system.add_notification_channel(Box::new(manager));
+        system.add_alert_rule(escalating_rule(vec![escalation_level(1, "manager", AlertSeverity::Critical)])).unwrap();
+
+        system.trigger_alert_event(failure_event(Utc::now())).unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let alert_id = system.get_active_alerts().unwrap()[0].id.clone();
+        system.resolve_alert(&alert_id, "oncall").unwrap();
+        tokio::time::sleep(Duration::from_secs(120)).await;
+
+        assert!(managed.lock().unwrap().is_empty());
+    }
 }
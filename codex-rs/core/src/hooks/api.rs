@@ -0,0 +1,418 @@
+//! Minimal HTTP API for operating the hooks system.
+//!
+//! Routes:
+//! - `GET /health` returns the consolidated health report.
+//! - `POST /api/hooks/{id}/test[?dry_run=true]` test-fires a single hook against the
+//!   JSON `LifecycleEvent` in the request body, or a synthetic event when the body is empty.
+//!
+//! Test-firing runs the hook for real, so `POST` routes require `Authorization: Bearer <token>`
+//! when the API is served with a token, and are only accepted from loopback peers otherwise.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::hooks::manager::HookManager;
+use crate::hooks::types::{HookError, LifecycleEvent};
+
+/// Maximum accepted request size, headers included.
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Serve the hooks HTTP API until the listener fails.
+///
+/// With `auth_token` set, `POST` routes require it as a bearer token; without one they are
+/// restricted to loopback peers.
+pub async fn serve_api(
+    manager: Arc<HookManager>,
+    listener: TcpListener,
+    auth_token: Option<String>,
+) -> Result<(), HookError> {
+    let auth_token = Arc::new(auth_token);
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let manager = manager.clone();
+        let auth_token = auth_token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(&manager, stream, peer, auth_token.as_deref()).await {
+                tracing::debug!("Hooks API connection failed: {}", e);
+            }
+        });
+    }
+}
+
+/// A parsed HTTP request.
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    /// Value of the `Authorization` header, if present.
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Read a single HTTP request, including a body delimited by `Content-Length`.
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut parts = lines.next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+    let header = |wanted: &str| {
+        headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+            .map(|(_, value)| *value)
+    };
+    let content_length = header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let authorization = header("authorization").map(str::to_string);
+    if header_end + content_length > MAX_REQUEST_BYTES {
+        return Ok(None);
+    }
+
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let body_end = buffer.len().min(header_end + content_length);
+
+    Ok(Some(Request {
+        method,
+        path,
+        query,
+        authorization,
+        body: buffer[header_end..body_end].to_vec(),
+    }))
+}
+
+/// Whether a request may use the `POST` routes.
+fn is_authorized(request: &Request, peer: SocketAddr, auth_token: Option<&str>) -> bool {
+    match auth_token {
+        Some(token) => request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|presented| constant_time_eq(presented.trim().as_bytes(), token.as_bytes())),
+        None => peer.ip().is_loopback(),
+    }
+}
+
+/// Compare two byte strings without short-circuiting on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Handle a single HTTP connection.
+async fn handle_connection(
+    manager: &HookManager,
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    auth_token: Option<&str>,
+) -> std::io::Result<()> {
+    let (status_code, body) = match read_request(&mut stream).await? {
+        Some(request) if request.method == "POST" && !is_authorized(&request, peer, auth_token) => {
+            (401, error_body("unauthorized"))
+        }
+        Some(request) => route(manager, request).await,
+        None => (400, error_body("malformed request")),
+    };
+
+    let reason = match status_code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status_code,
+        reason,
+        body.len(),
+        body
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Dispatch a request to its handler, returning the status code and JSON body.
+async fn route(manager: &HookManager, request: Request) -> (u16, String) {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["health"]) => {
+            let report = manager.overall_health().await;
+            (report.http_status_code(), to_json(&report))
+        }
+        ("POST", ["api", "hooks", hook_id, "test"]) => {
+            let dry_run = request
+                .query
+                .as_deref()
+                .is_some_and(|query| query.split('&').any(|pair| pair == "dry_run=true" || pair == "dry_run"));
+            test_fire(manager, hook_id, &request.body, dry_run).await
+        }
+        _ => (404, error_body("not found")),
+    }
+}
+
+/// Handle `POST /api/hooks/{id}/test`.
+async fn test_fire(manager: &HookManager, hook_id: &str, body: &[u8], dry_run: bool) -> (u16, String) {
+    let event = if body.iter().all(u8::is_ascii_whitespace) {
+        None
+    } else {
+        match serde_json::from_slice::<LifecycleEvent>(body) {
+            Ok(event) => Some(event),
+            Err(e) => return (400, error_body(&format!("invalid event: {}", e))),
+        }
+    };
+
+    match manager.test_fire(hook_id, event, dry_run).await {
+        Ok(result) => (200, to_json(&result)),
+        Err(e @ HookError::Registry(_)) => (404, error_body(&e.to_string())),
+        Err(e @ HookError::Validation(_)) => (400, error_body(&e.to_string())),
+        Err(e) => (500, error_body(&e.to_string())),
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| error_body(&e.to_string()))
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use std::collections::HashMap;
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::hooks::config::{GlobalHooksConfig, HookConfig, HooksConfig};
+    use crate::hooks::types::{
+        HookExecutionMode, HookPriority, HookType, HttpMethod, LifecycleEventType, WebhookPayloadMode,
+    };
+
+    fn webhook_config(url: String) -> HooksConfig {
+        let hook = HookConfig {
+            id: Some("notify".to_string()),
+            event: LifecycleEventType::TaskComplete,
+            hook_type: HookType::Webhook {
+                url,
+                method: HttpMethod::Post,
+                headers: HashMap::new(),
+                timeout: None,
+                retry_count: None,
+                payload_mode: WebhookPayloadMode::Raw,
+                success_criteria: None,
+                signing: None,
+                success_when: None,
+                capture: None,
+                body: None,
+            },
+            mode: HookExecutionMode::Async,
+            priority: HookPriority::NORMAL,
+            condition: None,
+            blocking: false,
+            required: false,
+            tags: Vec::new(),
+            description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
+        };
+
+        HooksConfig {
+            hooks: GlobalHooksConfig {
+                enabled: true,
+                timeout_seconds: 30,
+                parallel_execution: true,
+                session: Vec::new(),
+                task: vec![hook],
+                exec: Vec::new(),
+                patch: Vec::new(),
+                mcp: Vec::new(),
+                agent: Vec::new(),
+                error: Vec::new(),
+                integration: Vec::new(),
+                proxy: Default::default(),
+                message_queue: Default::default(),
+                payload_dedup_window_seconds: None,
+            },
+        }
+    }
+
+    async fn spawn_api(manager: HookManager) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_api(Arc::new(manager), listener, None));
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_fire_webhook_hits_mock_server() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let manager = HookManager::new(webhook_config(format!("{}/hook", server.uri())))
+            .await
+            .unwrap();
+        let addr = spawn_api(manager).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/api/hooks/notify/test", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["result"]["success"], serde_json::Value::Bool(true));
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_fire_dry_run_skips_execution() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let manager = HookManager::new(webhook_config(format!("{}/hook", server.uri())))
+            .await
+            .unwrap();
+        let addr = spawn_api(manager).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/api/hooks/notify/test?dry_run=true", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["result"]["metadata"]["dry_run"], serde_json::Value::Bool(true));
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_fire_rejects_unknown_hook_and_mismatched_event() {
+        let manager = HookManager::new(webhook_config("http://127.0.0.1:9/hook".to_string()))
+            .await
+            .unwrap();
+        let addr = spawn_api(manager).await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("http://{}/api/hooks/missing/test", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+
+        let event = LifecycleEvent::synthetic(LifecycleEventType::SessionStart);
+        let response = client
+            .post(format!("http://{}/api/hooks/notify/test", addr))
+            .json(&event)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_fire_requires_bearer_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let manager = HookManager::new(webhook_config(format!("{}/hook", server.uri())))
+            .await
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_api(Arc::new(manager), listener, Some("s3cret".to_string())));
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/api/hooks/notify/test", addr);
+
+        let response = client.post(&url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+
+        let response = client.post(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+
+        let response = client.post(&url).bearer_auth("s3cret").send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        server.verify().await;
+    }
+
+    #[test]
+    fn test_tokenless_api_only_authorizes_loopback_peers() {
+        let request = Request {
+            method: "POST".to_string(),
+            path: "/api/hooks/notify/test".to_string(),
+            query: None,
+            authorization: None,
+            body: Vec::new(),
+        };
+
+        assert!(is_authorized(&request, "127.0.0.1:5000".parse().unwrap(), None));
+        assert!(is_authorized(&request, "[::1]:5000".parse().unwrap(), None));
+        assert!(!is_authorized(&request, "192.168.1.20:5000".parse().unwrap(), None));
+        assert!(!is_authorized(&request, "127.0.0.1:5000".parse().unwrap(), Some("token")));
+    }
+}
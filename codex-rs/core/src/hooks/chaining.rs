@@ -76,17 +76,18 @@ pub struct ChainExecutor {
     config: ChainConfig,
 }
 
+impl Default for ChainExecutor {
+    fn default() -> Self {
+        Self::new(ChainConfig::default())
+    }
+}
+
 impl ChainExecutor {
     /// Create a new chain executor with the given configuration.
     pub fn new(config: ChainConfig) -> Self {
         Self { config }
     }
 
-    /// Create a new chain executor with default configuration.
-    pub fn default() -> Self {
-        Self::new(ChainConfig::default())
-    }
-
     /// Execute a chain of hooks with data passing.
     pub async fn execute_chain(
         &self,
@@ -172,8 +173,9 @@ impl ChainExecutor {
             }
         }
 
-        let total_duration = started_at.signed_duration_since(chrono::Utc::now()).to_std()
+        let total_duration = chrono::Utc::now().signed_duration_since(started_at).to_std()
             .unwrap_or(std::time::Duration::ZERO);
+        let success = results.iter().all(|r| r.result.success);
 
         Ok(ChainExecutionResult {
             chain_id,
@@ -181,7 +183,7 @@ impl ChainExecutor {
             final_data: context.data,
             total_duration,
             terminated_early,
-            success: results.iter().all(|r| r.result.success),
+            success,
         })
     }
 
@@ -338,6 +340,7 @@ pub mod chain_utils {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use async_trait::async_trait;
     use std::time::Duration;
@@ -422,8 +425,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_chain_with_failure() {
-        let mut config = ChainConfig::default();
-        config.continue_on_failure = false;
+        let config = ChainConfig {
+            continue_on_failure: false,
+            ..ChainConfig::default()
+        };
 
         let executor = ChainExecutor::new(config);
         
@@ -441,8 +446,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_chain_continue_on_failure() {
-        let mut config = ChainConfig::default();
-        config.continue_on_failure = true;
+        let config = ChainConfig {
+            continue_on_failure: true,
+            ..ChainConfig::default()
+        };
 
         let executor = ChainExecutor::new(config);
         
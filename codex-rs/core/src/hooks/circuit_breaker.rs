@@ -0,0 +1,289 @@
+//! Per-hook circuit breakers used to route around persistently failing hooks.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for hook circuit breakers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is allowed.
+    pub reset_timeout: Duration,
+    /// Failures further apart than this restart the count; any spacing counts when unset.
+    #[serde(default)]
+    pub failure_window: Option<Duration>,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            reset_timeout: Duration::from_secs(30),
+            failure_window: None,
+        }
+    }
+}
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are rejected until the reset timeout elapses.
+    Open,
+    /// A single trial request is allowed to probe for recovery.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    last_failure_at: Option<Instant>,
+    opened_at: Option<Instant>,
+    /// Whether the half-open trial request has been handed out.
+    trial_in_flight: bool,
+}
+
+/// Circuit breaker guarding a single hook.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: RwLock<BreakerState>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: RwLock::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                last_failure_at: None,
+                opened_at: None,
+                trial_in_flight: false,
+            }),
+        }
+    }
+
+    /// Current state, moving an expired open circuit to half-open.
+    pub fn state(&self) -> CircuitState {
+        if let Ok(mut inner) = self.inner.write() {
+            if inner.state == CircuitState::Open
+                && inner.opened_at.is_some_and(|opened| opened.elapsed() >= self.config.reset_timeout)
+            {
+                inner.state = CircuitState::HalfOpen;
+            }
+            inner.state
+        } else {
+            CircuitState::Closed
+        }
+    }
+
+    /// Take a permit to send a request through this circuit, or `None` while it is open.
+    ///
+    /// A half-open circuit hands out one trial permit until its outcome is recorded; a permit
+    /// dropped without an outcome counts as a failure so the trial slot is always released.
+    pub fn try_acquire(self: &Arc<Self>) -> Option<CircuitPermit> {
+        let allowed = match self.state() {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => match self.inner.write() {
+                Ok(mut inner) => !std::mem::replace(&mut inner.trial_in_flight, true),
+                Err(_) => true,
+            },
+        };
+        allowed.then(|| CircuitPermit {
+            breaker: self.clone(),
+            resolved: false,
+        })
+    }
+
+    /// Record a successful request, closing the circuit.
+    pub fn record_success(&self) {
+        if let Ok(mut inner) = self.inner.write() {
+            inner.state = CircuitState::Closed;
+            inner.consecutive_failures = 0;
+            inner.last_failure_at = None;
+            inner.opened_at = None;
+            inner.trial_in_flight = false;
+        }
+    }
+
+    /// Record a failed request, opening the circuit once the threshold is reached.
+    pub fn record_failure(&self) {
+        if let Ok(mut inner) = self.inner.write() {
+            let now = Instant::now();
+            let stale = match (self.config.failure_window, inner.last_failure_at) {
+                (Some(window), Some(last)) => now.duration_since(last) > window,
+                _ => false,
+            };
+            if stale {
+                inner.consecutive_failures = 0;
+            }
+            inner.consecutive_failures += 1;
+            inner.last_failure_at = Some(now);
+            inner.trial_in_flight = false;
+            if inner.state == CircuitState::HalfOpen
+                || inner.consecutive_failures >= self.config.failure_threshold
+            {
+                inner.state = CircuitState::Open;
+                inner.opened_at = Some(now);
+            }
+        }
+    }
+}
+
+/// Permission to send one request through a [`CircuitBreaker`].
+///
+/// Dropping the permit without calling [`record_success`](Self::record_success) or
+/// [`record_failure`](Self::record_failure), e.g. because the request future was cancelled
+/// or timed out, records a failure.
+#[must_use = "dropping a permit records a failure"]
+#[derive(Debug)]
+pub struct CircuitPermit {
+    breaker: Arc<CircuitBreaker>,
+    resolved: bool,
+}
+
+impl CircuitPermit {
+    /// Record that the request succeeded.
+    pub fn record_success(mut self) {
+        self.resolved = true;
+        self.breaker.record_success();
+    }
+
+    /// Record that the request failed.
+    pub fn record_failure(mut self) {
+        self.resolved = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if !self.resolved {
+            self.breaker.record_failure();
+        }
+    }
+}
+
+/// Circuit breakers keyed by hook ID, created on first use.
+#[derive(Debug, Default)]
+pub struct CircuitBreakerRegistry {
+    config: CircuitBreakerConfig,
+    breakers: RwLock<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    /// Create a registry whose breakers share `config`.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the breaker for a hook, creating it if needed.
+    pub fn breaker(&self, hook_id: &str) -> Arc<CircuitBreaker> {
+        if let Ok(breakers) = self.breakers.read() {
+            if let Some(breaker) = breakers.get(hook_id) {
+                return breaker.clone();
+            }
+        }
+
+        match self.breakers.write() {
+            Ok(mut breakers) => breakers
+                .entry(hook_id.to_string())
+                .or_insert_with(|| Arc::new(CircuitBreaker::new(self.config.clone())))
+                .clone(),
+            Err(_) => Arc::new(CircuitBreaker::new(self.config.clone())),
+        }
+    }
+
+    /// Current state of a hook's circuit, `Closed` if it has never been used.
+    pub fn state(&self, hook_id: &str) -> CircuitState {
+        match self.breakers.read() {
+            Ok(breakers) => breakers.get(hook_id).map_or(CircuitState::Closed, |b| b.state()),
+            Err(_) => CircuitState::Closed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_and_recovers() {
+        let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout: Duration::from_millis(20),
+            failure_window: None,
+        }));
+
+        breaker.record_failure();
+        breaker.try_acquire().unwrap().record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.try_acquire().is_none());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        let trial = breaker.try_acquire().unwrap();
+        // Only one trial request at a time
+        assert!(breaker.try_acquire().is_none());
+
+        // A failed trial re-opens immediately
+        trial.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(30));
+        breaker.try_acquire().unwrap().record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_dropped_trial_releases_half_open_slot() {
+        let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            reset_timeout: Duration::from_millis(20),
+            failure_window: None,
+        }));
+
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        let trial = breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_none());
+
+        // An abandoned trial counts as a failure instead of holding the slot forever
+        drop(trial);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_failures_outside_window_do_not_accumulate() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout: Duration::from_secs(60),
+            failure_window: Some(Duration::from_millis(20)),
+        });
+
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(30));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+}
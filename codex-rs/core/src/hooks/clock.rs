@@ -0,0 +1,109 @@
+//! Time sources for time-dependent hook logic.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+/// Source of wall-clock and monotonic time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Current wall-clock time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time.
+    fn instant(&self) -> Instant;
+}
+
+/// Clock backed by the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Shared handle to the system clock.
+    pub fn shared() -> Arc<dyn Clock> {
+        Arc::new(SystemClock)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock that only moves when advanced, for deterministic tests.
+#[derive(Debug)]
+pub struct MockClock {
+    start_time: DateTime<Utc>,
+    start_instant: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl MockClock {
+    /// Create a mock clock stopped at the current time.
+    pub fn new() -> Self {
+        Self::starting_at(Utc::now())
+    }
+
+    /// Create a mock clock stopped at `start_time`.
+    pub fn starting_at(start_time: DateTime<Utc>) -> Self {
+        Self {
+            start_time,
+            start_instant: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut elapsed) = self.elapsed.lock() {
+            *elapsed += duration;
+        }
+    }
+
+    /// Time advanced since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.lock().map(|elapsed| *elapsed).unwrap_or_default()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.elapsed()).unwrap_or(chrono::Duration::MAX);
+        self.start_time + elapsed
+    }
+
+    fn instant(&self) -> Instant {
+        self.start_instant + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_advanced() {
+        let start = Utc::now();
+        let clock = MockClock::starting_at(start);
+        let instant = clock.instant();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(90));
+        assert_eq!(clock.instant() - instant, Duration::from_secs(90));
+        assert_eq!(clock.elapsed(), Duration::from_secs(90));
+    }
+}
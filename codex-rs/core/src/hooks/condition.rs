@@ -0,0 +1,518 @@
+//! Condition expressions deciding whether a hook runs for an event.
+//!
+//! A condition compares event fields and environment variables against literals:
+//!
+//! ```text
+//! exit_code != 0 && (command.contains('cargo') || env.CI == true)
+//! ```
+//!
+//! Operators, loosest binding first: `||`, `&&`, `!`, then comparisons
+//! (`==`, `!=`, `<`, `<=`, `>`, `>=`) and `field.contains('text')`. Parentheses
+//! group. A field on its own is true when its value is `true`. Values compare
+//! numerically when both sides are numbers and as strings otherwise; ordering
+//! comparisons need a numeric literal.
+//!
+//! Fields an event does not carry, and unset environment variables, have no
+//! value: comparisons against them are false except `!=`, which is true.
+//!
+//! Malformed conditions, including unknown fields, are rejected by
+//! [`ConditionEvaluator::parse`] so they surface when hooks are loaded.
+//!
+//! Command hooks can also filter on the command line with a [`CommandPattern`].
+
+use std::cmp::Ordering;
+
+use regex::Regex;
+use wildmatch::WildMatch;
+
+use crate::hooks::context::HookContext;
+use crate::hooks::types::{HookError, LifecycleEvent};
+
+/// Prefix marking a command pattern as a regular expression instead of a glob.
+pub const REGEX_PATTERN_PREFIX: &str = "regex:";
+
+/// Event fields that conditions can refer to, besides `env.<NAME>`.
+pub const CONDITION_FIELDS: &[&str] = &[
+    "event_type",
+    "success",
+    "exit_code",
+    "duration_ms",
+    "task_id",
+    "session_id",
+    "call_id",
+    "model",
+    "prompt",
+    "command",
+    "cwd",
+    "stdout",
+    "stderr",
+    "output",
+    "server",
+    "tool",
+    "message",
+    "reasoning",
+    "error",
+];
+
+/// A parsed hook condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionEvaluator {
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Truthy(String),
+    Compare { field: String, op: Comparison, value: String },
+    Contains { field: String, value: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Compare(Comparison),
+    Word(String),
+    Quoted(String),
+}
+
+impl ConditionEvaluator {
+    /// Parse a condition, failing with a configuration error if it is malformed.
+    pub fn parse(condition: &str) -> Result<Self, HookError> {
+        let invalid = |reason: String| HookError::Configuration(format!("Invalid hook condition '{}': {}", condition, reason));
+
+        if condition.trim().is_empty() {
+            return Err(HookError::Configuration("Hook condition cannot be empty".to_string()));
+        }
+
+        let tokens = tokenize(condition).map_err(invalid)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.parse_or().map_err(invalid)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected {:?}", token)));
+        }
+
+        Ok(Self { expr })
+    }
+
+    /// Evaluate the condition against an event and its hook context.
+    pub fn evaluate(&self, event: &LifecycleEvent, context: &HookContext) -> bool {
+        evaluate(&self.expr, event, context)
+    }
+}
+
+/// Pattern selecting which commands a command hook fires for.
+///
+/// The command line is matched as its arguments joined by single spaces. A glob
+/// (`*`, `?`) must match the whole command line; a `regex:` pattern matches
+/// anywhere in it unless anchored with `^` and `$`. Matching is case-sensitive;
+/// prefix a regex with `(?i)` to ignore case.
+#[derive(Debug, Clone)]
+pub enum CommandPattern {
+    Glob(WildMatch),
+    Regex(Regex),
+}
+
+impl CommandPattern {
+    /// Parse a pattern, failing with a configuration error if it is empty or an invalid regex.
+    pub fn parse(pattern: &str) -> Result<Self, HookError> {
+        if let Some(regex) = pattern.strip_prefix(REGEX_PATTERN_PREFIX) {
+            return Regex::new(regex)
+                .map(CommandPattern::Regex)
+                .map_err(|e| HookError::Configuration(format!("Invalid command pattern '{}': {}", pattern, e)));
+        }
+        if pattern.trim().is_empty() {
+            return Err(HookError::Configuration("Command pattern cannot be empty".to_string()));
+        }
+        Ok(CommandPattern::Glob(WildMatch::new(pattern)))
+    }
+
+    /// Whether a command line matches the pattern.
+    pub fn matches(&self, command: &str) -> bool {
+        match self {
+            CommandPattern::Glob(glob) => glob.matches(command),
+            CommandPattern::Regex(regex) => regex.is_match(command),
+        }
+    }
+}
+
+/// The command line of an `exec_before`/`exec_after` event.
+pub fn event_command(event: &LifecycleEvent) -> Option<String> {
+    match event {
+        LifecycleEvent::ExecBefore { command, .. } | LifecycleEvent::ExecAfter { command, .. } => Some(command.join(" ")),
+        _ => None,
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(format!("expected '{}{}'", c, c));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.next_if_eq(&'=').is_some();
+                let token = match (c, followed_by_eq) {
+                    ('=', true) => Token::Compare(Comparison::Eq),
+                    ('!', true) => Token::Compare(Comparison::Ne),
+                    ('<', true) => Token::Compare(Comparison::Le),
+                    ('>', true) => Token::Compare(Comparison::Ge),
+                    ('<', false) => Token::Compare(Comparison::Lt),
+                    ('>', false) => Token::Compare(Comparison::Gt),
+                    ('!', false) => Token::Not,
+                    _ => return Err("expected '=='".to_string()),
+                };
+                tokens.push(token);
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => value.push(next),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(next) = chars.next_if(|next| !next.is_whitespace() && !"()&|=!<>'\"".contains(*next)) {
+                    word.push(next);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Word(word)) if word == "true" => Ok(Expr::Literal(true)),
+            Some(Token::Word(word)) if word == "false" => Ok(Expr::Literal(false)),
+            Some(Token::Word(word)) => {
+                if let Some(field) = word.strip_suffix(".contains") {
+                    let field = known_field(field)?;
+                    if self.next() != Some(Token::LParen) {
+                        return Err(format!("expected '(' after '{}'", word));
+                    }
+                    let value = self.parse_value()?;
+                    if self.next() != Some(Token::RParen) {
+                        return Err(format!("missing ')' after '{}'", word));
+                    }
+                    return Ok(Expr::Contains { field, value });
+                }
+
+                let field = known_field(&word)?;
+                let Some(Token::Compare(op)) = self.peek().cloned() else {
+                    return Ok(Expr::Truthy(field));
+                };
+                self.next();
+                let value = self.parse_value()?;
+                if !matches!(op, Comparison::Eq | Comparison::Ne) && value.parse::<f64>().is_err() {
+                    return Err(format!("'{}' is not a number", value));
+                }
+                Ok(Expr::Compare { field, op, value })
+            }
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of condition".to_string()),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => Ok(value),
+            Some(token) => Err(format!("expected a value, found {:?}", token)),
+            None => Err("expected a value".to_string()),
+        }
+    }
+}
+
+/// Check that a field name refers to something conditions can read.
+fn known_field(field: &str) -> Result<String, String> {
+    let known = match field.strip_prefix("env.") {
+        Some(name) => !name.is_empty(),
+        None => CONDITION_FIELDS.contains(&field),
+    };
+    if known {
+        Ok(field.to_string())
+    } else {
+        Err(format!("unknown field '{}'", field))
+    }
+}
+
+fn evaluate(expr: &Expr, event: &LifecycleEvent, context: &HookContext) -> bool {
+    match expr {
+        Expr::Literal(value) => *value,
+        Expr::Not(inner) => !evaluate(inner, event, context),
+        Expr::And(left, right) => evaluate(left, event, context) && evaluate(right, event, context),
+        Expr::Or(left, right) => evaluate(left, event, context) || evaluate(right, event, context),
+        Expr::Truthy(field) => field_value(field, event, context).is_some_and(|value| value == "true"),
+        Expr::Contains { field, value } => {
+            field_value(field, event, context).is_some_and(|field_value| field_value.contains(value.as_str()))
+        }
+        Expr::Compare { field, op, value } => {
+            let Some(field_value) = field_value(field, event, context) else {
+                return *op == Comparison::Ne;
+            };
+            match op {
+                Comparison::Eq => compare(&field_value, value) == Some(Ordering::Equal),
+                Comparison::Ne => compare(&field_value, value) != Some(Ordering::Equal),
+                Comparison::Lt => numeric_compare(&field_value, value) == Some(Ordering::Less),
+                Comparison::Le => matches!(numeric_compare(&field_value, value), Some(Ordering::Less | Ordering::Equal)),
+                Comparison::Gt => numeric_compare(&field_value, value) == Some(Ordering::Greater),
+                Comparison::Ge => matches!(numeric_compare(&field_value, value), Some(Ordering::Greater | Ordering::Equal)),
+            }
+        }
+    }
+}
+
+/// Compare numerically when both sides are numbers, otherwise as strings.
+fn compare(left: &str, right: &str) -> Option<Ordering> {
+    numeric_compare(left, right).or_else(|| Some(left.cmp(right)))
+}
+
+fn numeric_compare(left: &str, right: &str) -> Option<Ordering> {
+    let left = left.trim().parse::<f64>().ok()?;
+    let right = right.trim().parse::<f64>().ok()?;
+    left.partial_cmp(&right)
+}
+
+/// Read a field from the event, or an `env.<NAME>` variable from the context.
+pub fn field_value(field: &str, event: &LifecycleEvent, context: &HookContext) -> Option<String> {
+    if let Some(name) = field.strip_prefix("env.") {
+        return context.get_env(name).cloned();
+    }
+
+    match (field, event) {
+        ("event_type", _) => Some(event.event_type().to_string()),
+        ("success", LifecycleEvent::TaskComplete { success, .. })
+        | ("success", LifecycleEvent::PatchAfter { success, .. })
+        | ("success", LifecycleEvent::McpToolAfter { success, .. }) => Some(success.to_string()),
+        ("success", LifecycleEvent::ExecAfter { exit_code, .. }) => Some((*exit_code == 0).to_string()),
+        ("exit_code", LifecycleEvent::ExecAfter { exit_code, .. }) => Some(exit_code.to_string()),
+        ("duration_ms", LifecycleEvent::SessionEnd { duration, .. })
+        | ("duration_ms", LifecycleEvent::TaskComplete { duration, .. })
+        | ("duration_ms", LifecycleEvent::ExecAfter { duration, .. })
+        | ("duration_ms", LifecycleEvent::PatchAfter { duration, .. })
+        | ("duration_ms", LifecycleEvent::McpToolAfter { duration, .. }) => Some(duration.as_millis().to_string()),
+        ("task_id", _) => event.task_id().map(str::to_string),
+        ("session_id", LifecycleEvent::SessionStart { session_id, .. })
+        | ("session_id", LifecycleEvent::SessionEnd { session_id, .. })
+        | ("session_id", LifecycleEvent::TaskStart { session_id, .. })
+        | ("session_id", LifecycleEvent::TaskComplete { session_id, .. }) => Some(session_id.clone()),
+        ("call_id", LifecycleEvent::ExecBefore { call_id, .. })
+        | ("call_id", LifecycleEvent::ExecAfter { call_id, .. })
+        | ("call_id", LifecycleEvent::PatchBefore { call_id, .. })
+        | ("call_id", LifecycleEvent::PatchAfter { call_id, .. })
+        | ("call_id", LifecycleEvent::McpToolBefore { call_id, .. })
+        | ("call_id", LifecycleEvent::McpToolAfter { call_id, .. }) => Some(call_id.clone()),
+        ("model", LifecycleEvent::SessionStart { model, .. }) => Some(model.clone()),
+        ("prompt", LifecycleEvent::TaskStart { prompt, .. }) => Some(prompt.clone()),
+        ("command", _) => event_command(event),
+        ("cwd", LifecycleEvent::SessionStart { cwd, .. })
+        | ("cwd", LifecycleEvent::ExecBefore { cwd, .. }) => Some(cwd.display().to_string()),
+        ("stdout", LifecycleEvent::ExecAfter { stdout, .. }) => Some(stdout.clone()),
+        ("stderr", LifecycleEvent::ExecAfter { stderr, .. }) => Some(stderr.clone()),
+        ("output", LifecycleEvent::TaskComplete { output, .. }) => output.clone(),
+        ("server", LifecycleEvent::McpToolBefore { server, .. })
+        | ("server", LifecycleEvent::McpToolAfter { server, .. }) => Some(server.clone()),
+        ("tool", LifecycleEvent::McpToolBefore { tool, .. })
+        | ("tool", LifecycleEvent::McpToolAfter { tool, .. }) => Some(tool.clone()),
+        ("message", LifecycleEvent::AgentMessage { message, .. }) => Some(message.clone()),
+        ("reasoning", LifecycleEvent::AgentMessage { reasoning, .. }) => reasoning.clone(),
+        ("error", LifecycleEvent::ErrorOccurred { error, .. }) => Some(error.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn exec_after(exit_code: i32) -> LifecycleEvent {
+        LifecycleEvent::ExecAfter {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: vec!["cargo".to_string(), "test".to_string()],
+            exit_code,
+            stdout: String::new(),
+            stderr: "error: test failed".to_string(),
+            duration: Duration::from_millis(1500),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    fn holds(condition: &str, event: &LifecycleEvent) -> bool {
+        let context = HookContext::new(event.clone(), PathBuf::from("/tmp"))
+            .with_env("CI".to_string(), "true".to_string());
+        ConditionEvaluator::parse(condition).unwrap().evaluate(event, &context)
+    }
+
+    #[test]
+    fn test_exit_code_condition_on_command_complete() {
+        let failed = exec_after(101);
+        let passed = exec_after(0);
+
+        assert!(holds("exit_code != 0", &failed));
+        assert!(!holds("exit_code != 0", &passed));
+        assert!(holds("exit_code >= 100 && stderr.contains('failed')", &failed));
+        assert!(!holds("exit_code > 101", &failed));
+        assert!(holds("success", &passed));
+        assert!(!holds("success", &failed));
+        assert!(holds("duration_ms < 2000", &failed));
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        let event = exec_after(1);
+        assert!(holds("exit_code == 0 && false || true", &event));
+        assert!(!holds("exit_code == 0 && (false || true)", &event));
+        assert!(holds("!(exit_code == 0) && env.CI == true", &event));
+        assert!(holds("command.contains(\"cargo test\") || tool == 'missing'", &event));
+    }
+
+    #[test]
+    fn test_missing_fields_only_satisfy_not_equal() {
+        let event = exec_after(0);
+        assert!(!holds("tool == shell", &event));
+        assert!(holds("tool != shell", &event));
+        assert!(!holds("env.UNSET == ''", &event));
+        assert!(!holds("message.contains('x')", &event));
+    }
+
+    #[test]
+    fn test_command_patterns() {
+        let git_commit = "git commit -m 'fix bug'";
+        let cargo_build = "cargo build --release";
+
+        let glob = CommandPattern::parse("git *").unwrap();
+        assert!(glob.matches(git_commit));
+        assert!(!glob.matches(cargo_build));
+        // Globs are anchored and case-sensitive
+        assert!(!CommandPattern::parse("commit").unwrap().matches(git_commit));
+        assert!(!CommandPattern::parse("GIT *").unwrap().matches(git_commit));
+
+        let regex = CommandPattern::parse("regex:^git (commit|push)\\b").unwrap();
+        assert!(regex.matches(git_commit));
+        assert!(!regex.matches(cargo_build));
+        // Regexes match anywhere unless anchored
+        assert!(CommandPattern::parse("regex:commit").unwrap().matches(git_commit));
+        assert!(CommandPattern::parse("regex:(?i)^GIT").unwrap().matches(git_commit));
+
+        assert!(matches!(CommandPattern::parse("regex:git ("), Err(HookError::Configuration(_))));
+        assert!(matches!(CommandPattern::parse(" "), Err(HookError::Configuration(_))));
+    }
+
+    #[test]
+    fn test_malformed_conditions_are_configuration_errors() {
+        for condition in [
+            "",
+            "exit_code ==",
+            "exit_code = 0",
+            "(exit_code == 0",
+            "exit_code == 0 &",
+            "exit_code > high",
+            "status == ok",
+            "message.contains('x'",
+            "true false",
+        ] {
+            assert!(
+                matches!(ConditionEvaluator::parse(condition), Err(HookError::Configuration(_))),
+                "{:?} should not parse",
+                condition
+            );
+        }
+    }
+}
@@ -1,11 +1,14 @@
 //! Hook configuration parsing and validation.
 
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookType, LifecycleEventType};
+use crate::hooks::condition::{CommandPattern, ConditionEvaluator};
+use crate::hooks::proxy::ProxySettings;
+use crate::hooks::types::{FileSystemOperation, HookError, HookExecutionMode, HookPriority, HookType, LifecycleEventType};
 
 /// Main hooks configuration structure.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -23,6 +26,9 @@ pub struct GlobalHooksConfig {
     pub enabled: bool,
 
     /// Default timeout for hook execution.
+    ///
+    /// Applies to hooks without a timeout of their own. Left at its default of 30 seconds,
+    /// each executor's own default timeout applies instead.
     #[serde(default = "default_timeout_seconds")]
     pub timeout_seconds: u64,
 
@@ -61,6 +67,19 @@ pub struct GlobalHooksConfig {
     /// Custom integration hooks.
     #[serde(default)]
     pub integration: Vec<HookConfig>,
+
+    /// Proxy used for outbound HTTP from webhook hooks.
+    #[serde(default)]
+    pub proxy: ProxySettings,
+
+    /// Settings shared by message queue hooks.
+    #[serde(default)]
+    pub message_queue: MessageQueueSettings,
+
+    /// Suppress webhook and message queue sends identical to one delivered to the same
+    /// destination within this many seconds; every send goes out when unset.
+    #[serde(default)]
+    pub payload_dedup_window_seconds: Option<u64>,
 }
 
 impl Default for GlobalHooksConfig {
@@ -77,10 +96,36 @@ impl Default for GlobalHooksConfig {
             agent: Vec::new(),
             error: Vec::new(),
             integration: Vec::new(),
+            proxy: ProxySettings::default(),
+            message_queue: MessageQueueSettings::default(),
+            payload_dedup_window_seconds: None,
         }
     }
 }
 
+/// Settings shared by message queue hooks.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MessageQueueSettings {
+    /// Batch messages per destination; each message is sent on its own when unset.
+    #[serde(default)]
+    pub batching: Option<MessageBatchingConfig>,
+
+    /// Send SQS messages to this endpoint instead of the regional AWS endpoint.
+    #[serde(default)]
+    pub sqs_endpoint: Option<String>,
+}
+
+/// Batching of message queue sends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MessageBatchingConfig {
+    /// Send a batch once it holds this many messages.
+    pub max_batch: usize,
+
+    /// Send a batch this long after its first message, even if it is not full.
+    #[serde(default = "default_batch_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+}
+
 /// Configuration for a single hook.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HookConfig {
@@ -105,6 +150,12 @@ pub struct HookConfig {
     /// Optional condition for conditional execution.
     pub condition: Option<String>,
 
+    /// Only fire for commands matching this glob, or regex when prefixed with `regex:`.
+    ///
+    /// Only valid for `exec_before` and `exec_after` hooks.
+    #[serde(default)]
+    pub command_pattern: Option<String>,
+
     /// Whether this hook should block execution if it fails.
     #[serde(default)]
     pub blocking: bool,
@@ -134,6 +185,10 @@ pub struct HookConfig {
 
     /// Timeout for this specific hook (overrides global timeout).
     pub timeout: Option<Duration>,
+
+    /// Hook to run instead when this hook's circuit is open or it fails after retries.
+    #[serde(default)]
+    pub fallback: Option<Box<HookConfig>>,
 }
 
 impl HookConfig {
@@ -173,6 +228,33 @@ impl HookConfig {
                 "executable".hash(&mut hasher);
                 path.hash(&mut hasher);
             }
+            HookType::Database { connection_string, query, .. } => {
+                "database".hash(&mut hasher);
+                connection_string.hash(&mut hasher);
+                query.hash(&mut hasher);
+            }
+            HookType::MessageQueue { queue_url, message, .. } => {
+                "message_queue".hash(&mut hasher);
+                queue_url.hash(&mut hasher);
+                message.hash(&mut hasher);
+            }
+            HookType::FileSystem { operation, path, .. } => {
+                "file_system".hash(&mut hasher);
+                format!("{:?}", operation).hash(&mut hasher);
+                path.hash(&mut hasher);
+            }
+            HookType::CustomPlugin { plugin_name, .. } => {
+                "custom_plugin".hash(&mut hasher);
+                plugin_name.hash(&mut hasher);
+            }
+            HookType::PatchAnalysis { .. } => {
+                "patch_analysis".hash(&mut hasher);
+            }
+            HookType::DesktopNotification { title, body, .. } => {
+                "desktop_notification".hash(&mut hasher);
+                title.hash(&mut hasher);
+                body.hash(&mut hasher);
+            }
         }
 
         format!("hook_{:x}", hasher.finish())
@@ -184,7 +266,7 @@ impl HookConfig {
             // Create a temporary copy to generate ID
             let mut temp = self.clone();
             temp.ensure_id();
-            temp.id.unwrap()
+            temp.id.unwrap_or_default()
         })
     }
 
@@ -227,6 +309,20 @@ impl HookConfig {
                     )));
                 }
             }
+            // Validated by their executors when the hook runs
+            HookType::Database { .. }
+            | HookType::MessageQueue { .. }
+            | HookType::FileSystem { .. }
+            | HookType::CustomPlugin { .. }
+            | HookType::PatchAnalysis { .. }
+            | HookType::DesktopNotification { .. } => {}
+        }
+
+        if let HookType::Script { resource_limits: Some(limits), .. }
+        | HookType::Executable { resource_limits: Some(limits), .. }
+        | HookType::CustomPlugin { resource_limits: Some(limits), .. } = &self.hook_type
+        {
+            limits.validate()?;
         }
 
         // Validate condition syntax if present
@@ -234,6 +330,16 @@ impl HookConfig {
             self.validate_condition(condition)?;
         }
 
+        if let Some(pattern) = &self.command_pattern {
+            if !matches!(self.event, LifecycleEventType::ExecBefore | LifecycleEventType::ExecAfter) {
+                return Err(HookError::Configuration(format!(
+                    "Command pattern is only supported for exec_before and exec_after hooks, not {}",
+                    self.event
+                )));
+            }
+            CommandPattern::parse(pattern)?;
+        }
+
         // Validate dependencies
         for dep_id in &self.depends_on {
             if dep_id.trim().is_empty() {
@@ -253,31 +359,33 @@ impl HookConfig {
         Ok(())
     }
 
-    /// Validate condition syntax (basic validation for now).
+    /// Validate condition syntax.
     fn validate_condition(&self, condition: &str) -> Result<(), HookError> {
-        // Basic validation - ensure condition is not empty
-        if condition.trim().is_empty() {
-            return Err(HookError::Configuration(
-                "Hook condition cannot be empty".to_string(),
-            ));
-        }
-
-        // TODO: Implement more sophisticated condition parsing and validation
-        // For now, we just check for basic syntax
-
-        Ok(())
+        ConditionEvaluator::parse(condition).map(|_| ())
     }
 
-    /// Get the timeout for this hook, falling back to the provided default.
-    pub fn get_timeout(&self, default_timeout: Duration) -> Duration {
-        match &self.hook_type {
+    /// Timeout set explicitly for this hook, if any.
+    ///
+    /// `HookConfig::timeout` takes precedence over the timeout embedded in the
+    /// hook type; `None` means the executor default applies.
+    pub fn timeout_override(&self) -> Option<Duration> {
+        self.timeout.or(match &self.hook_type {
             HookType::Script { timeout, .. }
             | HookType::Webhook { timeout, .. }
             | HookType::McpTool { timeout, .. }
-            | HookType::Executable { timeout, .. } => {
-                timeout.unwrap_or(default_timeout)
-            }
-        }
+            | HookType::Executable { timeout, .. }
+            | HookType::Database { timeout, .. }
+            | HookType::MessageQueue { timeout, .. }
+            | HookType::FileSystem { timeout, .. }
+            | HookType::CustomPlugin { timeout, .. }
+            | HookType::PatchAnalysis { timeout }
+            | HookType::DesktopNotification { timeout, .. } => *timeout,
+        })
+    }
+
+    /// Get the timeout for this hook, falling back to the provided default.
+    pub fn get_timeout(&self, default_timeout: Duration) -> Duration {
+        self.timeout_override().unwrap_or(default_timeout)
     }
 }
 
@@ -317,6 +425,191 @@ pub fn validate_hooks_config(config: &HooksConfig) -> Result<(), HookError> {
     Ok(())
 }
 
+/// Class of configuration mistake found by `validate_hook_set`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HookWarningKind {
+    /// Hooks whose `depends_on` form a cycle.
+    DependencyCycle,
+    /// A fire-and-forget hook that is also marked required or blocking.
+    ContradictoryMode,
+    /// More than one hook with the same ID.
+    DuplicateId,
+    /// Filesystem hooks for the same event that delete or move a path other hooks also use.
+    ConflictingFileOperations,
+}
+
+impl HookWarningKind {
+    /// Stable name used in logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookWarningKind::DependencyCycle => "dependency_cycle",
+            HookWarningKind::ContradictoryMode => "contradictory_mode",
+            HookWarningKind::DuplicateId => "duplicate_id",
+            HookWarningKind::ConflictingFileOperations => "conflicting_file_operations",
+        }
+    }
+}
+
+/// A likely configuration mistake spanning one or more hooks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookWarning {
+    /// Class of mistake.
+    pub kind: HookWarningKind,
+    /// IDs of the hooks involved.
+    pub hook_ids: Vec<String>,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+impl HookWarning {
+    fn new(kind: HookWarningKind, hook_ids: Vec<String>, message: String) -> Self {
+        Self { kind, hook_ids, message }
+    }
+}
+
+impl std::fmt::Display for HookWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.kind.as_str(), self.message)
+    }
+}
+
+/// Check a set of hooks for mistakes that individual `HookConfig::validate` calls cannot see.
+///
+/// Flags dependency cycles, fire-and-forget hooks marked required or blocking, duplicate IDs,
+/// and filesystem hooks on the same event and path where one deletes or moves the path.
+pub fn validate_hook_set(hooks: &[HookConfig]) -> Vec<HookWarning> {
+    let mut warnings = Vec::new();
+
+    // Duplicate IDs, in first-seen order
+    let mut id_counts: Vec<(String, usize)> = Vec::new();
+    for hook in hooks {
+        let id = hook.get_id();
+        match id_counts.iter_mut().find(|(seen, _)| *seen == id) {
+            Some((_, count)) => *count += 1,
+            None => id_counts.push((id, 1)),
+        }
+    }
+    for (id, count) in id_counts.into_iter().filter(|(_, count)| *count > 1) {
+        warnings.push(HookWarning::new(
+            HookWarningKind::DuplicateId,
+            vec![id.clone()],
+            format!("Hook ID '{}' is used by {} hooks", id, count),
+        ));
+    }
+
+    // Contradictory mode and required/blocking combinations
+    for hook in hooks.iter().filter(|hook| hook.mode == HookExecutionMode::FireAndForget) {
+        let flags: Vec<_> = [("required", hook.required), ("blocking", hook.blocking)]
+            .into_iter()
+            .filter_map(|(name, set)| set.then_some(name))
+            .collect();
+        if !flags.is_empty() {
+            warnings.push(HookWarning::new(
+                HookWarningKind::ContradictoryMode,
+                vec![hook.get_id()],
+                format!(
+                    "Hook '{}' is fire-and-forget but marked {}; its result is never awaited",
+                    hook.get_id(),
+                    flags.join(" and ")
+                ),
+            ));
+        }
+    }
+
+    warnings.extend(find_dependency_cycles(hooks));
+    warnings.extend(find_file_conflicts(hooks));
+    warnings
+}
+
+/// Report each cycle in the `depends_on` graph once.
+fn find_dependency_cycles(hooks: &[HookConfig]) -> Vec<HookWarning> {
+    let dependencies: HashMap<String, &[String]> =
+        hooks.iter().map(|hook| (hook.get_id(), hook.depends_on.as_slice())).collect();
+
+    fn visit(
+        id: &str,
+        dependencies: &HashMap<String, &[String]>,
+        path: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(start) = path.iter().position(|on_path| on_path == id) {
+            cycles.push(path[start..].to_vec());
+            return;
+        }
+        if done.contains(id) {
+            return;
+        }
+
+        path.push(id.to_string());
+        for dep in dependencies.get(id).copied().unwrap_or_default() {
+            visit(dep, dependencies, path, done, cycles);
+        }
+        path.pop();
+        done.insert(id.to_string());
+    }
+
+    let mut done = HashSet::new();
+    let mut cycles = Vec::new();
+    for hook in hooks {
+        visit(&hook.get_id(), &dependencies, &mut Vec::new(), &mut done, &mut cycles);
+    }
+
+    cycles
+        .into_iter()
+        .map(|cycle| {
+            let mut chain = cycle.clone();
+            chain.push(cycle[0].clone());
+            HookWarning::new(
+                HookWarningKind::DependencyCycle,
+                cycle,
+                format!("Hooks depend on each other in a cycle: {}", chain.join(" -> ")),
+            )
+        })
+        .collect()
+}
+
+/// Report paths that one filesystem hook deletes or moves while another hook on the same event uses them.
+fn find_file_conflicts(hooks: &[HookConfig]) -> Vec<HookWarning> {
+    let mut by_path: Vec<((LifecycleEventType, &PathBuf), Vec<(String, &FileSystemOperation)>)> = Vec::new();
+    for hook in hooks {
+        let HookType::FileSystem { operation, path, .. } = &hook.hook_type else {
+            continue;
+        };
+        let key = (hook.event, path);
+        match by_path.iter_mut().find(|(seen, _)| *seen == key) {
+            Some((_, users)) => users.push((hook.get_id(), operation)),
+            None => by_path.push((key, vec![(hook.get_id(), operation)])),
+        }
+    }
+
+    let removes_path = |operation: &FileSystemOperation| {
+        matches!(operation, FileSystemOperation::Delete | FileSystemOperation::Move)
+    };
+
+    by_path
+        .into_iter()
+        .filter(|(_, users)| users.len() > 1 && users.iter().any(|(_, operation)| removes_path(operation)))
+        .map(|((event, path), users)| {
+            let operations: Vec<_> = users
+                .iter()
+                .map(|(id, operation)| format!("{} ({})", id, format!("{:?}", operation).to_lowercase()))
+                .collect();
+            HookWarning::new(
+                HookWarningKind::ConflictingFileOperations,
+                users.into_iter().map(|(id, _)| id).collect(),
+                format!(
+                    "Hooks on {} conflict over {}: {}",
+                    event,
+                    path.display(),
+                    operations.join(", ")
+                ),
+            )
+        })
+        .collect()
+}
+
 /// Get all hooks for a specific event type from the configuration.
 pub fn get_hooks_for_event(
     config: &HooksConfig,
@@ -354,10 +647,14 @@ fn default_enabled() -> bool {
     true
 }
 
-fn default_timeout_seconds() -> u64 {
+pub(crate) fn default_timeout_seconds() -> u64 {
     30
 }
 
+fn default_batch_flush_interval_ms() -> u64 {
+    100
+}
+
 fn default_parallel_execution() -> bool {
     true
 }
@@ -368,6 +665,7 @@ fn default_parallel() -> bool {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use std::collections::HashMap;
 
@@ -400,12 +698,14 @@ priority = 100
     #[test]
     fn test_hook_validation() {
         let hook = HookConfig {
+            id: None,
             event: LifecycleEventType::TaskStart,
             hook_type: HookType::Script {
                 command: vec!["echo".to_string(), "test".to_string()],
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: None,
+                resource_limits: None,
             },
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
@@ -414,6 +714,12 @@ priority = 100
             required: false,
             tags: Vec::new(),
             description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
         };
 
         assert!(hook.validate().is_ok());
@@ -422,12 +728,14 @@ priority = 100
     #[test]
     fn test_invalid_hook_validation() {
         let hook = HookConfig {
+            id: None,
             event: LifecycleEventType::TaskStart,
             hook_type: HookType::Script {
                 command: vec![], // Empty command should fail validation
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: None,
+                resource_limits: None,
             },
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
@@ -436,8 +744,137 @@ priority = 100
             required: false,
             tags: Vec::new(),
             description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
         };
 
         assert!(hook.validate().is_err());
     }
+
+    fn create_set_hook(id: &str, hook_type: HookType) -> HookConfig {
+        HookConfig {
+            id: Some(id.to_string()),
+            event: LifecycleEventType::TaskComplete,
+            hook_type,
+            mode: HookExecutionMode::Async,
+            priority: HookPriority::NORMAL,
+            condition: None,
+            blocking: false,
+            required: false,
+            tags: Vec::new(),
+            description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
+        }
+    }
+
+    fn script(id: &str) -> HookConfig {
+        create_set_hook(id, HookType::Script {
+            command: vec!["true".to_string()],
+            cwd: None,
+            environment: HashMap::new(),
+            timeout: None,
+            resource_limits: None,
+        })
+    }
+
+    fn file_op(id: &str, operation: FileSystemOperation, path: &str) -> HookConfig {
+        create_set_hook(id, HookType::FileSystem {
+            operation,
+            path: PathBuf::from(path),
+            target_path: None,
+            content: None,
+            timeout: None,
+            permissions: None,
+            max_events: None,
+            atomic: false,
+        })
+    }
+
+    fn kinds(warnings: &[HookWarning]) -> Vec<HookWarningKind> {
+        warnings.iter().map(|warning| warning.kind).collect()
+    }
+
+    #[test]
+    fn test_hook_set_without_conflicts() {
+        let mut notify = script("notify");
+        notify.depends_on = vec!["build".to_string()];
+        let hooks = vec![
+            script("build"),
+            notify,
+            file_op("write_report", FileSystemOperation::Write, "/tmp/report.txt"),
+            file_op("read_report", FileSystemOperation::Read, "/tmp/report.txt"),
+        ];
+
+        assert!(validate_hook_set(&hooks).is_empty());
+    }
+
+    #[test]
+    fn test_hook_set_flags_dependency_cycle() {
+        let mut a = script("a");
+        a.depends_on = vec!["b".to_string()];
+        let mut b = script("b");
+        b.depends_on = vec!["c".to_string()];
+        let mut c = script("c");
+        c.depends_on = vec!["a".to_string()];
+        let mut own = script("own");
+        own.depends_on = vec!["own".to_string()];
+
+        let warnings = validate_hook_set(&[a, b, c, own, script("unrelated")]);
+        assert_eq!(kinds(&warnings), vec![HookWarningKind::DependencyCycle, HookWarningKind::DependencyCycle]);
+        assert_eq!(warnings[0].hook_ids, vec!["a", "b", "c"]);
+        assert_eq!(warnings[0].message, "Hooks depend on each other in a cycle: a -> b -> c -> a");
+        assert_eq!(warnings[1].hook_ids, vec!["own"]);
+    }
+
+    #[test]
+    fn test_hook_set_flags_contradictory_mode() {
+        let mut required = script("required_ff");
+        required.mode = HookExecutionMode::FireAndForget;
+        required.required = true;
+        let mut blocking = script("blocking_ff");
+        blocking.mode = HookExecutionMode::FireAndForget;
+        blocking.blocking = true;
+        let mut fine = script("plain_ff");
+        fine.mode = HookExecutionMode::FireAndForget;
+
+        let warnings = validate_hook_set(&[required, blocking, fine]);
+        assert_eq!(kinds(&warnings), vec![HookWarningKind::ContradictoryMode; 2]);
+        assert_eq!(warnings[0].hook_ids, vec!["required_ff"]);
+        assert!(warnings[0].message.contains("marked required"));
+        assert_eq!(warnings[1].hook_ids, vec!["blocking_ff"]);
+    }
+
+    #[test]
+    fn test_hook_set_flags_duplicate_ids() {
+        let warnings = validate_hook_set(&[script("dup"), script("unique"), script("dup"), script("dup")]);
+        assert_eq!(kinds(&warnings), vec![HookWarningKind::DuplicateId]);
+        assert_eq!(warnings[0].hook_ids, vec!["dup"]);
+        assert_eq!(warnings[0].to_string(), "[duplicate_id] Hook ID 'dup' is used by 3 hooks");
+    }
+
+    #[test]
+    fn test_hook_set_flags_conflicting_file_operations() {
+        let mut other_event = file_op("read_later", FileSystemOperation::Read, "/tmp/cache");
+        other_event.event = LifecycleEventType::SessionEnd;
+        let hooks = vec![
+            file_op("clean", FileSystemOperation::Delete, "/tmp/cache"),
+            file_op("inspect", FileSystemOperation::Read, "/tmp/cache"),
+            file_op("other_path", FileSystemOperation::Read, "/tmp/other"),
+            other_event,
+        ];
+
+        let warnings = validate_hook_set(&hooks);
+        assert_eq!(kinds(&warnings), vec![HookWarningKind::ConflictingFileOperations]);
+        assert_eq!(warnings[0].hook_ids, vec!["clean", "inspect"]);
+        assert_eq!(warnings[0].message, "Hooks on task_complete conflict over /tmp/cache: clean (delete), inspect (read)");
+    }
 }
@@ -2,14 +2,19 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 
 use serde::{Deserialize, Serialize};
 use tempfile::NamedTempFile;
 use tokio::fs;
 
+use crate::hooks::condition::{field_value, CONDITION_FIELDS};
+use crate::hooks::secrets::EnvCapturePolicy;
 use crate::hooks::types::{HookError, HookType, LifecycleEvent};
 
+/// Context metadata key holding the ID of the hook being executed.
+pub const HOOK_ID_METADATA_KEY: &str = "hook_id";
+
 /// Context provided to hooks during execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HookContext {
@@ -27,6 +32,9 @@ pub struct HookContext {
     pub execution_timestamp: SystemTime,
     /// Type of hook being executed.
     pub hook_type: HookType,
+    /// When the current attempt times out, if the executor is running under a timeout.
+    #[serde(skip)]
+    pub deadline: Option<Instant>,
 }
 
 impl HookContext {
@@ -38,6 +46,7 @@ impl HookContext {
             cwd: None,
             environment: HashMap::new(),
             timeout: None,
+            resource_limits: None,
         };
 
         Self {
@@ -48,6 +57,7 @@ impl HookContext {
             working_directory,
             execution_timestamp: SystemTime::now(),
             hook_type: default_hook_type,
+            deadline: None,
         }
     }
 
@@ -75,6 +85,12 @@ impl HookContext {
         self
     }
 
+    /// Set when the current attempt times out.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
     /// Get an environment variable from the context.
     pub fn get_env(&self, key: &str) -> Option<&String> {
         self.environment.get(key)
@@ -85,6 +101,11 @@ impl HookContext {
         self.metadata.get(key)
     }
 
+    /// ID of the hook being executed, when the manager recorded one.
+    pub fn hook_id(&self) -> Option<&str> {
+        self.get_metadata(HOOK_ID_METADATA_KEY).and_then(|id| id.as_str())
+    }
+
     /// Create a temporary file with the given content and register it in the context.
     pub async fn create_temp_file(
         &mut self,
@@ -227,9 +248,58 @@ impl HookExecutionContext {
     }
 }
 
+/// How substituted values are escaped for the place a template is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateEscape {
+    /// Insert values unchanged, e.g. for arguments passed directly to a process.
+    None,
+    /// Single-quote values for a POSIX shell command line.
+    Shell,
+    /// Percent-encode values for a URL.
+    Url,
+    /// Reject values `cmd.exe` would interpret; cmd has no quoting that is safe for
+    /// arbitrary data, so such values fail the render instead.
+    Cmd,
+}
+
+/// Characters `cmd.exe` treats as syntax, even inside double quotes in some cases.
+const CMD_METACHARACTERS: &[char] = &['&', '|', '<', '>', '^', '%', '!', '"', '(', ')', '\r', '\n'];
+
+impl TemplateEscape {
+    fn apply(self, name: &str, value: &str) -> Result<String, HookError> {
+        Ok(match self {
+            TemplateEscape::None => value.to_string(),
+            TemplateEscape::Shell => format!("'{}'", value.replace('\'', r"'\''")),
+            TemplateEscape::Url => value
+                .bytes()
+                .map(|byte| match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+                    _ => format!("%{:02X}", byte),
+                })
+                .collect(),
+            TemplateEscape::Cmd if value.contains(CMD_METACHARACTERS) => {
+                return Err(HookError::Validation(format!(
+                    "Template variable '{}' contains characters cmd.exe would interpret",
+                    name
+                )));
+            }
+            TemplateEscape::Cmd => value.to_string(),
+        })
+    }
+}
+
 /// Template variable substitution for hook configurations.
+///
+/// `substitute` replaces `{name}` placeholders leniently. `render` replaces
+/// `${event.<field>}`, `${env.<NAME>}` and `${temp.<name>}` placeholders and fails on
+/// unknown variables; `$${` yields a literal `${`, and `${NAME}` without a namespace is
+/// left alone for the shell.
 pub struct TemplateSubstitution {
     variables: HashMap<String, String>,
+    /// Values for `render`, keyed by namespaced name.
+    namespaced: HashMap<String, String>,
+    event_type: String,
+    environment: HashMap<String, String>,
 }
 
 impl TemplateSubstitution {
@@ -252,6 +322,12 @@ impl TemplateSubstitution {
                 variables.insert("task_id".to_string(), task_id.clone());
                 variables.insert("prompt".to_string(), prompt.clone());
             }
+            LifecycleEvent::TaskComplete { task_id, success, output, duration, .. } => {
+                variables.insert("task_id".to_string(), task_id.clone());
+                variables.insert("success".to_string(), success.to_string());
+                variables.insert("output".to_string(), output.clone().unwrap_or_default());
+                variables.insert("duration_secs".to_string(), duration.as_secs().to_string());
+            }
             LifecycleEvent::ExecBefore { call_id, command, .. } => {
                 variables.insert("call_id".to_string(), call_id.clone());
                 variables.insert("command".to_string(), command.join(" "));
@@ -269,7 +345,96 @@ impl TemplateSubstitution {
             variables.insert(format!("temp.{}", name), path.to_string_lossy().to_string());
         }
 
-        Self { variables }
+        let mut namespaced: HashMap<String, String> = CONDITION_FIELDS
+            .iter()
+            .filter_map(|field| {
+                field_value(field, &context.event, context).map(|value| (format!("event.{}", field), value))
+            })
+            .collect();
+        for (name, path) in &context.temp_files {
+            namespaced.insert(format!("temp.{}", name), path.to_string_lossy().to_string());
+        }
+        for (name, value) in &context.environment {
+            namespaced.insert(format!("env.{}", name), value.clone());
+        }
+
+        Self {
+            variables,
+            namespaced,
+            event_type: context.event.event_type().to_string(),
+            environment: context.environment.clone(),
+        }
+    }
+
+    /// Expose only the environment variables `policy` captures to `render`, redacting secrets.
+    pub fn with_env_policy(mut self, policy: &EnvCapturePolicy) -> Self {
+        self.namespaced.retain(|name, _| !name.starts_with("env."));
+        for (name, value) in policy.capture(self.environment.clone()) {
+            self.namespaced.insert(format!("env.{}", name), value);
+        }
+        self
+    }
+
+    /// Replace `${namespace.name}` placeholders in `template`, escaping values with `escape`.
+    pub fn render(&self, template: &str, escape: TemplateEscape) -> Result<String, HookError> {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('$') {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            if let Some(escaped) = after.strip_prefix("${") {
+                rendered.push_str("${");
+                rest = escaped;
+            } else if let Some(body) = after.strip_prefix('{') {
+                let end = body.find('}').ok_or_else(|| {
+                    HookError::Configuration(format!("Unterminated template placeholder in '{}'", template))
+                })?;
+                let name = &body[..end];
+                if is_template_name(name) {
+                    rendered.push_str(&escape.apply(name, self.lookup(name)?)?);
+                } else {
+                    rendered.push_str(&rest[start..start + end + 3]);
+                }
+                rest = &body[end + 1..];
+            } else {
+                rendered.push('$');
+                rest = after;
+            }
+        }
+
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+
+    /// Render every string inside `value`, leaving JSON escaping to serialization.
+    pub fn render_json(&self, value: &serde_json::Value) -> Result<serde_json::Value, HookError> {
+        use serde_json::Value;
+
+        Ok(match value {
+            Value::String(template) => Value::String(self.render(template, TemplateEscape::None)?),
+            Value::Array(items) => Value::Array(items.iter().map(|item| self.render_json(item)).collect::<Result<_, _>>()?),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| Ok((key.clone(), self.render_json(item)?)))
+                    .collect::<Result<_, HookError>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+
+    fn lookup(&self, name: &str) -> Result<&str, HookError> {
+        if let Some(value) = self.namespaced.get(name) {
+            return Ok(value);
+        }
+        match name.strip_prefix("event.") {
+            Some(field) if CONDITION_FIELDS.contains(&field) => Err(HookError::Configuration(format!(
+                "Template variable '{}' is not available for {} events",
+                name, self.event_type
+            ))),
+            _ => Err(HookError::Configuration(format!("Unknown template variable '{}'", name))),
+        }
     }
 
     /// Substitute template variables in a string.
@@ -290,8 +455,16 @@ impl TemplateSubstitution {
     }
 }
 
+/// Whether a `${...}` body names a namespaced template variable rather than a shell expansion.
+fn is_template_name(name: &str) -> bool {
+    name.contains('.')
+        && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use chrono::Utc;
 
@@ -344,4 +517,66 @@ mod tests {
         let result = substitution.substitute(template);
         assert_eq!(result, "Task test_task started");
     }
+
+    #[test]
+    fn test_render_namespaced_templates() {
+        let event = LifecycleEvent::ExecAfter {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: vec!["echo".to_string(), "it's done".to_string()],
+            exit_code: 1,
+            duration: std::time::Duration::from_millis(5),
+            stdout: String::new(),
+            stderr: String::new(),
+            timestamp: Utc::now(),
+        };
+        let context = HookContext::new(event, PathBuf::from("/tmp"))
+            .with_env("DEPLOY_USER".to_string(), "alice".to_string())
+            .with_env("BRANCH".to_string(), "main & del /q *".to_string())
+            .with_env("GITHUB_TOKEN".to_string(), "t0ken".to_string());
+        let templates = TemplateSubstitution::from_context(&context);
+
+        let rendered = templates
+            .render("exit ${event.exit_code} by ${env.DEPLOY_USER}", TemplateEscape::None)
+            .unwrap();
+        assert_eq!(rendered, "exit 1 by alice");
+        assert_eq!(
+            templates.render("log ${event.command}", TemplateEscape::Shell).unwrap(),
+            r"log 'echo it'\''s done'"
+        );
+        assert_eq!(
+            templates.render("/runs?cmd=${event.command}", TemplateEscape::Url).unwrap(),
+            "/runs?cmd=echo%20it%27s%20done"
+        );
+
+        // Escapes and shell expansions pass through
+        assert_eq!(
+            templates.render("$${event.exit_code} ${HOME} ${HOST:-a.b} $1 $$", TemplateEscape::None).unwrap(),
+            "${event.exit_code} ${HOME} ${HOST:-a.b} $1 $$"
+        );
+
+        let err = templates.render("${event.session_id}", TemplateEscape::None).unwrap_err();
+        assert!(err.to_string().contains("'event.session_id' is not available for exec_after events"));
+        let err = templates.render("${event.nope} ${env.MISSING}", TemplateEscape::None).unwrap_err();
+        assert!(err.to_string().contains("Unknown template variable 'event.nope'"));
+        assert!(templates.render("${env.MISSING}", TemplateEscape::None).is_err());
+        assert!(templates.render("${event.exit_code", TemplateEscape::None).is_err());
+
+        // cmd.exe cannot quote arbitrary data, so values with its syntax are refused
+        assert_eq!(
+            templates.render("log ${event.command}", TemplateEscape::Cmd).unwrap(),
+            "log echo it's done"
+        );
+        let err = templates.render("log ${env.BRANCH}", TemplateEscape::Cmd).unwrap_err();
+        assert!(err.to_string().contains("'env.BRANCH' contains characters cmd.exe would interpret"));
+
+        // The capture policy hides secrets and variables outside its allow list
+        let policy = EnvCapturePolicy {
+            allow: vec!["GITHUB_*".to_string()],
+            ..Default::default()
+        };
+        let templates = templates.with_env_policy(&policy);
+        assert_eq!(templates.render("${env.GITHUB_TOKEN}", TemplateEscape::None).unwrap(), "<redacted>");
+        assert!(templates.render("${env.DEPLOY_USER}", TemplateEscape::None).is_err());
+    }
 }
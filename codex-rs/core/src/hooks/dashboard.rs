@@ -1,15 +1,18 @@
 //! Hook execution dashboard and monitoring system.
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::time::{Duration, SystemTime};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+#[cfg(feature = "system-metrics")]
+use sysinfo::{Disks, System};
+use tokio::sync::broadcast;
 
-use crate::hooks::metrics::{HookMetrics, MetricsCollector, PerformanceSummary};
-use crate::hooks::history::{HistoryManager, HistorySummary, ExecutionHistoryRecord};
-use crate::hooks::types::{HookError, LifecycleEventType};
+use crate::hooks::metrics::{MetricsCollector, PerformanceSummary};
+use crate::hooks::history::{HistoryManager, HistorySummary};
+use crate::hooks::types::{CancelReason, ExportFormat, HookError, LifecycleEventType, SkipReason};
 
 /// Real-time dashboard for hook execution monitoring.
 #[derive(Debug)]
@@ -22,6 +25,103 @@ pub struct HookDashboard {
     config: DashboardConfig,
     /// Real-time status tracking.
     status_tracker: Arc<RwLock<StatusTracker>>,
+    /// When the dashboard was created, for uptime.
+    started_at: Instant,
+    /// System CPU, memory and disk reader.
+    system_sampler: Arc<Mutex<SystemSampler>>,
+    /// Live feed of dashboard events.
+    event_sender: broadcast::Sender<DashboardEvent>,
+}
+
+/// Memory usage percentage above which the health score is reduced.
+const MEMORY_PRESSURE_THRESHOLD: f64 = 90.0;
+/// CPU usage percentage above which the health score is reduced.
+const CPU_PRESSURE_THRESHOLD: f64 = 90.0;
+/// Disk usage percentage above which the health score is reduced.
+const DISK_PRESSURE_THRESHOLD: f64 = 95.0;
+
+/// Reads system-wide resource usage through `sysinfo`.
+///
+/// Every reading is `None` on platforms `sysinfo` does not support, and in builds without
+/// the `system-metrics` feature.
+#[derive(Debug)]
+struct SystemSampler {
+    #[cfg(feature = "system-metrics")]
+    system: System,
+    #[cfg(feature = "system-metrics")]
+    disks: Disks,
+}
+
+/// One reading of system-wide resource usage, in percent.
+#[derive(Debug, Clone, Copy, Default)]
+struct SystemUsage {
+    cpu: Option<f64>,
+    memory: Option<f64>,
+    disk: Option<f64>,
+}
+
+impl SystemSampler {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "system-metrics")]
+            system: System::new(),
+            #[cfg(feature = "system-metrics")]
+            disks: Disks::new_with_refreshed_list(),
+        }
+    }
+
+    /// Take a reading; this build has no `sysinfo`, so every value is `None`.
+    #[cfg(not(feature = "system-metrics"))]
+    fn sample(&mut self) -> SystemUsage {
+        SystemUsage::default()
+    }
+
+    /// Take a reading. CPU usage is measured since the previous reading, so the
+    /// first one reports `None`.
+    #[cfg(feature = "system-metrics")]
+    fn sample(&mut self) -> SystemUsage {
+        if !sysinfo::IS_SUPPORTED_SYSTEM {
+            return SystemUsage::default();
+        }
+
+        let first_cpu_reading = self.system.cpus().is_empty();
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.disks.refresh(true);
+
+        let cpu = (!first_cpu_reading).then(|| f64::from(self.system.global_cpu_usage()));
+        let total_memory = self.system.total_memory();
+        let memory = (total_memory > 0)
+            .then(|| self.system.used_memory() as f64 / total_memory as f64 * 100.0);
+        let (total_space, available_space) = self
+            .disks
+            .list()
+            .iter()
+            .fold((0u64, 0u64), |(total, available), disk| {
+                (total + disk.total_space(), available + disk.available_space())
+            });
+        let disk = (total_space > 0)
+            .then(|| total_space.saturating_sub(available_space) as f64 / total_space as f64 * 100.0);
+
+        SystemUsage { cpu, memory, disk }
+    }
+}
+
+impl SystemUsage {
+    /// Multiplier applied to the health score for resources under pressure.
+    fn health_factor(&self) -> f64 {
+        let mut factor = 1.0;
+        if self.memory.is_some_and(|memory| memory > MEMORY_PRESSURE_THRESHOLD) {
+            factor *= 0.7;
+        }
+        if self.cpu.is_some_and(|cpu| cpu > CPU_PRESSURE_THRESHOLD) {
+            factor *= 0.9;
+        }
+        if self.disk.is_some_and(|disk| disk > DISK_PRESSURE_THRESHOLD) {
+            factor *= 0.9;
+        }
+        factor
+    }
 }
 
 /// Configuration for the hook dashboard.
@@ -39,6 +139,16 @@ pub struct DashboardConfig {
     pub slow_execution_threshold: Duration,
     /// Threshold for error rate alerts (percentage).
     pub error_rate_threshold: f64,
+    /// Events below this severity are left out of the recent events feed.
+    #[serde(default)]
+    pub min_display_severity: EventSeverity,
+    /// Maximum number of warning-or-worse events kept apart from the recent feed.
+    #[serde(default = "default_max_priority_events")]
+    pub max_priority_events: usize,
+}
+
+fn default_max_priority_events() -> usize {
+    100
 }
 
 impl Default for DashboardConfig {
@@ -50,6 +160,8 @@ impl Default for DashboardConfig {
             enable_notifications: true,
             slow_execution_threshold: Duration::from_secs(10),
             error_rate_threshold: 0.1, // 10%
+            min_display_severity: EventSeverity::Info,
+            max_priority_events: default_max_priority_events(),
         }
     }
 }
@@ -61,6 +173,9 @@ pub struct StatusTracker {
     pub active_executions: HashMap<String, ActiveExecution>,
     /// Recent events for real-time display.
     pub recent_events: Vec<DashboardEvent>,
+    /// Recent events of warning severity or worse, so floods of info events cannot evict them.
+    #[serde(default)]
+    pub priority_events: Vec<DashboardEvent>,
     /// Current system status.
     pub system_status: SystemStatus,
     /// Last update timestamp.
@@ -133,6 +248,8 @@ pub enum DashboardEventType {
     ExecutionFailed,
     /// Hook execution timed out.
     ExecutionTimedOut,
+    /// Hook execution was cancelled.
+    ExecutionCancelled,
     /// Performance threshold exceeded.
     PerformanceAlert,
     /// Error rate threshold exceeded.
@@ -144,9 +261,10 @@ pub enum DashboardEventType {
 }
 
 /// Event severity levels.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub enum EventSeverity {
     /// Informational event.
+    #[default]
     Info,
     /// Warning event.
     Warning,
@@ -184,10 +302,16 @@ pub struct DashboardData {
     pub active_executions: Vec<ActiveExecution>,
     /// Recent events.
     pub recent_events: Vec<DashboardEvent>,
+    /// Recent events of warning severity or worse.
+    #[serde(default)]
+    pub priority_events: Vec<DashboardEvent>,
     /// Hook statistics by type.
     pub hook_statistics: HashMap<String, HookStatistics>,
     /// Event type statistics.
     pub event_statistics: HashMap<LifecycleEventType, EventStatistics>,
+    /// Skipped hooks by reason.
+    #[serde(default)]
+    pub skipped_executions: HashMap<SkipReason, u64>,
     /// System health indicators.
     pub health_indicators: HealthIndicators,
     /// Last update timestamp.
@@ -203,10 +327,16 @@ pub struct HookStatistics {
     pub total_executions: u64,
     /// Success rate.
     pub success_rate: f64,
+    /// Exponentially-weighted success rate that tracks recent behavior.
+    #[serde(default)]
+    pub recent_success_rate: f64,
     /// Average execution time.
     pub average_duration: Duration,
     /// Last execution time.
     pub last_execution: Option<DateTime<Utc>>,
+    /// Times the hook was skipped, by reason.
+    #[serde(default)]
+    pub skipped_by_reason: HashMap<SkipReason, u64>,
     /// Current status.
     pub status: HookStatus,
 }
@@ -263,6 +393,7 @@ impl HookDashboard {
         history_manager: Arc<HistoryManager>,
         config: DashboardConfig,
     ) -> Self {
+        let (event_sender, _) = broadcast::channel(config.max_recent_events.max(1));
         Self {
             metrics_collector,
             history_manager,
@@ -270,12 +401,24 @@ impl HookDashboard {
             status_tracker: Arc::new(RwLock::new(StatusTracker {
                 active_executions: HashMap::new(),
                 recent_events: Vec::new(),
+                priority_events: Vec::new(),
                 system_status: SystemStatus::Healthy,
                 last_updated: Utc::now(),
             })),
+            started_at: Instant::now(),
+            system_sampler: Arc::new(Mutex::new(SystemSampler::new())),
+            event_sender,
         }
     }
 
+    /// Subscribe to dashboard events as they are added.
+    ///
+    /// The channel holds `max_recent_events` events; a subscriber that falls further
+    /// behind receives [`broadcast::error::RecvError::Lagged`] instead of slowing producers.
+    pub fn subscribe(&self) -> broadcast::Receiver<DashboardEvent> {
+        self.event_sender.subscribe()
+    }
+
     /// Create a dashboard with default configuration.
     pub fn with_defaults(
         metrics_collector: Arc<MetricsCollector>,
@@ -395,7 +538,7 @@ impl HookDashboard {
             timestamp: Utc::now(),
             message,
             severity,
-            hook_id: Some(hook_id),
+            hook_id: Some(hook_id.clone()),
             metadata: {
                 let mut metadata = HashMap::new();
                 metadata.insert("duration_ms".to_string(), serde_json::Value::Number(
@@ -430,14 +573,74 @@ impl HookDashboard {
         Ok(())
     }
 
+    /// Stop tracking an execution that was cancelled, recording why.
+    pub fn cancel_execution_tracking(&self, execution_id: &str, reason: CancelReason, duration: Duration) -> Result<(), HookError> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let hook_id = if let Ok(mut tracker) = self.status_tracker.write() {
+            match tracker.active_executions.remove(execution_id) {
+                Some(execution) => {
+                    tracker.last_updated = Utc::now();
+                    execution.hook_id
+                }
+                None => return Ok(()), // Execution not found
+            }
+        } else {
+            return Ok(());
+        };
+
+        self.add_dashboard_event(DashboardEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type: DashboardEventType::ExecutionCancelled,
+            timestamp: Utc::now(),
+            message: format!("Hook '{}' cancelled ({}) after {:?}", hook_id, reason.as_str(), duration),
+            severity: EventSeverity::Warning,
+            hook_id: Some(hook_id),
+            metadata: {
+                let mut metadata = HashMap::new();
+                metadata.insert("duration_ms".to_string(), serde_json::json!(duration.as_millis() as u64));
+                metadata.insert("cancel_reason".to_string(), serde_json::json!(reason));
+                metadata
+            },
+        })
+    }
+
+    /// Stop tracking every active execution, e.g. when the hook manager shuts down.
+    pub fn cancel_all_tracking(&self, reason: CancelReason) -> Result<(), HookError> {
+        let active: Vec<(String, DateTime<Utc>)> = match self.status_tracker.read() {
+            Ok(tracker) => tracker
+                .active_executions
+                .values()
+                .map(|execution| (execution.execution_id.clone(), execution.started_at))
+                .collect(),
+            Err(_) => return Ok(()),
+        };
+
+        for (execution_id, started_at) in active {
+            let duration = (Utc::now() - started_at).to_std().unwrap_or_default();
+            self.cancel_execution_tracking(&execution_id, reason, duration)?;
+        }
+        Ok(())
+    }
+
     /// Add a dashboard event.
     fn add_dashboard_event(&self, event: DashboardEvent) -> Result<(), HookError> {
+        // Sending only fails when nobody is subscribed
+        let _ = self.event_sender.send(event.clone());
+
         if let Ok(mut tracker) = self.status_tracker.write() {
-            tracker.recent_events.push(event);
+            if event.severity >= EventSeverity::Warning {
+                tracker.priority_events.push(event.clone());
+                let excess = tracker.priority_events.len().saturating_sub(self.config.max_priority_events);
+                tracker.priority_events.drain(..excess);
+            }
 
-            // Maintain size limit
-            if tracker.recent_events.len() > self.config.max_recent_events {
-                tracker.recent_events.remove(0);
+            if event.severity >= self.config.min_display_severity {
+                tracker.recent_events.push(event);
+                let excess = tracker.recent_events.len().saturating_sub(self.config.max_recent_events);
+                tracker.recent_events.drain(..excess);
             }
 
             tracker.last_updated = Utc::now();
@@ -455,14 +658,15 @@ impl HookDashboard {
         let history_summary = self.history_manager.get_summary()?;
 
         // Get current status
-        let (active_executions, recent_events, system_status) = if let Ok(tracker) = self.status_tracker.read() {
+        let (active_executions, recent_events, priority_events, system_status) = if let Ok(tracker) = self.status_tracker.read() {
             (
                 tracker.active_executions.values().cloned().collect(),
                 tracker.recent_events.clone(),
+                tracker.priority_events.clone(),
                 tracker.system_status.clone(),
             )
         } else {
-            (Vec::new(), Vec::new(), SystemStatus::Error)
+            (Vec::new(), Vec::new(), Vec::new(), SystemStatus::Error)
         };
 
         // Calculate hook statistics
@@ -471,6 +675,8 @@ impl HookDashboard {
         // Calculate event statistics
         let event_statistics = self.calculate_event_statistics().await?;
 
+        let skipped_executions = self.metrics_collector.get_metrics()?.skipped_executions;
+
         // Get health indicators
         let health_indicators = self.get_health_indicators().await?;
 
@@ -480,8 +686,10 @@ impl HookDashboard {
             history_summary,
             active_executions,
             recent_events,
+            priority_events,
             hook_statistics,
             event_statistics,
+            skipped_executions,
             health_indicators,
             last_updated: Utc::now(),
         })
@@ -489,13 +697,15 @@ impl HookDashboard {
 
     /// Calculate statistics for each hook.
     async fn calculate_hook_statistics(&self) -> Result<HashMap<String, HookStatistics>, HookError> {
-        let metrics = self.metrics_collector.get_metrics()?;
+        let mut metrics = self.metrics_collector.get_metrics()?;
         let mut statistics = HashMap::new();
 
         for (hook_id, hook_metrics) in metrics.by_hook_id {
-            let status = if hook_metrics.success_rate < 0.5 {
+            let skipped_by_reason = metrics.skipped_by_hook_id.remove(&hook_id).unwrap_or_default();
+            // Status follows recent behavior so outages show up on long-lived hooks
+            let status = if hook_metrics.recent_success_rate < 0.5 {
                 HookStatus::Error
-            } else if hook_metrics.success_rate < 0.9 {
+            } else if hook_metrics.recent_success_rate < 0.9 {
                 HookStatus::Warning
             } else {
                 HookStatus::Active
@@ -505,10 +715,32 @@ impl HookDashboard {
                 hook_id,
                 total_executions: hook_metrics.total_executions,
                 success_rate: hook_metrics.success_rate,
+                recent_success_rate: hook_metrics.recent_success_rate,
                 average_duration: hook_metrics.average_execution_time,
                 last_execution: hook_metrics.last_execution.map(|st| {
                     DateTime::from(st)
                 }),
+                skipped_by_reason,
+                status,
+            });
+        }
+
+        // Hooks that were only ever skipped
+        for (hook_id, skipped_by_reason) in metrics.skipped_by_hook_id {
+            let status = if skipped_by_reason.keys().all(|reason| *reason == SkipReason::Disabled) {
+                HookStatus::Disabled
+            } else {
+                HookStatus::Active
+            };
+
+            statistics.insert(hook_id.clone(), HookStatistics {
+                hook_id,
+                total_executions: 0,
+                success_rate: 0.0,
+                recent_success_rate: 0.0,
+                average_duration: Duration::ZERO,
+                last_execution: None,
+                skipped_by_reason,
                 status,
             });
         }
@@ -522,10 +754,15 @@ impl HookDashboard {
         let mut statistics = HashMap::new();
 
         for (event_type, event_metrics) in metrics.by_event_type {
+            let average_hooks_per_event = if event_metrics.occurrences > 0 {
+                event_metrics.total_executions as f64 / event_metrics.occurrences as f64
+            } else {
+                0.0
+            };
             statistics.insert(event_type, EventStatistics {
                 event_type,
                 total_hooks_triggered: event_metrics.total_executions,
-                average_hooks_per_event: 1.0, // TODO: Calculate based on actual data
+                average_hooks_per_event,
                 average_total_duration: event_metrics.average_execution_time,
                 overall_success_rate: event_metrics.success_rate,
             });
@@ -537,9 +774,16 @@ impl HookDashboard {
     /// Get system health indicators.
     async fn get_health_indicators(&self) -> Result<HealthIndicators, HookError> {
         let metrics = self.metrics_collector.get_metrics()?;
-        
-        // Calculate health score based on success rate and performance
-        let health_score = if metrics.total_executions == 0 {
+        // Refreshing sysinfo blocks, so it runs off the async worker threads
+        let sampler = Arc::clone(&self.system_sampler);
+        let usage = tokio::task::spawn_blocking(move || {
+            sampler.lock().map(|mut sampler| sampler.sample()).unwrap_or_default()
+        })
+        .await
+        .unwrap_or_default();
+
+        // Calculate health score based on success rate, performance and resource pressure
+        let execution_score = if metrics.total_executions == 0 {
             1.0 // No executions yet, assume healthy
         } else {
             let success_factor = metrics.success_rate;
@@ -550,8 +794,8 @@ impl HookDashboard {
             };
             success_factor * performance_factor
         };
+        let health_score = execution_score * usage.health_factor();
 
-        // Get system metrics (simplified for now)
         let active_connections = if let Ok(tracker) = self.status_tracker.read() {
             tracker.active_executions.len() as u32
         } else {
@@ -560,11 +804,11 @@ impl HookDashboard {
 
         Ok(HealthIndicators {
             health_score,
-            cpu_usage: None, // TODO: Implement actual system monitoring
-            memory_usage: None,
-            disk_usage: None,
+            cpu_usage: usage.cpu,
+            memory_usage: usage.memory,
+            disk_usage: usage.disk,
             active_connections,
-            uptime: Duration::from_secs(0), // TODO: Track actual uptime
+            uptime: self.started_at.elapsed(),
         })
     }
 
@@ -655,13 +899,24 @@ impl HookDashboard {
         serde_json::to_string_pretty(&data)
             .map_err(|e| HookError::Execution(format!("Failed to serialize dashboard data: {}", e)))
     }
+
+    /// Export dashboard data in the given format.
+    pub async fn export_dashboard_data_as(&self, format: ExportFormat) -> Result<Vec<u8>, HookError> {
+        format.encode(&self.get_dashboard_data().await?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
-    use crate::hooks::metrics::MetricsConfig;
+    use crate::hooks::config::{HookConfig, HooksConfig};
     use crate::hooks::history::HistoryConfig;
+    use crate::hooks::manager::HookManager;
+    use crate::hooks::metrics::MetricsConfig;
+    use crate::hooks::performance::SYSTEM_METRICS_SUPPORTED;
+    use crate::hooks::types::{HookExecutionMode, HookPriority, HookType, LifecycleEvent};
+    use std::path::PathBuf;
 
     #[tokio::test]
     async fn test_dashboard_creation() {
@@ -703,6 +958,27 @@ mod tests {
         assert!(!data.recent_events.is_empty()); // Should have events
     }
 
+    #[tokio::test]
+    async fn test_cancelled_execution_records_reason() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+
+        dashboard.start_execution_tracking(
+            "exec_1".to_string(),
+            "hook_1".to_string(),
+            LifecycleEventType::SessionEnd,
+            None,
+        ).unwrap();
+        dashboard.cancel_execution_tracking("exec_1", CancelReason::Shutdown, Duration::from_millis(40)).unwrap();
+
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        assert!(data.active_executions.is_empty());
+        let event = data.recent_events.last().unwrap();
+        assert_eq!(event.event_type, DashboardEventType::ExecutionCancelled);
+        assert_eq!(event.metadata["cancel_reason"], serde_json::json!("shutdown"));
+    }
+
     #[tokio::test]
     async fn test_dashboard_data_generation() {
         let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
@@ -716,6 +992,221 @@ mod tests {
         assert!(data.health_indicators.health_score <= 1.0);
     }
 
+    #[tokio::test]
+    async fn test_health_indicators_track_uptime() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let first = dashboard.get_health_indicators().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = dashboard.get_health_indicators().await.unwrap();
+
+        assert!(first.uptime >= Duration::from_millis(20));
+        assert!(second.uptime > first.uptime);
+        if SYSTEM_METRICS_SUPPORTED {
+            assert!(second.memory_usage.is_some_and(|memory| memory > 0.0 && memory <= 100.0));
+            assert!(second.cpu_usage.is_some());
+        }
+        assert!((0.0..=1.0).contains(&second.health_score));
+    }
+
+    #[test]
+    fn test_memory_pressure_lowers_health_factor() {
+        let relaxed = SystemUsage { cpu: Some(10.0), memory: Some(40.0), disk: Some(50.0) };
+        let pressured = SystemUsage { memory: Some(97.0), ..relaxed };
+        assert_eq!(relaxed.health_factor(), 1.0);
+        assert!(pressured.health_factor() < 0.9);
+        assert_eq!(SystemUsage::default().health_factor(), 1.0);
+    }
+
+    fn task_complete_hook(id: &str) -> HookConfig {
+        HookConfig {
+            id: Some(id.to_string()),
+            event: LifecycleEventType::TaskComplete,
+            hook_type: HookType::Script {
+                command: vec!["true".to_string()],
+                cwd: None,
+                environment: HashMap::new(),
+                timeout: None,
+                resource_limits: None,
+            },
+            mode: HookExecutionMode::Async,
+            priority: HookPriority::NORMAL,
+            condition: None,
+            blocking: false,
+            required: false,
+            tags: Vec::new(),
+            description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
+        }
+    }
+
+    fn task_complete_event() -> LifecycleEvent {
+        LifecycleEvent::TaskComplete {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            success: true,
+            output: None,
+            duration: Duration::from_secs(1),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_average_hooks_per_event() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+
+        let mut config = HooksConfig::default();
+        config.hooks.task = ["lint", "test", "notify"].into_iter().map(task_complete_hook).collect();
+        let manager = HookManager::new(config).await.unwrap().with_metrics_collector(metrics_collector.clone());
+
+        for _ in 0..2 {
+            let results = manager.dispatch_event_collected(task_complete_event()).await.unwrap();
+            assert_eq!(results.successful.len(), 3);
+        }
+        // An event without matching hooks still counts as an occurrence
+        manager.trigger_event(LifecycleEvent::SessionStart {
+            session_id: "session-1".to_string(),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        let task_complete = &data.event_statistics[&LifecycleEventType::TaskComplete];
+        assert_eq!(task_complete.total_hooks_triggered, 6);
+        assert_eq!(task_complete.average_hooks_per_event, 3.0);
+        assert_eq!(data.event_statistics[&LifecycleEventType::SessionStart].average_hooks_per_event, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_streams_events_in_order() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+        let mut events = dashboard.subscribe();
+
+        for (execution_id, hook_id) in [("exec_1", "lint"), ("exec_2", "test")] {
+            dashboard
+                .start_execution_tracking(execution_id.to_string(), hook_id.to_string(), LifecycleEventType::TaskComplete, None)
+                .unwrap();
+            dashboard.complete_execution_tracking(execution_id, true, Duration::from_millis(5), None).unwrap();
+        }
+
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let event = tokio::time::timeout(Duration::from_secs(1), events.recv()).await.unwrap().unwrap();
+            received.push((event.event_type, event.hook_id.unwrap()));
+        }
+        assert_eq!(
+            received,
+            vec![
+                (DashboardEventType::ExecutionStarted, "lint".to_string()),
+                (DashboardEventType::ExecutionCompleted, "lint".to_string()),
+                (DashboardEventType::ExecutionStarted, "test".to_string()),
+                (DashboardEventType::ExecutionCompleted, "test".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_is_told_it_lagged() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::new(
+            metrics_collector,
+            history_manager,
+            DashboardConfig {
+                max_recent_events: 2,
+                ..DashboardConfig::default()
+            },
+        );
+        let mut events = dashboard.subscribe();
+
+        for i in 0..5 {
+            dashboard
+                .start_execution_tracking(format!("exec_{}", i), format!("hook_{}", i), LifecycleEventType::TaskStart, None)
+                .unwrap();
+        }
+
+        assert!(matches!(events.recv().await, Err(broadcast::error::RecvError::Lagged(3))));
+        assert_eq!(events.recv().await.unwrap().hook_id.as_deref(), Some("hook_3"));
+    }
+
+    fn create_event(severity: EventSeverity, message: &str) -> DashboardEvent {
+        DashboardEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type: DashboardEventType::SystemStatusChange,
+            timestamp: Utc::now(),
+            message: message.to_string(),
+            severity,
+            hook_id: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_priority_events_survive_info_flood() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::new(
+            metrics_collector,
+            history_manager,
+            DashboardConfig {
+                max_recent_events: 10,
+                ..DashboardConfig::default()
+            },
+        );
+
+        dashboard.add_dashboard_event(create_event(EventSeverity::Critical, "disk full")).unwrap();
+        dashboard.add_dashboard_event(create_event(EventSeverity::Warning, "slow hook")).unwrap();
+        for i in 0..50 {
+            dashboard.add_dashboard_event(create_event(EventSeverity::Info, &format!("info {}", i))).unwrap();
+        }
+
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        assert_eq!(data.recent_events.len(), 10);
+        assert!(data.recent_events.iter().all(|event| event.severity == EventSeverity::Info));
+        let priority: Vec<&str> = data.priority_events.iter().map(|event| event.message.as_str()).collect();
+        assert_eq!(priority, ["disk full", "slow hook"]);
+    }
+
+    #[tokio::test]
+    async fn test_min_display_severity_filters_recent_feed() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::new(
+            metrics_collector,
+            history_manager,
+            DashboardConfig {
+                min_display_severity: EventSeverity::Error,
+                max_priority_events: 2,
+                ..DashboardConfig::default()
+            },
+        );
+
+        for severity in [EventSeverity::Info, EventSeverity::Warning, EventSeverity::Error, EventSeverity::Critical] {
+            dashboard.add_dashboard_event(create_event(severity.clone(), &format!("{:?}", severity))).unwrap();
+        }
+
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        let recent: Vec<&str> = data.recent_events.iter().map(|event| event.message.as_str()).collect();
+        assert_eq!(recent, ["Error", "Critical"]);
+        let priority: Vec<&str> = data.priority_events.iter().map(|event| event.message.as_str()).collect();
+        assert_eq!(priority, ["Error", "Critical"]);
+    }
+
     #[test]
     fn test_execution_status_serialization() {
         let status = ExecutionStatus::Running;
@@ -739,4 +1230,76 @@ mod tests {
         assert_eq!(event.event_type, DashboardEventType::ExecutionStarted);
         assert_eq!(event.severity, EventSeverity::Info);
     }
+
+    #[tokio::test]
+    async fn test_hook_status_follows_recent_success_rate() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+
+        for i in 0..210 {
+            let success = i < 200;
+            let error = (!success).then(|| "connection refused".to_string());
+            metrics_collector.record_execution(
+                "steady_hook",
+                LifecycleEventType::TaskComplete,
+                Duration::from_millis(5),
+                success,
+                error,
+                0,
+            );
+        }
+
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        let stats = &data.hook_statistics["steady_hook"];
+        assert!(stats.success_rate > 0.9);
+        assert!(stats.recent_success_rate < 0.5);
+        assert_eq!(stats.status, HookStatus::Error);
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_shows_skip_reasons() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+
+        metrics_collector.record_execution("active_hook", LifecycleEventType::TaskComplete, Duration::from_millis(5), true, None, 0);
+        metrics_collector.record_skip("active_hook", SkipReason::ConditionNotMet);
+        metrics_collector.record_skip("dormant_hook", SkipReason::Disabled);
+
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+        let data = dashboard.get_dashboard_data().await.unwrap();
+
+        assert_eq!(data.skipped_executions[&SkipReason::ConditionNotMet], 1);
+        assert_eq!(data.skipped_executions[&SkipReason::Disabled], 1);
+
+        let active = &data.hook_statistics["active_hook"];
+        assert_eq!(active.total_executions, 1);
+        assert_eq!(active.skipped_by_reason[&SkipReason::ConditionNotMet], 1);
+
+        let dormant = &data.hook_statistics["dormant_hook"];
+        assert_eq!(dormant.total_executions, 0);
+        assert_eq!(dormant.status, HookStatus::Disabled);
+    }
+
+    #[tokio::test]
+    async fn test_export_dashboard_data_message_pack_round_trip() {
+        let metrics_collector = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let history_manager = Arc::new(HistoryManager::new(HistoryConfig::default()).unwrap());
+        let dashboard = HookDashboard::with_defaults(metrics_collector, history_manager);
+
+        let exported = dashboard.export_dashboard_data_as(ExportFormat::MessagePack).await.unwrap();
+        let _: DashboardData = ExportFormat::MessagePack.decode(&exported).unwrap();
+
+        // Encode one snapshot both ways, since system metrics change between exports
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        let json = ExportFormat::Json.encode(&data).unwrap();
+        let packed = ExportFormat::MessagePack.encode(&data).unwrap();
+        assert!(packed.len() < json.len());
+
+        let from_packed: DashboardData = ExportFormat::MessagePack.decode(&packed).unwrap();
+        assert_eq!(
+            serde_json::to_value(&from_packed).unwrap(),
+            serde_json::to_value(&data).unwrap()
+        );
+    }
 }
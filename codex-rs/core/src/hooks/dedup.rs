@@ -0,0 +1,172 @@
+//! Content-hash deduplication of outgoing webhook and message queue payloads.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Result metadata key set to `true` when a send was suppressed as a duplicate.
+pub const DEDUPLICATED_METADATA_KEY: &str = "payload_deduplicated";
+
+/// Hash a payload, ignoring the fields at the given JSON pointers (e.g. send timestamps).
+pub fn content_hash(payload: &Value, volatile_fields: &[&str]) -> u64 {
+    let mut stable = payload.clone();
+    for pointer in volatile_fields {
+        if let Some((parent, field)) = pointer.rsplit_once('/') {
+            if let Some(Value::Object(map)) = stable.pointer_mut(parent) {
+                map.remove(field);
+            }
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    stable.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Delivery state of a (destination, payload hash) pair.
+#[derive(Debug, Clone, Copy)]
+enum SendState {
+    /// A send is in progress.
+    InFlight,
+    /// Delivered at this time.
+    Delivered(Instant),
+}
+
+/// Suppresses sends of an identical payload to the same destination within a time window.
+#[derive(Debug)]
+pub struct PayloadDeduplicator {
+    /// How long a delivered payload suppresses identical sends.
+    window: Duration,
+    /// Sends in progress, and when each (destination, payload hash) was last delivered.
+    sends: Mutex<HashMap<(String, u64), SendState>>,
+    /// Number of sends suppressed as duplicates.
+    suppressed: AtomicU64,
+}
+
+impl PayloadDeduplicator {
+    /// Create a deduplicator with the given window.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            sends: Mutex::new(HashMap::new()),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Window during which identical sends are suppressed.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Claim the send of a payload to `destination`, or `None` if an identical payload is being
+    /// sent now or was delivered within the window.
+    ///
+    /// The check and the claim happen under one lock, so of two identical concurrent sends only
+    /// one goes out. `None` counts as a suppression. Dropping the claim without calling
+    /// [`DedupClaim::delivered`] lets the next identical send through.
+    pub fn claim(self: &Arc<Self>, destination: &str, hash: u64) -> Option<DedupClaim> {
+        let key = (destination.to_string(), hash);
+        let Ok(mut sends) = self.sends.lock() else {
+            return Some(DedupClaim { dedup: self.clone(), key, delivered: false });
+        };
+
+        let now = Instant::now();
+        sends.retain(|_, state| match state {
+            SendState::InFlight => true,
+            SendState::Delivered(sent_at) => now.duration_since(*sent_at) < self.window,
+        });
+        if sends.contains_key(&key) {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        sends.insert(key.clone(), SendState::InFlight);
+        Some(DedupClaim { dedup: self.clone(), key, delivered: false })
+    }
+
+    /// Number of sends suppressed as duplicates.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+/// A send claimed with [`PayloadDeduplicator::claim`].
+#[derive(Debug)]
+#[must_use]
+pub struct DedupClaim {
+    dedup: Arc<PayloadDeduplicator>,
+    key: (String, u64),
+    delivered: bool,
+}
+
+impl DedupClaim {
+    /// Record the payload as delivered; failed sends should drop the claim instead.
+    pub fn delivered(mut self) {
+        self.delivered = true;
+        if let Ok(mut sends) = self.dedup.sends.lock() {
+            sends.insert(self.key.clone(), SendState::Delivered(Instant::now()));
+        }
+    }
+}
+
+impl Drop for DedupClaim {
+    fn drop(&mut self) {
+        if self.delivered {
+            return;
+        }
+        if let Ok(mut sends) = self.dedup.sends.lock() {
+            if matches!(sends.get(&self.key), Some(SendState::InFlight)) {
+                sends.remove(&self.key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_hash_ignores_volatile_fields() {
+        let first = json!({"event": {"type": "task_start", "timestamp": "2026-01-01T00:00:00Z"}});
+        let retry = json!({"event": {"type": "task_start", "timestamp": "2026-01-01T00:00:05Z"}});
+        let other = json!({"event": {"type": "task_complete", "timestamp": "2026-01-01T00:00:00Z"}});
+
+        assert_eq!(content_hash(&first, &["/event/timestamp"]), content_hash(&retry, &["/event/timestamp"]));
+        assert_ne!(content_hash(&first, &[]), content_hash(&retry, &[]));
+        assert_ne!(content_hash(&first, &["/event/timestamp"]), content_hash(&other, &["/event/timestamp"]));
+    }
+
+    #[test]
+    fn test_duplicates_are_scoped_to_destination_and_window() {
+        let dedup = Arc::new(PayloadDeduplicator::new(Duration::from_millis(100)));
+        dedup.claim("https://a", 1).unwrap().delivered();
+
+        assert!(dedup.claim("https://a", 1).is_none());
+        assert!(dedup.claim("https://b", 1).is_some());
+        assert!(dedup.claim("https://a", 2).is_some());
+        assert_eq!(dedup.suppressed(), 1);
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(dedup.claim("https://a", 1).is_some());
+        assert_eq!(dedup.suppressed(), 1);
+    }
+
+    #[test]
+    fn test_in_flight_send_suppresses_identical_send() {
+        let dedup = Arc::new(PayloadDeduplicator::new(Duration::from_secs(60)));
+        let first = dedup.claim("https://a", 1).unwrap();
+        assert!(dedup.claim("https://a", 1).is_none());
+
+        // A failed send releases its claim
+        drop(first);
+        assert!(dedup.claim("https://a", 1).is_some());
+        assert_eq!(dedup.suppressed(), 1);
+    }
+}
@@ -47,7 +47,7 @@ impl DependencyGraph {
         for dep_id in &hook.depends_on {
             self.dependents
                 .entry(dep_id.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(hook_id.clone());
         }
 
@@ -98,7 +98,7 @@ impl DependencyGraph {
             // Find hooks with no remaining dependencies
             let ready_hooks: Vec<_> = in_degree
                 .iter()
-                .filter(|(_, &degree)| degree == 0)
+                .filter(|&(_, &degree)| degree == 0)
                 .map(|(hook_id, _)| hook_id.clone())
                 .collect();
 
@@ -317,6 +317,7 @@ pub struct DependencyStats {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use crate::hooks::types::{HookType, HookExecutionMode, HookPriority, LifecycleEventType};
     use std::collections::HashMap;
@@ -331,6 +332,7 @@ mod tests {
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: Some(Duration::from_secs(5)),
+                resource_limits: None,
             },
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
@@ -343,6 +345,8 @@ mod tests {
             parallel: true,
             max_retries: 0,
             timeout: Some(Duration::from_secs(10)),
+            fallback: None,
+            command_pattern: None,
         }
     }
 
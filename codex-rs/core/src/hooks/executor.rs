@@ -1,23 +1,139 @@
 //! Hook execution framework and base executor.
 
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
 use futures::future::join_all;
-use tokio::sync::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncReadExt;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
 use tokio::time::timeout;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
+use crate::hooks::config::HookConfig;
 use crate::hooks::context::HookContext;
-use crate::hooks::types::{HookError, HookResult, HookExecutionMode, HookPriority};
+use crate::hooks::performance::{ConcurrencyMetrics, QueueMetrics, TimingBreakdown};
+use crate::hooks::types::{CancelReason, HookError, HookResult, HookExecutionMode, HookPriority, LifecycleEventType, SkipReason};
 
 /// Result type for hook executor operations.
 pub type HookExecutorResult = Result<HookResult, HookError>;
 
+/// Result metadata key under which executors report payload (de)serialization time, in microseconds.
+pub const SERIALIZATION_TIME_METADATA_KEY: &str = "serialization_time_us";
+
+/// Result metadata key holding base64-encoded raw output that was not valid UTF-8.
+pub const OUTPUT_DATA_METADATA_KEY: &str = "output_data";
+
+/// Result metadata key set to `true` when output had to be decoded lossily.
+pub const OUTPUT_LOSSY_METADATA_KEY: &str = "output_lossy";
+
+/// Result metadata key holding a process's captured stdout.
+pub const STDOUT_METADATA_KEY: &str = "stdout";
+
+/// Result metadata key holding a process's captured stderr.
+pub const STDERR_METADATA_KEY: &str = "stderr";
+
+/// Appended to captured output cut off at its size limit.
+pub const OUTPUT_TRUNCATED_MARKER: &str = "... [output truncated due to size limit]\n";
+
+/// Raw output captured from a process stream or HTTP response body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedOutput {
+    /// Output decoded as UTF-8, with invalid sequences replaced.
+    pub text: String,
+    /// Bytes exactly as captured.
+    pub bytes: Vec<u8>,
+    /// Whether decoding replaced invalid UTF-8 sequences.
+    pub lossy: bool,
+    /// Whether output past the size limit was dropped.
+    pub truncated: bool,
+}
+
+impl CapturedOutput {
+    /// Decode captured bytes, remembering whether the conversion was lossy.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let (text, lossy) = match String::from_utf8(bytes.clone()) {
+            Ok(text) => (text, false),
+            Err(_) => (String::from_utf8_lossy(&bytes).into_owned(), true),
+        };
+        Self { text, bytes, lossy, truncated: false }
+    }
+
+    /// Read `reader` to the end, keeping at most `max_size` bytes followed by a truncation marker.
+    ///
+    /// The rest of the stream is drained so the writer never blocks on a full pipe.
+    pub async fn read_limited<R>(mut reader: R, max_size: usize) -> Result<Self, HookError>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        let mut kept = Vec::new();
+        let mut truncated = false;
+        let mut buf = [0u8; 8192];
+        loop {
+            let read = reader
+                .read(&mut buf)
+                .await
+                .map_err(|e| HookError::Execution(format!("Failed to read output: {}", e)))?;
+            if read == 0 {
+                break;
+            }
+            let room = max_size.saturating_sub(kept.len());
+            kept.extend_from_slice(&buf[..read.min(room)]);
+            truncated |= read > room;
+        }
+
+        if truncated {
+            // Cut before a character split by the limit rather than mangling it
+            if let Err(e) = std::str::from_utf8(&kept) {
+                if e.error_len().is_none() {
+                    kept.truncate(e.valid_up_to());
+                }
+            }
+            if !kept.is_empty() && !kept.ends_with(b"\n") {
+                kept.push(b'\n');
+            }
+            kept.extend_from_slice(OUTPUT_TRUNCATED_MARKER.as_bytes());
+        }
+        Ok(Self { truncated, ..Self::from_bytes(kept) })
+    }
+
+    /// Whether no output was captured.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Flag lossy decoding on `result`, attaching the raw bytes as base64 when `binary_output` is set.
+    pub fn annotate(&self, mut result: HookResult, binary_output: bool) -> HookResult {
+        if !self.lossy {
+            return result;
+        }
+        if binary_output {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&self.bytes);
+            result = result.with_metadata(OUTPUT_DATA_METADATA_KEY.to_string(), serde_json::json!(encoded));
+        }
+        result.with_metadata(OUTPUT_LOSSY_METADATA_KEY.to_string(), serde_json::json!(true))
+    }
+}
+
+/// Record a process's stdout and stderr separately in `result`'s metadata.
+pub fn with_output_streams(result: HookResult, stdout: &CapturedOutput, stderr: &CapturedOutput) -> HookResult {
+    result
+        .with_metadata(STDOUT_METADATA_KEY.to_string(), serde_json::json!(stdout.text))
+        .with_metadata(STDERR_METADATA_KEY.to_string(), serde_json::json!(stderr.text))
+}
+
 /// Execution configuration for hook execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ExecutionConfig {
     /// Maximum execution time before timeout.
     pub timeout: Duration,
@@ -33,6 +149,8 @@ pub struct ExecutionConfig {
     pub retry_delay: Duration,
     /// Whether to isolate execution in a separate task.
     pub isolated: bool,
+    /// Wall-clock limit across all attempts and retry delays; `timeout` still applies per attempt.
+    pub total_deadline: Option<Duration>,
 }
 
 impl Default for ExecutionConfig {
@@ -45,7 +163,169 @@ impl Default for ExecutionConfig {
             max_retries: 0,
             retry_delay: Duration::from_millis(500),
             isolated: true,
+            total_deadline: None,
+        }
+    }
+}
+
+/// Partial execution settings; unset fields fall through to the next layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionOverrides {
+    /// Maximum execution time before timeout.
+    pub timeout: Option<Duration>,
+    /// Execution mode (blocking, async, fire-and-forget).
+    pub mode: Option<HookExecutionMode>,
+    /// Priority for execution ordering.
+    pub priority: Option<HookPriority>,
+    /// Whether this hook is required (failure stops execution).
+    pub required: Option<bool>,
+    /// Maximum number of retry attempts on failure.
+    pub max_retries: Option<u32>,
+    /// Delay between retry attempts.
+    pub retry_delay: Option<Duration>,
+    /// Whether to isolate execution in a separate task.
+    pub isolated: Option<bool>,
+}
+
+impl ExecutionOverrides {
+    /// Settings made explicitly on a hook.
+    ///
+    /// `HookConfig` fields left at their defaults (`mode = async`,
+    /// `priority = normal`, `required = false`, `max_retries = 0`) are treated
+    /// as unset so that event-type defaults can apply. The timeout comes from
+    /// [`HookConfig::timeout_override`], so a hook timeout always wins over the
+    /// executor default.
+    pub fn from_hook(hook: &HookConfig) -> Self {
+        let mode = if hook.blocking {
+            Some(HookExecutionMode::Blocking)
+        } else if hook.mode != HookExecutionMode::default() {
+            Some(hook.mode)
+        } else {
+            None
+        };
+
+        Self {
+            timeout: hook.timeout_override(),
+            mode,
+            priority: (hook.priority != HookPriority::default()).then_some(hook.priority),
+            required: hook.required.then_some(true),
+            max_retries: (hook.max_retries > 0).then_some(hook.max_retries),
+            retry_delay: None,
+            isolated: None,
+        }
+    }
+
+    /// Apply every set field on top of `config`.
+    pub fn apply(&self, config: &mut ExecutionConfig) {
+        if let Some(timeout) = self.timeout {
+            config.timeout = timeout;
+        }
+        if let Some(mode) = self.mode {
+            config.mode = mode;
+        }
+        if let Some(priority) = self.priority {
+            config.priority = priority;
+        }
+        if let Some(required) = self.required {
+            config.required = required;
+        }
+        if let Some(max_retries) = self.max_retries {
+            config.max_retries = max_retries;
+        }
+        if let Some(retry_delay) = self.retry_delay {
+            config.retry_delay = retry_delay;
+        }
+        if let Some(isolated) = self.isolated {
+            config.isolated = isolated;
+        }
+    }
+}
+
+/// Resolves the effective `ExecutionConfig` for a hook.
+///
+/// Layers are merged from lowest to highest precedence:
+///
+/// 1. the executor's `default_config()`;
+/// 2. the global hook timeout, when one is set;
+/// 3. the defaults registered for the triggering event type;
+/// 4. settings made explicitly on the hook (see [`ExecutionOverrides::from_hook`]).
+///
+/// A later layer only replaces the fields it sets.
+#[derive(Debug, Clone)]
+pub struct ExecutionConfigResolver {
+    global_timeout: Option<Duration>,
+    event_defaults: HashMap<LifecycleEventType, ExecutionOverrides>,
+}
+
+impl ExecutionConfigResolver {
+    /// Create a resolver with the built-in event-type defaults.
+    ///
+    /// Agent messages default to fire-and-forget; pre-flight events
+    /// (`exec_before`, `patch_before`, `mcp_tool_before`) default to blocking.
+    pub fn new() -> Self {
+        let fire_and_forget = ExecutionOverrides {
+            mode: Some(HookExecutionMode::FireAndForget),
+            ..Default::default()
+        };
+        let blocking = ExecutionOverrides {
+            mode: Some(HookExecutionMode::Blocking),
+            ..Default::default()
+        };
+
+        Self::empty()
+            .with_event_default(LifecycleEventType::AgentMessage, fire_and_forget)
+            .with_event_default(LifecycleEventType::ExecBefore, blocking.clone())
+            .with_event_default(LifecycleEventType::PatchBefore, blocking.clone())
+            .with_event_default(LifecycleEventType::McpToolBefore, blocking)
+    }
+
+    /// Create a resolver without any event-type defaults.
+    pub fn empty() -> Self {
+        Self {
+            global_timeout: None,
+            event_defaults: HashMap::new(),
+        }
+    }
+
+    /// Use `timeout` instead of executor defaults for hooks without a timeout of their own.
+    pub fn with_global_timeout(mut self, timeout: Duration) -> Self {
+        self.global_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the defaults for an event type, replacing any existing ones.
+    pub fn with_event_default(mut self, event_type: LifecycleEventType, overrides: ExecutionOverrides) -> Self {
+        self.event_defaults.insert(event_type, overrides);
+        self
+    }
+
+    /// Resolve the effective configuration from explicit hook overrides.
+    pub fn resolve(
+        &self,
+        executor: &dyn HookExecutor,
+        event_type: LifecycleEventType,
+        hook_overrides: &ExecutionOverrides,
+    ) -> ExecutionConfig {
+        let mut config = executor.default_config();
+        if let Some(timeout) = self.global_timeout {
+            config.timeout = timeout;
         }
+        if let Some(event_defaults) = self.event_defaults.get(&event_type) {
+            event_defaults.apply(&mut config);
+        }
+        hook_overrides.apply(&mut config);
+        config
+    }
+
+    /// Resolve the effective configuration for a configured hook.
+    pub fn resolve_for_hook(&self, executor: &dyn HookExecutor, hook: &HookConfig) -> ExecutionConfig {
+        self.resolve(executor, hook.event, &ExecutionOverrides::from_hook(hook))
+    }
+}
+
+impl Default for ExecutionConfigResolver {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -60,30 +340,58 @@ pub struct ExecutionContext {
     pub config: ExecutionConfig,
     /// Start time for performance tracking.
     pub start_time: Instant,
-    /// Cancellation token.
-    pub cancelled: Arc<RwLock<bool>>,
+    /// Cancellation token, holding why the execution was cancelled.
+    pub cancelled: Arc<RwLock<Option<CancelReason>>>,
+    /// ID of the hook being executed, when known.
+    pub hook_id: Option<String>,
+    /// IDs of hooks that must succeed before this one runs.
+    pub depends_on: Vec<String>,
 }
 
 impl ExecutionContext {
     /// Create a new execution context.
     pub fn new(hook_context: HookContext, config: ExecutionConfig) -> Self {
         Self {
-            execution_id: format!("exec_{}", std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos()),
+            execution_id: format!("exec_{}", uuid::Uuid::new_v4()),
             hook_context,
             config,
             start_time: Instant::now(),
-            cancelled: Arc::new(RwLock::new(false)),
+            cancelled: Arc::new(RwLock::new(None)),
+            hook_id: None,
+            depends_on: Vec::new(),
         }
     }
 
+    /// Set the ID of the hook being executed.
+    pub fn with_hook_id(mut self, hook_id: impl Into<String>) -> Self {
+        self.hook_id = Some(hook_id.into());
+        self
+    }
+
+    /// Run only after the hooks with these IDs have succeeded.
+    pub fn with_dependencies(mut self, depends_on: Vec<String>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
+
+    /// ID that dependent hooks refer to this execution by.
+    pub(crate) fn dependency_key(&self) -> String {
+        self.hook_id.clone().unwrap_or_else(|| self.execution_id.clone())
+    }
+
     /// Check if execution has been cancelled.
     pub async fn is_cancelled(&self) -> bool {
+        self.cancelled.read().await.is_some()
+    }
+
+    /// Why the execution was cancelled, if it was.
+    pub async fn cancel_reason(&self) -> Option<CancelReason> {
         *self.cancelled.read().await
     }
 
-    /// Cancel the execution.
-    pub async fn cancel(&self) {
-        *self.cancelled.write().await = true;
+    /// Cancel the execution; the first reason given is kept.
+    pub async fn cancel(&self, reason: CancelReason) {
+        self.cancelled.write().await.get_or_insert(reason);
     }
 
     /// Get elapsed execution time.
@@ -93,7 +401,7 @@ impl ExecutionContext {
 }
 
 /// Result of hook execution with detailed information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ExecutionResult {
     /// Execution ID for tracking.
     pub execution_id: String,
@@ -107,8 +415,12 @@ pub struct ExecutionResult {
     pub retry_attempts: u32,
     /// Whether execution was cancelled.
     pub cancelled: bool,
+    /// Why the execution was cancelled.
+    pub cancel_reason: Option<CancelReason>,
     /// Error details if execution failed.
     pub error_details: Option<String>,
+    /// Per-phase timing, populated by the coordinator.
+    pub timing_breakdown: Option<TimingBreakdown>,
 }
 
 /// Aggregated results from multiple hook executions.
@@ -128,6 +440,30 @@ pub struct AggregatedResults {
     pub average_duration: Duration,
     /// Success rate (0.0 to 1.0).
     pub success_rate: f64,
+    /// Hooks that matched the event but did not run.
+    pub skipped: Vec<SkippedHook>,
+}
+
+/// A hook that was not run, and why.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SkippedHook {
+    /// Hook ID.
+    pub hook_id: String,
+    /// Why the hook was skipped.
+    pub reason: SkipReason,
+    /// Additional detail, such as the missing executor type.
+    pub detail: Option<String>,
+}
+
+impl SkippedHook {
+    /// Create a skip record.
+    pub fn new(hook_id: impl Into<String>, reason: SkipReason, detail: Option<String>) -> Self {
+        Self {
+            hook_id: hook_id.into(),
+            reason,
+            detail,
+        }
+    }
 }
 
 impl AggregatedResults {
@@ -159,9 +495,25 @@ impl AggregatedResults {
             total_duration,
             average_duration,
             success_rate,
+            skipped: Vec::new(),
         }
     }
 
+    /// Add hooks that were skipped rather than executed.
+    pub fn with_skipped(mut self, skipped: Vec<SkippedHook>) -> Self {
+        self.skipped.extend(skipped);
+        self
+    }
+
+    /// Number of skipped hooks for each reason.
+    pub fn skipped_by_reason(&self) -> HashMap<SkipReason, usize> {
+        let mut counts = HashMap::new();
+        for skipped in &self.skipped {
+            *counts.entry(skipped.reason).or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Check if any critical (required) hooks failed.
     pub fn has_critical_failures(&self) -> bool {
         self.failed.iter().any(|r| r.config.required)
@@ -170,16 +522,153 @@ impl AggregatedResults {
     /// Get summary statistics.
     pub fn summary(&self) -> String {
         format!(
-            "Executed {} hooks: {} successful, {} failed, {} cancelled (success rate: {:.1}%)",
+            "Executed {} hooks: {} successful, {} failed, {} cancelled, {} skipped (success rate: {:.1}%)",
             self.results.len(),
             self.successful.len(),
             self.failed.len(),
             self.cancelled.len(),
+            self.skipped.len(),
             self.success_rate * 100.0
         )
     }
 }
 
+/// Failure message for hooks cancelled because their event's time budget ran out.
+pub const EVENT_BUDGET_EXHAUSTED: &str = "Event time budget exhausted";
+
+/// Whether a non-required hook should no longer start because `deadline` has passed.
+fn budget_exhausted(deadline: Option<Instant>, context: &ExecutionContext) -> bool {
+    !context.config.required && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Skip record for a hook that was not started because the event's budget ran out.
+fn budget_skip(context: &ExecutionContext) -> SkippedHook {
+    SkippedHook::new(context.dependency_key(), SkipReason::BudgetExhausted, Some(EVENT_BUDGET_EXHAUSTED.to_string()))
+}
+
+/// Skip record for a hook whose dependency failed or did not run, if it has one.
+fn dependency_skip(context: &ExecutionContext, outcomes: &HashMap<String, bool>) -> Option<SkippedHook> {
+    let (dep, outcome) = context
+        .depends_on
+        .iter()
+        .map(|dep| (dep, outcomes.get(dep)))
+        .find(|(_, outcome)| *outcome != Some(&true))?;
+    let detail = match outcome {
+        Some(_) => format!("Dependency '{}' failed", dep),
+        None => format!("Dependency '{}' did not run", dep),
+    };
+    Some(SkippedHook::new(context.dependency_key(), SkipReason::DependencyFailed, Some(detail)))
+}
+
+/// Order in which hooks of each mode run within a batch.
+fn mode_phase(mode: HookExecutionMode) -> u8 {
+    match mode {
+        HookExecutionMode::Blocking => 0,
+        HookExecutionMode::Async => 1,
+        HookExecutionMode::FireAndForget => 2,
+    }
+}
+
+/// Skip records for hooks that depend on a hook of the batch that runs after them.
+///
+/// Blocking hooks run before async hooks, and fire-and-forget hooks are never awaited, so a
+/// hook may only depend on hooks of an earlier phase or on blocking or async hooks of its own.
+fn mode_mismatch_skips(contexts: &[&ExecutionContext]) -> Vec<SkippedHook> {
+    let modes: HashMap<String, HookExecutionMode> = contexts
+        .iter()
+        .map(|context| (context.dependency_key(), context.config.mode))
+        .collect();
+
+    contexts
+        .iter()
+        .filter_map(|context| {
+            let mode = context.config.mode;
+            let (dep, dep_mode) = context
+                .depends_on
+                .iter()
+                .filter_map(|dep| modes.get(dep).map(|dep_mode| (dep, *dep_mode)))
+                .find(|(_, dep_mode)| {
+                    mode_phase(*dep_mode) > mode_phase(mode) || *dep_mode == HookExecutionMode::FireAndForget
+                })?;
+            Some(SkippedHook::new(
+                context.dependency_key(),
+                SkipReason::DependencyModeMismatch,
+                Some(format!(
+                    "{:?} hook cannot depend on {:?} hook '{}', which is not awaited before it",
+                    mode, dep_mode, dep
+                )),
+            ))
+        })
+        .collect()
+}
+
+/// Group executions into waves where each hook depends only on hooks in earlier waves.
+///
+/// Dependencies on hooks outside `executions` are ignored. Within a wave the input
+/// order is kept. Fails with a configuration error when the dependencies form a cycle.
+fn dependency_waves(
+    executions: Vec<(Arc<dyn HookExecutor>, ExecutionContext)>,
+) -> Result<Vec<Vec<(Arc<dyn HookExecutor>, ExecutionContext)>>, HookError> {
+    let keys: HashSet<String> = executions.iter().map(|(_, context)| context.dependency_key()).collect();
+    let mut placed = HashSet::new();
+    let mut remaining = executions;
+    let mut waves = Vec::new();
+
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|(_, context)| {
+            context
+                .depends_on
+                .iter()
+                .all(|dep| !keys.contains(dep) || placed.contains(dep))
+        });
+        if ready.is_empty() {
+            let mut hook_ids: Vec<_> = blocked.iter().map(|(_, context)| context.dependency_key()).collect();
+            hook_ids.sort();
+            return Err(HookError::Configuration(format!(
+                "Circular dependency detected between hooks: {}",
+                hook_ids.join(", ")
+            )));
+        }
+
+        placed.extend(ready.iter().map(|(_, context)| context.dependency_key()));
+        waves.push(ready);
+        remaining = blocked;
+    }
+
+    Ok(waves)
+}
+
+/// Failed result for an execution whose retries ran past `ExecutionConfig::total_deadline`.
+fn deadline_exceeded(
+    execution_id: String,
+    config: ExecutionConfig,
+    start_time: Instant,
+    attempts: u32,
+    last_error: Option<String>,
+) -> ExecutionResult {
+    let total_deadline = config.total_deadline.unwrap_or_default();
+    let message = format!("Execution exceeded total deadline of {:?}", total_deadline);
+    ExecutionResult {
+        execution_id,
+        result: HookResult::failure(message.clone(), start_time.elapsed()),
+        config,
+        duration: start_time.elapsed(),
+        retry_attempts: attempts.saturating_sub(1),
+        cancelled: false,
+        cancel_reason: None,
+        error_details: Some(match last_error {
+            Some(last_error) => format!("{} (last error: {})", message, last_error),
+            None => message,
+        }),
+        timing_breakdown: None,
+    }
+}
+
+/// Check whether an optional event-type filter accepts the context's event.
+pub fn event_filter_allows(filter: Option<&HashSet<LifecycleEventType>>, context: &HookContext) -> bool {
+    filter.map_or(true, |event_types| event_types.contains(&context.event.event_type()))
+}
+
 /// Enhanced trait for hook executors with advanced execution capabilities.
 #[async_trait]
 pub trait HookExecutor: Send + Sync {
@@ -195,7 +684,7 @@ pub trait HookExecutor: Send + Sync {
         debug!("Starting hook execution: {} ({})", execution_id, self.executor_type());
 
         // Check if already cancelled
-        if exec_context.is_cancelled().await {
+        if let Some(reason) = exec_context.cancel_reason().await {
             return ExecutionResult {
                 execution_id,
                 result: HookResult::failure("Execution cancelled before start".to_string(), Duration::ZERO),
@@ -203,17 +692,20 @@ pub trait HookExecutor: Send + Sync {
                 duration: start_time.elapsed(),
                 retry_attempts: 0,
                 cancelled: true,
+                cancel_reason: Some(reason),
                 error_details: Some("Pre-execution cancellation".to_string()),
+                timing_breakdown: None,
             };
         }
 
         let mut retry_attempts = 0;
         let mut last_error = None;
+        let deadline = config.total_deadline.map(|total| start_time + total);
 
         // Retry loop
         loop {
             // Check for cancellation before each attempt
-            if exec_context.is_cancelled().await {
+            if let Some(reason) = exec_context.cancel_reason().await {
                 return ExecutionResult {
                     execution_id,
                     result: HookResult::failure("Execution cancelled".to_string(), start_time.elapsed()),
@@ -221,13 +713,29 @@ pub trait HookExecutor: Send + Sync {
                     duration: start_time.elapsed(),
                     retry_attempts,
                     cancelled: true,
+                    cancel_reason: Some(reason),
                     error_details: Some("Mid-execution cancellation".to_string()),
+                    timing_breakdown: None,
                 };
             }
 
-            // Execute with timeout
-            let execution_future = self.execute(&exec_context.hook_context);
-            let _result: Result<HookExecutorResult, _> = match timeout(config.timeout, execution_future).await {
+            // Each attempt gets the per-attempt timeout, cut short by the total deadline
+            let attempt_timeout = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        warn!("Hook execution exceeded its total deadline: {}", execution_id);
+                        return deadline_exceeded(execution_id, config, start_time, retry_attempts, last_error);
+                    }
+                    config.timeout.min(remaining)
+                }
+                None => config.timeout,
+            };
+
+            // Execute with timeout; the executor sees the deadline so internal retries can respect it
+            let attempt_context = exec_context.hook_context.clone().with_deadline(Instant::now() + attempt_timeout);
+            let execution_future = self.execute(&attempt_context);
+            let retryable = match timeout(attempt_timeout, execution_future).await {
                 Ok(Ok(hook_result)) => {
                     debug!("Hook execution successful: {} (attempt {})", execution_id, retry_attempts + 1);
                     return ExecutionResult {
@@ -237,32 +745,42 @@ pub trait HookExecutor: Send + Sync {
                         duration: start_time.elapsed(),
                         retry_attempts,
                         cancelled: false,
+                        cancel_reason: None,
                         error_details: None,
+                        timing_breakdown: None,
                     };
                 }
                 Ok(Err(e)) => {
                     warn!("Hook execution failed: {} - {} (attempt {})", execution_id, e, retry_attempts + 1);
                     last_error = Some(e.to_string());
-                    Err(e)
+                    self.is_retryable(&e)
                 }
                 Err(_) => {
-                    warn!("Hook execution timed out: {} after {:?} (attempt {})", execution_id, config.timeout, retry_attempts + 1);
-                    let timeout_error = format!("Execution timed out after {:?}", config.timeout);
-                    last_error = Some(timeout_error.clone());
-                    Err(HookError::Execution(timeout_error))
+                    warn!("Hook execution timed out: {} after {:?} (attempt {})", execution_id, attempt_timeout, retry_attempts + 1);
+                    let timeout_error = format!("Execution timed out after {:?}", attempt_timeout);
+                    last_error = Some(timeout_error);
+                    true
                 }
             };
 
             retry_attempts += 1;
 
             // Check if we should retry
+            if !retryable {
+                debug!("Hook execution error is not retryable: {}", execution_id);
+                break;
+            }
             if retry_attempts > config.max_retries {
                 break;
             }
 
-            // Wait before retry
-            if config.retry_delay > Duration::ZERO {
-                tokio::time::sleep(config.retry_delay).await;
+            // Wait before retry, waking at the total deadline if it comes first
+            let retry_delay = match deadline {
+                Some(deadline) => config.retry_delay.min(deadline.saturating_duration_since(Instant::now())),
+                None => config.retry_delay,
+            };
+            if retry_delay > Duration::ZERO {
+                tokio::time::sleep(retry_delay).await;
             }
         }
 
@@ -275,7 +793,9 @@ pub trait HookExecutor: Send + Sync {
             duration: start_time.elapsed(),
             retry_attempts: retry_attempts.saturating_sub(1),
             cancelled: false,
+            cancel_reason: None,
             error_details: Some(error_msg),
+            timing_breakdown: None,
         }
     }
 
@@ -304,15 +824,125 @@ pub trait HookExecutor: Send + Sync {
     async fn cleanup(&self, _context: &HookContext) -> Result<(), HookError> {
         Ok(())
     }
+
+    /// Whether an error returned by `execute` is worth retrying.
+    ///
+    /// Returning `false` ends the retry loop after the current attempt.
+    fn is_retryable(&self, _error: &HookError) -> bool {
+        true
+    }
+
+    /// Check whether this executor is able to run hooks right now.
+    async fn health_check(&self) -> Result<(), HookError> {
+        Ok(())
+    }
+}
+
+/// A failed fire-and-forget execution kept for later inspection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadLetter {
+    /// ID of the hook that failed.
+    pub hook_id: String,
+    /// Execution ID of the failed run.
+    pub execution_id: String,
+    /// Event the hook was handling.
+    pub event_type: LifecycleEventType,
+    /// Why the execution failed.
+    pub error: String,
+    /// Short description of the hook context.
+    pub context_summary: String,
+    /// When the failure was recorded.
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Bounded store of dead letters, optionally mirrored to a JSONL file.
+#[derive(Debug)]
+struct DeadLetterSink {
+    capacity: usize,
+    path: Option<PathBuf>,
+    entries: std::sync::Mutex<VecDeque<DeadLetter>>,
+}
+
+impl DeadLetterSink {
+    /// Keep `letter`, dropping the oldest entry when the ring is full.
+    fn record(&self, letter: DeadLetter) {
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::append_to_file(path, &letter) {
+                warn!("Failed to write dead letter to {}: {}", path.display(), e);
+            }
+        }
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        entries.push_back(letter);
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+    }
+
+    fn append_to_file(path: &Path, letter: &DeadLetter) -> Result<(), HookError> {
+        let line = serde_json::to_string(letter)
+            .map_err(|e| HookError::Execution(format!("Failed to serialize dead letter: {}", e)))?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| HookError::Execution(format!("Failed to open dead letter file: {}", e)))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| HookError::Execution(format!("Failed to write dead letter file: {}", e)))
+    }
+}
+
+/// What the coordinator does with an event's remaining hooks once a required blocking hook fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequiredFailurePolicy {
+    /// Keep running every hook.
+    #[default]
+    Continue,
+    /// Skip the remaining blocking hooks; async and fire-and-forget hooks still run.
+    SkipBlocking,
+    /// Skip every hook that has not started yet.
+    SkipAll,
+}
+
+/// An execution the coordinator is tracking.
+#[derive(Debug)]
+struct ActiveExecution {
+    context: Arc<ExecutionContext>,
+    /// Aborts the spawned task of an isolated hook once it has started.
+    abort: Option<tokio::task::AbortHandle>,
+}
+
+impl ActiveExecution {
+    /// Flag the execution as cancelled and stop its task, if it has one.
+    async fn cancel(&self, reason: CancelReason) {
+        self.context.cancel(reason).await;
+        if let Some(abort) = &self.abort {
+            abort.abort();
+        }
+    }
 }
 
 /// Advanced execution coordinator that manages multiple hook executions.
 #[derive(Debug)]
 pub struct ExecutionCoordinator {
     /// Active executions being tracked.
-    active_executions: Arc<Mutex<HashMap<String, Arc<ExecutionContext>>>>,
+    active_executions: Arc<Mutex<HashMap<String, ActiveExecution>>>,
     /// Global execution statistics.
     stats: Arc<RwLock<ExecutionStats>>,
+    /// Concurrency caps keyed by `HookExecutor::executor_type`.
+    executor_limits: HashMap<String, Arc<ConcurrencyLimit>>,
+    /// Concurrency caps keyed by the event type being handled.
+    event_limits: HashMap<LifecycleEventType, Arc<ConcurrencyLimit>>,
+    /// Cap on all hooks run by this coordinator.
+    global_limit: Option<Arc<ConcurrencyLimit>>,
+    /// Handling of the remaining hooks after a required blocking hook fails.
+    required_failure_policy: RequiredFailurePolicy,
+    /// Where failed fire-and-forget executions are recorded.
+    dead_letters: Option<Arc<DeadLetterSink>>,
+    /// Running totals of hooks running and waiting for a slot.
+    load: Arc<LoadTracker>,
 }
 
 /// Global execution statistics.
@@ -322,135 +952,724 @@ pub struct ExecutionStats {
     pub successful_executions: u64,
     pub failed_executions: u64,
     pub cancelled_executions: u64,
+    pub panicked_executions: u64,
     pub total_execution_time: Duration,
     pub average_execution_time: Duration,
+    /// Utilization of each capped executor type.
+    pub executor_utilization: HashMap<String, ExecutorUtilization>,
+    /// Utilization of each capped event type.
+    pub event_utilization: HashMap<LifecycleEventType, ExecutorUtilization>,
+    /// Utilization of the global cap, when one is set.
+    pub global_utilization: Option<ExecutorUtilization>,
 }
 
-impl ExecutionCoordinator {
-    /// Create a new execution coordinator.
-    pub fn new() -> Self {
+/// Utilization of a concurrency cap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutorUtilization {
+    /// Maximum hooks of this type allowed to run at once.
+    pub max_concurrent: usize,
+    /// Hooks of this type running now.
+    pub in_flight: usize,
+    /// Highest number of hooks of this type seen running at once.
+    pub peak_in_flight: usize,
+    /// Hooks of this type that had to wait for a free slot.
+    pub waited: u64,
+    /// Hooks waiting for a free slot now.
+    pub queued: usize,
+    /// Highest number of hooks seen waiting at once.
+    pub peak_queued: usize,
+    /// Total time hooks spent waiting for a free slot.
+    pub total_wait_time: Duration,
+}
+
+/// Semaphore limiting how many hooks run at once.
+#[derive(Debug)]
+struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    in_flight: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+    waited: AtomicU64,
+    queued: AtomicUsize,
+    peak_queued: AtomicUsize,
+    total_wait_micros: AtomicU64,
+}
+
+impl ConcurrencyLimit {
+    fn new(max_concurrent: usize) -> Self {
         Self {
-            active_executions: Arc::new(Mutex::new(HashMap::new())),
-            stats: Arc::new(RwLock::new(ExecutionStats::default())),
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            in_flight: AtomicUsize::new(0),
+            peak_in_flight: AtomicUsize::new(0),
+            waited: AtomicU64::new(0),
+            queued: AtomicUsize::new(0),
+            peak_queued: AtomicUsize::new(0),
+            total_wait_micros: AtomicU64::new(0),
         }
     }
 
-    /// Execute multiple hooks with different execution modes.
-    pub async fn execute_hooks(
-        &self,
-        executions: Vec<(Arc<dyn HookExecutor>, ExecutionContext)>,
-    ) -> AggregatedResults {
-        info!("Starting coordinated execution of {} hooks", executions.len());
+    /// Wait for a free slot; the slot is released when the guard is dropped.
+    async fn acquire(self: &Arc<Self>) -> Result<ConcurrencySlot, HookError> {
+        let permit = match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.waited.fetch_add(1, Ordering::Relaxed);
+                let queued = self.queued.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak_queued.fetch_max(queued, Ordering::SeqCst);
+                let _dequeue = QueuedGuard(&self.queued);
+
+                let wait_start = Instant::now();
+                let permit = self
+                    .semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| HookError::Execution(format!("Concurrency limit unavailable: {}", e)))?;
+                self.total_wait_micros
+                    .fetch_add(wait_start.elapsed().as_micros() as u64, Ordering::Relaxed);
+                permit
+            }
+        };
 
-        // Separate executions by mode
-        let (blocking, async_hooks, fire_and_forget): (Vec<_>, Vec<_>, Vec<_>) = executions
-            .into_iter()
-            .fold((Vec::new(), Vec::new(), Vec::new()), |mut acc, (executor, context)| {
-                match context.config.mode {
-                    HookExecutionMode::Blocking => acc.0.push((executor, context)),
-                    HookExecutionMode::Async => acc.1.push((executor, context)),
-                    HookExecutionMode::FireAndForget => acc.2.push((executor, context)),
-                }
-                acc
-            });
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
 
-        let mut all_results = Vec::new();
+        Ok(ConcurrencySlot {
+            limit: self.clone(),
+            _permit: permit,
+        })
+    }
 
-        // Execute blocking hooks sequentially
-        for (executor, context) in blocking {
-            let result = self.execute_single_tracked(executor, context).await;
-            all_results.push(result);
+    fn utilization(&self) -> ExecutorUtilization {
+        ExecutorUtilization {
+            max_concurrent: self.max_concurrent,
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            peak_in_flight: self.peak_in_flight.load(Ordering::SeqCst),
+            waited: self.waited.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::SeqCst),
+            peak_queued: self.peak_queued.load(Ordering::SeqCst),
+            total_wait_time: Duration::from_micros(self.total_wait_micros.load(Ordering::Relaxed)),
         }
+    }
+}
 
-        // Execute async hooks in parallel
-        if !async_hooks.is_empty() {
-            let async_futures: Vec<_> = async_hooks
-                .into_iter()
-                .map(|(executor, context)| self.execute_single_tracked(executor, context))
-                .collect();
-
-            let async_results = join_all(async_futures).await;
-            all_results.extend(async_results);
-        }
+/// Takes a hook back off a queue when it gets a slot or stops waiting.
+struct QueuedGuard<'a>(&'a AtomicUsize);
 
-        // Execute fire-and-forget hooks (don't wait for completion)
-        for (executor, context) in fire_and_forget {
-            let coordinator = self.clone();
-            tokio::spawn(async move {
-                let _result = coordinator.execute_single_tracked(executor, context).await;
-                // Fire-and-forget results are not included in aggregated results
-            });
-        }
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
 
-        // Update global statistics
-        self.update_stats(&all_results).await;
+/// Held while a capped hook runs.
+struct ConcurrencySlot {
+    limit: Arc<ConcurrencyLimit>,
+    _permit: OwnedSemaphorePermit,
+}
 
-        let aggregated = AggregatedResults::from_results(all_results);
-        info!("Coordinated execution completed: {}", aggregated.summary());
-        aggregated
+impl Drop for ConcurrencySlot {
+    fn drop(&mut self) {
+        self.limit.in_flight.fetch_sub(1, Ordering::SeqCst);
     }
+}
 
-    /// Execute a single hook with tracking.
-    async fn execute_single_tracked(
-        &self,
-        executor: Arc<dyn HookExecutor>,
-        context: ExecutionContext,
-    ) -> ExecutionResult {
-        let execution_id = context.execution_id.clone();
+/// Time-weighted totals of the hooks running and waiting for a slot since the coordinator
+/// was created.
+#[derive(Debug)]
+struct LoadTracker {
+    totals: std::sync::Mutex<LoadTotals>,
+}
 
-        // Track active execution
-        {
-            let mut active = self.active_executions.lock().await;
-            active.insert(execution_id.clone(), Arc::new(context.clone()));
-        }
+#[derive(Debug)]
+struct LoadTotals {
+    since: Instant,
+    last_change: Instant,
+    running: usize,
+    queued: usize,
+    /// `running` integrated over time, in hook-seconds.
+    running_seconds: f64,
+    /// `queued` integrated over time, in hook-seconds.
+    queued_seconds: f64,
+    /// Hooks that have left the queue with their slots.
+    dequeued: u64,
+}
 
-        // Prepare for execution
-        if let Err(e) = executor.prepare(&context.hook_context).await {
-            warn!("Hook preparation failed: {} - {}", execution_id, e);
-            return ExecutionResult {
-                execution_id: execution_id.clone(),
-                result: HookResult::failure(format!("Preparation failed: {}", e), Duration::ZERO),
-                config: context.config.clone(),
-                duration: Duration::ZERO,
-                retry_attempts: 0,
-                cancelled: false,
-                error_details: Some(format!("Preparation error: {}", e)),
-            };
+impl LoadTracker {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            totals: std::sync::Mutex::new(LoadTotals {
+                since: now,
+                last_change: now,
+                running: 0,
+                queued: 0,
+                running_seconds: 0.0,
+                queued_seconds: 0.0,
+                dequeued: 0,
+            }),
         }
+    }
 
-        // Execute the hook
-        let result = if context.config.isolated {
-            // Execute in isolated task
-            let executor_clone = executor.clone();
-            let context_clone = context.clone();
-            tokio::spawn(async move {
-                executor_clone.execute_with_context(&context_clone).await
-            })
-            .await
-            .unwrap_or_else(|e| ExecutionResult {
-                execution_id: execution_id.clone(),
-                result: HookResult::failure(format!("Task join error: {}", e), context.elapsed()),
-                config: context.config.clone(),
-                duration: context.elapsed(),
-                retry_attempts: 0,
-                cancelled: false,
-                error_details: Some(format!("Isolation error: {}", e)),
-            })
-        } else {
-            // Execute directly
-            executor.execute_with_context(&context).await
+    /// Close the current interval at the old counts, then apply `change`.
+    fn update(&self, change: impl FnOnce(&mut LoadTotals)) {
+        let Ok(mut totals) = self.totals.lock() else {
+            return;
         };
+        let now = Instant::now();
+        let elapsed = now.duration_since(totals.last_change).as_secs_f64();
+        totals.running_seconds += totals.running as f64 * elapsed;
+        totals.queued_seconds += totals.queued as f64 * elapsed;
+        totals.last_change = now;
+        change(&mut totals);
+    }
 
-        // Cleanup after execution
-        if let Err(e) = executor.cleanup(&context.hook_context).await {
-            warn!("Hook cleanup failed: {} - {}", execution_id, e);
+    /// Average hooks running, average hooks queued, and hooks dequeued per second.
+    fn averages(&self) -> (f64, f64, f64) {
+        self.update(|_| {});
+        let Ok(totals) = self.totals.lock() else {
+            return (0.0, 0.0, 0.0);
+        };
+        let elapsed = totals.since.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        (
+            totals.running_seconds / elapsed,
+            totals.queued_seconds / elapsed,
+            totals.dequeued as f64 / elapsed,
+        )
+    }
+}
+
+/// Counts a hook as queued until it has its slots, then as running until dropped.
+struct LoadGuard {
+    tracker: Arc<LoadTracker>,
+    running: bool,
+}
+
+impl LoadGuard {
+    fn queued(tracker: Arc<LoadTracker>) -> Self {
+        tracker.update(|totals| totals.queued += 1);
+        Self { tracker, running: false }
+    }
+
+    fn start(&mut self) {
+        self.tracker.update(|totals| {
+            totals.queued -= 1;
+            totals.running += 1;
+            totals.dequeued += 1;
+        });
+        self.running = true;
+    }
+}
+
+impl Drop for LoadGuard {
+    fn drop(&mut self) {
+        let running = self.running;
+        self.tracker.update(|totals| {
+            if running {
+                totals.running -= 1;
+            } else {
+                totals.queued -= 1;
+            }
+        });
+    }
+}
+
+/// Removes an execution from the active set however its tracking call ends, including
+/// when the future running it is dropped.
+struct ActiveExecutionGuard {
+    active: Arc<Mutex<HashMap<String, ActiveExecution>>>,
+    execution_id: String,
+}
+
+impl Drop for ActiveExecutionGuard {
+    fn drop(&mut self) {
+        let execution_id = std::mem::take(&mut self.execution_id);
+        if let Ok(mut active) = self.active.try_lock() {
+            active.remove(&execution_id);
+            return;
+        }
+        // The set is locked elsewhere; remove the entry once it is free
+        let active = self.active.clone();
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                active.lock().await.remove(&execution_id);
+            });
+        }
+    }
+}
+
+/// Aborts an isolated hook task when the future awaiting it is dropped.
+struct AbortOnDrop(tokio::task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl ExecutionCoordinator {
+    /// Create a new execution coordinator.
+    pub fn new() -> Self {
+        Self {
+            active_executions: Arc::new(Mutex::new(HashMap::new())),
+            stats: Arc::new(RwLock::new(ExecutionStats::default())),
+            executor_limits: HashMap::new(),
+            event_limits: HashMap::new(),
+            global_limit: None,
+            required_failure_policy: RequiredFailurePolicy::default(),
+            dead_letters: None,
+            load: Arc::new(LoadTracker::new()),
+        }
+    }
+
+    /// Allow at most `max_concurrent` hooks of `executor_type` to run at once.
+    pub fn with_executor_concurrency_limit(mut self, executor_type: impl Into<String>, max_concurrent: usize) -> Self {
+        self.executor_limits.insert(
+            executor_type.into(),
+            Arc::new(ConcurrencyLimit::new(max_concurrent.max(1))),
+        );
+        self
+    }
+
+    /// Allow at most `max_concurrent` hooks for `event_type` to run at once.
+    pub fn with_event_concurrency_limit(mut self, event_type: LifecycleEventType, max_concurrent: usize) -> Self {
+        self.event_limits.insert(event_type, Arc::new(ConcurrencyLimit::new(max_concurrent.max(1))));
+        self
+    }
+
+    /// Set what happens to an event's remaining hooks once a required blocking hook fails.
+    pub fn with_required_failure_policy(mut self, policy: RequiredFailurePolicy) -> Self {
+        self.required_failure_policy = policy;
+        self
+    }
+
+    /// Record failed fire-and-forget executions, keeping the newest `capacity` in memory and
+    /// appending every one to `path` as JSONL when given.
+    pub fn with_dead_letter_queue(mut self, capacity: usize, path: Option<PathBuf>) -> Self {
+        self.dead_letters = Some(Arc::new(DeadLetterSink {
+            capacity: capacity.max(1),
+            path,
+            entries: std::sync::Mutex::new(VecDeque::new()),
+        }));
+        self
+    }
+
+    /// Take the dead letters recorded in memory, oldest first.
+    pub fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters
+            .as_ref()
+            .and_then(|sink| sink.entries.lock().ok().map(|mut entries| entries.drain(..).collect()))
+            .unwrap_or_default()
+    }
+
+    /// Allow at most `max_concurrent` hooks to run at once across all events; the rest queue.
+    pub fn with_global_concurrency_limit(mut self, max_concurrent: usize) -> Self {
+        self.global_limit = Some(Arc::new(ConcurrencyLimit::new(max_concurrent.max(1))));
+        self
+    }
+
+    /// Execute multiple hooks with different execution modes.
+    pub async fn execute_hooks(
+        &self,
+        executions: Vec<(Arc<dyn HookExecutor>, ExecutionContext)>,
+    ) -> AggregatedResults {
+        self.execute_hooks_until(executions, None).await
+    }
+
+    /// Execute multiple hooks, giving non-required hooks at most `budget` in total.
+    ///
+    /// Once the budget is spent, non-required hooks that have not started are skipped with
+    /// `SkipReason::BudgetExhausted` and those still running are cancelled. Required hooks
+    /// always run to completion.
+    pub async fn execute_hooks_within(
+        &self,
+        executions: Vec<(Arc<dyn HookExecutor>, ExecutionContext)>,
+        budget: Duration,
+    ) -> AggregatedResults {
+        self.execute_hooks_until(executions, Some(Instant::now() + budget)).await
+    }
+
+    async fn execute_hooks_until(
+        &self,
+        executions: Vec<(Arc<dyn HookExecutor>, ExecutionContext)>,
+        deadline: Option<Instant>,
+    ) -> AggregatedResults {
+        info!("Starting coordinated execution of {} hooks", executions.len());
+
+        // A dependency cycle is a configuration error, so none of the hooks run
+        let keys: Vec<String> = executions.iter().map(|(_, context)| context.dependency_key()).collect();
+        let waves = match dependency_waves(executions) {
+            Ok(waves) => waves,
+            Err(e) => {
+                error!("Not running hooks: {}", e);
+                let skipped = keys
+                    .into_iter()
+                    .map(|key| SkippedHook::new(key, SkipReason::DependencyCycle, Some(e.to_string())))
+                    .collect();
+                return AggregatedResults::default().with_skipped(skipped);
+            }
+        };
+
+        let mut all_results = Vec::new();
+        // Whether each hook that has finished or been skipped succeeded, keyed by hook ID
+        let mut outcomes: HashMap<String, bool> = HashMap::new();
+        // Reason to skip everything not yet started, once a required blocking hook fails
+        let mut halted: Option<String> = None;
+
+        // Hooks waiting on a later mode would run before their dependency, so they are skipped
+        let contexts: Vec<&ExecutionContext> = waves.iter().flatten().map(|(_, context)| context).collect();
+        let mut skipped = mode_mismatch_skips(&contexts);
+        for skip in &skipped {
+            warn!("Not running hook {}: {}", skip.hook_id, skip.detail.as_deref().unwrap_or_default());
+            outcomes.insert(skip.hook_id.clone(), false);
+        }
+
+        // Separate executions by mode, keeping dependency order within each mode
+        let mut blocking = Vec::new();
+        let mut async_waves = Vec::new();
+        let mut fire_and_forget = Vec::new();
+        for wave in waves {
+            let mut async_wave = Vec::new();
+            for (executor, context) in wave {
+                if outcomes.contains_key(&context.dependency_key()) {
+                    continue;
+                }
+                match context.config.mode {
+                    HookExecutionMode::Blocking => blocking.push((executor, context)),
+                    HookExecutionMode::Async => async_wave.push((executor, context)),
+                    HookExecutionMode::FireAndForget => fire_and_forget.push((executor, context)),
+                }
+            }
+            if !async_wave.is_empty() {
+                async_waves.push(async_wave);
+            }
+        }
+
+        // Execute blocking hooks sequentially, dependencies first
+        for (executor, context) in blocking {
+            let key = context.dependency_key();
+            if let Some(detail) = &halted {
+                skipped.push(SkippedHook::new(key.clone(), SkipReason::RequiredHookFailed, Some(detail.clone())));
+                outcomes.insert(key, false);
+                continue;
+            }
+            if let Some(skip) = dependency_skip(&context, &outcomes) {
+                skipped.push(skip);
+                outcomes.insert(key, false);
+                continue;
+            }
+            if budget_exhausted(deadline, &context) {
+                skipped.push(budget_skip(&context));
+                outcomes.insert(key, false);
+                continue;
+            }
+            let result = self.execute_single_until(executor, context, deadline).await;
+            let succeeded = result.result.success && !result.cancelled;
+            if !succeeded && result.config.required && self.required_failure_policy != RequiredFailurePolicy::Continue {
+                warn!("Required hook {} failed, skipping the remaining blocking hooks", key);
+                halted = Some(format!("Required hook '{}' failed", key));
+            }
+            outcomes.insert(key, succeeded);
+            all_results.push(result);
+        }
+
+        // Under `SkipAll` nothing else starts after a required failure
+        let (async_waves, fire_and_forget) = match &halted {
+            Some(detail) if self.required_failure_policy == RequiredFailurePolicy::SkipAll => {
+                skipped.extend(async_waves.iter().flatten().chain(&fire_and_forget).map(|(_, context)| {
+                    SkippedHook::new(context.dependency_key(), SkipReason::RequiredHookFailed, Some(detail.clone()))
+                }));
+                (Vec::new(), Vec::new())
+            }
+            _ => (async_waves, fire_and_forget),
+        };
+
+        // Execute async hooks in parallel, one dependency wave at a time
+        for wave in async_waves {
+            let mut runnable = Vec::new();
+            for (executor, context) in wave {
+                let skip = dependency_skip(&context, &outcomes)
+                    .or_else(|| budget_exhausted(deadline, &context).then(|| budget_skip(&context)));
+                match skip {
+                    Some(skip) => {
+                        outcomes.insert(context.dependency_key(), false);
+                        skipped.push(skip);
+                    }
+                    None => runnable.push((executor, context)),
+                }
+            }
+
+            let keys: Vec<_> = runnable.iter().map(|(_, context)| context.dependency_key()).collect();
+            let async_futures: Vec<_> = runnable
+                .into_iter()
+                .map(|(executor, context)| self.execute_single_until(executor, context, deadline))
+                .collect();
+
+            let async_results = join_all(async_futures).await;
+            for (key, result) in keys.into_iter().zip(&async_results) {
+                outcomes.insert(key, result.result.success && !result.cancelled);
+            }
+            all_results.extend(async_results);
+        }
+
+        // Execute fire-and-forget hooks (don't wait for completion)
+        for (executor, context) in fire_and_forget {
+            if let Some(skip) = dependency_skip(&context, &outcomes) {
+                skipped.push(skip);
+                continue;
+            }
+            let coordinator = self.clone();
+            tokio::spawn(async move {
+                let hook_id = context.dependency_key();
+                let event_type = context.hook_context.event.event_type();
+                let context_summary = format!(
+                    "{} event in {}",
+                    event_type,
+                    context.hook_context.working_directory.display()
+                );
+                let result = coordinator.execute_single_tracked(executor, context).await;
+
+                // Fire-and-forget results are not included in aggregated results, so
+                // failures go to the dead-letter sink instead
+                if let Some(sink) = &coordinator.dead_letters {
+                    if !result.result.success && !result.cancelled {
+                        sink.record(DeadLetter {
+                            hook_id,
+                            execution_id: result.execution_id.clone(),
+                            event_type,
+                            error: result
+                                .result
+                                .error
+                                .clone()
+                                .or_else(|| result.error_details.clone())
+                                .unwrap_or_else(|| "unknown error".to_string()),
+                            context_summary,
+                            failed_at: Utc::now(),
+                        });
+                    }
+                }
+            });
+        }
+
+        // Update global statistics
+        self.update_stats(&all_results).await;
+
+        let aggregated = AggregatedResults::from_results(all_results).with_skipped(skipped);
+        info!("Coordinated execution completed: {}", aggregated.summary());
+        aggregated
+    }
+
+    /// Execute a single hook, cancelling it at `deadline` unless it is required.
+    async fn execute_single_until(
+        &self,
+        executor: Arc<dyn HookExecutor>,
+        context: ExecutionContext,
+        deadline: Option<Instant>,
+    ) -> ExecutionResult {
+        let Some(deadline) = deadline.filter(|_| !context.config.required) else {
+            return self.execute_single_tracked(executor, context).await;
+        };
+
+        let execution_id = context.execution_id.clone();
+        let config = context.config.clone();
+        let start_time = context.start_time;
+        match tokio::time::timeout_at(deadline.into(), self.execute_single_tracked(executor, context)).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!("Hook execution cancelled, event time budget exhausted: {}", execution_id);
+                ExecutionResult {
+                    execution_id,
+                    result: HookResult::failure(EVENT_BUDGET_EXHAUSTED.to_string(), start_time.elapsed()),
+                    config,
+                    duration: start_time.elapsed(),
+                    retry_attempts: 0,
+                    cancelled: true,
+                    cancel_reason: Some(CancelReason::BudgetExhausted),
+                    error_details: Some("Event budget cancellation".to_string()),
+                    timing_breakdown: None,
+                }
+            }
         }
+    }
+
+    /// Execute a single hook with tracking.
+    async fn execute_single_tracked(
+        &self,
+        executor: Arc<dyn HookExecutor>,
+        context: ExecutionContext,
+    ) -> ExecutionResult {
+        let execution_id = context.execution_id.clone();
 
-        // Remove from active executions
+        // Track active execution until this call returns or its future is dropped
         {
             let mut active = self.active_executions.lock().await;
-            active.remove(&execution_id);
+            active.insert(
+                execution_id.clone(),
+                ActiveExecution {
+                    context: Arc::new(context.clone()),
+                    abort: None,
+                },
+            );
+        }
+        let _tracked = ActiveExecutionGuard {
+            active: self.active_executions.clone(),
+            execution_id: execution_id.clone(),
+        };
+
+        // Wait for a slot under each cap that applies, always in the same order, taking
+        // the global slot last so hooks queued on a narrower cap don't hold one
+        let mut load = LoadGuard::queued(self.load.clone());
+        let wait_start = Instant::now();
+        let limits = [
+            self.event_limits.get(&context.hook_context.event.event_type()),
+            self.executor_limits.get(executor.executor_type()),
+            self.global_limit.as_ref(),
+        ];
+        let mut slots = Vec::with_capacity(limits.len());
+        for limit in limits.into_iter().flatten() {
+            match limit.acquire().await {
+                Ok(slot) => slots.push(slot),
+                Err(e) => {
+                    warn!("Hook could not get a concurrency slot: {} - {}", execution_id, e);
+                    return ExecutionResult {
+                        execution_id: execution_id.clone(),
+                        result: HookResult::failure(e.to_string(), Duration::ZERO),
+                        config: context.config.clone(),
+                        duration: Duration::ZERO,
+                        retry_attempts: 0,
+                        cancelled: false,
+                        cancel_reason: None,
+                        error_details: Some(e.to_string()),
+                        timing_breakdown: None,
+                    };
+                }
+            }
         }
+        let wait_time = wait_start.elapsed();
+        load.start();
+
+        // Prepare for execution
+        let setup_start = Instant::now();
+        let prepared = executor.prepare(&context.hook_context).await;
+        let setup_time = setup_start.elapsed();
+        if let Err(e) = prepared {
+            warn!("Hook preparation failed: {} - {}", execution_id, e);
+            return ExecutionResult {
+                execution_id: execution_id.clone(),
+                result: HookResult::failure(format!("Preparation failed: {}", e), Duration::ZERO),
+                config: context.config.clone(),
+                duration: Duration::ZERO,
+                retry_attempts: 0,
+                cancelled: false,
+                cancel_reason: None,
+                error_details: Some(format!("Preparation error: {}", e)),
+                timing_breakdown: Some(TimingBreakdown {
+                    setup_time,
+                    execution_time: Duration::ZERO,
+                    cleanup_time: Duration::ZERO,
+                    wait_time,
+                    serialization_time: Duration::ZERO,
+                }),
+            };
+        }
+
+        // Execute the hook, converting panics into failed results
+        let execution_start = Instant::now();
+        let outcome = if context.config.isolated {
+            // Execute in isolated task
+            let executor_clone = executor.clone();
+            let context_clone = context.clone();
+            let handle = tokio::spawn(async move {
+                executor_clone.execute_with_context(&context_clone).await
+            });
+            // Abort the task if this future is dropped, e.g. when the event budget runs out
+            let _abort = AbortOnDrop(handle.abort_handle());
+            // Let cancellation stop a hook that is stuck inside `execute`
+            if let Some(active) = self.active_executions.lock().await.get_mut(&execution_id) {
+                active.abort = Some(handle.abort_handle());
+            }
+            match handle.await {
+                Ok(result) => Ok(result),
+                Err(e) if e.is_panic() => Err(panic_message(e.into_panic().as_ref())),
+                Err(e) if e.is_cancelled() => Ok(ExecutionResult {
+                    execution_id: execution_id.clone(),
+                    result: HookResult::failure("Execution cancelled".to_string(), context.elapsed()),
+                    config: context.config.clone(),
+                    duration: context.elapsed(),
+                    retry_attempts: 0,
+                    cancelled: true,
+                    cancel_reason: context.cancel_reason().await,
+                    error_details: Some("Isolated task aborted".to_string()),
+                    timing_breakdown: None,
+                }),
+                Err(e) => Ok(ExecutionResult {
+                    execution_id: execution_id.clone(),
+                    result: HookResult::failure(format!("Task join error: {}", e), context.elapsed()),
+                    config: context.config.clone(),
+                    duration: context.elapsed(),
+                    retry_attempts: 0,
+                    cancelled: false,
+                    cancel_reason: None,
+                    error_details: Some(format!("Isolation error: {}", e)),
+                    timing_breakdown: None,
+                }),
+            }
+        } else {
+            // Execute directly
+            AssertUnwindSafe(executor.execute_with_context(&context))
+                .catch_unwind()
+                .await
+                .map_err(|payload| panic_message(payload.as_ref()))
+        };
+
+        let mut result = match outcome {
+            Ok(result) => result,
+            Err(message) => {
+                let error = HookError::Panic(message);
+                error!("Hook execution panicked: {} - {}", execution_id, error);
+                self.stats.write().await.panicked_executions += 1;
+                ExecutionResult {
+                    execution_id: execution_id.clone(),
+                    result: HookResult::failure(error.to_string(), context.elapsed()),
+                    config: context.config.clone(),
+                    duration: context.elapsed(),
+                    retry_attempts: 0,
+                    cancelled: false,
+                    cancel_reason: None,
+                    error_details: Some(error.to_string()),
+                    timing_breakdown: None,
+                }
+            }
+        };
+        let execution_total = execution_start.elapsed();
+
+        // Cleanup after execution
+        let cleanup_start = Instant::now();
+        if let Err(e) = executor.cleanup(&context.hook_context).await {
+            warn!("Hook cleanup failed: {} - {}", execution_id, e);
+        }
+        let cleanup_time = cleanup_start.elapsed();
+
+        // Executors report payload (de)serialization separately from the rest of execution
+        let serialization_time = result
+            .result
+            .metadata
+            .get(SERIALIZATION_TIME_METADATA_KEY)
+            .and_then(|value| value.as_u64())
+            .map(Duration::from_micros)
+            .unwrap_or_default()
+            .min(execution_total);
+        result.timing_breakdown = Some(TimingBreakdown {
+            setup_time,
+            execution_time: execution_total - serialization_time,
+            cleanup_time,
+            wait_time,
+            serialization_time,
+        });
 
         result
     }
@@ -458,19 +1677,40 @@ impl ExecutionCoordinator {
     /// Cancel a specific execution.
     pub async fn cancel_execution(&self, execution_id: &str) -> bool {
         let active = self.active_executions.lock().await;
-        if let Some(context) = active.get(execution_id) {
-            context.cancel().await;
+        if let Some(execution) = active.get(execution_id) {
+            execution.cancel(CancelReason::ById).await;
             true
         } else {
             false
         }
     }
 
-    /// Cancel all active executions.
-    pub async fn cancel_all(&self) {
+    /// Cancel all active executions for the given reason.
+    pub async fn cancel_all(&self, reason: CancelReason) {
         let active = self.active_executions.lock().await;
-        for context in active.values() {
-            context.cancel().await;
+        for execution in active.values() {
+            execution.cancel(reason).await;
+        }
+    }
+
+    /// Drain active executions on shutdown, cancelling them with `CancelReason::Shutdown`.
+    pub async fn shutdown(&self) {
+        info!("Draining active hook executions for shutdown");
+        self.cancel_all(CancelReason::Shutdown).await;
+    }
+
+    /// Best-effort synchronous variant of `shutdown` for callers that cannot await, such as `Drop`.
+    pub fn shutdown_now(&self) {
+        let Ok(active) = self.active_executions.try_lock() else {
+            return;
+        };
+        for execution in active.values() {
+            if let Ok(mut cancelled) = execution.context.cancelled.try_write() {
+                cancelled.get_or_insert(CancelReason::Shutdown);
+            }
+            if let Some(abort) = &execution.abort {
+                abort.abort();
+            }
         }
     }
 
@@ -505,7 +1745,80 @@ impl ExecutionCoordinator {
 
     /// Get current execution statistics.
     pub async fn get_stats(&self) -> ExecutionStats {
-        self.stats.read().await.clone()
+        let mut stats = self.stats.read().await.clone();
+        stats.executor_utilization = self
+            .executor_limits
+            .iter()
+            .map(|(executor_type, limit)| (executor_type.clone(), limit.utilization()))
+            .collect();
+        stats.event_utilization = self
+            .event_limits
+            .iter()
+            .map(|(event_type, limit)| (*event_type, limit.utilization()))
+            .collect();
+        stats.global_utilization = self.global_limit.as_ref().map(|limit| limit.utilization());
+        stats
+    }
+
+    /// Number of hooks waiting for a free slot under any concurrency cap.
+    pub fn queue_depth(&self) -> usize {
+        self.all_limits().map(|limit| limit.queued.load(Ordering::SeqCst)).sum()
+    }
+
+    /// Concurrency and queueing figures for `PerformanceCollector::update_concurrency_metrics`.
+    pub async fn concurrency_metrics(&self) -> ConcurrencyMetrics {
+        let utilizations: Vec<_> = self.all_limits().map(|limit| limit.utilization()).collect();
+        let queue_depth = self.queue_depth();
+        // Active executions include hooks still waiting for a slot
+        let current_concurrent = self.active_executions.lock().await.len().saturating_sub(queue_depth);
+
+        let waited: u64 = utilizations.iter().map(|u| u.waited).sum();
+        let total_wait: Duration = utilizations.iter().map(|u| u.total_wait_time).sum();
+        let average_wait_time = if waited > 0 { total_wait / waited as u32 } else { Duration::ZERO };
+        let (average_concurrent, average_depth, throughput) = self.load.averages();
+        let thread_pool_utilization = self
+            .global_limit
+            .as_ref()
+            .map(|limit| limit.in_flight.load(Ordering::SeqCst) as f64 / limit.max_concurrent as f64 * 100.0)
+            .unwrap_or(0.0);
+
+        ConcurrencyMetrics {
+            current_concurrent: current_concurrent as u32,
+            max_concurrent: utilizations
+                .iter()
+                .map(|u| u.peak_in_flight)
+                .max()
+                .unwrap_or(0)
+                .max(current_concurrent) as u32,
+            average_concurrent,
+            thread_pool_utilization,
+            queue_wait_times: QueueMetrics {
+                current_depth: queue_depth as u32,
+                max_depth: utilizations.iter().map(|u| u.peak_queued).max().unwrap_or(0) as u32,
+                average_depth,
+                average_wait_time,
+                throughput,
+            },
+        }
+    }
+
+    /// Every configured concurrency cap.
+    fn all_limits(&self) -> impl Iterator<Item = &Arc<ConcurrencyLimit>> {
+        self.event_limits
+            .values()
+            .chain(self.executor_limits.values())
+            .chain(self.global_limit.iter())
+    }
+}
+
+/// Extract a readable message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
     }
 }
 
@@ -514,6 +1827,12 @@ impl Clone for ExecutionCoordinator {
         Self {
             active_executions: self.active_executions.clone(),
             stats: self.stats.clone(),
+            executor_limits: self.executor_limits.clone(),
+            event_limits: self.event_limits.clone(),
+            global_limit: self.global_limit.clone(),
+            required_failure_policy: self.required_failure_policy,
+            dead_letters: self.dead_letters.clone(),
+            load: self.load.clone(),
         }
     }
 }
@@ -525,102 +1844,221 @@ impl Default for ExecutionCoordinator {
 }
 
 // Re-export executors from the executors module
-pub use crate::hooks::executors::{ScriptExecutor, WebhookExecutor, McpToolExecutor};
+pub use crate::hooks::executors::{ExecutableExecutor, ScriptExecutor, WebhookExecutor, McpToolExecutor};
 
-// Placeholder implementation for ExecutableExecutor - will be implemented later
-pub struct ExecutableExecutor;
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use crate::hooks::types::LifecycleEvent;
 
+    use tokio::time::sleep;
 
+    // Mock executor for testing
+    struct MockExecutor {
+        should_fail: bool,
+        execution_time: Duration,
+        call_count: Arc<Mutex<u32>>,
+    }
 
-#[async_trait]
-impl HookExecutor for ExecutableExecutor {
-    async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
-        // TODO: Implement in Phase 2.3
-        Err(HookError::Execution("ExecutableExecutor not yet implemented".to_string()))
+    impl MockExecutor {
+        fn new(should_fail: bool, execution_time: Duration) -> Self {
+            Self {
+                should_fail,
+                execution_time,
+                call_count: Arc::new(Mutex::new(0)),
+            }
+        }
+
+        async fn get_call_count(&self) -> u32 {
+            *self.call_count.lock().await
+        }
+    }
+
+    #[async_trait]
+    impl HookExecutor for MockExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            {
+                let mut count = self.call_count.lock().await;
+                *count += 1;
+            }
+
+            sleep(self.execution_time).await;
+
+            if self.should_fail {
+                Err(HookError::Execution("Mock execution failed".to_string()))
+            } else {
+                Ok(HookResult::success(Some("Mock success".to_string()), self.execution_time))
+            }
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "mock"
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
+
+        fn estimated_duration(&self) -> Option<Duration> {
+            Some(self.execution_time)
+        }
+    }
+
+    // Executor that fails with a configuration error it reports as permanent.
+    #[derive(Default)]
+    struct PermanentFailureExecutor {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HookExecutor for PermanentFailureExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(HookError::Configuration("missing command".to_string()))
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "permanent"
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
+
+        fn is_retryable(&self, error: &HookError) -> bool {
+            !matches!(error, HookError::Configuration(_))
+        }
+    }
+
+    // Executor that always panics mid-execution.
+    struct PanickingExecutor;
+
+    #[async_trait]
+    impl HookExecutor for PanickingExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            panic!("executor exploded");
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
+    }
+
+    // Executor reporting a configurable type that tracks its own peak concurrency.
+    struct ConcurrencyProbeExecutor {
+        executor_type: &'static str,
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    impl ConcurrencyProbeExecutor {
+        fn new(executor_type: &'static str) -> Self {
+            Self {
+                executor_type,
+                running: Arc::new(AtomicUsize::new(0)),
+                peak: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HookExecutor for ConcurrencyProbeExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            sleep(Duration::from_millis(50)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(HookResult::success(None, Duration::from_millis(50)))
+        }
+
+        fn executor_type(&self) -> &'static str {
+            self.executor_type
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
     }
 
-    fn executor_type(&self) -> &'static str {
-        "executable"
-    }
+    struct SlowPrepareExecutor;
 
-    fn can_execute(&self, _context: &HookContext) -> bool {
-        // TODO: Implement in Phase 2.3
-        false
-    }
+    #[async_trait]
+    impl HookExecutor for SlowPrepareExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            sleep(Duration::from_millis(30)).await;
+            Ok(HookResult::success(None, Duration::from_millis(30)).with_metadata(
+                SERIALIZATION_TIME_METADATA_KEY.to_string(),
+                serde_json::json!(5_000),
+            ))
+        }
 
-    fn estimated_duration(&self) -> Option<Duration> {
-        Some(Duration::from_secs(30)) // Executables can vary widely
-    }
+        fn executor_type(&self) -> &'static str {
+            "slow_prepare"
+        }
 
-    fn default_config(&self) -> ExecutionConfig {
-        ExecutionConfig {
-            timeout: Duration::from_secs(300), // 5 minutes for custom executables
-            mode: HookExecutionMode::Async,
-            priority: HookPriority::NORMAL,
-            required: false,
-            max_retries: 1,
-            retry_delay: Duration::from_secs(1),
-            isolated: true,
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
+
+        async fn prepare(&self, _context: &HookContext) -> Result<(), HookError> {
+            sleep(Duration::from_millis(50)).await;
+            Ok(())
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::hooks::types::LifecycleEvent;
+    // Executor whose preparation always fails.
+    struct FailingPrepareExecutor;
 
-    use tokio::time::sleep;
+    #[async_trait]
+    impl HookExecutor for FailingPrepareExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            Ok(HookResult::success(None, Duration::ZERO))
+        }
 
-    // Mock executor for testing
-    struct MockExecutor {
-        should_fail: bool,
-        execution_time: Duration,
-        call_count: Arc<Mutex<u32>>,
-    }
+        fn executor_type(&self) -> &'static str {
+            "failing_prepare"
+        }
 
-    impl MockExecutor {
-        fn new(should_fail: bool, execution_time: Duration) -> Self {
-            Self {
-                should_fail,
-                execution_time,
-                call_count: Arc::new(Mutex::new(0)),
-            }
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
         }
 
-        async fn get_call_count(&self) -> u32 {
-            *self.call_count.lock().await
+        async fn prepare(&self, _context: &HookContext) -> Result<(), HookError> {
+            Err(HookError::Configuration("prepare exploded".to_string()))
         }
     }
 
+    // Executor that appends its name to a shared log when it runs.
+    struct OrderProbeExecutor {
+        name: &'static str,
+        should_fail: bool,
+        log: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
     #[async_trait]
-    impl HookExecutor for MockExecutor {
+    impl HookExecutor for OrderProbeExecutor {
         async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
-            {
-                let mut count = self.call_count.lock().await;
-                *count += 1;
-            }
-
-            sleep(self.execution_time).await;
-
+            sleep(Duration::from_millis(10)).await;
+            self.log.lock().unwrap().push(self.name);
             if self.should_fail {
-                Err(HookError::Execution("Mock execution failed".to_string()))
+                Err(HookError::Execution(format!("{} failed", self.name)))
             } else {
-                Ok(HookResult::success(Some("Mock success".to_string()), self.execution_time))
+                Ok(HookResult::success(None, Duration::from_millis(10)))
             }
         }
 
         fn executor_type(&self) -> &'static str {
-            "mock"
+            "order_probe"
         }
 
         fn can_execute(&self, _context: &HookContext) -> bool {
             true
         }
-
-        fn estimated_duration(&self) -> Option<Duration> {
-            Some(self.execution_time)
-        }
     }
 
     fn create_test_context() -> HookContext {
@@ -662,8 +2100,12 @@ mod tests {
         let exec_context = ExecutionContext::new(hook_context, config);
 
         assert!(!exec_context.is_cancelled().await);
-        exec_context.cancel().await;
+        exec_context.cancel(CancelReason::User).await;
         assert!(exec_context.is_cancelled().await);
+
+        // A later cancellation does not overwrite the original reason
+        exec_context.cancel(CancelReason::Shutdown).await;
+        assert_eq!(exec_context.cancel_reason().await, Some(CancelReason::User));
     }
 
     #[tokio::test]
@@ -686,9 +2128,11 @@ mod tests {
     async fn test_failed_execution_with_retries() {
         let executor = MockExecutor::new(true, Duration::from_millis(50));
         let hook_context = create_test_context();
-        let mut config = ExecutionConfig::default();
-        config.max_retries = 2;
-        config.retry_delay = Duration::from_millis(10);
+        let config = ExecutionConfig {
+            max_retries: 2,
+            retry_delay: Duration::from_millis(10),
+            ..ExecutionConfig::default()
+        };
         let exec_context = ExecutionContext::new(hook_context, config);
 
         let result = executor.execute_with_context(&exec_context).await;
@@ -703,8 +2147,10 @@ mod tests {
     async fn test_execution_timeout() {
         let executor = MockExecutor::new(false, Duration::from_millis(200));
         let hook_context = create_test_context();
-        let mut config = ExecutionConfig::default();
-        config.timeout = Duration::from_millis(50); // Shorter than execution time
+        let config = ExecutionConfig {
+            timeout: Duration::from_millis(50), // Shorter than execution time
+            ..ExecutionConfig::default()
+        };
         let exec_context = ExecutionContext::new(hook_context, config);
 
         let result = executor.execute_with_context(&exec_context).await;
@@ -714,6 +2160,73 @@ mod tests {
         assert!(result.error_details.unwrap().contains("timed out"));
     }
 
+    #[tokio::test]
+    async fn test_total_deadline_stops_retries_early() {
+        // Failing attempts with long retry delays stop at the deadline, mid-delay
+        let executor = MockExecutor::new(true, Duration::from_millis(50));
+        let config = ExecutionConfig {
+            max_retries: 10,
+            retry_delay: Duration::from_millis(100),
+            total_deadline: Some(Duration::from_millis(300)),
+            ..ExecutionConfig::default()
+        };
+        let context = ExecutionContext::new(create_test_context(), config);
+
+        let result = executor.execute_with_context(&context).await;
+        assert!(!result.result.success);
+        assert!(result.result.error.unwrap().contains("exceeded total deadline"));
+        assert!(result.error_details.unwrap().contains("last error"));
+        assert!(result.duration < Duration::from_millis(400), "{:?}", result.duration);
+        assert!(executor.get_call_count().await < 11);
+        assert_eq!(result.retry_attempts, executor.get_call_count().await - 1);
+
+        // An attempt stuck past the deadline is cut short even though its timeout is longer
+        let executor = MockExecutor::new(false, Duration::from_secs(5));
+        let config = ExecutionConfig {
+            timeout: Duration::from_secs(10),
+            max_retries: 3,
+            retry_delay: Duration::ZERO,
+            total_deadline: Some(Duration::from_millis(200)),
+            ..ExecutionConfig::default()
+        };
+        let context = ExecutionContext::new(create_test_context(), config);
+
+        let result = executor.execute_with_context(&context).await;
+        assert!(!result.result.success);
+        assert!(result.duration < Duration::from_millis(300), "{:?}", result.duration);
+        assert_eq!(executor.get_call_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_skips_retries() {
+        let executor = PermanentFailureExecutor::default();
+        let config = ExecutionConfig {
+            max_retries: 3,
+            retry_delay: Duration::from_secs(5),
+            ..ExecutionConfig::default()
+        };
+        let context = ExecutionContext::new(create_test_context(), config);
+
+        let result = executor.execute_with_context(&context).await;
+        assert!(!result.result.success);
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(result.retry_attempts, 0);
+        assert!(result.error_details.unwrap().contains("missing command"));
+
+        // Retryable errors still use the full retry budget
+        let executor = MockExecutor::new(true, Duration::ZERO);
+        let config = ExecutionConfig {
+            max_retries: 2,
+            retry_delay: Duration::ZERO,
+            ..ExecutionConfig::default()
+        };
+        let context = ExecutionContext::new(create_test_context(), config);
+
+        let result = executor.execute_with_context(&context).await;
+        assert_eq!(executor.get_call_count().await, 3);
+        assert_eq!(result.retry_attempts, 2);
+    }
+
     #[tokio::test]
     async fn test_execution_cancellation() {
         let executor = MockExecutor::new(false, Duration::from_millis(200));
@@ -722,11 +2235,12 @@ mod tests {
         let exec_context = ExecutionContext::new(hook_context, config);
 
         // Cancel before execution
-        exec_context.cancel().await;
+        exec_context.cancel(CancelReason::User).await;
         let result = executor.execute_with_context(&exec_context).await;
 
         assert!(!result.result.success);
         assert!(result.cancelled);
+        assert_eq!(result.cancel_reason, Some(CancelReason::User));
         assert_eq!(result.retry_attempts, 0);
         assert_eq!(executor.get_call_count().await, 0); // Should not execute
     }
@@ -741,7 +2255,9 @@ mod tests {
                 duration: Duration::from_millis(100),
                 retry_attempts: 0,
                 cancelled: false,
+                cancel_reason: None,
                 error_details: None,
+                timing_breakdown: None,
             },
             ExecutionResult {
                 execution_id: "2".to_string(),
@@ -750,7 +2266,9 @@ mod tests {
                 duration: Duration::from_millis(50),
                 retry_attempts: 1,
                 cancelled: false,
+                cancel_reason: None,
                 error_details: Some("error".to_string()),
+                timing_breakdown: None,
             },
         ];
 
@@ -839,12 +2357,574 @@ mod tests {
         // The important thing is that the coordinator handled the cancellation request properly
     }
 
+    // Context for a hook that keeps failing and retrying until it is cancelled.
+    fn create_retrying_context() -> ExecutionContext {
+        let config = ExecutionConfig {
+            max_retries: 50,
+            retry_delay: Duration::from_millis(20),
+            ..ExecutionConfig::default()
+        };
+        ExecutionContext::new(create_test_context(), config)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_reason_recorded_for_cancel_by_id_and_shutdown() {
+        for reason in [CancelReason::ById, CancelReason::Shutdown] {
+            let coordinator = ExecutionCoordinator::new();
+            let exec_context = create_retrying_context();
+            let execution_id = exec_context.execution_id.clone();
+            let executor = Arc::new(MockExecutor::new(true, Duration::from_millis(10)));
+            let executions = vec![(executor as Arc<dyn HookExecutor>, exec_context)];
+
+            let coordinator_clone = coordinator.clone();
+            let execution_task = tokio::spawn(async move { coordinator_clone.execute_hooks(executions).await });
+
+            sleep(Duration::from_millis(100)).await;
+            match reason {
+                CancelReason::ById => assert!(coordinator.cancel_execution(&execution_id).await),
+                _ => coordinator.shutdown().await,
+            }
+
+            let results = execution_task.await.unwrap();
+            assert_eq!(results.cancelled.len(), 1);
+            assert_eq!(results.cancelled[0].cancel_reason, Some(reason));
+            assert!(coordinator.get_active_executions().await.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_aborts_isolated_hook_stuck_in_execute() {
+        for reason in [CancelReason::ById, CancelReason::Shutdown] {
+            let coordinator = ExecutionCoordinator::new();
+            let exec_context = ExecutionContext::new(create_test_context(), ExecutionConfig::default());
+            let execution_id = exec_context.execution_id.clone();
+            let executor = Arc::new(MockExecutor::new(false, Duration::from_secs(30)));
+            let executions = vec![(executor as Arc<dyn HookExecutor>, exec_context)];
+
+            let coordinator_clone = coordinator.clone();
+            let execution_task = tokio::spawn(async move { coordinator_clone.execute_hooks(executions).await });
+
+            sleep(Duration::from_millis(50)).await;
+            let cancelled_at = Instant::now();
+            match reason {
+                CancelReason::ById => assert!(coordinator.cancel_execution(&execution_id).await),
+                _ => coordinator.cancel_all(reason).await,
+            }
+
+            let results = tokio::time::timeout(Duration::from_secs(1), execution_task)
+                .await
+                .expect("cancellation should stop the hook promptly")
+                .unwrap();
+            assert!(cancelled_at.elapsed() < Duration::from_millis(500));
+            assert_eq!(results.cancelled.len(), 1);
+            assert_eq!(results.cancelled[0].cancel_reason, Some(reason));
+            assert!(coordinator.get_active_executions().await.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_reason_recorded_for_budget_exhaustion() {
+        let coordinator = ExecutionCoordinator::new();
+        let executor = Arc::new(MockExecutor::new(false, Duration::from_millis(500)));
+        let exec_context = ExecutionContext::new(create_test_context(), ExecutionConfig::default());
+
+        let results = coordinator
+            .execute_hooks_within(vec![(executor as Arc<dyn HookExecutor>, exec_context)], Duration::from_millis(50))
+            .await;
+
+        assert_eq!(results.cancelled.len(), 1);
+        assert_eq!(results.cancelled[0].cancel_reason, Some(CancelReason::BudgetExhausted));
+        assert_eq!(results.cancelled[0].result.error.as_deref(), Some(EVENT_BUDGET_EXHAUSTED));
+    }
+
+    #[tokio::test]
+    async fn test_executor_panic_is_contained() {
+        let coordinator = ExecutionCoordinator::new();
+        let hook_context = create_test_context();
+
+        let direct = ExecutionConfig {
+            mode: HookExecutionMode::Blocking,
+            isolated: false,
+            ..ExecutionConfig::default()
+        };
+        let isolated = ExecutionConfig {
+            mode: HookExecutionMode::Blocking,
+            isolated: true,
+            ..ExecutionConfig::default()
+        };
+
+        let executions = vec![
+            (Arc::new(PanickingExecutor) as Arc<dyn HookExecutor>, ExecutionContext::new(hook_context.clone(), direct)),
+            (Arc::new(PanickingExecutor) as Arc<dyn HookExecutor>, ExecutionContext::new(hook_context.clone(), isolated)),
+        ];
+        let results = coordinator.execute_hooks(executions).await;
+
+        assert_eq!(results.failed.len(), 2);
+        for result in &results.results {
+            let details = result.error_details.as_deref().unwrap();
+            assert!(details.contains("Hook panicked"));
+            assert!(details.contains("executor exploded"));
+        }
+        assert!(coordinator.get_active_executions().await.is_empty());
+
+        // The coordinator keeps dispatching after a panic.
+        let executor = Arc::new(MockExecutor::new(false, Duration::from_millis(10)));
+        let executions = vec![(
+            executor as Arc<dyn HookExecutor>,
+            ExecutionContext::new(hook_context, ExecutionConfig::default()),
+        )];
+        let results = coordinator.execute_hooks(executions).await;
+        assert_eq!(results.successful.len(), 1);
+
+        let stats = coordinator.get_stats().await;
+        assert_eq!(stats.panicked_executions, 2);
+        assert_eq!(stats.failed_executions, 2);
+        assert_eq!(stats.successful_executions, 1);
+    }
+
+    fn dependent_execution(
+        name: &'static str,
+        depends_on: &[&str],
+        should_fail: bool,
+        mode: HookExecutionMode,
+        log: &Arc<std::sync::Mutex<Vec<&'static str>>>,
+    ) -> (Arc<dyn HookExecutor>, ExecutionContext) {
+        let executor = OrderProbeExecutor { name, should_fail, log: log.clone() };
+        let config = ExecutionConfig { mode, ..ExecutionConfig::default() };
+        let context = ExecutionContext::new(create_test_context(), config)
+            .with_hook_id(name)
+            .with_dependencies(depends_on.iter().map(|dep| dep.to_string()).collect());
+        (Arc::new(executor), context)
+    }
+
+    #[tokio::test]
+    async fn test_dependency_chain_runs_in_order() {
+        for mode in [HookExecutionMode::Blocking, HookExecutionMode::Async] {
+            let coordinator = ExecutionCoordinator::new();
+            let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+            // Listed in reverse so only the dependencies can produce the right order
+            let executions = vec![
+                dependent_execution("c", &["b"], false, mode, &log),
+                dependent_execution("b", &["a"], false, mode, &log),
+                dependent_execution("a", &[], false, mode, &log),
+            ];
+            let results = coordinator.execute_hooks(executions).await;
+
+            assert_eq!(results.successful.len(), 3);
+            assert_eq!(*log.lock().unwrap(), vec!["a", "b", "c"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_dependency_skips_dependents() {
+        let coordinator = ExecutionCoordinator::new();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let executions = vec![
+            dependent_execution("a", &[], true, HookExecutionMode::Blocking, &log),
+            dependent_execution("b", &["a"], false, HookExecutionMode::Async, &log),
+            dependent_execution("c", &["b"], false, HookExecutionMode::Async, &log),
+            dependent_execution("d", &[], false, HookExecutionMode::Async, &log),
+        ];
+        let results = coordinator.execute_hooks(executions).await;
+
+        assert_eq!(*log.lock().unwrap(), vec!["a", "d"]);
+        assert_eq!(results.failed.len(), 1);
+        assert_eq!(results.successful.len(), 1);
+        let skipped: Vec<_> = results.skipped.iter().map(|s| (s.hook_id.as_str(), s.reason)).collect();
+        assert_eq!(skipped, vec![("b", SkipReason::DependencyFailed), ("c", SkipReason::DependencyFailed)]);
+        assert_eq!(results.skipped[0].detail.as_deref(), Some("Dependency 'a' failed"));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_on_later_mode_is_skipped() {
+        let coordinator = ExecutionCoordinator::new();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let executions = vec![
+            dependent_execution("gate", &["probe"], false, HookExecutionMode::Blocking, &log),
+            dependent_execution("probe", &[], false, HookExecutionMode::Async, &log),
+            dependent_execution("after_gate", &["gate"], false, HookExecutionMode::Async, &log),
+            dependent_execution("after_missing", &["missing"], false, HookExecutionMode::Async, &log),
+        ];
+        let results = coordinator.execute_hooks(executions).await;
+
+        // The blocking hook would otherwise run before the async hook it waits for
+        assert_eq!(*log.lock().unwrap(), vec!["probe"]);
+        let skipped: Vec<_> = results.skipped.iter().map(|s| (s.hook_id.as_str(), s.reason)).collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("gate", SkipReason::DependencyModeMismatch),
+                ("after_missing", SkipReason::DependencyFailed),
+                ("after_gate", SkipReason::DependencyFailed),
+            ]
+        );
+        assert_eq!(results.skipped[1].detail.as_deref(), Some("Dependency 'missing' did not run"));
+    }
+
+    fn required_blocking(
+        name: &'static str,
+        should_fail: bool,
+        log: &Arc<std::sync::Mutex<Vec<&'static str>>>,
+    ) -> (Arc<dyn HookExecutor>, ExecutionContext) {
+        let (executor, mut context) = dependent_execution(name, &[], should_fail, HookExecutionMode::Blocking, log);
+        context.config.required = true;
+        (executor, context)
+    }
+
+    #[tokio::test]
+    async fn test_required_failure_skips_remaining_blocking_hooks() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let executions = || {
+            vec![
+                required_blocking("first", true, &log),
+                required_blocking("second", false, &log),
+                required_blocking("third", false, &log),
+                dependent_execution("background", &[], false, HookExecutionMode::Async, &log),
+            ]
+        };
+
+        let coordinator = ExecutionCoordinator::new().with_required_failure_policy(RequiredFailurePolicy::SkipBlocking);
+        let results = coordinator.execute_hooks(executions()).await;
+        assert_eq!(*log.lock().unwrap(), vec!["first", "background"]);
+        assert!(results.has_critical_failures());
+        let skipped: Vec<_> = results.skipped.iter().map(|s| (s.hook_id.as_str(), s.reason)).collect();
+        assert_eq!(
+            skipped,
+            vec![("second", SkipReason::RequiredHookFailed), ("third", SkipReason::RequiredHookFailed)]
+        );
+        assert_eq!(results.skipped[0].detail.as_deref(), Some("Required hook 'first' failed"));
+
+        log.lock().unwrap().clear();
+        let coordinator = ExecutionCoordinator::new().with_required_failure_policy(RequiredFailurePolicy::SkipAll);
+        let results = coordinator.execute_hooks(executions()).await;
+        assert_eq!(*log.lock().unwrap(), vec!["first"]);
+        assert_eq!(results.skipped_by_reason()[&SkipReason::RequiredHookFailed], 3);
+
+        // By default every hook still runs
+        log.lock().unwrap().clear();
+        let results = ExecutionCoordinator::new().execute_hooks(executions()).await;
+        assert_eq!(*log.lock().unwrap(), vec!["first", "second", "third", "background"]);
+        assert!(results.skipped.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dependency_cycle_runs_nothing() {
+        let coordinator = ExecutionCoordinator::new();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let executions = vec![
+            dependent_execution("a", &["b"], false, HookExecutionMode::Async, &log),
+            dependent_execution("b", &["a"], false, HookExecutionMode::Blocking, &log),
+            dependent_execution("c", &[], false, HookExecutionMode::Async, &log),
+        ];
+        let results = coordinator.execute_hooks(executions).await;
+
+        assert!(log.lock().unwrap().is_empty());
+        assert!(results.results.is_empty());
+        assert_eq!(results.skipped_by_reason()[&SkipReason::DependencyCycle], 3);
+        let detail = results.skipped[0].detail.as_deref().unwrap();
+        assert!(detail.contains("Circular dependency detected between hooks: a, b"), "{}", detail);
+    }
+
+    #[tokio::test]
+    async fn test_executor_concurrency_cap() {
+        let coordinator = ExecutionCoordinator::new().with_executor_concurrency_limit("database", 3);
+        let database = Arc::new(ConcurrencyProbeExecutor::new("database"));
+        let webhook = Arc::new(ConcurrencyProbeExecutor::new("webhook"));
+
+        let hook_context = create_test_context();
+        let config = ExecutionConfig {
+            mode: HookExecutionMode::Async,
+            isolated: false,
+            ..ExecutionConfig::default()
+        };
+        let mut executions = Vec::new();
+        for _ in 0..20 {
+            executions.push((
+                database.clone() as Arc<dyn HookExecutor>,
+                ExecutionContext::new(hook_context.clone(), config.clone()),
+            ));
+            executions.push((
+                webhook.clone() as Arc<dyn HookExecutor>,
+                ExecutionContext::new(hook_context.clone(), config.clone()),
+            ));
+        }
+
+        let results = coordinator.execute_hooks(executions).await;
+        assert_eq!(results.successful.len(), 40);
+
+        assert_eq!(database.peak.load(Ordering::SeqCst), 3);
+        assert!(webhook.peak.load(Ordering::SeqCst) > 3);
+
+        let stats = coordinator.get_stats().await;
+        let utilization = &stats.executor_utilization["database"];
+        assert_eq!(utilization.max_concurrent, 3);
+        assert_eq!(utilization.peak_in_flight, 3);
+        assert_eq!(utilization.in_flight, 0);
+        assert!(utilization.waited > 0);
+        assert!(!stats.executor_utilization.contains_key("webhook"));
+    }
+
+    #[tokio::test]
+    async fn test_global_and_event_concurrency_caps() {
+        let coordinator = ExecutionCoordinator::new()
+            .with_global_concurrency_limit(3)
+            .with_event_concurrency_limit(LifecycleEventType::TaskStart, 1);
+        let probe = Arc::new(ConcurrencyProbeExecutor::new("probe"));
+
+        let config = ExecutionConfig { mode: HookExecutionMode::Async, ..ExecutionConfig::default() };
+        let task_context = HookContext::new(
+            LifecycleEvent::synthetic(LifecycleEventType::TaskStart),
+            std::path::PathBuf::from("/tmp"),
+        );
+        let mut executions = Vec::new();
+        for i in 0..12 {
+            executions.push((
+                probe.clone() as Arc<dyn HookExecutor>,
+                ExecutionContext::new(create_test_context(), config.clone()).with_hook_id(format!("session_{}", i)),
+            ));
+        }
+        for i in 0..3 {
+            executions.push((
+                probe.clone() as Arc<dyn HookExecutor>,
+                ExecutionContext::new(task_context.clone(), config.clone()).with_hook_id(format!("task_{}", i)),
+            ));
+        }
+
+        let running = coordinator.clone();
+        let handle = tokio::spawn(async move { running.execute_hooks(executions).await });
+        sleep(Duration::from_millis(20)).await;
+        let metrics = coordinator.concurrency_metrics().await;
+        assert_eq!(metrics.current_concurrent, 3);
+        assert_eq!(metrics.queue_wait_times.current_depth, 12);
+        assert_eq!(coordinator.queue_depth(), 12);
+
+        let results = handle.await.unwrap();
+        assert_eq!(results.successful.len(), 15);
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 3);
+
+        let stats = coordinator.get_stats().await;
+        let global = stats.global_utilization.unwrap();
+        assert_eq!(global.peak_in_flight, 3);
+        assert_eq!(global.queued, 0);
+        assert_eq!(stats.event_utilization[&LifecycleEventType::TaskStart].peak_in_flight, 1);
+
+        let metrics = coordinator.concurrency_metrics().await;
+        assert_eq!(metrics.queue_wait_times.current_depth, 0);
+        assert!(metrics.queue_wait_times.max_depth >= 10);
+        assert!(metrics.queue_wait_times.average_wait_time > Duration::ZERO);
+        assert!(metrics.average_concurrent > 0.0);
+        assert!(metrics.average_concurrent <= 3.0);
+        assert!(metrics.queue_wait_times.average_depth > 0.0);
+        assert!(metrics.queue_wait_times.throughput > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_fire_and_forget_lands_in_dead_letters() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("dead_letters.jsonl");
+        let coordinator = ExecutionCoordinator::new().with_dead_letter_queue(2, Some(path.clone()));
+
+        let config = ExecutionConfig { mode: HookExecutionMode::FireAndForget, ..ExecutionConfig::default() };
+        let mut executions = Vec::new();
+        for (i, should_fail) in [true, false, true, true].into_iter().enumerate() {
+            executions.push((
+                Arc::new(MockExecutor::new(should_fail, Duration::from_millis(10))) as Arc<dyn HookExecutor>,
+                ExecutionContext::new(create_test_context(), config.clone()).with_hook_id(format!("hook_{}", i)),
+            ));
+        }
+        let results = coordinator.execute_hooks(executions).await;
+        assert!(results.results.is_empty());
+
+        // Fire-and-forget hooks finish in the background
+        for _ in 0..100 {
+            if std::fs::read_to_string(&path).map(|s| s.lines().count()).unwrap_or(0) == 3 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        sleep(Duration::from_millis(20)).await;
+        let on_disk: Vec<DeadLetter> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let mut failed: Vec<_> = on_disk.iter().map(|letter| letter.hook_id.as_str()).collect();
+        failed.sort();
+        assert_eq!(failed, vec!["hook_0", "hook_2", "hook_3"]);
+        assert!(on_disk[0].error.contains("Mock execution failed"));
+        assert_eq!(on_disk[0].event_type, LifecycleEventType::SessionStart);
+
+        // Only the newest entries stay in memory, and draining empties the ring
+        let drained = coordinator.drain_dead_letters();
+        assert_eq!(drained.len(), 2);
+        assert!(drained.iter().all(|letter| on_disk.contains(letter)));
+        assert!(coordinator.drain_dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_timing_breakdown_covers_execution() {
+        let coordinator = ExecutionCoordinator::new();
+        let context = ExecutionContext::new(create_test_context(), ExecutionConfig::default());
+
+        let started = Instant::now();
+        let results = coordinator
+            .execute_hooks(vec![(Arc::new(SlowPrepareExecutor) as Arc<dyn HookExecutor>, context)])
+            .await;
+        let elapsed = started.elapsed();
+
+        let timing = results.results[0].timing_breakdown.clone().unwrap();
+        assert!(timing.setup_time >= Duration::from_millis(50));
+        assert!(timing.execution_time >= Duration::from_millis(20));
+        assert_eq!(timing.serialization_time, Duration::from_millis(5));
+        assert!(timing.total() <= elapsed);
+        assert!(elapsed - timing.total() < Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn test_failed_preparation_releases_active_execution() {
+        let coordinator = ExecutionCoordinator::new();
+        let context = ExecutionContext::new(create_test_context(), ExecutionConfig::default());
+
+        let results = coordinator
+            .execute_hooks(vec![(Arc::new(FailingPrepareExecutor) as Arc<dyn HookExecutor>, context)])
+            .await;
+
+        assert_eq!(results.failed.len(), 1);
+        let details = results.results[0].error_details.as_deref().unwrap();
+        assert!(details.contains("prepare exploded"));
+        assert!(coordinator.get_active_executions().await.is_empty());
+    }
+
+    fn create_hook_config(event: LifecycleEventType) -> HookConfig {
+        HookConfig {
+            id: Some("resolver_hook".to_string()),
+            event,
+            hook_type: crate::hooks::types::HookType::Script {
+                command: vec!["true".to_string()],
+                cwd: None,
+                environment: HashMap::new(),
+                timeout: None,
+                resource_limits: None,
+            },
+            mode: HookExecutionMode::default(),
+            priority: HookPriority::default(),
+            condition: None,
+            blocking: false,
+            required: false,
+            tags: Vec::new(),
+            description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
+        }
+    }
+
+    #[test]
+    fn test_resolver_hook_timeout_precedence() {
+        let executor = MockExecutor::new(false, Duration::from_millis(1));
+        let resolver = ExecutionConfigResolver::new();
+        let mut hook = create_hook_config(LifecycleEventType::TaskComplete);
+
+        // No hook timeout: executor default.
+        let config = resolver.resolve_for_hook(&executor, &hook);
+        assert_eq!(config.timeout, executor.default_config().timeout);
+
+        // Timeout embedded in the hook type overrides the executor default.
+        hook.hook_type = crate::hooks::types::HookType::Script {
+            command: vec!["true".to_string()],
+            cwd: None,
+            environment: HashMap::new(),
+            timeout: Some(Duration::from_secs(5)),
+            resource_limits: None,
+        };
+        assert_eq!(resolver.resolve_for_hook(&executor, &hook).timeout, Duration::from_secs(5));
+
+        // `HookConfig::timeout` wins over both.
+        hook.timeout = Some(Duration::from_secs(1));
+        assert_eq!(resolver.resolve_for_hook(&executor, &hook).timeout, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_resolver_global_timeout_sits_between_executor_and_hook() {
+        let executor = MockExecutor::new(false, Duration::from_millis(1));
+        let resolver = ExecutionConfigResolver::new().with_global_timeout(Duration::from_secs(7));
+        let mut hook = create_hook_config(LifecycleEventType::TaskComplete);
+
+        assert_eq!(resolver.resolve_for_hook(&executor, &hook).timeout, Duration::from_secs(7));
+
+        hook.timeout = Some(Duration::from_secs(1));
+        assert_eq!(resolver.resolve_for_hook(&executor, &hook).timeout, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_resolver_applies_event_default_when_hook_does_not_override() {
+        let executor = MockExecutor::new(false, Duration::from_millis(1));
+        let resolver = ExecutionConfigResolver::new();
+
+        let hook = create_hook_config(LifecycleEventType::AgentMessage);
+        let config = resolver.resolve_for_hook(&executor, &hook);
+        assert_eq!(config.mode, HookExecutionMode::FireAndForget);
+
+        let hook = create_hook_config(LifecycleEventType::ExecBefore);
+        let config = resolver.resolve_for_hook(&executor, &hook);
+        assert_eq!(config.mode, HookExecutionMode::Blocking);
+
+        // Events without defaults keep the executor's configuration.
+        let hook = create_hook_config(LifecycleEventType::TaskComplete);
+        let config = resolver.resolve_for_hook(&executor, &hook);
+        assert_eq!(config.mode, executor.default_config().mode);
+    }
+
+    #[test]
+    fn test_resolver_hook_settings_win_over_event_defaults() {
+        let executor = MockExecutor::new(false, Duration::from_millis(1));
+        let resolver = ExecutionConfigResolver::new().with_event_default(
+            LifecycleEventType::AgentMessage,
+            ExecutionOverrides {
+                mode: Some(HookExecutionMode::FireAndForget),
+                timeout: Some(Duration::from_secs(5)),
+                max_retries: Some(2),
+                ..Default::default()
+            },
+        );
+
+        let mut hook = create_hook_config(LifecycleEventType::AgentMessage);
+        hook.blocking = true;
+        hook.timeout = Some(Duration::from_secs(1));
+
+        let config = resolver.resolve_for_hook(&executor, &hook);
+        assert_eq!(config.mode, HookExecutionMode::Blocking);
+        assert_eq!(config.timeout, Duration::from_secs(1));
+        // Fields the hook leaves unset still come from the event defaults.
+        assert_eq!(config.max_retries, 2);
+        assert_eq!(config.retry_delay, executor.default_config().retry_delay);
+    }
+
+    #[test]
+    fn test_event_filter_allows() {
+        let context = create_test_context();
+        assert!(event_filter_allows(None, &context));
+
+        let session_only: HashSet<_> = [LifecycleEventType::SessionStart].into_iter().collect();
+        assert!(event_filter_allows(Some(&session_only), &context));
+
+        let task_only: HashSet<_> = [LifecycleEventType::TaskComplete].into_iter().collect();
+        assert!(!event_filter_allows(Some(&task_only), &context));
+    }
+
     #[test]
     fn test_executor_default_configs() {
         let script = ScriptExecutor::new();
         let webhook = WebhookExecutor::new();
         let mcp = McpToolExecutor::new();
-        let executable = ExecutableExecutor;
+        let executable = ExecutableExecutor::new();
 
         assert_eq!(script.executor_type(), "script");
         assert_eq!(webhook.executor_type(), "webhook");
@@ -863,4 +2943,19 @@ mod tests {
         assert_eq!(mcp.default_config().timeout, Duration::from_secs(120));
         assert_eq!(executable.default_config().timeout, Duration::from_secs(300));
     }
+
+    #[tokio::test]
+    async fn test_read_limited_truncates_at_the_limit() {
+        let output = CapturedOutput::read_limited(&b"0123456789abcdef"[..], 10).await.unwrap();
+        assert_eq!(output.text, format!("0123456789\n{}", OUTPUT_TRUNCATED_MARKER));
+        assert!(!output.lossy);
+
+        let output = CapturedOutput::read_limited(&b"0123456789"[..], 10).await.unwrap();
+        assert_eq!(output.text, "0123456789");
+
+        // A character split by the limit is dropped whole
+        let output = CapturedOutput::read_limited("ab\u{e9}cd".as_bytes(), 3).await.unwrap();
+        assert_eq!(output.text, format!("ab\n{}", OUTPUT_TRUNCATED_MARKER));
+        assert!(!output.lossy);
+    }
 }
\ No newline at end of file
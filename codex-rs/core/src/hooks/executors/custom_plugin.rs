@@ -1,19 +1,26 @@
 //! Custom plugin hook executor for extensible functionality.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
 use async_trait::async_trait;
 use serde_json::Value;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
+use tokio::sync::OnceCell;
 use tokio::time::timeout;
 
 use crate::hooks::context::HookContext;
-use crate::hooks::executor::{ExecutionConfig, HookExecutor, HookExecutorResult};
-use crate::hooks::types::{HookError, HookResult, HookType};
+use crate::hooks::executor::{
+    event_filter_allows, with_output_streams, CapturedOutput, ExecutionConfig, HookExecutor, HookExecutorResult,
+};
+use crate::hooks::executors::executable::EXIT_CODE_METADATA_KEY;
+use crate::hooks::executors::resource_limits;
+use crate::hooks::secrets::EnvCapturePolicy;
+use crate::hooks::types::{HookError, HookResult, HookType, LifecycleEventType, PluginProtocol, ResourceLimits};
 
 /// Custom plugin hook executor for running external plugins.
 #[derive(Debug)]
@@ -22,6 +29,69 @@ pub struct CustomPluginExecutor {
     default_timeout: Duration,
     /// Default plugin directory.
     default_plugin_dir: PathBuf,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
+    /// Which environment variables are passed to plugins, and which are redacted.
+    env_policy: EnvCapturePolicy,
+    /// Interpreter command for each plugin file extension (lowercase, without the dot).
+    interpreters: HashMap<String, Vec<String>>,
+    /// Maximum bytes captured from each of stdout and stderr.
+    max_output_size: usize,
+    /// `--info` answers keyed by canonical plugin path.
+    plugin_infos: Mutex<HashMap<PathBuf, CachedPluginInfo>>,
+}
+
+/// Cached `--info` answer for one version of a plugin file.
+#[derive(Debug)]
+struct CachedPluginInfo {
+    /// Modification time of the plugin file the answer belongs to.
+    modified: Option<SystemTime>,
+    /// Shared so concurrent first lookups wait on a single `--info` run.
+    info: Arc<OnceCell<PluginInfo>>,
+}
+
+/// Default interpreter commands by plugin file extension.
+fn default_interpreters() -> HashMap<String, Vec<String>> {
+    let interpreters: &[(&str, &[&str])] = if cfg!(windows) {
+        &[
+            ("py", &["python"]),
+            ("js", &["node"]),
+            ("rb", &["ruby"]),
+            ("ps1", &["powershell", "-NoProfile", "-ExecutionPolicy", "Bypass", "-File"]),
+            ("cmd", &["cmd", "/c"]),
+            ("bat", &["cmd", "/c"]),
+        ]
+    } else {
+        &[
+            ("py", &["python3"]),
+            ("js", &["node"]),
+            ("rb", &["ruby"]),
+            ("sh", &["bash"]),
+        ]
+    };
+    interpreters
+        .iter()
+        .map(|(extension, command)| {
+            (extension.to_string(), command.iter().map(|arg| arg.to_string()).collect())
+        })
+        .collect()
+}
+
+/// Longest shebang line read from an extensionless plugin.
+const MAX_SHEBANG_LEN: usize = 256;
+
+/// Read the interpreter command from a file's `#!` line, split on whitespace.
+fn read_shebang(path: &Path) -> Option<Vec<String>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut buffer = [0u8; MAX_SHEBANG_LEN];
+    let read = std::io::Read::read(&mut file, &mut buffer).ok()?;
+    let line = buffer[..read].strip_prefix(b"#!")?;
+    let line = line.split(|byte| *byte == b'\n').next()?;
+    let command: Vec<String> = String::from_utf8_lossy(line)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    (!command.is_empty()).then_some(command)
 }
 
 impl CustomPluginExecutor {
@@ -30,6 +100,11 @@ impl CustomPluginExecutor {
         Self {
             default_timeout: Duration::from_secs(30),
             default_plugin_dir: PathBuf::from("~/.codex/plugins"),
+            event_filter: None,
+            env_policy: EnvCapturePolicy::default(),
+            interpreters: default_interpreters(),
+            max_output_size: 1024 * 1024,
+            plugin_infos: Mutex::new(HashMap::new()),
         }
     }
 
@@ -38,7 +113,43 @@ impl CustomPluginExecutor {
         Self {
             default_timeout: timeout,
             default_plugin_dir: plugin_dir,
+            event_filter: None,
+            env_policy: EnvCapturePolicy::default(),
+            interpreters: default_interpreters(),
+            max_output_size: 1024 * 1024,
+            plugin_infos: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the maximum bytes captured from each of stdout and stderr.
+    pub fn with_max_output_size(mut self, size: usize) -> Self {
+        self.max_output_size = size;
+        self
+    }
+
+    /// Run plugins with `extension` through `command`, such as `["python3", "-u"]`.
+    ///
+    /// An empty command runs those plugins directly.
+    pub fn with_interpreter(mut self, extension: &str, command: Vec<String>) -> Self {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        if command.is_empty() {
+            self.interpreters.remove(&extension);
+        } else {
+            self.interpreters.insert(extension, command);
         }
+        self
+    }
+
+    /// Control which environment variables plugins receive in their input.
+    pub fn with_env_policy(mut self, env_policy: EnvCapturePolicy) -> Self {
+        self.env_policy = env_policy;
+        self
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
     }
 
     /// Execute a custom plugin.
@@ -47,20 +158,37 @@ impl CustomPluginExecutor {
         plugin_name: &str,
         plugin_config: &HashMap<String, Value>,
         plugin_path: Option<&Path>,
+        plugin_protocol: PluginProtocol,
         context: &HookContext,
         operation_timeout: Duration,
-    ) -> Result<String, HookError> {
+    ) -> Result<PluginRun, HookError> {
         // Determine plugin path
         let plugin_executable = self.resolve_plugin_path(plugin_name, plugin_path)?;
 
         // Validate plugin exists and is executable
         self.validate_plugin(&plugin_executable)?;
 
-        // Prepare plugin input
-        let plugin_input = self.prepare_plugin_input(plugin_config, context)?;
+        // Plugins declaring structured output are held to the output contract
+        let structured_output = self.cached_plugin_info(&plugin_executable).await.structured_output;
+
+        // Prepare plugin input in the plugin's wire format
+        let plugin_input = plugin_protocol.encode(&self.build_plugin_input(plugin_config, context))?;
+
+        let limits = match &context.hook_type {
+            HookType::CustomPlugin { resource_limits: Some(limits), .. } => limits.clone(),
+            _ => ResourceLimits::default(),
+        };
 
         // Execute the plugin
-        self.run_plugin(&plugin_executable, &plugin_input, operation_timeout).await
+        self.run_plugin(
+            &plugin_executable,
+            &plugin_input,
+            plugin_protocol,
+            structured_output,
+            &limits,
+            operation_timeout,
+        )
+        .await
     }
 
     /// Resolve the full path to the plugin executable.
@@ -68,6 +196,9 @@ impl CustomPluginExecutor {
         if let Some(path) = plugin_path {
             // Use provided path
             Ok(path.to_path_buf())
+        } else if let Some(plugin) = self.manifest_plugin(plugin_name)? {
+            // Plugins declared in the manifest resolve to their entry path
+            Ok(plugin.entry)
         } else {
             // Look for plugin in default directory
             let expanded_dir = self.expand_path(&self.default_plugin_dir)?;
@@ -142,13 +273,17 @@ impl CustomPluginExecutor {
         Ok(())
     }
 
-    /// Prepare input data for the plugin.
-    fn prepare_plugin_input(
-        &self,
-        plugin_config: &HashMap<String, Value>,
-        context: &HookContext,
-    ) -> Result<String, HookError> {
-        let input_data = serde_json::json!({
+    /// Build the input document sent to the plugin.
+    fn build_plugin_input(&self, plugin_config: &HashMap<String, Value>, context: &HookContext) -> Value {
+        self.build_plugin_input_from_env(plugin_config, context, std::env::vars())
+    }
+
+    /// Build the input document, capturing `environment` through the executor's policy.
+    fn build_plugin_input_from_env<I>(&self, plugin_config: &HashMap<String, Value>, context: &HookContext, environment: I) -> Value
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        serde_json::json!({
             "config": plugin_config,
             "context": {
                 "event": {
@@ -157,26 +292,24 @@ impl CustomPluginExecutor {
                     "timestamp": chrono::Utc::now().to_rfc3339(),
                 },
                 "hook": {
-                    "id": context.config.id,
-                    "description": context.config.description,
-                    "tags": context.config.tags,
+                    "id": context.hook_id(),
                 },
                 "working_directory": context.working_directory,
             },
-            "environment": std::env::vars().collect::<HashMap<String, String>>(),
-        });
-
-        serde_json::to_string_pretty(&input_data)
-            .map_err(|e| HookError::Execution(format!("Failed to serialize plugin input: {}", e)))
+            "environment": self.env_policy.capture(environment),
+        })
     }
 
     /// Run the plugin executable.
     async fn run_plugin(
         &self,
         plugin_path: &Path,
-        input_data: &str,
+        input_data: &[u8],
+        protocol: PluginProtocol,
+        structured_output: bool,
+        limits: &ResourceLimits,
         operation_timeout: Duration,
-    ) -> Result<String, HookError> {
+    ) -> Result<PluginRun, HookError> {
         tracing::info!("Executing plugin: {}", plugin_path.display());
         tracing::debug!("Plugin input size: {} bytes", input_data.len());
 
@@ -185,10 +318,12 @@ impl CustomPluginExecutor {
 
         // Configure the command
         command
+            .env("CODEX_PLUGIN_PROTOCOL", protocol.as_str())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .kill_on_drop(true);
+        resource_limits::apply(limits, &mut command);
 
         // Spawn the process
         let mut child = command
@@ -198,7 +333,7 @@ impl CustomPluginExecutor {
         // Send input to the plugin
         if let Some(mut stdin) = child.stdin.take() {
             stdin
-                .write_all(input_data.as_bytes())
+                .write_all(input_data)
                 .await
                 .map_err(|e| HookError::Execution(format!("Failed to write to plugin stdin: {}", e)))?;
             stdin
@@ -207,18 +342,28 @@ impl CustomPluginExecutor {
                 .map_err(|e| HookError::Execution(format!("Failed to close plugin stdin: {}", e)))?;
         }
 
-        // Wait for the plugin to complete with timeout
-        let output_result = timeout(operation_timeout, async {
-            let output = child
-                .wait_with_output()
-                .await
-                .map_err(|e| HookError::Execution(format!("Failed to wait for plugin: {}", e)))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| HookError::Execution("Failed to capture plugin stdout".to_string()))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| HookError::Execution("Failed to capture plugin stderr".to_string()))?;
 
-            Ok::<tokio::process::Output, HookError>(output)
+        // Wait for the plugin to complete with timeout, capping what is kept of each stream
+        let output_result = timeout(operation_timeout, async {
+            let (stdout, stderr, status) = tokio::join!(
+                CapturedOutput::read_limited(stdout, self.max_output_size),
+                CapturedOutput::read_limited(stderr, self.max_output_size),
+                child.wait(),
+            );
+            let status = status.map_err(|e| HookError::Execution(format!("Failed to wait for plugin: {}", e)))?;
+            Ok::<_, HookError>((status, stdout?, stderr?))
         })
         .await;
 
-        let output = match output_result {
+        let (status, stdout, stderr) = match output_result {
             Ok(Ok(output)) => output,
             Ok(Err(e)) => return Err(e),
             Err(_) => {
@@ -231,64 +376,157 @@ impl CustomPluginExecutor {
             }
         };
 
-        // Process the output
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        Ok(PluginRun {
+            output: Self::interpret_output(
+                &status,
+                &stdout,
+                &stderr,
+                protocol,
+                structured_output,
+                limits,
+                self.max_output_size,
+            ),
+            exit_code: status.code().unwrap_or(-1),
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Turn a finished plugin's exit status and output into the hook output or failure.
+    fn interpret_output(
+        status: &std::process::ExitStatus,
+        stdout: &CapturedOutput,
+        stderr: &CapturedOutput,
+        protocol: PluginProtocol,
+        structured_output: bool,
+        limits: &ResourceLimits,
+        max_output_size: usize,
+    ) -> Result<String, HookError> {
+        if let Some(limit_exceeded) = resource_limits::violation(limits, status, &stderr.text) {
+            return Err(HookError::Execution(limit_exceeded));
+        }
 
-        if !output.status.success() {
+        if !status.success() {
             let error_msg = if stderr.is_empty() {
-                format!("Plugin failed with exit code: {}", output.status.code().unwrap_or(-1))
+                format!("Plugin failed with exit code: {}", status.code().unwrap_or(-1))
             } else {
-                format!("Plugin failed: {}", stderr.trim())
+                format!("Plugin failed: {}", stderr.text.trim())
             };
             return Err(HookError::Execution(error_msg));
         }
 
+        // A cut-off response would be parsed as something the plugin never sent
+        if stdout.truncated {
+            return Err(HookError::Execution(format!(
+                "Plugin output exceeded max_output_size of {} bytes",
+                max_output_size
+            )));
+        }
+
+        if structured_output {
+            let response: Value = match protocol {
+                PluginProtocol::JsonStdio => serde_json::from_str(&stdout.text)
+                    .map_err(|e| contract_violation(format!("output is not JSON: {}", e)))?,
+                _ => protocol.decode(&stdout.bytes)?,
+            };
+            return Self::contract_output(&response);
+        }
+
+        // Binary protocols must produce a well-formed frame
+        if protocol != PluginProtocol::JsonStdio {
+            let frame: Value = protocol.decode(&stdout.bytes)?;
+            return Ok(Self::structured_output(&frame).unwrap_or_else(|| frame.to_string()));
+        }
+
         // Try to parse the output as JSON for structured results
-        if let Ok(json_output) = serde_json::from_str::<Value>(&stdout) {
-            if let Some(result) = json_output.get("result") {
-                return Ok(result.to_string());
-            } else if let Some(message) = json_output.get("message") {
-                return Ok(message.to_string());
+        if let Ok(json_output) = serde_json::from_str::<Value>(&stdout.text) {
+            if let Some(output) = Self::structured_output(&json_output) {
+                return Ok(output);
             }
         }
 
         // Return raw stdout if not JSON or no structured fields
-        Ok(stdout.trim().to_string())
+        Ok(stdout.text.trim().to_string())
+    }
+
+    /// Check a response against the structured output contract and return its result.
+    ///
+    /// The response must be an object whose `status` is `"success"` or `"error"`;
+    /// `error` is a string, required when the status is `"error"`, which fails the hook.
+    fn contract_output(response: &Value) -> Result<String, HookError> {
+        let Some(response) = response.as_object() else {
+            return Err(contract_violation("expected a JSON object".to_string()));
+        };
+
+        let error = match response.get("error") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(error)) => Some(error),
+            Some(other) => return Err(contract_violation(format!("'error' must be a string, got {}", other))),
+        };
+
+        match response.get("status").and_then(Value::as_str) {
+            Some("success") => Ok(response.get("result").map(|result| result.to_string()).unwrap_or_default()),
+            Some("error") => match error {
+                Some(error) => Err(HookError::Execution(format!("Plugin reported an error: {}", error))),
+                None => Err(contract_violation("'error' is required when 'status' is \"error\"".to_string())),
+            },
+            Some(status) => Err(contract_violation(format!("unknown status '{}'", status))),
+            None => Err(contract_violation("missing string field 'status'".to_string())),
+        }
+    }
+
+    /// Pick the `result` or `message` field out of a structured plugin response.
+    fn structured_output(response: &Value) -> Option<String> {
+        response
+            .get("result")
+            .or_else(|| response.get("message"))
+            .map(|value| value.to_string())
+    }
+
+    /// Plugin manifest in the default plugin directory, if one exists.
+    fn manifest(&self) -> Result<Option<PluginManifest>, HookError> {
+        PluginManifest::load(&self.expand_path(&self.default_plugin_dir)?)
+    }
+
+    /// Manifest entry declaring `plugin_name`, if any.
+    fn manifest_plugin(&self, plugin_name: &str) -> Result<Option<ManifestPlugin>, HookError> {
+        Ok(self.manifest()?.and_then(|manifest| manifest.find(plugin_name).cloned()))
+    }
+
+    /// Interpreter command for a plugin, or `None` to execute it directly.
+    ///
+    /// A manifest entry's interpreter wins. Otherwise the extension picks from the
+    /// interpreter map, case-insensitively. On Unix, extensionless plugins use the
+    /// interpreter named by their shebang line.
+    fn resolve_interpreter(&self, plugin_path: &Path) -> Option<Vec<String>> {
+        let declared = self.manifest().ok().flatten().and_then(|manifest| {
+            manifest
+                .plugins
+                .into_iter()
+                .find(|plugin| plugin.entry == plugin_path)
+                .and_then(|plugin| plugin.interpreter)
+        });
+        if let Some(interpreter) = declared {
+            return (!interpreter.is_empty()).then_some(interpreter);
+        }
+
+        match plugin_path.extension().and_then(|ext| ext.to_str()) {
+            Some(extension) => self.interpreters.get(&extension.to_ascii_lowercase()).cloned(),
+            None if cfg!(unix) => read_shebang(plugin_path),
+            None => None,
+        }
     }
 
     /// Create the appropriate command for executing the plugin.
     fn create_plugin_command(&self, plugin_path: &Path) -> Result<Command, HookError> {
-        let extension = plugin_path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("");
-
-        let mut command = match extension {
-            "py" => {
-                let mut cmd = Command::new("python3");
+        let mut command = match self.resolve_interpreter(plugin_path) {
+            Some(interpreter) => {
+                let mut cmd = Command::new(&interpreter[0]);
+                cmd.args(&interpreter[1..]);
                 cmd.arg(plugin_path);
                 cmd
             }
-            "js" => {
-                let mut cmd = Command::new("node");
-                cmd.arg(plugin_path);
-                cmd
-            }
-            "rb" => {
-                let mut cmd = Command::new("ruby");
-                cmd.arg(plugin_path);
-                cmd
-            }
-            "sh" => {
-                let mut cmd = Command::new("bash");
-                cmd.arg(plugin_path);
-                cmd
-            }
-            _ => {
-                // Try to execute directly
-                Command::new(plugin_path)
-            }
+            None => Command::new(plugin_path),
         };
 
         // Set environment variables
@@ -301,9 +539,9 @@ impl CustomPluginExecutor {
     /// Expand path with home directory support.
     fn expand_path(&self, path: &Path) -> Result<PathBuf, HookError> {
         if let Some(path_str) = path.to_str() {
-            if path_str.starts_with("~/") {
+            if let Some(rest) = path_str.strip_prefix("~/") {
                 if let Some(home) = dirs::home_dir() {
-                    return Ok(home.join(&path_str[2..]));
+                    return Ok(home.join(rest));
                 }
             }
         }
@@ -344,6 +582,44 @@ impl CustomPluginExecutor {
         Ok(())
     }
 
+    /// Plugin information, running the plugin with `--info` only when its file is new or changed.
+    pub async fn cached_plugin_info(&self, plugin_path: &Path) -> PluginInfo {
+        let key = plugin_path.canonicalize().unwrap_or_else(|_| plugin_path.to_path_buf());
+        let modified = std::fs::metadata(&key).and_then(|metadata| metadata.modified()).ok();
+
+        let cell = match self.plugin_infos.lock() {
+            Ok(mut infos) => {
+                let entry = infos.entry(key).or_insert_with(|| CachedPluginInfo {
+                    modified,
+                    info: Arc::new(OnceCell::new()),
+                });
+                if entry.modified != modified {
+                    *entry = CachedPluginInfo {
+                        modified,
+                        info: Arc::new(OnceCell::new()),
+                    };
+                }
+                entry.info.clone()
+            }
+            Err(_) => Arc::new(OnceCell::new()),
+        };
+
+        cell.get_or_init(|| async {
+            self.get_plugin_info(plugin_path)
+                .await
+                .unwrap_or_else(|_| PluginInfo::unknown(plugin_path))
+        })
+        .await
+        .clone()
+    }
+
+    /// Forget all cached plugin information.
+    pub fn clear_plugin_info_cache(&self) {
+        if let Ok(mut infos) = self.plugin_infos.lock() {
+            infos.clear();
+        }
+    }
+
     /// Get plugin information.
     async fn get_plugin_info(&self, plugin_path: &Path) -> Result<PluginInfo, HookError> {
         // Try to get plugin info by running it with --info flag
@@ -351,44 +627,103 @@ impl CustomPluginExecutor {
         command.arg("--info");
         command.stdout(Stdio::piped());
         command.stderr(Stdio::piped());
+        // A plugin that hangs on `--info` is killed when the timeout drops it
+        command.kill_on_drop(true);
 
         let output = timeout(Duration::from_secs(5), command.output()).await;
 
         match output {
             Ok(Ok(output)) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Ok(info) = serde_json::from_str::<PluginInfo>(&stdout) {
-                    Ok(info)
-                } else {
-                    // Fallback to basic info
-                    Ok(PluginInfo {
-                        name: plugin_path
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("unknown")
-                            .to_string(),
-                        version: "unknown".to_string(),
-                        description: "Custom plugin".to_string(),
-                        author: "unknown".to_string(),
-                        supported_events: vec!["*".to_string()],
-                    })
-                }
+                // Fall back to basic info when the plugin did not describe itself
+                Ok(serde_json::from_str::<PluginInfo>(&stdout).unwrap_or_else(|_| PluginInfo::unknown(plugin_path)))
             }
-            _ => {
-                // Fallback to basic info from filename
-                Ok(PluginInfo {
-                    name: plugin_path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string(),
-                    version: "unknown".to_string(),
-                    description: "Custom plugin".to_string(),
-                    author: "unknown".to_string(),
-                    supported_events: vec!["*".to_string()],
-                })
+            // Fallback to basic info from filename
+            _ => Ok(PluginInfo::unknown(plugin_path)),
+        }
+    }
+}
+
+/// A plugin process that ran to completion.
+#[derive(Debug)]
+struct PluginRun {
+    /// Hook output, or the failure the plugin's exit status or output amounts to.
+    output: Result<String, HookError>,
+    stdout: CapturedOutput,
+    stderr: CapturedOutput,
+    exit_code: i32,
+}
+
+/// Manifest file names looked up in the plugin directory, in order.
+const MANIFEST_FILES: [&str; 2] = ["plugins.toml", "plugin.json"];
+
+/// Plugins declared by a manifest in the plugin directory.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub plugins: Vec<ManifestPlugin>,
+}
+
+/// A plugin declared in a manifest.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ManifestPlugin {
+    pub name: String,
+    /// Executable path, relative to the plugin directory unless absolute.
+    pub entry: PathBuf,
+    /// Interpreter command overriding the extension map; empty runs the entry directly.
+    #[serde(default)]
+    pub interpreter: Option<Vec<String>>,
+    /// Event types the plugin handles, or `*` for all.
+    #[serde(default = "default_supported_events")]
+    pub supported_events: Vec<String>,
+}
+
+fn default_supported_events() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+impl PluginManifest {
+    /// Load the first manifest found in `plugin_dir`, with entry paths made absolute.
+    pub fn load(plugin_dir: &Path) -> Result<Option<Self>, HookError> {
+        for file_name in MANIFEST_FILES {
+            let path = plugin_dir.join(file_name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                HookError::Configuration(format!("Failed to read plugin manifest {}: {}", path.display(), e))
+            })?;
+            let parsed = if file_name.ends_with(".toml") {
+                toml::from_str::<Self>(&content).map_err(|e| e.to_string())
+            } else {
+                serde_json::from_str::<Self>(&content).map_err(|e| e.to_string())
+            };
+            let mut manifest = parsed.map_err(|e| {
+                HookError::Configuration(format!("Failed to parse plugin manifest {}: {}", path.display(), e))
+            })?;
+
+            for plugin in &mut manifest.plugins {
+                if plugin.entry.is_relative() {
+                    plugin.entry = plugin_dir.join(&plugin.entry);
+                }
             }
+            return Ok(Some(manifest));
         }
+        Ok(None)
+    }
+
+    /// The plugin declared under `name`.
+    pub fn find(&self, name: &str) -> Option<&ManifestPlugin> {
+        self.plugins.iter().find(|plugin| plugin.name == name)
+    }
+}
+
+impl ManifestPlugin {
+    /// Whether the plugin declares support for `event_type`.
+    pub fn supports(&self, event_type: LifecycleEventType) -> bool {
+        let event_type = event_type.to_string();
+        self.supported_events.iter().any(|event| event == "*" || *event == event_type)
     }
 }
 
@@ -400,6 +735,32 @@ pub struct PluginInfo {
     pub description: String,
     pub author: String,
     pub supported_events: Vec<String>,
+    /// Whether the plugin's output follows the `{status, result, error}` contract.
+    #[serde(default)]
+    pub structured_output: bool,
+}
+
+impl PluginInfo {
+    /// Basic information for a plugin that did not describe itself.
+    fn unknown(plugin_path: &Path) -> Self {
+        Self {
+            name: plugin_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            version: "unknown".to_string(),
+            description: "Custom plugin".to_string(),
+            author: "unknown".to_string(),
+            supported_events: vec!["*".to_string()],
+            structured_output: false,
+        }
+    }
+}
+
+/// Error for plugin output that breaks the structured output contract.
+fn contract_violation(reason: String) -> HookError {
+    HookError::Execution(format!("Plugin output violates the structured output contract: {}", reason))
 }
 
 impl Default for CustomPluginExecutor {
@@ -415,23 +776,39 @@ impl HookExecutor for CustomPluginExecutor {
     }
 
     fn can_execute(&self, context: &HookContext) -> bool {
-        matches!(context.hook_type, HookType::CustomPlugin { .. })
+        let HookType::CustomPlugin { plugin_name, plugin_path, .. } = &context.hook_type else {
+            return false;
+        };
+        if !event_filter_allows(self.event_filter.as_ref(), context) {
+            return false;
+        }
+
+        // Manifest plugins only run for the events they declare
+        plugin_path.is_some()
+            || self
+                .manifest_plugin(plugin_name)
+                .ok()
+                .flatten()
+                .is_none_or(|plugin| plugin.supports(context.event.event_type()))
     }
 
     async fn execute(&self, context: &HookContext) -> HookExecutorResult {
         let start_time = Instant::now();
 
-        let (plugin_name, plugin_config, hook_timeout, plugin_path) = match &context.hook_type {
+        let (plugin_name, plugin_config, hook_timeout, plugin_path, plugin_protocol) = match &context.hook_type {
             HookType::CustomPlugin {
                 plugin_name,
                 plugin_config,
                 timeout,
                 plugin_path,
+                plugin_protocol,
+                ..
             } => (
                 plugin_name,
                 plugin_config,
                 timeout.unwrap_or(self.default_timeout),
                 plugin_path.as_deref(),
+                *plugin_protocol,
             ),
             _ => {
                 return Ok(HookResult::failure(
@@ -454,16 +831,26 @@ impl HookExecutor for CustomPluginExecutor {
         // Execute the plugin with timeout
         let plugin_result = timeout(
             hook_timeout,
-            self.execute_plugin(plugin_name, plugin_config, plugin_path, context, hook_timeout),
+            self.execute_plugin(plugin_name, plugin_config, plugin_path, plugin_protocol, context, hook_timeout),
         )
         .await;
 
         let duration = start_time.elapsed();
 
         match plugin_result {
-            Ok(Ok(output)) => {
-                tracing::info!("Plugin executed successfully in {:?}", duration);
-                Ok(HookResult::success(Some(output), duration))
+            Ok(Ok(run)) => {
+                let result = match run.output {
+                    Ok(output) => {
+                        tracing::info!("Plugin executed successfully in {:?}", duration);
+                        HookResult::success(Some(output), duration)
+                    }
+                    Err(e) => {
+                        tracing::error!("Plugin execution failed: {}", e);
+                        HookResult::failure(e.to_string(), duration)
+                    }
+                };
+                Ok(with_output_streams(result, &run.stdout, &run.stderr)
+                    .with_metadata(EXIT_CODE_METADATA_KEY.to_string(), serde_json::json!(run.exit_code)))
             }
             Ok(Err(e)) => {
                 tracing::error!("Plugin execution failed: {}", e);
@@ -489,15 +876,17 @@ impl HookExecutor for CustomPluginExecutor {
             max_retries: 1,
             isolated: true,
             mode: crate::hooks::types::HookExecutionMode::Async,
+            ..ExecutionConfig::default()
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
-    use crate::hooks::config::HookConfig;
-    use crate::hooks::types::{HookExecutionMode, HookPriority, LifecycleEvent, LifecycleEventType};
+    use crate::hooks::context::HOOK_ID_METADATA_KEY;
+    use crate::hooks::types::{LifecycleEvent, LifecycleEventType};
     use std::fs;
     use tempfile::TempDir;
 
@@ -508,10 +897,9 @@ mod tests {
     ) -> HookContext {
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
             model: "gpt-4".to_string(),
-            provider: "openai".to_string(),
-            timestamp: std::time::SystemTime::now(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let hook_type = HookType::CustomPlugin {
@@ -519,26 +907,13 @@ mod tests {
             plugin_config,
             timeout: Some(Duration::from_secs(10)),
             plugin_path,
+            plugin_protocol: PluginProtocol::default(),
+            resource_limits: None,
         };
 
-        let config = HookConfig {
-            id: Some("test_plugin_hook".to_string()),
-            event: LifecycleEventType::SessionStart,
-            hook_type: hook_type.clone(),
-            mode: HookExecutionMode::Async,
-            priority: HookPriority::NORMAL,
-            condition: None,
-            blocking: false,
-            required: false,
-            tags: Vec::new(),
-            description: Some("Test custom plugin hook".to_string()),
-            depends_on: Vec::new(),
-            parallel: true,
-            max_retries: 0,
-            timeout: Some(Duration::from_secs(10)),
-        };
-
-        HookContext::new(event, PathBuf::from("/tmp"), hook_type, config)
+        HookContext::new(event, PathBuf::from("/tmp"))
+            .with_hook_type(hook_type)
+            .with_metadata(HOOK_ID_METADATA_KEY.to_string(), "test_plugin_hook".into())
     }
 
     #[tokio::test]
@@ -573,6 +948,67 @@ mod tests {
         assert_eq!(resolved, plugin_path);
     }
 
+    #[tokio::test]
+    async fn test_manifest_resolves_plugin_by_declared_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("bin")).unwrap();
+        let entry = temp_dir.path().join("bin").join("format-impl.sh");
+        fs::write(&entry, "#!/bin/sh\necho formatted\n").unwrap();
+        fs::write(temp_dir.path().join("fallback.sh"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            temp_dir.path().join("plugins.toml"),
+            r#"
+[[plugins]]
+name = "formatter"
+entry = "bin/format-impl.sh"
+interpreter = ["dash", "-e"]
+supported_events = ["session_start"]
+"#,
+        )
+        .unwrap();
+        let executor = CustomPluginExecutor::with_settings(Duration::from_secs(5), temp_dir.path().to_path_buf());
+
+        let manifest = PluginManifest::load(temp_dir.path()).unwrap().unwrap();
+        let formatter = manifest.find("formatter").unwrap();
+        assert_eq!(formatter.entry, entry);
+        assert!(formatter.supports(LifecycleEventType::SessionStart));
+        assert!(!formatter.supports(LifecycleEventType::SessionEnd));
+
+        // The manifest wins; undeclared plugins still fall back to extension probing
+        assert_eq!(executor.resolve_plugin_path("formatter", None).unwrap(), entry);
+        assert_eq!(
+            executor.resolve_plugin_path("fallback", None).unwrap(),
+            temp_dir.path().join("fallback.sh")
+        );
+        assert_eq!(
+            executor.resolve_interpreter(&entry),
+            Some(vec!["dash".to_string(), "-e".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manifest_plugins_declining_the_event_are_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("plugin.json"),
+            r#"{"plugins": [
+                {"name": "on-end", "entry": "end.sh", "supported_events": ["session_end"]},
+                {"name": "anytime", "entry": "any.sh"}
+            ]}"#,
+        )
+        .unwrap();
+        let executor = CustomPluginExecutor::with_settings(Duration::from_secs(5), temp_dir.path().to_path_buf());
+
+        // The test context fires session_start
+        assert!(!executor.can_execute(&create_custom_plugin_context("on-end", HashMap::new(), None)));
+        assert!(executor.can_execute(&create_custom_plugin_context("anytime", HashMap::new(), None)));
+        assert!(executor.can_execute(&create_custom_plugin_context("undeclared", HashMap::new(), None)));
+
+        fs::write(temp_dir.path().join("plugin.json"), "{not json").unwrap();
+        let err = executor.resolve_plugin_path("anytime", None).unwrap_err();
+        assert!(err.to_string().contains("Failed to parse plugin manifest"));
+    }
+
     #[tokio::test]
     async fn test_plugin_input_preparation() {
         let executor = CustomPluginExecutor::new();
@@ -582,14 +1018,30 @@ mod tests {
         
         let context = create_custom_plugin_context("test_plugin", config.clone(), None);
         
-        let input = executor.prepare_plugin_input(&config, &context).unwrap();
+        let input = executor.build_plugin_input(&config, &context);
         
         // Verify the input contains expected structure
-        assert!(input.contains("\"config\""));
-        assert!(input.contains("\"context\""));
-        assert!(input.contains("\"environment\""));
-        assert!(input.contains("\"key1\""));
-        assert!(input.contains("\"value1\""));
+        assert!(input["context"].is_object());
+        assert!(input["environment"].is_object());
+        assert_eq!(input["config"]["key1"], "value1");
+        assert_eq!(input["config"]["key2"], 42);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_input_redacts_secret_environment() {
+        let executor = CustomPluginExecutor::new();
+        let config = HashMap::new();
+        let context = create_custom_plugin_context("test_plugin", config.clone(), None);
+
+        let environment = [
+            ("AWS_SECRET_ACCESS_KEY".to_string(), "wJalrXUtnFEMI/K7MDENG".to_string()),
+            ("HOME".to_string(), "/home/dev".to_string()),
+        ];
+        let input = executor.build_plugin_input_from_env(&config, &context, environment);
+
+        assert_eq!(input["environment"]["AWS_SECRET_ACCESS_KEY"], crate::hooks::secrets::REDACTED_VALUE);
+        assert_eq!(input["environment"]["HOME"], "/home/dev");
+        assert!(!input.to_string().contains("wJalrXUtnFEMI"));
     }
 
     #[tokio::test]
@@ -607,6 +1059,7 @@ mod tests {
         assert!(executor.validate_plugin(&dir_path).is_err());
     }
 
+    #[cfg(unix)]
     #[tokio::test]
     async fn test_create_plugin_command() {
         let executor = CustomPluginExecutor::new();
@@ -627,6 +1080,74 @@ mod tests {
         assert_eq!(cmd.as_std().get_program(), "bash");
     }
 
+    fn command_line(cmd: &Command) -> Vec<String> {
+        let cmd = cmd.as_std();
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extensionless_plugin_uses_shebang() {
+        let executor = CustomPluginExecutor::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let plugin = temp_dir.path().join("lint");
+        std::fs::write(&plugin, "#!/usr/bin/env python3 -u\nprint('hi')\n").unwrap();
+        let cmd = executor.create_plugin_command(&plugin).unwrap();
+        assert_eq!(
+            command_line(&cmd),
+            vec!["/usr/bin/env".to_string(), "python3".to_string(), "-u".to_string(), plugin.display().to_string()]
+        );
+
+        // Without a shebang the plugin is executed directly
+        let binary = temp_dir.path().join("binary");
+        std::fs::write(&binary, [0x7f, b'E', b'L', b'F']).unwrap();
+        let cmd = executor.create_plugin_command(&binary).unwrap();
+        assert_eq!(command_line(&cmd), vec![binary.display().to_string()]);
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_windows_interpreter_mappings() {
+        let executor = CustomPluginExecutor::new();
+
+        let cmd = executor.create_plugin_command(Path::new("plugin.py")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["python", "plugin.py"]);
+
+        let cmd = executor.create_plugin_command(Path::new("plugin.ps1")).unwrap();
+        assert_eq!(
+            command_line(&cmd),
+            vec!["powershell", "-NoProfile", "-ExecutionPolicy", "Bypass", "-File", "plugin.ps1"]
+        );
+
+        let cmd = executor.create_plugin_command(Path::new("plugin.cmd")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["cmd", "/c", "plugin.cmd"]);
+        let cmd = executor.create_plugin_command(Path::new("PLUGIN.BAT")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["cmd", "/c", "PLUGIN.BAT"]);
+
+        // Anything else runs directly
+        let cmd = executor.create_plugin_command(Path::new("plugin.exe")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["plugin.exe"]);
+    }
+
+    #[tokio::test]
+    async fn test_interpreter_map_is_configurable() {
+        let executor = CustomPluginExecutor::new()
+            .with_interpreter(".lua", vec!["lua5.4".to_string()])
+            .with_interpreter("js", vec!["deno".to_string(), "run".to_string()])
+            .with_interpreter("rb", Vec::new());
+
+        let cmd = executor.create_plugin_command(Path::new("plugin.lua")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["lua5.4", "plugin.lua"]);
+        let cmd = executor.create_plugin_command(Path::new("plugin.js")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["deno", "run", "plugin.js"]);
+        let cmd = executor.create_plugin_command(Path::new("plugin.rb")).unwrap();
+        assert_eq!(command_line(&cmd), vec!["plugin.rb"]);
+    }
+
     #[tokio::test]
     async fn test_invalid_plugin_configuration() {
         let executor = CustomPluginExecutor::new();
@@ -676,6 +1197,103 @@ echo '{"result": "Plugin executed successfully", "input_received": true}'
         }
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_plugin_streams_are_captured_separately_and_truncated() {
+        use crate::hooks::executor::{OUTPUT_TRUNCATED_MARKER, STDERR_METADATA_KEY, STDOUT_METADATA_KEY};
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_path = temp_dir.path().join("chatty");
+        fs::write(&plugin_path, "#!/bin/sh\ncat >/dev/null\necho done\necho 'deprecated option' >&2\n").unwrap();
+        fs::set_permissions(&plugin_path, fs::Permissions::from_mode(0o755)).unwrap();
+        let context = create_custom_plugin_context("chatty", HashMap::new(), Some(plugin_path.clone()));
+
+        let result = CustomPluginExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("done"));
+        assert_eq!(result.metadata[STDOUT_METADATA_KEY], serde_json::json!("done\n"));
+        assert_eq!(result.metadata[STDERR_METADATA_KEY], serde_json::json!("deprecated option\n"));
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(0));
+
+        let result = CustomPluginExecutor::new().with_max_output_size(4).execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Plugin output exceeded max_output_size of 4 bytes"));
+        assert_eq!(
+            result.metadata[STDOUT_METADATA_KEY],
+            serde_json::json!(format!("done\n{}", OUTPUT_TRUNCATED_MARKER))
+        );
+        assert_eq!(
+            result.metadata[STDERR_METADATA_KEY],
+            serde_json::json!(format!("depr\n{}", OUTPUT_TRUNCATED_MARKER))
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_msgpack_plugin_round_trip() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let executor = CustomPluginExecutor::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        // Echo the MessagePack frame back unchanged
+        let plugin_path = temp_dir.path().join("echo_frame.sh");
+        fs::write(&plugin_path, "#!/bin/bash\n[ \"$CODEX_PLUGIN_PROTOCOL\" = msgpack ] || exit 3\ncat\n").unwrap();
+        fs::set_permissions(&plugin_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let frame = serde_json::json!({
+            "result": {"artifact": "report.bin", "size": 4096, "ok": true},
+            "input_received": true,
+        });
+        let input = PluginProtocol::MsgPackStdio.encode(&frame).unwrap();
+        assert_ne!(input, serde_json::to_vec(&frame).unwrap());
+
+        let output = executor
+            .run_plugin(
+                &plugin_path,
+                &input,
+                PluginProtocol::MsgPackStdio,
+                false,
+                &ResourceLimits::default(),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap()
+            .output
+            .unwrap();
+        let result: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result, frame["result"]);
+
+        // A JSON reply is not a valid frame for a binary protocol
+        let json_plugin = temp_dir.path().join("json_reply.sh");
+        fs::write(&json_plugin, "#!/bin/bash\ncat >/dev/null\necho '{\"result\": \"ok\"}'\n").unwrap();
+        fs::set_permissions(&json_plugin, fs::Permissions::from_mode(0o755)).unwrap();
+        let err = executor
+            .run_plugin(
+                &json_plugin,
+                &input,
+                PluginProtocol::CborStdio,
+                false,
+                &ResourceLimits::default(),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap()
+            .output
+            .unwrap_err();
+        assert!(err.to_string().contains("CBOR"));
+    }
+
+    #[test]
+    fn test_plugin_protocol_codecs_round_trip() {
+        let value = serde_json::json!({"config": {"key": "value"}, "count": 3});
+        for protocol in [PluginProtocol::JsonStdio, PluginProtocol::MsgPackStdio, PluginProtocol::CborStdio] {
+            let decoded: Value = protocol.decode(&protocol.encode(&value).unwrap()).unwrap();
+            assert_eq!(decoded, value, "{:?}", protocol);
+        }
+    }
+
     #[test]
     fn test_plugin_info_serialization() {
         let info = PluginInfo {
@@ -684,6 +1302,7 @@ echo '{"result": "Plugin executed successfully", "input_received": true}'
             description: "A test plugin".to_string(),
             author: "Test Author".to_string(),
             supported_events: vec!["session.start".to_string(), "task.complete".to_string()],
+            structured_output: true,
         };
 
         let serialized = serde_json::to_string(&info).unwrap();
@@ -692,5 +1311,111 @@ echo '{"result": "Plugin executed successfully", "input_received": true}'
         assert_eq!(deserialized.name, "test_plugin");
         assert_eq!(deserialized.version, "1.0.0");
         assert_eq!(deserialized.supported_events.len(), 2);
+        assert!(deserialized.structured_output);
+
+        // Plugins that predate the field do not declare structured output
+        let legacy: PluginInfo = serde_json::from_str(
+            r#"{"name": "old", "version": "0.1", "description": "", "author": "", "supported_events": []}"#,
+        )
+        .unwrap();
+        assert!(!legacy.structured_output);
+    }
+
+    #[test]
+    fn test_structured_output_contract() {
+        let output = CustomPluginExecutor::contract_output(&serde_json::json!({
+            "status": "success",
+            "result": {"files": 3},
+        }))
+        .unwrap();
+        assert_eq!(output, r#"{"files":3}"#);
+
+        let error = CustomPluginExecutor::contract_output(&serde_json::json!({
+            "status": "error",
+            "error": "lint failed",
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("Plugin reported an error: lint failed"));
+
+        for response in [
+            serde_json::json!("done"),
+            serde_json::json!({"result": "done"}),
+            serde_json::json!({"status": "ok"}),
+            serde_json::json!({"status": "error"}),
+            serde_json::json!({"status": "success", "error": 7}),
+        ] {
+            let error = CustomPluginExecutor::contract_output(&response).unwrap_err();
+            assert!(error.to_string().contains("violates the structured output contract"), "{}", error);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_declared_structured_output_is_enforced() {
+        let temp_dir = TempDir::new().unwrap();
+        let write_plugin = |name: &str, response: &str| {
+            use std::os::unix::fs::PermissionsExt;
+
+            let path = temp_dir.path().join(name);
+            let script = format!(
+                "#!/bin/sh\nif [ \"$1\" = --info ]; then\n  echo '{}'\n  exit 0\nfi\necho '{}'\n",
+                r#"{"name": "p", "version": "1", "description": "", "author": "", "supported_events": ["*"], "structured_output": true}"#,
+                response,
+            );
+            fs::write(&path, script).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        let executor = CustomPluginExecutor::new();
+
+        let conforming = write_plugin("conforming", r#"{"status": "success", "result": "checked"}"#);
+        let context = create_custom_plugin_context("conforming", HashMap::new(), Some(conforming));
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.as_deref(), Some(r#""checked""#));
+
+        let malformed = write_plugin("malformed", r#"{"result": "checked"}"#);
+        let context = create_custom_plugin_context("malformed", HashMap::new(), Some(malformed));
+        let result = executor.execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("missing string field 'status'"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_plugin_info_cache_refreshes_when_plugin_changes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let calls = temp_dir.path().join("calls");
+        let path = temp_dir.path().join("described");
+        let write_plugin = |version: &str| {
+            let script = format!(
+                "#!/bin/sh\necho x >> '{}'\nsleep 0.2\necho '{{\"name\": \"described\", \"version\": \"{}\", \"description\": \"\", \"author\": \"\", \"supported_events\": [\"*\"]}}'\n",
+                calls.display(),
+                version,
+            );
+            fs::write(&path, script).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        let info_runs = || fs::read_to_string(&calls).map(|s| s.lines().count()).unwrap_or(0);
+        let executor = CustomPluginExecutor::new();
+
+        write_plugin("1");
+        let (first, second) = tokio::join!(executor.cached_plugin_info(&path), executor.cached_plugin_info(&path));
+        assert_eq!(first.version, "1");
+        assert_eq!(second.version, "1");
+        assert_eq!(executor.cached_plugin_info(&path).await.version, "1");
+        assert_eq!(info_runs(), 1);
+
+        write_plugin("2");
+        let touched = SystemTime::now() + Duration::from_secs(10);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(touched).unwrap();
+        assert_eq!(executor.cached_plugin_info(&path).await.version, "2");
+        assert_eq!(info_runs(), 2);
+
+        executor.clear_plugin_info_cache();
+        assert_eq!(executor.cached_plugin_info(&path).await.version, "2");
+        assert_eq!(info_runs(), 3);
     }
 }
@@ -0,0 +1,229 @@
+//! Desktop notification hook executor for local, interactive sessions.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::hooks::context::{HookContext, TemplateSubstitution};
+use crate::hooks::executor::{event_filter_allows, ExecutionConfig, HookExecutor, HookExecutorResult};
+use crate::hooks::types::{HookError, HookExecutionMode, HookResult, HookType, LifecycleEventType, NotificationUrgency};
+
+/// Result metadata key recording whether a notification was actually shown.
+pub const NOTIFICATION_DELIVERED_METADATA_KEY: &str = "notification_delivered";
+
+/// Application name notifications are attributed to.
+#[cfg(feature = "desktop-notifications")]
+const NOTIFICATION_APP_NAME: &str = "Codex";
+
+/// Executor that shows OS desktop notifications.
+#[derive(Debug, Clone)]
+pub struct DesktopNotificationExecutor {
+    /// Default timeout for showing a notification.
+    default_timeout: Duration,
+    /// Reports whether a desktop session is available.
+    display_probe: fn() -> bool,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
+}
+
+impl DesktopNotificationExecutor {
+    /// Create a new desktop notification executor.
+    pub fn new() -> Self {
+        Self {
+            default_timeout: Duration::from_secs(5),
+            display_probe: has_display,
+            event_filter: None,
+        }
+    }
+
+    /// Use `probe` instead of the environment to decide whether a display is available.
+    pub fn with_display_probe(mut self, probe: fn() -> bool) -> Self {
+        self.display_probe = probe;
+        self
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
+    /// Render the notification title and body from the event.
+    fn render(&self, context: &HookContext) -> Result<(String, String, NotificationUrgency), HookError> {
+        match &context.hook_type {
+            HookType::DesktopNotification { title, body, urgency, .. } => {
+                let substitution = TemplateSubstitution::from_context(context);
+                Ok((substitution.substitute(title), substitution.substitute(body), *urgency))
+            }
+            _ => Err(HookError::Configuration(
+                "DesktopNotificationExecutor can only execute DesktopNotification hooks".to_string(),
+            )),
+        }
+    }
+}
+
+impl Default for DesktopNotificationExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a desktop session is available to show notifications.
+///
+/// macOS and Windows always have one; elsewhere an X11 or Wayland display is required.
+pub fn has_display() -> bool {
+    if cfg!(any(target_os = "macos", windows)) {
+        return true;
+    }
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()))
+}
+
+/// Show a notification, blocking until the platform accepts it.
+#[cfg(feature = "desktop-notifications")]
+fn show_notification(title: &str, body: &str, urgency: NotificationUrgency) -> Result<(), HookError> {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname(NOTIFICATION_APP_NAME).summary(title).body(body);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    notification.urgency(match urgency {
+        NotificationUrgency::Low => notify_rust::Urgency::Low,
+        NotificationUrgency::Normal => notify_rust::Urgency::Normal,
+        NotificationUrgency::Critical => notify_rust::Urgency::Critical,
+    });
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    let _ = urgency;
+
+    notification
+        .show()
+        .map(|_| ())
+        .map_err(|e| HookError::Execution(format!("Failed to show desktop notification: {}", e)))
+}
+
+/// Fail the notification: this build has no desktop notification client.
+#[cfg(not(feature = "desktop-notifications"))]
+fn show_notification(_title: &str, _body: &str, _urgency: NotificationUrgency) -> Result<(), HookError> {
+    Err(HookError::Execution(
+        "Failed to show desktop notification: desktop notification support is not enabled in this build; rebuild codex-core with the `desktop-notifications` feature".to_string(),
+    ))
+}
+
+#[async_trait]
+impl HookExecutor for DesktopNotificationExecutor {
+    fn executor_type(&self) -> &'static str {
+        "desktop_notification"
+    }
+
+    fn can_execute(&self, context: &HookContext) -> bool {
+        matches!(context.hook_type, HookType::DesktopNotification { .. })
+            && event_filter_allows(self.event_filter.as_ref(), context)
+    }
+
+    async fn execute(&self, context: &HookContext) -> HookExecutorResult {
+        let start_time = Instant::now();
+        let (title, body, urgency) = self.render(context)?;
+
+        if !(self.display_probe)() {
+            tracing::debug!("No display available, skipping desktop notification: {}", title);
+            return Ok(HookResult::success(
+                Some("Desktop notification skipped: no display available".to_string()),
+                start_time.elapsed(),
+            )
+            .with_metadata(NOTIFICATION_DELIVERED_METADATA_KEY.to_string(), serde_json::json!(false)));
+        }
+
+        let shown_title = title.clone();
+        let shown = tokio::task::spawn_blocking(move || show_notification(&shown_title, &body, urgency))
+            .await
+            .map_err(|e| HookError::Execution(format!("Desktop notification task failed: {}", e)))?;
+
+        match shown {
+            Ok(()) => Ok(HookResult::success(Some(format!("Desktop notification shown: {}", title)), start_time.elapsed())
+                .with_metadata(NOTIFICATION_DELIVERED_METADATA_KEY.to_string(), serde_json::json!(true))),
+            Err(e) => {
+                tracing::warn!("Desktop notification failed: {}", e);
+                Ok(HookResult::failure(e.to_string(), start_time.elapsed())
+                    .with_metadata(NOTIFICATION_DELIVERED_METADATA_KEY.to_string(), serde_json::json!(false)))
+            }
+        }
+    }
+
+    fn estimated_duration(&self) -> Option<Duration> {
+        Some(Duration::from_millis(100))
+    }
+
+    fn default_config(&self) -> ExecutionConfig {
+        ExecutionConfig {
+            timeout: self.default_timeout,
+            mode: HookExecutionMode::FireAndForget,
+            isolated: false,
+            ..ExecutionConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::hooks::types::LifecycleEvent;
+    use std::path::PathBuf;
+
+    fn create_notification_context() -> HookContext {
+        let event = LifecycleEvent::TaskComplete {
+            task_id: "task-42".to_string(),
+            session_id: "session-1".to_string(),
+            success: true,
+            output: Some("3 files changed".to_string()),
+            duration: Duration::from_secs(95),
+            timestamp: chrono::Utc::now(),
+        };
+
+        HookContext::new(event, PathBuf::from("/tmp")).with_hook_type(HookType::DesktopNotification {
+            title: "Task {task_id} finished".to_string(),
+            body: "{output} in {duration_secs}s".to_string(),
+            urgency: NotificationUrgency::Low,
+            timeout: None,
+        })
+    }
+
+    #[test]
+    fn test_render_templates_from_event() {
+        let executor = DesktopNotificationExecutor::new();
+        let (title, body, urgency) = executor.render(&create_notification_context()).unwrap();
+        assert_eq!(title, "Task task-42 finished");
+        assert_eq!(body, "3 files changed in 95s");
+        assert_eq!(urgency, NotificationUrgency::Low);
+    }
+
+    #[tokio::test]
+    async fn test_headless_is_a_successful_no_op() {
+        let executor = DesktopNotificationExecutor::new().with_display_probe(|| false);
+        let context = create_notification_context();
+        assert!(executor.can_execute(&context));
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.unwrap().contains("no display available"));
+        assert_eq!(result.metadata[NOTIFICATION_DELIVERED_METADATA_KEY], serde_json::json!(false));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    #[tokio::test]
+    async fn test_notification_shown_on_supported_platform() {
+        if !has_display() {
+            return;
+        }
+
+        let result = DesktopNotificationExecutor::new().execute(&create_notification_context()).await.unwrap();
+        if result.success {
+            assert_eq!(result.output.as_deref(), Some("Desktop notification shown: Task task-42 finished"));
+            assert_eq!(result.metadata[NOTIFICATION_DELIVERED_METADATA_KEY], serde_json::json!(true));
+        } else {
+            // A display without a notification daemon reports a clear failure
+            assert!(result.error.unwrap().contains("Failed to show desktop notification"));
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Executable hook executor for running arbitrary binaries.
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::hooks::context::{HookContext, TemplateEscape, TemplateSubstitution};
+use crate::hooks::executor::{CapturedOutput, ExecutionConfig, HookExecutor, HookExecutorResult};
+use crate::hooks::executors::resource_limits;
+use crate::hooks::secrets::EnvCapturePolicy;
+use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookResult, HookType};
+
+/// Result metadata key holding the process exit code.
+pub const EXIT_CODE_METADATA_KEY: &str = "exit_code";
+
+/// Number of trailing stderr lines included in failure messages.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Executor that spawns a configured executable, writing the serialized hook context to its stdin.
+#[derive(Debug, Clone)]
+pub struct ExecutableExecutor {
+    /// Bytes kept of each of stdout and stderr; the rest is drained and dropped.
+    max_output_size: usize,
+    /// Which environment variables `${env.*}` templates may expand, and which are redacted.
+    env_policy: EnvCapturePolicy,
+}
+
+/// Output of an executable that ran to completion.
+struct ExecutableRun {
+    status: std::process::ExitStatus,
+    stdout: CapturedOutput,
+    stderr: CapturedOutput,
+}
+
+impl ExecutableExecutor {
+    /// Create an executable executor keeping up to 1MB of each output stream.
+    pub fn new() -> Self {
+        Self {
+            max_output_size: 1024 * 1024,
+            env_policy: EnvCapturePolicy::default(),
+        }
+    }
+
+    /// Set how many bytes of stdout and stderr are kept.
+    pub fn with_max_output_size(mut self, size: usize) -> Self {
+        self.max_output_size = size;
+        self
+    }
+
+    /// Control which environment variables `${env.*}` templates in arguments expand.
+    pub fn with_env_policy(mut self, env_policy: EnvCapturePolicy) -> Self {
+        self.env_policy = env_policy;
+        self
+    }
+
+    /// Spawn the executable and wait for it to exit, killing it after `operation_timeout`.
+    async fn run(&self, context: &HookContext, operation_timeout: Duration) -> Result<ExecutableRun, HookError> {
+        let HookType::Executable { path, args, cwd, environment, resource_limits: limits, .. } = &context.hook_type else {
+            return Err(HookError::Configuration(
+                "ExecutableExecutor can only execute Executable hooks".to_string(),
+            ));
+        };
+
+        let input = serde_json::to_vec(context)
+            .map_err(|e| HookError::Execution(format!("Failed to serialize hook context: {}", e)))?;
+
+        let templates = TemplateSubstitution::from_context(context).with_env_policy(&self.env_policy);
+        let args = args
+            .iter()
+            .map(|arg| templates.render(arg, TemplateEscape::None))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut command = Command::new(path);
+        command
+            .args(&args)
+            .current_dir(cwd.as_ref().unwrap_or(&context.working_directory))
+            .envs(&context.environment)
+            .envs(environment)
+            .env("CODEX_HOOK_TYPE", "executable")
+            .env("CODEX_EVENT_TYPE", context.event.event_type().to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(limits) = limits {
+            resource_limits::apply(limits, &mut command);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| HookError::Execution(format!("Failed to spawn executable {}: {}", path.display(), e)))?;
+
+        // Feed stdin concurrently so a process that never reads it cannot stall on a full stdout pipe
+        let stdin = child.stdin.take();
+        let feed_stdin = async move {
+            let Some(mut stdin) = stdin else {
+                return Ok(());
+            };
+            match stdin.write_all(&input).await {
+                // The process may exit without reading its input
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+                result => result,
+            }
+        };
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| HookError::Execution("Failed to capture executable stdout".to_string()))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| HookError::Execution("Failed to capture executable stderr".to_string()))?;
+
+        // Cap what is kept of each stream so a chatty executable cannot exhaust memory
+        let run = async {
+            let (fed, stdout, stderr, status) = tokio::join!(
+                feed_stdin,
+                CapturedOutput::read_limited(stdout, self.max_output_size),
+                CapturedOutput::read_limited(stderr, self.max_output_size),
+                child.wait(),
+            );
+            fed.map_err(|e| HookError::Execution(format!("Failed to write to executable stdin: {}", e)))?;
+            let status = status.map_err(|e| HookError::Execution(format!("Failed to wait for executable: {}", e)))?;
+            Ok::<_, HookError>(ExecutableRun { status, stdout: stdout?, stderr: stderr? })
+        };
+
+        match timeout(operation_timeout, run).await {
+            Ok(output) => output,
+            // Dropping the child kills the process
+            Err(_) => Err(HookError::Execution(format!(
+                "Executable timed out after {:?}",
+                operation_timeout
+            ))),
+        }
+    }
+
+    /// The last `STDERR_TAIL_LINES` lines of stderr.
+    fn stderr_tail(stderr: &str) -> String {
+        let lines: Vec<_> = stderr.trim_end().lines().collect();
+        lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+    }
+}
+
+impl Default for ExecutableExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HookExecutor for ExecutableExecutor {
+    async fn execute(&self, context: &HookContext) -> HookExecutorResult {
+        let start_time = Instant::now();
+        let (operation_timeout, limits) = match &context.hook_type {
+            HookType::Executable { timeout, resource_limits, .. } => (
+                timeout.unwrap_or(self.default_config().timeout),
+                resource_limits.clone().unwrap_or_default(),
+            ),
+            _ => {
+                return Err(HookError::Configuration(
+                    "ExecutableExecutor can only execute Executable hooks".to_string(),
+                ));
+            }
+        };
+
+        let ExecutableRun { status, stdout, stderr } = match self.run(context, operation_timeout).await {
+            Ok(run) => run,
+            Err(e) => {
+                tracing::error!("Executable hook error: {}", e);
+                return Ok(HookResult::failure(e.to_string(), start_time.elapsed()));
+            }
+        };
+
+        let exit_code = status.code().unwrap_or(-1);
+
+        if status.success() {
+            tracing::info!("Executable hook completed in {:?}", start_time.elapsed());
+            let result = HookResult::success(Some(stdout.text.trim_end().to_string()), start_time.elapsed())
+                .with_metadata(EXIT_CODE_METADATA_KEY.to_string(), serde_json::json!(exit_code));
+            return Ok(stdout.annotate(result, false));
+        }
+
+        let tail = Self::stderr_tail(&stderr.text);
+        tracing::warn!("Executable hook failed: exit_code={}, stderr={}", exit_code, tail);
+        let error_msg = if let Some(limit_exceeded) = resource_limits::violation(&limits, &status, &stderr.text) {
+            limit_exceeded
+        } else if tail.is_empty() {
+            format!("Executable failed with exit code {}", exit_code)
+        } else {
+            format!("Executable failed with exit code {}: {}", exit_code, tail)
+        };
+        let result = HookResult::failure(error_msg, start_time.elapsed())
+            .with_metadata(EXIT_CODE_METADATA_KEY.to_string(), serde_json::json!(exit_code));
+        Ok(stderr.annotate(result, false))
+    }
+
+    fn executor_type(&self) -> &'static str {
+        "executable"
+    }
+
+    fn can_execute(&self, context: &HookContext) -> bool {
+        matches!(context.hook_type, HookType::Executable { .. })
+    }
+
+    fn estimated_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs(30)) // Executables can vary widely
+    }
+
+    fn default_config(&self) -> ExecutionConfig {
+        ExecutionConfig {
+            timeout: Duration::from_secs(300), // 5 minutes for custom executables
+            mode: HookExecutionMode::Async,
+            priority: HookPriority::NORMAL,
+            required: false,
+            max_retries: 1,
+            retry_delay: Duration::from_secs(1),
+            isolated: true,
+            total_deadline: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::hooks::types::LifecycleEvent;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn create_executable_context(path: &str, args: &[&str], timeout: Option<Duration>) -> HookContext {
+        let event = LifecycleEvent::TaskStart {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            prompt: "do work".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        HookContext::new(event, std::env::temp_dir()).with_hook_type(HookType::Executable {
+            path: PathBuf::from(path),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: None,
+            environment: HashMap::new(),
+            timeout,
+            resource_limits: None,
+        })
+    }
+
+    #[test]
+    fn test_can_execute_only_executable_hooks() {
+        let executor = ExecutableExecutor::new();
+        assert!(executor.can_execute(&create_executable_context("/bin/echo", &[], None)));
+
+        let mut context = create_executable_context("/bin/echo", &[], None);
+        context.hook_type = HookType::PatchAnalysis { timeout: None };
+        assert!(!executor.can_execute(&context));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_runs_echo() {
+        let context = create_executable_context("/bin/echo", &["hello", "hooks"], None);
+
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("hello hooks"));
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(0));
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_runs_echo() {
+        let context = create_executable_context("cmd", &["/c", "echo", "hello"], None);
+
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("hello"));
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(0));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_env_templates_in_args_follow_capture_policy() {
+        let context = create_executable_context("/bin/echo", &["${env.API_TOKEN}", "${env.BUILD_ID}"], None)
+            .with_env("API_TOKEN".to_string(), "tok-123".to_string())
+            .with_env("BUILD_ID".to_string(), "42".to_string());
+
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("<redacted> 42"));
+
+        let policy = EnvCapturePolicy { allow: vec!["API_*".to_string()], ..Default::default() };
+        let result = ExecutableExecutor::new().with_env_policy(policy).execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_deref().unwrap().contains("env.BUILD_ID"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdin_carries_serialized_context() {
+        let context = create_executable_context("/bin/cat", &[], None);
+
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        let echoed: HookContext = serde_json::from_str(result.output.as_deref().unwrap()).unwrap();
+        assert_eq!(echoed.event.event_type(), context.event.event_type());
+        assert_eq!(echoed.working_directory, context.working_directory);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_is_capped_at_max_output_size() {
+        let script = "head -c 1000000 /dev/zero | tr '\\0' a";
+        let context = create_executable_context("/bin/sh", &["-c", script], None);
+
+        let result = ExecutableExecutor::new().with_max_output_size(10).execute(&context).await.unwrap();
+        assert!(result.success);
+        let output = result.output.unwrap();
+        assert!(output.starts_with("aaaaaaaaaa\n"));
+        assert!(output.ends_with(crate::hooks::executor::OUTPUT_TRUNCATED_MARKER.trim_end()));
+        assert!(output.len() < 100);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_nonzero_exit_reports_stderr_tail() {
+        let script = "for i in $(seq 1 30); do echo err$i >&2; done; exit 3";
+        let context = create_executable_context("/bin/sh", &["-c", script], None);
+
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(3));
+        let error = result.error.unwrap();
+        assert!(error.starts_with("Executable failed with exit code 3: err11\n"));
+        assert!(error.ends_with("err30"));
+        assert!(!error.contains("err10\n"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_timeout_kills_executable() {
+        let context = create_executable_context("/bin/sleep", &["5"], Some(Duration::from_millis(100)));
+
+        let start = Instant::now();
+        let result = ExecutableExecutor::new().execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_default_timeout_is_five_minutes() {
+        assert_eq!(ExecutableExecutor::new().default_config().timeout, Duration::from_secs(300));
+    }
+}
@@ -1,23 +1,169 @@
 //! File system hook executor for file operations and monitoring.
 
+#[cfg(feature = "fs-watch")]
 use std::collections::HashMap;
-use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+#[cfg(feature = "fs-watch")]
+use notify::event::{ModifyKind, RenameMode};
+#[cfg(feature = "fs-watch")]
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "fs-watch")]
+use tokio::sync::mpsc;
 use tokio::time::timeout;
 
 use crate::hooks::context::HookContext;
-use crate::hooks::executor::{ExecutionConfig, HookExecutor, HookExecutorResult};
-use crate::hooks::types::{FileSystemOperation, HookError, HookResult, HookType};
+use crate::hooks::executor::{event_filter_allows, ExecutionConfig, HookExecutor, HookExecutorResult};
+use crate::hooks::types::{FileSystemOperation, HookError, HookResult, HookType, LifecycleEventType};
 
 /// File system hook executor for performing file operations.
 #[derive(Debug)]
 pub struct FileSystemExecutor {
     /// Default timeout for file operations.
     default_timeout: Duration,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
+    /// Maximum number of files copied concurrently within one directory copy.
+    copy_concurrency: usize,
+    /// Repeated changes of the same kind to the same path within this window are reported once.
+    #[cfg_attr(not(feature = "fs-watch"), allow(dead_code))]
+    watch_debounce: Duration,
+    /// Normalized roots that operations must stay within (deny-list only when unset).
+    allowed_roots: Option<Vec<PathBuf>>,
+}
+
+/// Directories that file system hooks may never touch.
+const DENIED_SYSTEM_PATHS: [&str; 6] = ["/etc", "/bin", "/sbin", "/usr/bin", "/usr/sbin", "/boot"];
+
+/// Resolve `path` to an absolute path without requiring it to exist.
+///
+/// Symlinks are resolved for every prefix that exists; `.` and `..` in the
+/// remainder are applied lexically.
+fn normalize_path(path: &Path) -> std::io::Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut resolved = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => resolved.push(component),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(name) => {
+                resolved.push(name);
+                if let Ok(canonical) = resolved.canonicalize() {
+                    resolved = canonical;
+                }
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Default number of files copied concurrently within one directory copy.
+const DEFAULT_COPY_CONCURRENCY: usize = 8;
+
+/// Default window within which repeated changes to a path are collapsed.
+const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Longest part of the hook timeout kept free after a watch window closes.
+const MAX_WATCH_MARGIN: Duration = Duration::from_secs(1);
+
+/// How long a watch collects events within `hook_timeout`.
+///
+/// The manager cancels hooks at their timeout, so the window closes a margin earlier (a tenth
+/// of the timeout, at most a second) for a watch without events to end with a clean result.
+fn watch_window(hook_timeout: Duration) -> Duration {
+    hook_timeout.saturating_sub(MAX_WATCH_MARGIN.min(hook_timeout / 10))
+}
+
+/// Operation parameters resolved from a `HookType::FileSystem` hook.
+struct FileSystemRequest<'a> {
+    operation: &'a FileSystemOperation,
+    path: &'a Path,
+    target_path: Option<&'a Path>,
+    content: Option<&'a str>,
+    permissions: Option<u32>,
+    /// Hook timeout; a watch collects events for [`watch_window`] of it.
+    timeout: Duration,
+    max_events: Option<usize>,
+    atomic: bool,
+}
+
+/// Removes a temporary file when dropped unless it was persisted.
+struct TempFileGuard {
+    path: Option<PathBuf>,
+}
+
+impl TempFileGuard {
+    fn new(path: PathBuf) -> Self {
+        Self { path: Some(path) }
+    }
+
+    /// Keep the file; call once it has been renamed into place.
+    fn persist(mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Kind of change observed by a file system watch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+impl ChangeKind {
+    /// Stable name used in watch output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Removed => "removed",
+        }
+    }
+
+    /// Map a notify event to the changes it describes; access and unknown events are ignored.
+    #[cfg(feature = "fs-watch")]
+    fn from_event(event: &Event) -> Vec<(ChangeKind, PathBuf)> {
+        let kind = match event.kind {
+            EventKind::Create(_) => ChangeKind::Created,
+            EventKind::Remove(_) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => ChangeKind::Created,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                return match event.paths.as_slice() {
+                    [from, to, ..] => vec![(ChangeKind::Removed, from.clone()), (ChangeKind::Created, to.clone())],
+                    _ => Vec::new(),
+                };
+            }
+            EventKind::Modify(_) => ChangeKind::Modified,
+            EventKind::Access(_) | EventKind::Any | EventKind::Other => return Vec::new(),
+        };
+        event.paths.iter().map(|path| (kind, path.clone())).collect()
+    }
 }
 
 impl FileSystemExecutor {
@@ -25,6 +171,10 @@ impl FileSystemExecutor {
     pub fn new() -> Self {
         Self {
             default_timeout: Duration::from_secs(10),
+            event_filter: None,
+            copy_concurrency: DEFAULT_COPY_CONCURRENCY,
+            watch_debounce: DEFAULT_WATCH_DEBOUNCE,
+            allowed_roots: None,
         }
     }
 
@@ -32,18 +182,56 @@ impl FileSystemExecutor {
     pub fn with_timeout(timeout: Duration) -> Self {
         Self {
             default_timeout: timeout,
+            event_filter: None,
+            copy_concurrency: DEFAULT_COPY_CONCURRENCY,
+            watch_debounce: DEFAULT_WATCH_DEBOUNCE,
+            allowed_roots: None,
         }
     }
 
+    /// Set how many files may be copied concurrently within one directory copy.
+    pub fn with_copy_concurrency(mut self, concurrency: usize) -> Self {
+        self.copy_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the window within which repeated changes to the same path are reported once.
+    pub fn with_watch_debounce(mut self, debounce: Duration) -> Self {
+        self.watch_debounce = debounce;
+        self
+    }
+
+    /// Only allow operations on paths that resolve inside one of `roots`.
+    ///
+    /// Roots are normalized once here, so symlinked roots compare against the
+    /// resolved paths of their contents.
+    pub fn with_allowed_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.allowed_roots = Some(
+            roots
+                .into_iter()
+                .map(|root| normalize_path(&root).unwrap_or(root))
+                .collect(),
+        );
+        self
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
     /// Execute a file system operation.
-    async fn execute_filesystem_operation(
-        &self,
-        operation: &FileSystemOperation,
-        path: &Path,
-        target_path: Option<&Path>,
-        content: Option<&str>,
-        permissions: Option<u32>,
-    ) -> Result<String, HookError> {
+    async fn execute_filesystem_operation(&self, request: &FileSystemRequest<'_>) -> Result<String, HookError> {
+        let FileSystemRequest {
+            operation,
+            path,
+            target_path,
+            content,
+            permissions,
+            ..
+        } = *request;
+
         match operation {
             FileSystemOperation::Create => {
                 self.create_file_or_directory(path, content).await
@@ -52,7 +240,11 @@ impl FileSystemExecutor {
                 self.read_file(path).await
             }
             FileSystemOperation::Write => {
-                self.write_file(path, content.unwrap_or("")).await
+                if request.atomic {
+                    self.write_file_atomic(path, content.unwrap_or("")).await
+                } else {
+                    self.write_file(path, content.unwrap_or("")).await
+                }
             }
             FileSystemOperation::Append => {
                 self.append_to_file(path, content.unwrap_or("")).await
@@ -79,7 +271,7 @@ impl FileSystemExecutor {
                 self.change_permissions(path, perms).await
             }
             FileSystemOperation::Watch => {
-                self.watch_file_or_directory(path).await
+                self.watch_file_or_directory(path, watch_window(request.timeout), request.max_events).await
             }
         }
     }
@@ -168,6 +360,66 @@ impl FileSystemExecutor {
         ))
     }
 
+    /// Write content to a file by writing a temp file in the same directory and renaming it into place.
+    ///
+    /// The temp file is fsynced before the rename and removed if the write fails or is
+    /// cancelled, so the target always holds either its old or its new content.
+    async fn write_file_atomic(&self, path: &Path, content: &str) -> Result<String, HookError> {
+        tracing::info!("Atomically writing to file: {}", path.display());
+
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| HookError::Execution(format!("Invalid file path: {}", path.display())))?;
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        tokio::fs::create_dir_all(&parent)
+            .await
+            .map_err(|e| HookError::Execution(format!("Failed to create parent directory: {}", e)))?;
+
+        let temp_path = parent.join(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            uuid::Uuid::new_v4()
+        ));
+        // Created synchronously so the guard owns the file before any cancellation point
+        let temp_file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .map_err(|e| HookError::Execution(format!("Failed to create temp file: {}", e)))?;
+        let guard = TempFileGuard::new(temp_path.clone());
+
+        if let Ok(metadata) = fs::metadata(path) {
+            temp_file
+                .set_permissions(metadata.permissions())
+                .map_err(|e| HookError::Execution(format!("Failed to preserve file permissions: {}", e)))?;
+        }
+
+        let mut temp_file = tokio::fs::File::from_std(temp_file);
+        temp_file
+            .write_all(content.as_bytes())
+            .await
+            .map_err(|e| HookError::Execution(format!("Failed to write temp file: {}", e)))?;
+        temp_file
+            .sync_all()
+            .await
+            .map_err(|e| HookError::Execution(format!("Failed to sync temp file: {}", e)))?;
+        drop(temp_file);
+
+        tokio::fs::rename(&temp_path, path)
+            .await
+            .map_err(|e| HookError::Execution(format!("Failed to replace file: {}", e)))?;
+        guard.persist();
+
+        Ok(format!(
+            "File written atomically: {} ({} bytes)",
+            path.display(),
+            content.len()
+        ))
+    }
+
     /// Append content to a file.
     async fn append_to_file(&self, path: &Path, content: &str) -> Result<String, HookError> {
         tracing::info!("Appending to file: {}", path.display());
@@ -252,29 +504,58 @@ impl FileSystemExecutor {
     }
 
     /// Recursively copy a directory.
+    ///
+    /// Directories are created first, parents before children, then files are
+    /// copied with up to `copy_concurrency` copies in flight. Per-file failures
+    /// are collected and reported together once every copy has been attempted.
     async fn copy_directory_recursive(&self, source: &Path, target: &Path) -> Result<(), HookError> {
-        tokio::fs::create_dir_all(target)
-            .await
-            .map_err(|e| HookError::Execution(format!("Failed to create target directory: {}", e)))?;
+        let mut files = Vec::new();
+        let mut pending_dirs = VecDeque::from([(source.to_path_buf(), target.to_path_buf())]);
 
-        let mut entries = tokio::fs::read_dir(source)
-            .await
-            .map_err(|e| HookError::Execution(format!("Failed to read source directory: {}", e)))?;
+        while let Some((source_dir, target_dir)) = pending_dirs.pop_front() {
+            self.check_path_allowed(&target_dir)?;
+            tokio::fs::create_dir_all(&target_dir)
+                .await
+                .map_err(|e| HookError::Execution(format!("Failed to create target directory: {}", e)))?;
 
-        while let Some(entry) = entries.next_entry()
-            .await
-            .map_err(|e| HookError::Execution(format!("Failed to read directory entry: {}", e)))? {
-            
-            let source_path = entry.path();
-            let target_path = target.join(entry.file_name());
+            let mut entries = tokio::fs::read_dir(&source_dir)
+                .await
+                .map_err(|e| HookError::Execution(format!("Failed to read source directory: {}", e)))?;
 
-            if source_path.is_dir() {
-                self.copy_directory_recursive(&source_path, &target_path).await?;
-            } else {
+            while let Some(entry) = entries.next_entry()
+                .await
+                .map_err(|e| HookError::Execution(format!("Failed to read directory entry: {}", e)))? {
+
+                let source_path = entry.path();
+                let target_path = target_dir.join(entry.file_name());
+
+                if source_path.is_dir() {
+                    pending_dirs.push_back((source_path, target_path));
+                } else {
+                    files.push((source_path, target_path));
+                }
+            }
+        }
+
+        let total_files = files.len();
+        let failures: Vec<String> = stream::iter(files)
+            .map(|(source_path, target_path)| async move {
                 tokio::fs::copy(&source_path, &target_path)
                     .await
-                    .map_err(|e| HookError::Execution(format!("Failed to copy file: {}", e)))?;
-            }
+                    .map_err(|e| format!("{}: {}", source_path.display(), e))
+            })
+            .buffer_unordered(self.copy_concurrency)
+            .filter_map(|result| async move { result.err() })
+            .collect()
+            .await;
+
+        if !failures.is_empty() {
+            return Err(HookError::Execution(format!(
+                "Failed to copy {} of {} files: {}",
+                failures.len(),
+                total_files,
+                failures.join("; ")
+            )));
         }
 
         Ok(())
@@ -330,20 +611,92 @@ impl FileSystemExecutor {
     }
 
     /// Watch a file or directory for changes.
-    async fn watch_file_or_directory(&self, path: &Path) -> Result<String, HookError> {
+    ///
+    /// Collects change events until `window` elapses, `max_events` changes were
+    /// seen, or the watched path itself is removed.
+    #[cfg(feature = "fs-watch")]
+    async fn watch_file_or_directory(
+        &self,
+        path: &Path,
+        window: Duration,
+        max_events: Option<usize>,
+    ) -> Result<String, HookError> {
         tracing::info!("Setting up watch for: {}", path.display());
 
         if !path.exists() {
             return Err(HookError::Execution(format!("Path does not exist: {}", path.display())));
         }
 
-        // For now, we'll simulate file watching
-        // In a real implementation, you would use notify crate or similar
-        tokio::time::sleep(Duration::from_millis(100)).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            // The receiver is gone once the watch has finished
+            let _ = tx.send(event);
+        })
+        .map_err(|e| HookError::Execution(format!("Failed to create file watcher: {}", e)))?;
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|e| HookError::Execution(format!("Failed to watch {}: {}", path.display(), e)))?;
+
+        let deadline = tokio::time::Instant::now() + window;
+        let mut changes: Vec<(ChangeKind, PathBuf)> = Vec::new();
+        let mut last_seen: HashMap<(PathBuf, ChangeKind), Instant> = HashMap::new();
+        let mut root_removed = false;
+
+        'watch: while max_events.is_none_or(|max| changes.len() < max) {
+            let event = match tokio::time::timeout_at(deadline, rx.recv()).await {
+                Ok(Some(Ok(event))) => event,
+                Ok(Some(Err(e))) => {
+                    tracing::warn!("File watch error on {}: {}", path.display(), e);
+                    continue;
+                }
+                Ok(None) | Err(_) => break,
+            };
+
+            for (kind, changed) in ChangeKind::from_event(&event) {
+                let now = Instant::now();
+                let key = (changed.clone(), kind);
+                let debounced = last_seen
+                    .get(&key)
+                    .is_some_and(|seen| now.duration_since(*seen) < self.watch_debounce);
+                last_seen.insert(key, now);
+                if !debounced {
+                    changes.push((kind, changed));
+                }
 
-        Ok(format!(
-            "File system watch established for: {} (Note: This is a simulated watch)",
-            path.display()
+                if !path.exists() {
+                    root_removed = true;
+                    break 'watch;
+                }
+                if max_events.is_some_and(|max| changes.len() >= max) {
+                    break 'watch;
+                }
+            }
+        }
+
+        if changes.is_empty() && !root_removed {
+            return Ok(format!("No changes observed on {}", path.display()));
+        }
+
+        let mut output = format!("Observed {} change events on {}:", changes.len(), path.display());
+        for (kind, changed) in &changes {
+            output.push_str(&format!("\n{} {}", kind.as_str(), changed.display()));
+        }
+        if root_removed {
+            output.push_str("\n(watched path was removed)");
+        }
+        Ok(output)
+    }
+
+    /// Fail the watch: this build has no file system watcher.
+    #[cfg(not(feature = "fs-watch"))]
+    async fn watch_file_or_directory(
+        &self,
+        _path: &Path,
+        _window: Duration,
+        _max_events: Option<usize>,
+    ) -> Result<String, HookError> {
+        Err(HookError::Configuration(
+            "Watch support is not enabled in this build; rebuild codex-core with the `fs-watch` feature".to_string(),
         ))
     }
 
@@ -388,7 +741,7 @@ impl FileSystemExecutor {
                 
                 // Validate permission value (should be valid octal)
                 if let Some(perms) = permissions {
-                    if *perms > 0o777 {
+                    if perms > 0o777 {
                         return Err(HookError::Configuration(
                             "Invalid permissions value (must be <= 0o777)".to_string(),
                         ));
@@ -399,49 +752,45 @@ impl FileSystemExecutor {
         }
 
         // Security check: prevent operations outside of allowed directories
-        if let Ok(canonical_path) = path.canonicalize() {
-            let path_str = canonical_path.to_string_lossy();
-            
-            // Prevent operations on system directories
-            let dangerous_paths = ["/etc", "/bin", "/sbin", "/usr/bin", "/usr/sbin", "/boot"];
-            for dangerous in &dangerous_paths {
-                if path_str.starts_with(dangerous) {
-                    return Err(HookError::Configuration(format!(
-                        "Operation not allowed on system directory: {}",
-                        dangerous
-                    )));
-                }
-            }
+        self.check_path_allowed(path)?;
+        if let Some(target) = target_path {
+            self.check_path_allowed(target)?;
         }
 
         Ok(())
     }
 
-    /// Get file/directory information.
-    async fn get_path_info(&self, path: &Path) -> Result<String, HookError> {
-        if !path.exists() {
-            return Ok(format!("Path does not exist: {}", path.display()));
+    /// Reject paths that escape the allowed roots, or that resolve into protected
+    /// system directories when no roots are configured.
+    ///
+    /// Paths are normalized with [`normalize_path`], so the check also applies to
+    /// files that do not exist yet: their existing parent is resolved instead.
+    fn check_path_allowed(&self, path: &Path) -> Result<(), HookError> {
+        let resolved = normalize_path(path).map_err(|e| {
+            HookError::Configuration(format!("Failed to resolve path {}: {}", path.display(), e))
+        })?;
+
+        if let Some(roots) = &self.allowed_roots {
+            if roots.iter().any(|root| resolved.starts_with(root)) {
+                return Ok(());
+            }
+            return Err(HookError::Configuration(format!(
+                "Path {} resolves outside the allowed roots",
+                path.display()
+            )));
         }
 
-        let metadata = tokio::fs::metadata(path)
-            .await
-            .map_err(|e| HookError::Execution(format!("Failed to get metadata: {}", e)))?;
-
-        let file_type = if metadata.is_dir() {
-            "directory"
-        } else if metadata.is_file() {
-            "file"
-        } else {
-            "other"
-        };
+        // Prevent operations on system directories
+        for dangerous in &DENIED_SYSTEM_PATHS {
+            if resolved.starts_with(dangerous) {
+                return Err(HookError::Configuration(format!(
+                    "Operation not allowed on system directory: {}",
+                    dangerous
+                )));
+            }
+        }
 
-        Ok(format!(
-            "Path: {} | Type: {} | Size: {} bytes | Modified: {:?}",
-            path.display(),
-            file_type,
-            metadata.len(),
-            metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH)
-        ))
+        Ok(())
     }
 }
 
@@ -459,12 +808,13 @@ impl HookExecutor for FileSystemExecutor {
 
     fn can_execute(&self, context: &HookContext) -> bool {
         matches!(context.hook_type, HookType::FileSystem { .. })
+            && event_filter_allows(self.event_filter.as_ref(), context)
     }
 
     async fn execute(&self, context: &HookContext) -> HookExecutorResult {
         let start_time = Instant::now();
 
-        let (operation, path, target_path, content, hook_timeout, permissions) = match &context.hook_type {
+        let request = match &context.hook_type {
             HookType::FileSystem {
                 operation,
                 path,
@@ -472,14 +822,18 @@ impl HookExecutor for FileSystemExecutor {
                 content,
                 timeout,
                 permissions,
-            } => (
+                max_events,
+                atomic,
+            } => FileSystemRequest {
                 operation,
                 path,
-                target_path.as_deref(),
-                content.as_deref(),
-                timeout.unwrap_or(self.default_timeout),
-                *permissions,
-            ),
+                target_path: target_path.as_deref(),
+                content: content.as_deref(),
+                permissions: *permissions,
+                timeout: timeout.unwrap_or(self.default_timeout),
+                max_events: *max_events,
+                atomic: *atomic,
+            },
             _ => {
                 return Ok(HookResult::failure(
                     "Invalid hook type for file system executor".to_string(),
@@ -488,6 +842,16 @@ impl HookExecutor for FileSystemExecutor {
             }
         };
 
+        let FileSystemRequest {
+            operation,
+            path,
+            target_path,
+            content,
+            permissions,
+            timeout: hook_timeout,
+            ..
+        } = request;
+
         // Validate configuration
         if let Err(e) = self.validate_config(operation, path, target_path, content, permissions) {
             return Ok(HookResult::failure(
@@ -503,9 +867,10 @@ impl HookExecutor for FileSystemExecutor {
         );
 
         // Execute the file system operation with timeout
+        let operation_timeout = hook_timeout;
         let operation_result = timeout(
-            hook_timeout,
-            self.execute_filesystem_operation(operation, path, target_path, content, permissions),
+            operation_timeout,
+            self.execute_filesystem_operation(&request),
         )
         .await;
 
@@ -521,9 +886,9 @@ impl HookExecutor for FileSystemExecutor {
                 Ok(HookResult::failure(e.to_string(), duration))
             }
             Err(_) => {
-                tracing::error!("File system operation timed out after {:?}", hook_timeout);
+                tracing::error!("File system operation timed out after {:?}", operation_timeout);
                 Ok(HookResult::failure(
-                    format!("File system operation timed out after {:?}", hook_timeout),
+                    format!("File system operation timed out after {:?}", operation_timeout),
                     duration,
                 ))
             }
@@ -534,21 +899,31 @@ impl HookExecutor for FileSystemExecutor {
         Some(Duration::from_secs(2))
     }
 
+    fn is_retryable(&self, error: &HookError) -> bool {
+        match error {
+            HookError::Configuration(_) | HookError::Validation(_) => false,
+            HookError::Io(e) => e.kind() != std::io::ErrorKind::PermissionDenied,
+            HookError::Execution(message) => !message.to_lowercase().contains("permission denied"),
+            _ => true,
+        }
+    }
+
     fn default_config(&self) -> ExecutionConfig {
         ExecutionConfig {
             timeout: self.default_timeout,
             max_retries: 1,
             isolated: false, // File operations might need access to the file system
-            mode: crate::hooks::types::HookExecutionMode::Sync, // File operations are typically synchronous
+            mode: crate::hooks::types::HookExecutionMode::Blocking, // File operations are typically synchronous
+            ..ExecutionConfig::default()
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
-    use crate::hooks::config::HookConfig;
-    use crate::hooks::types::{HookExecutionMode, HookPriority, LifecycleEvent, LifecycleEventType};
+    use crate::hooks::types::LifecycleEvent;
     use tempfile::TempDir;
 
     fn create_filesystem_context(
@@ -560,7 +935,7 @@ mod tests {
         let event = LifecycleEvent::SessionEnd {
             session_id: "test_session".to_string(),
             duration: Duration::from_secs(10),
-            timestamp: std::time::SystemTime::now(),
+            timestamp: chrono::Utc::now(),
         };
 
         let hook_type = HookType::FileSystem {
@@ -570,26 +945,67 @@ mod tests {
             content,
             timeout: Some(Duration::from_secs(5)),
             permissions: Some(0o644),
+            max_events: None,
+            atomic: false,
         };
 
-        let config = HookConfig {
-            id: Some("test_fs_hook".to_string()),
-            event: LifecycleEventType::SessionEnd,
-            hook_type: hook_type.clone(),
-            mode: HookExecutionMode::Sync,
-            priority: HookPriority::NORMAL,
-            condition: None,
-            blocking: false,
-            required: false,
-            tags: Vec::new(),
-            description: Some("Test file system hook".to_string()),
-            depends_on: Vec::new(),
-            parallel: true,
-            max_retries: 0,
-            timeout: Some(Duration::from_secs(5)),
+        HookContext::new(event, PathBuf::from("/tmp")).with_hook_type(hook_type)
+    }
+
+    #[cfg(feature = "fs-watch")]
+    fn create_watch_context(path: PathBuf, window: Duration, max_events: Option<usize>) -> HookContext {
+        let event = LifecycleEvent::SessionStart {
+            session_id: "test_session".to_string(),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
+        };
+        HookContext::new(event, PathBuf::from("/tmp")).with_hook_type(HookType::FileSystem {
+            operation: FileSystemOperation::Watch,
+            path,
+            target_path: None,
+            content: None,
+            timeout: Some(window),
+            permissions: None,
+            max_events,
+            atomic: false,
+        })
+    }
+
+    fn create_atomic_write_context(path: PathBuf, content: &str) -> HookContext {
+        let event = LifecycleEvent::SessionStart {
+            session_id: "test_session".to_string(),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
+        HookContext::new(event, PathBuf::from("/tmp")).with_hook_type(HookType::FileSystem {
+            operation: FileSystemOperation::Write,
+            path,
+            target_path: None,
+            content: Some(content.to_string()),
+            timeout: Some(Duration::from_secs(5)),
+            permissions: None,
+            max_events: None,
+            atomic: true,
+        })
+    }
+
+    fn temp_files(dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "tmp"))
+            .collect()
+    }
 
-        HookContext::new(event, PathBuf::from("/tmp"), hook_type, config)
+    /// Run `action` shortly after the watch has been established.
+    #[cfg(feature = "fs-watch")]
+    fn after_watch_started(action: impl FnOnce() + Send + 'static) {
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            action();
+        });
     }
 
     #[tokio::test]
@@ -806,6 +1222,98 @@ mod tests {
         assert!(result.error.unwrap().contains("Target path is required"));
     }
 
+    /// Build a nested tree of small files and return the number of files created.
+    fn create_file_tree(root: &Path, dirs: usize, files_per_dir: usize) -> usize {
+        for d in 0..dirs {
+            let dir = root.join(format!("dir_{}", d)).join("nested");
+            std::fs::create_dir_all(&dir).unwrap();
+            for f in 0..files_per_dir {
+                std::fs::write(dir.join(format!("file_{}.txt", f)), format!("{}-{}", d, f)).unwrap();
+            }
+        }
+        dirs * files_per_dir
+    }
+
+    fn count_files(root: &Path) -> usize {
+        let mut count = 0;
+        for entry in std::fs::read_dir(root).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                count += count_files(&path);
+            } else {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[tokio::test]
+    async fn test_parallel_directory_copy_is_complete() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let expected = create_file_tree(&source, 10, 20);
+
+        for concurrency in [1, 16] {
+            let executor = FileSystemExecutor::new().with_copy_concurrency(concurrency);
+            let target = temp_dir.path().join(format!("target_{}", concurrency));
+            executor.copy_directory_recursive(&source, &target).await.unwrap();
+
+            assert_eq!(count_files(&target), expected);
+            let copied = std::fs::read_to_string(target.join("dir_7").join("nested").join("file_13.txt")).unwrap();
+            assert_eq!(copied, "7-13");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_directory_copy_aggregates_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        create_file_tree(&source, 2, 3);
+
+        // Pre-create a directory where a file should land so that copy fails.
+        let target = temp_dir.path().join("target");
+        std::fs::create_dir_all(target.join("dir_0").join("nested").join("file_1.txt")).unwrap();
+
+        let executor = FileSystemExecutor::new().with_copy_concurrency(4);
+        let error = executor.copy_directory_recursive(&source, &target).await.unwrap_err();
+        assert!(error.to_string().contains("Failed to copy 1 of 6 files"));
+
+        // The remaining files are still copied.
+        assert_eq!(count_files(&target.join("dir_1")), 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "timing-sensitive benchmark"]
+    async fn bench_parallel_directory_copy_reduces_wall_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let expected = create_file_tree(&source, 50, 40);
+
+        let start = std::time::Instant::now();
+        FileSystemExecutor::new()
+            .with_copy_concurrency(1)
+            .copy_directory_recursive(&source, &temp_dir.path().join("sequential"))
+            .await
+            .unwrap();
+        let sequential = start.elapsed();
+
+        let start = std::time::Instant::now();
+        FileSystemExecutor::new()
+            .with_copy_concurrency(32)
+            .copy_directory_recursive(&source, &temp_dir.path().join("parallel"))
+            .await
+            .unwrap();
+        let parallel = start.elapsed();
+
+        assert_eq!(count_files(&temp_dir.path().join("parallel")), expected);
+        assert!(
+            parallel < sequential,
+            "parallel copy ({:?}) was not faster than sequential copy ({:?})",
+            parallel,
+            sequential
+        );
+    }
+
     #[test]
     fn test_filesystem_operation_serialization() {
         let operation = FileSystemOperation::Create;
@@ -815,4 +1323,325 @@ mod tests {
         let deserialized: FileSystemOperation = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, FileSystemOperation::Create);
     }
+
+    #[test]
+    fn test_normalize_path_without_existing_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().canonicalize().unwrap();
+
+        let path = temp_dir.path().join("missing/./nested/../file.txt");
+        assert_eq!(normalize_path(&path).unwrap(), base.join("missing/file.txt"));
+    }
+
+    #[test]
+    fn test_create_under_denied_root_is_blocked() {
+        let executor = FileSystemExecutor::new();
+        let content = Some("data");
+
+        for path in ["/etc/codex-hooks-test/new.conf", "/tmp/../etc/codex-hooks-test.conf"] {
+            let path = Path::new(path);
+            let result = executor.validate_config(&FileSystemOperation::Write, path, None, content, None);
+            assert!(matches!(result, Err(HookError::Configuration(_))), "{} should be denied", path.display());
+            assert!(!path.exists());
+        }
+
+        // Copy targets are checked as well
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.txt");
+        fs::write(&source, "data").unwrap();
+        let result = executor.validate_config(
+            &FileSystemOperation::Copy,
+            &source,
+            Some(Path::new("/etc/codex-hooks-copy.txt")),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_through_symlink_to_denied_root_is_blocked() {
+        let executor = FileSystemExecutor::new();
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("config");
+        std::os::unix::fs::symlink("/etc", &link).unwrap();
+
+        let path = link.join("codex-hooks-test/new.conf");
+        let result = executor.validate_config(&FileSystemOperation::Create, &path, None, None, None);
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+
+        let allowed = temp_dir.path().join("logs/new.log");
+        assert!(executor.validate_config(&FileSystemOperation::Write, &allowed, None, Some("ok"), None).is_ok());
+    }
+
+    #[cfg(feature = "fs-watch")]
+    #[tokio::test]
+    async fn test_watch_returns_after_max_events() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let context = create_watch_context(root.clone(), Duration::from_secs(10), Some(2));
+
+        let dir = root.clone();
+        after_watch_started(move || {
+            fs::write(dir.join("a.txt"), "a").unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            fs::write(dir.join("b.txt"), "b").unwrap();
+        });
+
+        let start = Instant::now();
+        let result = FileSystemExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let output = result.output.unwrap();
+        assert!(output.starts_with("Observed 2 change events"), "{}", output);
+        assert!(output.contains(&format!("created {}", root.join("a.txt").display())), "{}", output);
+    }
+
+    #[cfg(feature = "fs-watch")]
+    #[tokio::test]
+    async fn test_watch_without_changes_succeeds_at_window_end() {
+        let temp_dir = TempDir::new().unwrap();
+        let hook_timeout = Duration::from_millis(200);
+        let context = create_watch_context(temp_dir.path().to_path_buf(), hook_timeout, None);
+
+        // The manager applies the same timeout around the executor; the watch must end first
+        let result = timeout(hook_timeout, FileSystemExecutor::new().execute(&context))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.unwrap().starts_with("No changes observed"));
+    }
+
+    #[test]
+    fn test_watch_window_leaves_margin_before_hook_timeout() {
+        assert_eq!(watch_window(Duration::from_millis(200)), Duration::from_millis(180));
+        assert_eq!(watch_window(Duration::from_secs(30)), Duration::from_secs(29));
+        assert_eq!(watch_window(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[cfg(feature = "fs-watch")]
+    #[tokio::test]
+    async fn test_watch_debounces_rapid_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let file = root.join("log.txt");
+        fs::write(&file, "").unwrap();
+        let context = create_watch_context(root, Duration::from_millis(1500), None);
+
+        let target = file.clone();
+        after_watch_started(move || {
+            for i in 0..5 {
+                fs::write(&target, format!("line {}", i)).unwrap();
+            }
+        });
+
+        let executor = FileSystemExecutor::new().with_watch_debounce(Duration::from_secs(5));
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let output = result.output.unwrap();
+        let modified = format!("modified {}", file.display());
+        assert_eq!(output.lines().filter(|line| *line == modified).count(), 1, "{}", output);
+    }
+
+    #[cfg(feature = "fs-watch")]
+    #[tokio::test]
+    async fn test_watch_ends_when_watched_path_is_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let watched = temp_dir.path().canonicalize().unwrap().join("watched");
+        fs::create_dir(&watched).unwrap();
+        fs::write(watched.join("file.txt"), "data").unwrap();
+        let context = create_watch_context(watched.clone(), Duration::from_secs(10), None);
+
+        let dir = watched.clone();
+        after_watch_started(move || fs::remove_dir_all(dir).unwrap());
+
+        let start = Instant::now();
+        let result = FileSystemExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.output.unwrap().ends_with("(watched path was removed)"));
+    }
+
+    #[test]
+    fn test_allowed_roots_accept_paths_inside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("workspace");
+        fs::create_dir(&root).unwrap();
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![root.clone()]);
+
+        let new_file = root.join("logs/new.log");
+        assert!(executor.validate_config(&FileSystemOperation::Write, &new_file, None, Some("ok"), None).is_ok());
+        let inner_dotdot = root.join("logs/../other.log");
+        assert!(executor.validate_config(&FileSystemOperation::Create, &inner_dotdot, None, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_roots_reject_dotdot_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("workspace");
+        fs::create_dir(&root).unwrap();
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![root.clone()]);
+
+        let escape = root.join("../outside.txt");
+        let result = executor.validate_config(&FileSystemOperation::Write, &escape, None, Some("data"), None);
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+
+        // A sibling sharing the root's name as a prefix is not inside it
+        let sibling = temp_dir.path().join("workspace-other/file.txt");
+        let result = executor.validate_config(&FileSystemOperation::Create, &sibling, None, None, None);
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+
+        // Copy targets are checked as well
+        let source = root.join("source.txt");
+        fs::write(&source, "data").unwrap();
+        let result = executor.validate_config(
+            &FileSystemOperation::Copy,
+            &source,
+            Some(&root.join("../../copy.txt")),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_allowed_roots_reject_symlink_escape() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("workspace");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir(&root).unwrap();
+        fs::create_dir(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![root.clone()]);
+
+        // Neither the new file nor its parent exist inside the root once the link is resolved
+        let path = root.join("link/nested/new.txt");
+        let result = executor.validate_config(&FileSystemOperation::Create, &path, None, None, None);
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+        assert!(!outside.join("nested").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_directory_copy_checks_subdirectories_before_creating_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("workspace");
+        let outside = temp_dir.path().join("outside");
+        let source = temp_dir.path().join("source");
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::create_dir(&outside).unwrap();
+        fs::create_dir_all(source.join("link/nested")).unwrap();
+        fs::write(source.join("link/nested/file.txt"), "data").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("target/link")).unwrap();
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![root.clone()]);
+
+        let result = executor.copy_directory_recursive(&source, &root.join("target")).await;
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+        assert!(!outside.join("nested").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_allowed_roots_resolve_symlinked_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let real = temp_dir.path().join("real");
+        fs::create_dir(&real).unwrap();
+        let link = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![link]);
+
+        let path = real.join("file.txt");
+        assert!(executor.validate_config(&FileSystemOperation::Write, &path, None, Some("ok"), None).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_roots_replace_deny_list() {
+        let executor = FileSystemExecutor::new().with_allowed_roots(vec![PathBuf::from("/etc")]);
+        let path = Path::new("/etc/codex-hooks-test/new.conf");
+        assert!(executor.validate_config(&FileSystemOperation::Write, path, None, Some("data"), None).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write_replaces_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "old = true").unwrap();
+
+        let context = create_atomic_write_context(path.clone(), "new = true");
+        let result = FileSystemExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new = true");
+        assert!(temp_files(temp_dir.path()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_atomic_write_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("script.sh");
+        fs::write(&path, "echo old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+
+        let context = create_atomic_write_context(path.clone(), "echo new");
+        let result = FileSystemExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o750);
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write_failure_removes_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        // Renaming a file over a non-empty directory fails
+        let path = temp_dir.path().join("occupied");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("inner.txt"), "data").unwrap();
+
+        let result = FileSystemExecutor::new().write_file_atomic(&path, "data").await;
+        assert!(matches!(result, Err(HookError::Execution(_))));
+        assert!(temp_files(temp_dir.path()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_interrupted_atomic_write_keeps_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "original").unwrap();
+        let content = "x".repeat(16 * 1024 * 1024);
+
+        let executor = FileSystemExecutor::new();
+        let mut write = Box::pin(executor.write_file_atomic(&path, &content));
+        // Drive the write until its temp file exists, then abandon it mid-way
+        while temp_files(temp_dir.path()).is_empty() {
+            assert!(
+                futures::poll!(write.as_mut()).is_pending(),
+                "write finished before it could be interrupted"
+            );
+            tokio::task::yield_now().await;
+        }
+        drop(write);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+        assert!(temp_files(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_permission_denied_is_not_retryable() {
+        let executor = FileSystemExecutor::new();
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert!(!executor.is_retryable(&HookError::Io(denied)));
+        assert!(!executor.is_retryable(&HookError::Execution(
+            "Failed to write file: Permission denied (os error 13)".to_string()
+        )));
+        assert!(!executor.is_retryable(&HookError::Configuration("bad path".to_string())));
+
+        let busy = std::io::Error::from(std::io::ErrorKind::WouldBlock);
+        assert!(executor.is_retryable(&HookError::Io(busy)));
+        assert!(executor.is_retryable(&HookError::Execution("disk busy".to_string())));
+    }
 }
@@ -1,6 +1,6 @@
 //! MCP tool executor for calling MCP tools as hooks.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
@@ -8,18 +8,21 @@ use serde_json::{json, Value};
 use tracing::{debug, error, info, warn};
 
 use crate::hooks::context::HookContext;
-use crate::hooks::executor::{ExecutionConfig, HookExecutor, HookExecutorResult};
-use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookResult, HookType};
+use crate::hooks::executor::{event_filter_allows, ExecutionConfig, HookExecutor, HookExecutorResult};
+use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookResult, HookType, LifecycleEventType};
 
 /// Executor for calling MCP tools as hooks.
 #[derive(Debug, Clone)]
 pub struct McpToolExecutor {
     /// Default timeout for MCP tool calls.
+    #[allow(dead_code)] // Will be applied once tool calls go to a real MCP server.
     default_timeout: Duration,
     /// Maximum response size to capture.
     max_response_size: usize,
     /// Default server configuration.
     default_server: Option<String>,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
 }
 
 impl Default for McpToolExecutor {
@@ -35,6 +38,7 @@ impl McpToolExecutor {
             default_timeout: Duration::from_secs(60),
             max_response_size: 1024 * 1024, // 1MB default
             default_server: None,
+            event_filter: None,
         }
     }
 
@@ -44,6 +48,7 @@ impl McpToolExecutor {
             default_timeout: timeout,
             max_response_size: 1024 * 1024,
             default_server: None,
+            event_filter: None,
         }
     }
 
@@ -59,6 +64,12 @@ impl McpToolExecutor {
         self
     }
 
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
     /// Extract MCP tool configuration from hook context.
     fn extract_mcp_config(&self, context: &HookContext) -> Result<McpConfig, HookError> {
         match &context.hook_type {
@@ -194,7 +205,6 @@ impl McpToolExecutor {
             result: result.result,
             error: result.error,
             duration,
-            arguments,
         })
     }
 
@@ -320,6 +330,7 @@ impl HookExecutor for McpToolExecutor {
 
     fn can_execute(&self, context: &HookContext) -> bool {
         matches!(context.hook_type, HookType::McpTool { .. })
+            && event_filter_allows(self.event_filter.as_ref(), context)
     }
 
     fn estimated_duration(&self) -> Option<Duration> {
@@ -335,6 +346,7 @@ impl HookExecutor for McpToolExecutor {
             max_retries: 2,
             retry_delay: Duration::from_secs(2),
             isolated: true,
+            total_deadline: None,
         }
     }
 
@@ -386,8 +398,6 @@ struct McpResult {
     error: Option<String>,
     /// Duration of the tool call.
     duration: Duration,
-    /// Arguments that were passed to the tool.
-    arguments: HashMap<String, Value>,
 }
 
 /// Simulated MCP result (placeholder).
@@ -400,6 +410,7 @@ struct SimulatedMcpResult {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use crate::hooks::types::LifecycleEvent;
     use std::path::PathBuf;
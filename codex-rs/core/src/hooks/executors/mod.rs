@@ -2,22 +2,26 @@
 
 pub mod custom_plugin;
 pub mod database;
+pub mod desktop_notification;
+pub mod executable;
 pub mod filesystem;
 pub mod mcp;
 pub mod message_queue;
+pub mod patch_analysis;
+pub mod resource_limits;
 pub mod script;
 pub mod webhook;
 
 pub use custom_plugin::CustomPluginExecutor;
 pub use database::DatabaseExecutor;
+pub use desktop_notification::DesktopNotificationExecutor;
+pub use executable::ExecutableExecutor;
 pub use filesystem::FileSystemExecutor;
 pub use mcp::McpToolExecutor;
 pub use message_queue::MessageQueueExecutor;
-pub use script::ScriptExecutor;
+pub use patch_analysis::PatchAnalysisExecutor;
+pub use script::{OutputLineCallback, OutputStream, ScriptExecutor};
 pub use webhook::WebhookExecutor;
 
-// Re-export the ExecutableExecutor from the executor module
-pub use crate::hooks::executor::ExecutableExecutor;
-
 #[cfg(test)]
 mod tests;
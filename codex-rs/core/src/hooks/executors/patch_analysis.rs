@@ -0,0 +1,524 @@
+//! Patch analysis hook executor for summarizing applied patches.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::hooks::context::HookContext;
+use crate::hooks::executor::{event_filter_allows, ExecutionConfig, HookExecutor, HookExecutorResult};
+use crate::hooks::types::{HookError, HookExecutionMode, HookResult, HookType, LifecycleEvent, LifecycleEventType};
+
+/// Context metadata key holding the unified diff to analyze.
+pub const PATCH_METADATA_KEY: &str = "patch";
+
+/// Result metadata key holding the computed patch statistics.
+pub const PATCH_STATS_METADATA_KEY: &str = "patch_stats";
+
+/// Patches kept while waiting for their `PatchAfter` event.
+const MAX_PENDING_PATCHES: usize = 64;
+
+/// How a file was changed by a patch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    /// The file was created.
+    Added,
+    /// The file was removed.
+    Deleted,
+    /// The file was edited in place.
+    Modified,
+}
+
+/// Statistics for a single file touched by a patch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileChangeStats {
+    /// Path of the file relative to the repository root.
+    pub path: String,
+    /// How the file was changed.
+    pub change: FileChangeKind,
+    /// Number of lines added.
+    pub lines_added: usize,
+    /// Number of lines removed.
+    pub lines_removed: usize,
+    /// Language inferred from the file extension, if known.
+    pub language: Option<String>,
+}
+
+/// Aggregate statistics for a unified diff.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PatchStats {
+    /// Per-file statistics in patch order.
+    pub files: Vec<FileChangeStats>,
+    /// Total lines added across all files.
+    pub lines_added: usize,
+    /// Total lines removed across all files.
+    pub lines_removed: usize,
+    /// Distinct languages affected, sorted by name.
+    pub languages: Vec<String>,
+}
+
+impl PatchStats {
+    /// Compute statistics from a unified diff (plain or `git diff` output).
+    pub fn from_unified_diff(patch: &str) -> Self {
+        let mut files: Vec<FileChangeStats> = Vec::new();
+        let mut current: Option<FileChangeStats> = None;
+        let mut seen_hunk = false;
+        let mut old_remaining = 0usize;
+        let mut new_remaining = 0usize;
+
+        for line in patch.lines() {
+            // Inside a hunk, lines are classified by their first character
+            if old_remaining > 0 || new_remaining > 0 {
+                if let Some(file) = current.as_mut() {
+                    match line.chars().next() {
+                        Some('+') => {
+                            file.lines_added += 1;
+                            new_remaining = new_remaining.saturating_sub(1);
+                            continue;
+                        }
+                        Some('-') => {
+                            file.lines_removed += 1;
+                            old_remaining = old_remaining.saturating_sub(1);
+                            continue;
+                        }
+                        Some(' ') | None => {
+                            old_remaining = old_remaining.saturating_sub(1);
+                            new_remaining = new_remaining.saturating_sub(1);
+                            continue;
+                        }
+                        Some('\\') => continue,
+                        Some(_) => {
+                            // Truncated hunk; fall through to header parsing
+                            old_remaining = 0;
+                            new_remaining = 0;
+                        }
+                    }
+                }
+            }
+
+            if let Some(rest) = line.strip_prefix("diff --git ") {
+                files.extend(current.take());
+                seen_hunk = false;
+                let path = rest.split_whitespace().last().map(strip_diff_prefix).unwrap_or_default();
+                current = Some(new_file_stats(path, FileChangeKind::Modified));
+            } else if let Some(rest) = line.strip_prefix("--- ") {
+                if current.is_none() || seen_hunk {
+                    files.extend(current.take());
+                    seen_hunk = false;
+                    current = Some(new_file_stats(String::new(), FileChangeKind::Modified));
+                }
+                if let Some(file) = current.as_mut() {
+                    match diff_header_path(rest) {
+                        Some(path) if file.path.is_empty() => file.path = path,
+                        Some(_) => {}
+                        None => file.change = FileChangeKind::Added,
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("+++ ") {
+                if let Some(file) = current.as_mut() {
+                    match diff_header_path(rest) {
+                        Some(path) => file.path = path,
+                        None => file.change = FileChangeKind::Deleted,
+                    }
+                }
+            } else if line.starts_with("new file mode") {
+                if let Some(file) = current.as_mut() {
+                    file.change = FileChangeKind::Added;
+                }
+            } else if line.starts_with("deleted file mode") {
+                if let Some(file) = current.as_mut() {
+                    file.change = FileChangeKind::Deleted;
+                }
+            } else if line.starts_with("@@") {
+                if let Some((old_count, new_count)) = parse_hunk_header(line) {
+                    seen_hunk = true;
+                    old_remaining = old_count;
+                    new_remaining = new_count;
+                }
+            }
+        }
+        files.extend(current.take());
+
+        for file in &mut files {
+            file.language = language_for_path(&file.path).map(str::to_string);
+        }
+
+        let languages: BTreeSet<String> = files.iter().filter_map(|f| f.language.clone()).collect();
+
+        Self {
+            lines_added: files.iter().map(|f| f.lines_added).sum(),
+            lines_removed: files.iter().map(|f| f.lines_removed).sum(),
+            languages: languages.into_iter().collect(),
+            files,
+        }
+    }
+}
+
+/// Create empty statistics for a file.
+fn new_file_stats(path: String, change: FileChangeKind) -> FileChangeStats {
+    FileChangeStats {
+        path,
+        change,
+        lines_added: 0,
+        lines_removed: 0,
+        language: None,
+    }
+}
+
+/// Path from a `---`/`+++` header line, or `None` for `/dev/null`.
+fn diff_header_path(rest: &str) -> Option<String> {
+    // Plain diffs may append a tab-separated timestamp
+    let path = rest.split('\t').next().unwrap_or(rest).trim();
+    if path == "/dev/null" {
+        None
+    } else {
+        Some(strip_diff_prefix(path))
+    }
+}
+
+/// Strip the `a/` or `b/` prefix used by `git diff`.
+fn strip_diff_prefix(path: &str) -> String {
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Parse the old and new line counts from a `@@ -l,s +l,s @@` header.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let mut parts = line.split_whitespace().skip(1);
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+
+    let count = |range: &str| -> Option<usize> {
+        match range.split_once(',') {
+            Some((_, count)) => count.parse().ok(),
+            None => Some(1),
+        }
+    };
+
+    Some((count(old)?, count(new)?))
+}
+
+/// Language name for a file path, inferred from its extension.
+fn language_for_path(path: &str) -> Option<&'static str> {
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "Rust",
+        "py" => "Python",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "ts" | "tsx" => "TypeScript",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "sh" | "bash" | "zsh" => "Shell",
+        "html" | "htm" => "HTML",
+        "css" | "scss" => "CSS",
+        "md" => "Markdown",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        "sql" => "SQL",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Join the per-file changes of a `PatchBefore` event into one diff, in path order.
+pub fn patch_from_changes(changes: &HashMap<PathBuf, String>) -> String {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| changes[path].trim_end_matches('\n'))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Patches announced by `PatchBefore` events, held until the matching `PatchAfter`.
+#[derive(Debug, Default)]
+pub struct PendingPatches {
+    patches: Mutex<VecDeque<(String, String)>>,
+}
+
+impl PendingPatches {
+    /// Patch to expose to hooks handling `event`.
+    ///
+    /// `PatchBefore` changes are remembered by call id so the `PatchAfter` for the same
+    /// call can be analyzed; other events carry no patch.
+    pub fn patch_for_event(&self, event: &LifecycleEvent) -> Option<String> {
+        let Ok(mut patches) = self.patches.lock() else {
+            return None;
+        };
+        match event {
+            LifecycleEvent::PatchBefore { call_id, changes, .. } => {
+                let patch = patch_from_changes(changes);
+                patches.retain(|(pending, _)| pending != call_id);
+                if patches.len() >= MAX_PENDING_PATCHES {
+                    patches.pop_front();
+                }
+                patches.push_back((call_id.clone(), patch.clone()));
+                Some(patch)
+            }
+            LifecycleEvent::PatchAfter { call_id, .. } => {
+                let index = patches.iter().position(|(pending, _)| pending == call_id)?;
+                patches.remove(index).map(|(_, patch)| patch)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Executor that computes structured statistics for an applied patch.
+#[derive(Debug, Clone)]
+pub struct PatchAnalysisExecutor {
+    /// Default timeout for patch analysis.
+    default_timeout: Duration,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
+}
+
+impl PatchAnalysisExecutor {
+    /// Create a new patch analysis executor.
+    pub fn new() -> Self {
+        Self {
+            default_timeout: Duration::from_secs(5),
+            event_filter: None,
+        }
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
+    /// Read the unified diff supplied in the hook context.
+    fn patch_from_context<'a>(&self, context: &'a HookContext) -> Result<&'a str, HookError> {
+        context
+            .get_metadata(PATCH_METADATA_KEY)
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| {
+                HookError::Context(format!(
+                    "Patch analysis requires the patch in context metadata under '{}'",
+                    PATCH_METADATA_KEY
+                ))
+            })
+    }
+}
+
+impl Default for PatchAnalysisExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HookExecutor for PatchAnalysisExecutor {
+    fn executor_type(&self) -> &'static str {
+        "patch_analysis"
+    }
+
+    fn can_execute(&self, context: &HookContext) -> bool {
+        matches!(context.hook_type, HookType::PatchAnalysis { .. })
+            && context.event.event_type() == LifecycleEventType::PatchAfter
+            && event_filter_allows(self.event_filter.as_ref(), context)
+    }
+
+    async fn execute(&self, context: &HookContext) -> HookExecutorResult {
+        let start_time = Instant::now();
+
+        let patch = match self.patch_from_context(context) {
+            Ok(patch) => patch,
+            Err(e) => return Ok(HookResult::failure(e.to_string(), start_time.elapsed())),
+        };
+
+        let stats = PatchStats::from_unified_diff(patch);
+        tracing::info!(
+            "Analyzed patch: {} files, +{} -{}",
+            stats.files.len(),
+            stats.lines_added,
+            stats.lines_removed
+        );
+
+        let stats_value = serde_json::to_value(&stats)?;
+        Ok(HookResult::success(Some(stats_value.to_string()), start_time.elapsed())
+            .with_metadata(PATCH_STATS_METADATA_KEY.to_string(), stats_value))
+    }
+
+    fn estimated_duration(&self) -> Option<Duration> {
+        Some(Duration::from_millis(10))
+    }
+
+    fn default_config(&self) -> ExecutionConfig {
+        ExecutionConfig {
+            timeout: self.default_timeout,
+            mode: HookExecutionMode::Async,
+            isolated: false,
+            ..ExecutionConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const SAMPLE_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,5 @@
+ use std::fmt;
+-use std::io;
++use std::io::{self, Write};
++use std::path::Path;
+
+ fn main() {}
+@@ -10,3 +11,2 @@ impl Foo {
+-    // --- old comment
+     let x = 1;
+     let y = 2;
+diff --git a/scripts/build.py b/scripts/build.py
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/scripts/build.py
+@@ -0,0 +1,3 @@
++import sys
++
++print(sys.argv)
+diff --git a/docs/OLD.md b/docs/OLD.md
+deleted file mode 100644
+index 4444444..0000000
+--- a/docs/OLD.md
++++ /dev/null
+@@ -1,2 +0,0 @@
+-# Old
+-Removed docs.
+";
+
+    fn create_patch_context(patch: Option<&str>) -> HookContext {
+        let event = LifecycleEvent::PatchAfter {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            success: true,
+            applied_files: vec![PathBuf::from("src/lib.rs")],
+            duration: Duration::from_millis(5),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let context = HookContext::new(event, PathBuf::from("/tmp"))
+            .with_hook_type(HookType::PatchAnalysis { timeout: None });
+        match patch {
+            Some(patch) => context.with_metadata(PATCH_METADATA_KEY.to_string(), patch.into()),
+            None => context,
+        }
+    }
+
+    #[test]
+    fn test_patch_stats_from_unified_diff() {
+        let stats = PatchStats::from_unified_diff(SAMPLE_DIFF);
+
+        assert_eq!(stats.files.len(), 3);
+        assert_eq!(stats.lines_added, 5);
+        assert_eq!(stats.lines_removed, 4);
+        assert_eq!(stats.languages, vec!["Markdown", "Python", "Rust"]);
+
+        let lib = &stats.files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(lib.change, FileChangeKind::Modified);
+        assert_eq!((lib.lines_added, lib.lines_removed), (2, 2));
+        assert_eq!(lib.language.as_deref(), Some("Rust"));
+
+        let build = &stats.files[1];
+        assert_eq!(build.path, "scripts/build.py");
+        assert_eq!(build.change, FileChangeKind::Added);
+        assert_eq!((build.lines_added, build.lines_removed), (3, 0));
+
+        let docs = &stats.files[2];
+        assert_eq!(docs.path, "docs/OLD.md");
+        assert_eq!(docs.change, FileChangeKind::Deleted);
+        assert_eq!((docs.lines_added, docs.lines_removed), (0, 2));
+    }
+
+    #[test]
+    fn test_patch_stats_plain_diff_without_git_headers() {
+        let patch = "\
+--- config.toml\t2024-01-01 00:00:00
++++ config.toml\t2024-01-02 00:00:00
+@@ -1 +1 @@
+-debug = false
++debug = true
+";
+        let stats = PatchStats::from_unified_diff(patch);
+
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(stats.files[0].path, "config.toml");
+        assert_eq!(stats.files[0].change, FileChangeKind::Modified);
+        assert_eq!((stats.lines_added, stats.lines_removed), (1, 1));
+        assert_eq!(stats.languages, vec!["TOML"]);
+    }
+
+    #[tokio::test]
+    async fn test_patch_analysis_executor_returns_stats() {
+        let executor = PatchAnalysisExecutor::new();
+        let context = create_patch_context(Some(SAMPLE_DIFF));
+        assert!(executor.can_execute(&context));
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+
+        let stats: PatchStats =
+            serde_json::from_value(result.metadata[PATCH_STATS_METADATA_KEY].clone()).unwrap();
+        assert_eq!(stats, PatchStats::from_unified_diff(SAMPLE_DIFF));
+        assert_eq!(stats.files.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_patch_analysis_requires_patch_metadata() {
+        let executor = PatchAnalysisExecutor::new();
+        let context = create_patch_context(None);
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains(PATCH_METADATA_KEY));
+    }
+
+    #[test]
+    fn test_pending_patches_carry_before_changes_to_after() {
+        let pending = PendingPatches::default();
+        let before = LifecycleEvent::PatchBefore {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            changes: HashMap::from([
+                (PathBuf::from("src/lib.rs"), "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n fn a() {}\n+fn b() {}\n".to_string()),
+                (PathBuf::from("README.md"), "--- a/README.md\n+++ b/README.md\n@@ -1 +0,0 @@\n-old\n".to_string()),
+            ]),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let patch = pending.patch_for_event(&before).unwrap();
+        let stats = PatchStats::from_unified_diff(&patch);
+        assert_eq!(stats.files.len(), 2);
+        assert_eq!(stats.files[0].path, "README.md");
+        assert_eq!((stats.lines_added, stats.lines_removed), (1, 1));
+
+        let after = create_patch_context(None).event;
+        assert_eq!(pending.patch_for_event(&after), Some(patch));
+        // Each patch is handed to a single `PatchAfter`
+        assert_eq!(pending.patch_for_event(&after), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! Enforcement of resource limits on spawned hook processes.
+
+use std::process::ExitStatus;
+
+use tokio::process::Command;
+
+use crate::hooks::types::ResourceLimits;
+
+/// Prefix of failure messages for processes stopped by a resource limit.
+pub const RESOURCE_LIMIT_ERROR_PREFIX: &str = "Resource limit exceeded";
+
+/// Stderr phrases of processes that died because an allocation failed.
+const ALLOCATION_FAILURE_MARKERS: &[&str] = &[
+    "out of memory",
+    "cannot allocate",
+    "memory exhausted",
+    "memoryerror",
+    "bad_alloc",
+    "failed to allocate",
+];
+
+/// Apply `limits` to `command` so they take effect before the process starts.
+///
+/// On Linux the limits become `setrlimit` limits and, with a cgroup, a move into
+/// that cgroup. Other platforms only log a warning and rely on the hook timeout.
+pub fn apply(limits: &ResourceLimits, command: &mut Command) {
+    if limits.is_empty() {
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let max_memory_bytes = limits.max_memory_bytes;
+        let max_cpu_seconds = limits.max_cpu_seconds;
+        let cgroup_procs = limits
+            .cgroup
+            .as_ref()
+            .and_then(|cgroup| std::ffi::CString::new(cgroup.join("cgroup.procs").as_os_str().as_bytes()).ok());
+
+        // SAFETY: the closure runs between fork and exec and only makes
+        // async-signal-safe system calls, without allocating.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(cgroup_procs) = &cgroup_procs {
+                    linux::join_cgroup(cgroup_procs)?;
+                }
+                if let Some(bytes) = max_memory_bytes {
+                    linux::check(libc::setrlimit(libc::RLIMIT_AS, &linux::rlimit(bytes, bytes)))?;
+                }
+                if let Some(seconds) = max_cpu_seconds {
+                    // SIGXCPU at the soft limit, SIGKILL a second later for processes ignoring it
+                    linux::check(libc::setrlimit(
+                        libc::RLIMIT_CPU,
+                        &linux::rlimit(seconds, seconds.saturating_add(1)),
+                    ))?;
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = command;
+        tracing::warn!("Resource limits are only enforced on Linux; relying on the hook timeout");
+    }
+}
+
+/// Describe the limit that stopped a process, if its exit was caused by one.
+pub fn violation(limits: &ResourceLimits, status: &ExitStatus, stderr: &str) -> Option<String> {
+    if limits.is_empty() || status.success() {
+        return None;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        let memory_limited = limits.max_memory_bytes.is_some() || limits.cgroup.is_some();
+        match (status.signal(), limits.max_cpu_seconds) {
+            (Some(libc::SIGXCPU | libc::SIGKILL), Some(seconds)) => {
+                return Some(format!("{}: CPU time limit of {}s", RESOURCE_LIMIT_ERROR_PREFIX, seconds));
+            }
+            (Some(libc::SIGKILL | libc::SIGSEGV | libc::SIGABRT), _) if memory_limited => {
+                return Some(memory_violation(limits));
+            }
+            _ => {}
+        }
+
+        let stderr = stderr.to_lowercase();
+        if memory_limited && ALLOCATION_FAILURE_MARKERS.iter().any(|marker| stderr.contains(marker)) {
+            return Some(memory_violation(limits));
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = stderr;
+
+    None
+}
+
+/// Failure message for a process that ran out of memory under `limits`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn memory_violation(limits: &ResourceLimits) -> String {
+    match limits.max_memory_bytes {
+        Some(bytes) => format!("{}: memory limit of {} bytes", RESOURCE_LIMIT_ERROR_PREFIX, bytes),
+        None => format!("{}: cgroup memory limit", RESOURCE_LIMIT_ERROR_PREFIX),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::ffi::CStr;
+    use std::io;
+
+    /// A limit with the given soft and hard values.
+    pub(super) fn rlimit(soft: u64, hard: u64) -> libc::rlimit {
+        libc::rlimit {
+            rlim_cur: soft as libc::rlim_t,
+            rlim_max: hard as libc::rlim_t,
+        }
+    }
+
+    /// Turn a libc return code into an error carrying `errno`.
+    pub(super) fn check(result: libc::c_int) -> io::Result<()> {
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Move the calling process into the cgroup owning `cgroup_procs`.
+    pub(super) fn join_cgroup(cgroup_procs: &CStr) -> io::Result<()> {
+        // SAFETY: `cgroup_procs` is a valid C string and the descriptor is closed before returning.
+        unsafe {
+            let fd = libc::open(cgroup_procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // Writing 0 moves the writer itself
+            let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+            let error = io::Error::last_os_error();
+            libc::close(fd);
+            if written != 1 {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    async fn run_limited(script: &str, limits: &ResourceLimits) -> (ExitStatus, String) {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script).stderr(std::process::Stdio::piped());
+        apply(limits, &mut command);
+        let output = command.output().await.unwrap();
+        (output.status, String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_cpu_limit_stops_busy_loop() {
+        let limits = ResourceLimits {
+            max_cpu_seconds: Some(1),
+            ..Default::default()
+        };
+        let (status, stderr) = run_limited("while :; do :; done", &limits).await;
+        assert_eq!(
+            violation(&limits, &status, &stderr).as_deref(),
+            Some("Resource limit exceeded: CPU time limit of 1s")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ordinary_failures_are_not_limit_violations() {
+        let limits = ResourceLimits {
+            max_memory_bytes: Some(512 * 1024 * 1024),
+            max_cpu_seconds: Some(5),
+            cgroup: None,
+        };
+        let (status, stderr) = run_limited("echo oops >&2; exit 3", &limits).await;
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(violation(&limits, &status, &stderr), None);
+    }
+}
@@ -1,19 +1,63 @@
 //! Script executor for running shell scripts and commands as hooks.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
 use tracing::{debug, error, info, warn};
 
-use crate::hooks::context::HookContext;
-use crate::hooks::executor::{ExecutionConfig, HookExecutor, HookExecutorResult};
-use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookResult, HookType};
+use crate::hooks::context::{HookContext, TemplateEscape, TemplateSubstitution};
+use crate::hooks::executor::{
+    event_filter_allows, with_output_streams, CapturedOutput, ExecutionConfig, HookExecutor, HookExecutorResult,
+    OUTPUT_TRUNCATED_MARKER,
+};
+use crate::hooks::executors::executable::EXIT_CODE_METADATA_KEY;
+use crate::hooks::executors::resource_limits;
+use crate::hooks::secrets::EnvCapturePolicy;
+use crate::hooks::types::{
+    HookError, HookExecutionMode, HookPriority, HookResult, HookType, LifecycleEventType, ResourceLimits,
+};
+
+/// Output stream a script line was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Longest line streamed to an [`OutputLineCallback`]; the rest of a longer line is dropped.
+pub const MAX_STREAMED_LINE_LENGTH: usize = 64 * 1024;
+
+/// Appended to a streamed line cut at [`MAX_STREAMED_LINE_LENGTH`].
+pub const LINE_TRUNCATED_MARKER: &str = "... [line truncated]";
+
+/// Callback receiving each line of script output as soon as it is read.
+#[derive(Clone)]
+pub struct OutputLineCallback(Arc<dyn Fn(OutputStream, &str) + Send + Sync>);
+
+impl OutputLineCallback {
+    /// Wrap a function called with each output line, without its trailing newline.
+    ///
+    /// Lines longer than [`MAX_STREAMED_LINE_LENGTH`] are cut and end in [`LINE_TRUNCATED_MARKER`].
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(OutputStream, &str) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+}
+
+impl std::fmt::Debug for OutputLineCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OutputLineCallback")
+    }
+}
 
 /// Executor for running shell scripts and commands.
 #[derive(Debug, Clone)]
@@ -24,8 +68,16 @@ pub struct ScriptExecutor {
     pub default_working_dir: Option<PathBuf>,
     /// Environment variables to always include.
     pub base_environment: HashMap<String, String>,
-    /// Maximum output size to capture (in bytes).
+    /// Maximum bytes captured from each of stdout and stderr.
     pub max_output_size: usize,
+    /// Attach raw output as base64 `output_data` metadata when it is not valid UTF-8.
+    pub binary_output: bool,
+    /// Event types this executor handles (all events when unset).
+    pub event_filter: Option<HashSet<LifecycleEventType>>,
+    /// Receives output lines while the script runs, enabling streaming mode.
+    pub line_callback: Option<OutputLineCallback>,
+    /// Which environment variables `${env.*}` templates may expand, and which are redacted.
+    pub env_policy: EnvCapturePolicy,
 }
 
 impl Default for ScriptExecutor {
@@ -48,6 +100,10 @@ impl ScriptExecutor {
             default_working_dir: None,
             base_environment: HashMap::new(),
             max_output_size: 1024 * 1024, // 1MB default
+            binary_output: false,
+            event_filter: None,
+            line_callback: None,
+            env_policy: EnvCapturePolicy::default(),
         }
     }
 
@@ -71,25 +127,62 @@ impl ScriptExecutor {
         self
     }
 
-    /// Set maximum output size to capture.
+    /// Set the maximum bytes captured from each of stdout and stderr.
     pub fn with_max_output_size(mut self, size: usize) -> Self {
         self.max_output_size = size;
         self
     }
 
+    /// Preserve non-UTF-8 output as base64 in result metadata.
+    pub fn with_binary_output(mut self, enabled: bool) -> Self {
+        self.binary_output = enabled;
+        self
+    }
+
+    /// Stream output line by line to `callback` while the script runs.
+    ///
+    /// In streaming mode stdout and stderr share the `max_output_size` cap, and lines past it are
+    /// still forwarded but no longer buffered.
+    pub fn with_output_streaming(mut self, callback: OutputLineCallback) -> Self {
+        self.line_callback = Some(callback);
+        self
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
+    /// Control which environment variables `${env.*}` templates expand.
+    pub fn with_env_policy(mut self, env_policy: EnvCapturePolicy) -> Self {
+        self.env_policy = env_policy;
+        self
+    }
+
     /// Extract script configuration from hook context.
     fn extract_script_config(&self, context: &HookContext) -> Result<ScriptConfig, HookError> {
         match &context.hook_type {
-            HookType::Script { command, cwd, environment, timeout: _ } => {
+            HookType::Script { command, cwd, environment, resource_limits, .. } => {
                 if command.is_empty() {
                     return Err(HookError::Configuration("Script command cannot be empty".to_string()));
                 }
 
+                // Quote substituted values so event data cannot inject shell syntax; cmd.exe
+                // cannot quote safely, so values carrying its syntax are refused instead
+                let escape = if cfg!(windows) { TemplateEscape::Cmd } else { TemplateEscape::Shell };
+                let templates = TemplateSubstitution::from_context(context).with_env_policy(&self.env_policy);
+                let command = command
+                    .iter()
+                    .map(|arg| templates.render(arg, escape))
+                    .collect::<Result<Vec<_>, _>>()?;
+
                 Ok(ScriptConfig {
-                    command: command.clone(),
+                    command,
                     environment: environment.clone(),
                     working_dir: cwd.clone(),
                     shell: self.default_shell.clone(),
+                    resource_limits: resource_limits.clone().unwrap_or_default(),
                 })
             }
             _ => Err(HookError::Configuration(
@@ -155,6 +248,8 @@ impl ScriptExecutor {
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
         cmd.stdin(Stdio::null());
+        cmd.kill_on_drop(true);
+        resource_limits::apply(&config.resource_limits, &mut cmd);
 
         // Spawn the process
         let mut child = cmd.spawn().map_err(|e| {
@@ -170,8 +265,16 @@ impl ScriptExecutor {
         })?;
 
         // Read output streams
-        let stdout_task = tokio::spawn(Self::read_stream(stdout, self.max_output_size));
-        let stderr_task = tokio::spawn(Self::read_stream(stderr, self.max_output_size));
+        let (stdout_task, stderr_task) = match &self.line_callback {
+            Some(callback) => (
+                tokio::spawn(Self::stream_lines(stdout, OutputStream::Stdout, callback.clone(), self.max_output_size)),
+                tokio::spawn(Self::stream_lines(stderr, OutputStream::Stderr, callback.clone(), self.max_output_size)),
+            ),
+            None => (
+                tokio::spawn(CapturedOutput::read_limited(stdout, self.max_output_size)),
+                tokio::spawn(CapturedOutput::read_limited(stderr, self.max_output_size)),
+            ),
+        };
 
         // Wait for process completion
         let exit_status = child.wait().await.map_err(|e| {
@@ -189,6 +292,7 @@ impl ScriptExecutor {
         let duration = start_time.elapsed();
         let exit_code = exit_status.code().unwrap_or(-1);
         let success = exit_status.success();
+        let limit_exceeded = resource_limits::violation(&config.resource_limits, &exit_status, &stderr_output.text);
 
         debug!(
             "Script execution completed: exit_code={}, success={}, duration={:?}",
@@ -201,32 +305,72 @@ impl ScriptExecutor {
             stdout: stdout_output,
             stderr: stderr_output,
             duration,
-            command: config.command,
-            working_dir,
+            limit_exceeded,
         })
     }
 
-    /// Read from a stream with size limit.
-    async fn read_stream<R>(reader: R, max_size: usize) -> Result<String, HookError>
+    /// Forward each line to `callback` as it is read, buffering up to `max_size` bytes of the stream.
+    ///
+    /// The stream is drained to the end so a chatty script never blocks on a full pipe.
+    async fn stream_lines<R>(
+        reader: R,
+        stream: OutputStream,
+        callback: OutputLineCallback,
+        max_size: usize,
+    ) -> Result<CapturedOutput, HookError>
     where
         R: tokio::io::AsyncRead + Unpin,
     {
         let mut buf_reader = BufReader::new(reader);
-        let mut output = String::new();
-        let mut line = String::new();
-
-        while buf_reader.read_line(&mut line).await.map_err(|e| {
-            HookError::Execution(format!("Failed to read line: {}", e))
-        })? > 0 {
-            if output.len() + line.len() > max_size {
-                output.push_str("... [output truncated due to size limit]\n");
-                break;
+        let mut output = Vec::new();
+        let mut line = Vec::new();
+        let mut truncated = false;
+        let read_error = |e: std::io::Error| HookError::Execution(format!("Failed to read line: {}", e));
+
+        while (&mut buf_reader)
+            .take(MAX_STREAMED_LINE_LENGTH as u64)
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(read_error)?
+            > 0
+        {
+            if line.len() == MAX_STREAMED_LINE_LENGTH && !line.ends_with(b"\n") {
+                // Drop the rest of the line without buffering it
+                loop {
+                    let available = buf_reader.fill_buf().await.map_err(read_error)?;
+                    if available.is_empty() {
+                        break;
+                    }
+                    match available.iter().position(|&byte| byte == b'\n') {
+                        Some(end) => {
+                            buf_reader.consume(end + 1);
+                            break;
+                        }
+                        None => {
+                            let len = available.len();
+                            buf_reader.consume(len);
+                        }
+                    }
+                }
+                line.extend_from_slice(LINE_TRUNCATED_MARKER.as_bytes());
+                line.push(b'\n');
+            }
+
+            let text = String::from_utf8_lossy(&line);
+            (callback.0)(stream, text.trim_end_matches(['\n', '\r']));
+
+            if !truncated {
+                if output.len() + line.len() > max_size {
+                    output.extend_from_slice(OUTPUT_TRUNCATED_MARKER.as_bytes());
+                    truncated = true;
+                } else {
+                    output.extend_from_slice(&line);
+                }
             }
-            output.push_str(&line);
             line.clear();
         }
 
-        Ok(output)
+        Ok(CapturedOutput { truncated, ..CapturedOutput::from_bytes(output) })
     }
 }
 
@@ -246,36 +390,50 @@ impl HookExecutor for ScriptExecutor {
         // Execute the script
         match self.execute_script(script_config, environment).await {
             Ok(result) => {
-                if result.success {
+                let hook_result = if result.success {
                     info!(
                         "Script hook executed successfully: exit_code={}, duration={:?}",
                         result.exit_code, result.duration
                     );
 
-                    let output = if !result.stdout.is_empty() {
-                        Some(result.stdout)
+                    let captured = if !result.stdout.is_empty() {
+                        Some(&result.stdout)
                     } else if !result.stderr.is_empty() {
-                        Some(result.stderr)
+                        Some(&result.stderr)
                     } else {
-                        Some(format!("Script completed with exit code {}", result.exit_code))
+                        None
                     };
 
-                    Ok(HookResult::success(output, start_time.elapsed()))
+                    match captured {
+                        Some(captured) => captured.annotate(
+                            HookResult::success(Some(captured.text.clone()), start_time.elapsed()),
+                            self.binary_output,
+                        ),
+                        None => HookResult::success(
+                            Some(format!("Script completed with exit code {}", result.exit_code)),
+                            start_time.elapsed(),
+                        ),
+                    }
                 } else {
                     warn!(
                         "Script hook failed: exit_code={}, stderr={}",
                         result.exit_code,
-                        result.stderr.trim()
+                        result.stderr.text.trim()
                     );
 
-                    let error_msg = if !result.stderr.is_empty() {
-                        format!("Script failed with exit code {}: {}", result.exit_code, result.stderr.trim())
+                    let error_msg = if let Some(limit_exceeded) = &result.limit_exceeded {
+                        limit_exceeded.clone()
+                    } else if !result.stderr.is_empty() {
+                        format!("Script failed with exit code {}: {}", result.exit_code, result.stderr.text.trim())
                     } else {
                         format!("Script failed with exit code {}", result.exit_code)
                     };
 
-                    Ok(HookResult::failure(error_msg, start_time.elapsed()))
-                }
+                    result.stderr.annotate(HookResult::failure(error_msg, start_time.elapsed()), self.binary_output)
+                };
+
+                Ok(with_output_streams(hook_result, &result.stdout, &result.stderr)
+                    .with_metadata(EXIT_CODE_METADATA_KEY.to_string(), serde_json::json!(result.exit_code)))
             }
             Err(e) => {
                 error!("Script hook execution error: {}", e);
@@ -290,6 +448,7 @@ impl HookExecutor for ScriptExecutor {
 
     fn can_execute(&self, context: &HookContext) -> bool {
         matches!(context.hook_type, HookType::Script { .. })
+            && event_filter_allows(self.event_filter.as_ref(), context)
     }
 
     fn estimated_duration(&self) -> Option<Duration> {
@@ -305,6 +464,7 @@ impl HookExecutor for ScriptExecutor {
             max_retries: 1,
             retry_delay: Duration::from_millis(500),
             isolated: true,
+            total_deadline: None,
         }
     }
 
@@ -348,6 +508,8 @@ struct ScriptConfig {
     working_dir: Option<PathBuf>,
     /// Shell to use for execution.
     shell: String,
+    /// Limits on the spawned process.
+    resource_limits: ResourceLimits,
 }
 
 /// Result of script execution.
@@ -358,22 +520,22 @@ struct ScriptResult {
     /// Whether the script executed successfully.
     success: bool,
     /// Standard output from the script.
-    stdout: String,
+    stdout: CapturedOutput,
     /// Standard error from the script.
-    stderr: String,
+    stderr: CapturedOutput,
     /// Duration of script execution.
     duration: Duration,
-    /// Command that was executed.
-    command: Vec<String>,
-    /// Working directory where script was executed.
-    working_dir: PathBuf,
+    /// Resource limit that stopped the script, if any.
+    limit_exceeded: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use crate::hooks::types::LifecycleEvent;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     fn create_test_context(command: Vec<String>) -> HookContext {
         let event = LifecycleEvent::SessionStart {
@@ -388,6 +550,7 @@ mod tests {
             cwd: None,
             environment: HashMap::new(),
             timeout: None,
+            resource_limits: None,
         };
 
         HookContext::new(event, PathBuf::from("/tmp"))
@@ -424,11 +587,7 @@ mod tests {
     #[tokio::test]
     async fn test_successful_script_execution() {
         let executor = ScriptExecutor::new();
-        let command = if cfg!(windows) {
-            vec!["echo".to_string(), "Hello World".to_string()]
-        } else {
-            vec!["echo".to_string(), "Hello World".to_string()]
-        };
+        let command = vec!["echo".to_string(), "Hello World".to_string()];
         let context = create_test_context(command);
 
         let result = executor.execute(&context).await.unwrap();
@@ -454,6 +613,200 @@ mod tests {
         assert!(result.error.is_some());
     }
 
+    #[tokio::test]
+    async fn test_exit_code_recorded_in_metadata() {
+        let executor = ScriptExecutor::new();
+
+        let result = executor.execute(&create_test_context(vec!["exit".to_string(), "2".to_string()])).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(2));
+
+        let result = executor.execute(&create_test_context(vec!["exit".to_string(), "0".to_string()])).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.metadata[EXIT_CODE_METADATA_KEY], serde_json::json!(0));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_memory_limit_stops_memory_hungry_script() {
+        let mut context = create_test_context(vec![
+            "x=$(head -c 300000000 /dev/zero | tr '\\0' a);".to_string(),
+            "echo survived".to_string(),
+        ]);
+        if let HookType::Script { resource_limits, .. } = &mut context.hook_type {
+            *resource_limits = Some(ResourceLimits {
+                max_memory_bytes: Some(64 * 1024 * 1024),
+                ..Default::default()
+            });
+        }
+
+        let result = ScriptExecutor::new().execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(!result.output.unwrap_or_default().contains("survived"));
+        let error = result.error.unwrap();
+        assert!(
+            error.starts_with("Resource limit exceeded: memory limit of 67108864 bytes"),
+            "{}",
+            error
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_non_utf8_output_is_flagged_and_captured() {
+        use crate::hooks::executor::{OUTPUT_DATA_METADATA_KEY, OUTPUT_LOSSY_METADATA_KEY};
+
+        let context = create_test_context(vec!["printf".to_string(), r"'ok\377\376'".to_string()]);
+
+        let result = ScriptExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("ok\u{FFFD}\u{FFFD}"));
+        assert_eq!(result.metadata.get(OUTPUT_LOSSY_METADATA_KEY), Some(&serde_json::json!(true)));
+        assert!(!result.metadata.contains_key(OUTPUT_DATA_METADATA_KEY));
+
+        let result = ScriptExecutor::new().with_binary_output(true).execute(&context).await.unwrap();
+        assert_eq!(result.metadata.get(OUTPUT_LOSSY_METADATA_KEY), Some(&serde_json::json!(true)));
+        // base64 of b"ok\xff\xfe"
+        assert_eq!(result.metadata.get(OUTPUT_DATA_METADATA_KEY), Some(&serde_json::json!("b2v//g==")));
+
+        let context = create_test_context(vec!["echo".to_string(), "plain".to_string()]);
+        let result = ScriptExecutor::new().with_binary_output(true).execute(&context).await.unwrap();
+        assert!(!result.metadata.contains_key(OUTPUT_LOSSY_METADATA_KEY));
+        assert!(!result.metadata.contains_key(OUTPUT_DATA_METADATA_KEY));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_delivers_lines_incrementally() {
+        use std::sync::Mutex;
+
+        let start = Instant::now();
+        let received: Arc<Mutex<Vec<(Duration, OutputStream, String)>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let executor = ScriptExecutor::new().with_output_streaming(OutputLineCallback::new(move |stream, line| {
+            sink.lock().unwrap().push((start.elapsed(), stream, line.to_string()));
+        }));
+        let context = create_test_context(vec![
+            "for i in 1 2 3; do echo line$i; sleep 0.3; done; echo done >&2".to_string(),
+        ]);
+
+        let result = executor.execute(&context).await.unwrap();
+        let finished = start.elapsed();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("line1\nline2\nline3\n"));
+
+        let received = received.lock().unwrap();
+        let lines: Vec<_> = received.iter().map(|(_, stream, line)| (*stream, line.as_str())).collect();
+        assert_eq!(
+            lines,
+            vec![
+                (OutputStream::Stdout, "line1"),
+                (OutputStream::Stdout, "line2"),
+                (OutputStream::Stdout, "line3"),
+                (OutputStream::Stderr, "done"),
+            ]
+        );
+        // The first line arrives while the script is still sleeping, not at exit
+        assert!(received[0].0 + Duration::from_millis(500) < finished);
+        assert!(received[1].0 > received[0].0 + Duration::from_millis(200));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streams_are_captured_separately_and_truncated() {
+        use crate::hooks::executor::{STDERR_METADATA_KEY, STDOUT_METADATA_KEY};
+
+        let context = create_test_context(vec!["echo out; echo warn >&2".to_string()]);
+        let result = ScriptExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.as_deref(), Some("out\n"));
+        assert_eq!(result.metadata[STDOUT_METADATA_KEY], serde_json::json!("out\n"));
+        assert_eq!(result.metadata[STDERR_METADATA_KEY], serde_json::json!("warn\n"));
+
+        let context = create_test_context(vec!["printf 0123456789abcdef; printf 0123456789abcdef >&2".to_string()]);
+        let result = ScriptExecutor::new().with_max_output_size(10).execute(&context).await.unwrap();
+        assert!(result.success);
+        let truncated = serde_json::json!(format!("0123456789\n{}", OUTPUT_TRUNCATED_MARKER));
+        assert_eq!(result.metadata[STDOUT_METADATA_KEY], truncated);
+        assert_eq!(result.metadata[STDERR_METADATA_KEY], truncated);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_caps_buffer_but_forwards_every_line() {
+        let forwarded = Arc::new(AtomicUsize::new(0));
+        let counter = forwarded.clone();
+        let executor = ScriptExecutor::new()
+            .with_max_output_size(64)
+            .with_output_streaming(OutputLineCallback::new(move |_, _| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }));
+        let context = create_test_context(vec!["seq 1 1000".to_string()]);
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(forwarded.load(Ordering::Relaxed), 1000);
+        let output = result.output.unwrap();
+        assert!(output.starts_with("1\n2\n"));
+        assert!(output.ends_with("... [output truncated due to size limit]\n"));
+        assert!(output.len() < 64 + 64);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_cuts_overlong_lines() {
+        use std::sync::Mutex;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let executor = ScriptExecutor::new().with_output_streaming(OutputLineCallback::new(move |_, line| {
+            sink.lock().unwrap().push(line.to_string());
+        }));
+        let command = format!("head -c {} /dev/zero | tr '\\0' x; echo; echo after", MAX_STREAMED_LINE_LENGTH * 3);
+        let context = create_test_context(vec![command]);
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].len(), MAX_STREAMED_LINE_LENGTH + LINE_TRUNCATED_MARKER.len());
+        assert!(received[0].ends_with(LINE_TRUNCATED_MARKER));
+        assert_eq!(received[1], "after");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_caps_each_stream_separately() {
+        use crate::hooks::executor::{STDERR_METADATA_KEY, STDOUT_METADATA_KEY};
+
+        let executor = ScriptExecutor::new()
+            .with_max_output_size(16)
+            .with_output_streaming(OutputLineCallback::new(|_, _| {}));
+        let context = create_test_context(vec!["echo 0123456789; echo 0123456789 >&2".to_string()]);
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.metadata[STDOUT_METADATA_KEY], serde_json::json!("0123456789\n"));
+        assert_eq!(result.metadata[STDERR_METADATA_KEY], serde_json::json!("0123456789\n"));
+    }
+
+    #[test]
+    fn test_env_templates_follow_capture_policy() {
+        let context = create_test_context(vec!["echo".to_string(), "${env.API_TOKEN}/${env.BUILD_ID}".to_string()])
+            .with_env("API_TOKEN".to_string(), "tok-123".to_string())
+            .with_env("BUILD_ID".to_string(), "42".to_string());
+
+        let config = ScriptExecutor::new().extract_script_config(&context).unwrap();
+        assert!(!config.command.join(" ").contains("tok-123"));
+        assert!(config.command[1].contains(crate::hooks::secrets::REDACTED_VALUE));
+        assert!(config.command[1].contains("42"));
+
+        let policy = EnvCapturePolicy { allow: vec!["API_*".to_string()], ..Default::default() };
+        let executor = ScriptExecutor::new().with_env_policy(policy);
+        let err = executor.extract_script_config(&context).unwrap_err();
+        assert!(err.to_string().contains("env.BUILD_ID"));
+    }
+
     #[tokio::test]
     async fn test_script_environment_variables() {
         let executor = ScriptExecutor::new();
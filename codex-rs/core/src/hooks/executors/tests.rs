@@ -1,22 +1,25 @@
 //! Unit tests for individual hook executors.
 
+#![allow(clippy::unwrap_used)]
+
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use crate::hooks::config::HookConfig;
 use crate::hooks::context::HookContext;
-use crate::hooks::executor::HookExecutor;
+use crate::hooks::executor::{ExecutionConfig, ExecutionContext, HookExecutor};
 use crate::hooks::executors::{ScriptExecutor, WebhookExecutor, McpToolExecutor};
 use crate::hooks::types::{
-    HookExecutionMode, HookPriority, HookType, LifecycleEvent, HttpMethod,
+    HookExecutionMode, HookType, LifecycleEvent, HttpMethod,
 };
 
 /// Helper function to create a test context for script execution.
 fn create_script_context(command: Vec<String>) -> HookContext {
     let event = LifecycleEvent::SessionStart {
         session_id: "test_session".to_string(),
-        user_id: Some("test_user".to_string()),
+        model: "test-model".to_string(),
+        cwd: PathBuf::from("/tmp"),
+        timestamp: chrono::Utc::now(),
     };
     
     let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp"));
@@ -26,28 +29,19 @@ fn create_script_context(command: Vec<String>) -> HookContext {
         cwd: None,
         environment: HashMap::new(),
         timeout: Some(Duration::from_secs(5)),
+        resource_limits: None,
     };
     
-    let config = HookConfig {
-        event: "session.start".to_string(),
-        hook_type: hook_type.clone(),
-        description: Some("Test script hook".to_string()),
-        enabled: true,
-        required: false,
-        timeout: Some(Duration::from_secs(10)),
-        mode: HookExecutionMode::Async,
-        priority: HookPriority::NORMAL,
-        conditions: HashMap::new(),
-    };
-    
-    HookContext::new(event, working_dir, hook_type, config)
+    HookContext::new(event, working_dir).with_hook_type(hook_type)
 }
 
 /// Helper function to create a test context for webhook execution.
 fn create_webhook_context(url: String, method: HttpMethod) -> HookContext {
     let event = LifecycleEvent::SessionStart {
         session_id: "test_session".to_string(),
-        user_id: Some("test_user".to_string()),
+        model: "test-model".to_string(),
+        cwd: PathBuf::from("/tmp"),
+        timestamp: chrono::Utc::now(),
     };
     
     let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp"));
@@ -58,28 +52,24 @@ fn create_webhook_context(url: String, method: HttpMethod) -> HookContext {
         headers: HashMap::new(),
         timeout: Some(Duration::from_secs(10)),
         retry_count: Some(3),
+        payload_mode: crate::hooks::types::WebhookPayloadMode::Raw,
+        success_criteria: None,
+        signing: None,
+        success_when: None,
+        capture: None,
+        body: None,
     };
     
-    let config = HookConfig {
-        event: "session.start".to_string(),
-        hook_type: hook_type.clone(),
-        description: Some("Test webhook hook".to_string()),
-        enabled: true,
-        required: false,
-        timeout: Some(Duration::from_secs(15)),
-        mode: HookExecutionMode::Async,
-        priority: HookPriority::NORMAL,
-        conditions: HashMap::new(),
-    };
-    
-    HookContext::new(event, working_dir, hook_type, config)
+    HookContext::new(event, working_dir).with_hook_type(hook_type)
 }
 
 /// Helper function to create a test context for MCP tool execution.
 fn create_mcp_context(server: String, tool: String) -> HookContext {
     let event = LifecycleEvent::SessionStart {
         session_id: "test_session".to_string(),
-        user_id: Some("test_user".to_string()),
+        model: "test-model".to_string(),
+        cwd: PathBuf::from("/tmp"),
+        timestamp: chrono::Utc::now(),
     };
     
     let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp"));
@@ -90,19 +80,7 @@ fn create_mcp_context(server: String, tool: String) -> HookContext {
         timeout: Some(Duration::from_secs(15)),
     };
     
-    let config = HookConfig {
-        event: "session.start".to_string(),
-        hook_type: hook_type.clone(),
-        description: Some("Test MCP hook".to_string()),
-        enabled: true,
-        required: false,
-        timeout: Some(Duration::from_secs(20)),
-        mode: HookExecutionMode::Async,
-        priority: HookPriority::NORMAL,
-        conditions: HashMap::new(),
-    };
-    
-    HookContext::new(event, working_dir, hook_type, config)
+    HookContext::new(event, working_dir).with_hook_type(hook_type)
 }
 
 #[cfg(test)]
@@ -208,15 +186,21 @@ mod script_executor_tests {
             *timeout = Some(Duration::from_secs(1));
         }
         
+        // The timeout is enforced by the execution layer around the executor
+        let config = ExecutionConfig {
+            timeout: Duration::from_secs(1),
+            max_retries: 0,
+            ..ExecutionConfig::default()
+        };
+        let exec_context = ExecutionContext::new(context, config);
+        
         let start = std::time::Instant::now();
-        let result = executor.execute(&context).await;
+        let result = executor.execute_with_context(&exec_context).await;
         let duration = start.elapsed();
         
-        assert!(result.is_ok());
-        let hook_result = result.unwrap();
-        
         // Should fail due to timeout
-        assert!(!hook_result.success);
+        assert!(!result.result.success);
+        assert!(result.error_details.unwrap().contains("timed out"));
         // Should not take much longer than the timeout
         assert!(duration < Duration::from_secs(3));
     }
@@ -317,7 +301,7 @@ mod mcp_executor_tests {
         let executor = McpToolExecutor::new();
         let context = create_mcp_context(
             "test_server".to_string(),
-            "test_tool".to_string(),
+            "log_event".to_string(),
         );
         
         let result = executor.execute(&context).await;
@@ -352,7 +336,6 @@ mod mcp_executor_tests {
 #[cfg(test)]
 mod executor_trait_tests {
     use super::*;
-    use crate::hooks::executor::ExecutionConfig;
 
     #[tokio::test]
     async fn test_all_executors_implement_trait() {
@@ -387,8 +370,8 @@ mod executor_trait_tests {
         assert!(mcp_config.timeout > Duration::from_secs(0));
         
         // All should have retry capabilities
-        assert!(script_config.max_retries >= 0);
-        assert!(webhook_config.max_retries >= 0);
-        assert!(mcp_config.max_retries >= 0);
+        assert!(script_config.max_retries > 0);
+        assert!(webhook_config.max_retries > 0);
+        assert!(mcp_config.max_retries > 0);
     }
 }
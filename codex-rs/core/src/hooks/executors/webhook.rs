@@ -1,30 +1,273 @@
 //! Webhook executor for sending HTTP requests as hooks.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use regex::Regex;
 use reqwest::{Client, Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sha2::Sha256;
 use tracing::{debug, error, info, warn};
 
-use crate::hooks::context::HookContext;
-use crate::hooks::executor::{ExecutionConfig, HookExecutor, HookExecutorResult};
-use crate::hooks::types::{HookError, HookExecutionMode, HookPriority, HookResult, HookType};
+use crate::hooks::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerRegistry, CircuitState};
+use crate::hooks::context::{HookContext, TemplateEscape, TemplateSubstitution};
+use crate::hooks::dedup::{content_hash, PayloadDeduplicator, DEDUPLICATED_METADATA_KEY};
+use crate::hooks::proxy::ProxySettings;
+use crate::hooks::secrets::{EnvCapturePolicy, EnvSecretProvider, SecretProvider};
+use crate::hooks::executor::{
+    event_filter_allows, CapturedOutput, ExecutionConfig, HookExecutor, HookExecutorResult,
+    SERIALIZATION_TIME_METADATA_KEY,
+};
+use crate::hooks::types::{
+    HookError, HookExecutionMode, HookPriority, HookResult, HookType, LifecycleEvent, LifecycleEventType,
+    WebhookPayloadMode, WebhookSigning, WebhookSuccessCriteria,
+};
+
+/// Metadata key holding the response value selected by a webhook's `capture` path.
+pub const CAPTURED_METADATA_KEY: &str = "captured";
+
+/// Metadata key holding the state of a webhook's circuit after a request was refused.
+pub const CIRCUIT_STATE_METADATA_KEY: &str = "circuit_state";
+
+/// Metadata key listing the delays, in milliseconds, waited before each webhook retry.
+pub const RETRY_DELAYS_METADATA_KEY: &str = "retry_delays_ms";
+
+/// Backoff applied between attempts of a failing webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookRetryPolicy {
+    /// Retries made when the hook does not set `retry_count`.
+    pub max_retries: u32,
+    /// Delay ceiling before the first retry; doubled for each later one.
+    pub base_delay: Duration,
+    /// Upper bound on any single delay, including `Retry-After`.
+    pub max_delay: Duration,
+    /// Wait a random duration up to the ceiling ("full jitter") instead of the ceiling itself.
+    pub jitter: bool,
+}
+
+impl Default for WebhookRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl WebhookRetryPolicy {
+    /// Delay ceiling before retry number `attempt` (0-based): `base * 2^attempt`, capped at `max_delay`.
+    pub fn backoff_ceiling(&self, attempt: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(attempt)).min(self.max_delay)
+    }
+
+    /// Delay before retry number `attempt`, preferring the server's `Retry-After` when given.
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+        let ceiling = self.backoff_ceiling(attempt);
+        if self.jitter {
+            let millis = u64::try_from(ceiling.as_millis()).unwrap_or(u64::MAX);
+            Duration::from_millis(rand::rng().random_range(0..=millis))
+        } else {
+            ceiling
+        }
+    }
+}
+
+/// Whether a response status is worth retrying: rate limiting or a server error.
+fn is_retryable_status(status_code: u16) -> bool {
+    status_code == 429 || (500..600).contains(&status_code)
+}
+
+/// Parse a `Retry-After` value given in seconds or as an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Header carrying the request signature when the hook does not name one.
+pub const DEFAULT_SIGNATURE_HEADER: &str = "X-Codex-Signature";
+
+/// Header carrying the signed timestamp when the hook does not name one.
+pub const DEFAULT_SIGNATURE_TIMESTAMP_HEADER: &str = "X-Codex-Timestamp";
+
+/// Sign a webhook body, returning the `sha256=<hex>` header value.
+///
+/// The HMAC-SHA256 covers `<timestamp>.<body>` so a captured request cannot be replayed
+/// with a fresh timestamp.
+pub fn sign_webhook_body(secret: &[u8], timestamp: i64, body: &[u8]) -> Result<String, HookError> {
+    let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(secret)
+        .map_err(|e| HookError::Configuration(format!("Invalid webhook signing secret: {}", e)))?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(format!("sha256={}", hex::encode(mac.finalize().into_bytes())))
+}
+
+/// Schema version of the structured webhook payload.
+pub const WEBHOOK_PAYLOAD_VERSION: &str = "1.0";
+
+/// Versioned webhook payload with an event-specific `data` section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookPayload {
+    /// Payload schema version.
+    pub version: String,
+    /// Type of the lifecycle event.
+    pub event_type: LifecycleEventType,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Session the event belongs to, if known.
+    pub session_id: Option<String>,
+    /// Event-specific fields.
+    pub data: Value,
+}
+
+impl WebhookPayload {
+    /// Build the structured payload for a lifecycle event.
+    pub fn from_event(event: &LifecycleEvent) -> Self {
+        Self {
+            version: WEBHOOK_PAYLOAD_VERSION.to_string(),
+            event_type: event.event_type(),
+            timestamp: event.timestamp(),
+            session_id: event.session_id().map(str::to_string),
+            data: Self::event_data(event),
+        }
+    }
+
+    /// Serialize the payload into a JSON value.
+    pub fn to_value(&self) -> Value {
+        json!({
+            "version": self.version,
+            "event_type": self.event_type,
+            "timestamp": self.timestamp.to_rfc3339(),
+            "session_id": self.session_id,
+            "data": self.data,
+        })
+    }
+
+    /// Event-specific `data` section for each lifecycle event.
+    fn event_data(event: &LifecycleEvent) -> Value {
+        match event {
+            LifecycleEvent::SessionStart { model, cwd, .. } => json!({
+                "model": model,
+                "cwd": cwd.to_string_lossy(),
+            }),
+            LifecycleEvent::SessionEnd { duration, .. } => json!({
+                "duration_ms": duration.as_millis(),
+            }),
+            LifecycleEvent::TaskStart { task_id, prompt, .. } => json!({
+                "task_id": task_id,
+                "prompt": prompt,
+            }),
+            LifecycleEvent::TaskComplete { task_id, success, output, duration, .. } => json!({
+                "task_id": task_id,
+                "success": success,
+                "output": output,
+                "duration_ms": duration.as_millis(),
+            }),
+            LifecycleEvent::ExecBefore { call_id, task_id, command, cwd, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "command": command,
+                "cwd": cwd.to_string_lossy(),
+            }),
+            LifecycleEvent::ExecAfter { call_id, task_id, command, exit_code, stdout, stderr, duration, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "command": command,
+                "exit_code": exit_code,
+                "stdout": stdout,
+                "stderr": stderr,
+                "duration_ms": duration.as_millis(),
+            }),
+            LifecycleEvent::PatchBefore { call_id, task_id, changes, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "changes": changes,
+            }),
+            LifecycleEvent::PatchAfter { call_id, task_id, success, applied_files, duration, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "success": success,
+                "applied_files": applied_files,
+                "duration_ms": duration.as_millis(),
+            }),
+            LifecycleEvent::McpToolBefore { call_id, task_id, server, tool, arguments, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "server": server,
+                "tool": tool,
+                "arguments": arguments,
+            }),
+            LifecycleEvent::McpToolAfter { call_id, task_id, server, tool, success, result, duration, .. } => json!({
+                "call_id": call_id,
+                "task_id": task_id,
+                "server": server,
+                "tool": tool,
+                "success": success,
+                "result": result,
+                "duration_ms": duration.as_millis(),
+            }),
+            LifecycleEvent::AgentMessage { task_id, message, reasoning, .. } => json!({
+                "task_id": task_id,
+                "message": message,
+                "reasoning": reasoning,
+            }),
+            LifecycleEvent::ErrorOccurred { task_id, error, context, .. } => json!({
+                "task_id": task_id,
+                "error": error,
+                "context": context,
+            }),
+        }
+    }
+}
 
 /// Executor for sending HTTP webhook requests.
 #[derive(Debug, Clone)]
 pub struct WebhookExecutor {
     /// HTTP client for making requests.
     client: Client,
-    /// Default timeout for HTTP requests.
-    default_timeout: Duration,
+    /// Overall timeout configured on the HTTP client.
+    client_timeout: Duration,
+    /// Proxy settings the HTTP client was built with.
+    proxy: ProxySettings,
     /// Maximum response size to capture.
     max_response_size: usize,
+    /// Attach the raw response body as base64 `output_data` metadata when it is not valid UTF-8.
+    binary_output: bool,
     /// Default headers to include in all requests.
     default_headers: HashMap<String, String>,
+    /// Event types this executor handles (all events when unset).
+    event_filter: Option<HashSet<LifecycleEventType>>,
+    /// Suppresses identical payloads re-sent to the same endpoint, when enabled.
+    dedup: Option<Arc<PayloadDeduplicator>>,
+    /// Resolves signing secret references.
+    secret_provider: Arc<dyn SecretProvider>,
+    /// Backoff between attempts of a failing webhook.
+    retry_policy: WebhookRetryPolicy,
+    /// Per-URL circuit breakers, when enabled.
+    circuit_breakers: Option<Arc<CircuitBreakerRegistry>>,
+    /// Which environment variables raw payloads carry, and which are redacted.
+    env_policy: EnvCapturePolicy,
 }
 
+/// Generated payload fields that differ between otherwise identical sends.
+const VOLATILE_PAYLOAD_FIELDS: &[&str] = &["/event/timestamp"];
+
 impl Default for WebhookExecutor {
     fn default() -> Self {
         Self::new()
@@ -34,36 +277,59 @@ impl Default for WebhookExecutor {
 impl WebhookExecutor {
     /// Create a new webhook executor with default settings.
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(60))
-            .user_agent("Codex-Hooks/1.0")
-            .build()
-            .expect("Failed to create HTTP client");
-
         Self {
-            client,
-            default_timeout: Duration::from_secs(30),
+            // Only TLS setup can fail without a proxy, and `Client::new` handles that the same way
+            client: Self::build_client(Duration::from_secs(60), &ProxySettings::System)
+                .unwrap_or_else(|_| Client::new()),
+            client_timeout: Duration::from_secs(60),
+            proxy: ProxySettings::System,
             max_response_size: 1024 * 1024, // 1MB default
+            binary_output: false,
             default_headers: HashMap::new(),
+            event_filter: None,
+            dedup: None,
+            secret_provider: Arc::new(EnvSecretProvider),
+            retry_policy: WebhookRetryPolicy::default(),
+            circuit_breakers: None,
+            env_policy: EnvCapturePolicy::default(),
         }
     }
 
     /// Create a webhook executor with custom timeout.
     pub fn with_timeout(timeout: Duration) -> Self {
-        let client = Client::builder()
-            .timeout(timeout)
-            .user_agent("Codex-Hooks/1.0")
-            .build()
-            .expect("Failed to create HTTP client");
-
         Self {
-            client,
-            default_timeout: timeout,
+            client: Self::build_client(timeout, &ProxySettings::System).unwrap_or_else(|_| Client::new()),
+            client_timeout: timeout,
+            proxy: ProxySettings::System,
             max_response_size: 1024 * 1024,
+            binary_output: false,
             default_headers: HashMap::new(),
+            event_filter: None,
+            dedup: None,
+            secret_provider: Arc::new(EnvSecretProvider),
+            retry_policy: WebhookRetryPolicy::default(),
+            circuit_breakers: None,
+            env_policy: EnvCapturePolicy::default(),
         }
     }
 
+    /// Route requests according to `proxy`, rebuilding the HTTP client.
+    pub fn with_proxy(mut self, proxy: ProxySettings) -> Result<Self, HookError> {
+        self.client = Self::build_client(self.client_timeout, &proxy)?;
+        self.proxy = proxy;
+        Ok(self)
+    }
+
+    /// Proxy settings used for webhook requests.
+    pub fn proxy(&self) -> &ProxySettings {
+        &self.proxy
+    }
+
+    /// Build the HTTP client used for webhook requests.
+    fn build_client(timeout: Duration, proxy: &ProxySettings) -> Result<Client, HookError> {
+        proxy.build_client(timeout, "Codex-Hooks/1.0")
+    }
+
     /// Add default headers to include in all requests.
     pub fn with_default_headers(mut self, headers: HashMap<String, String>) -> Self {
         self.default_headers = headers;
@@ -76,14 +342,105 @@ impl WebhookExecutor {
         self
     }
 
+    /// Preserve non-UTF-8 response bodies as base64 in result metadata.
+    pub fn with_binary_output(mut self, enabled: bool) -> Self {
+        self.binary_output = enabled;
+        self
+    }
+
+    /// Suppress identical payloads sent to the same endpoint within `window`.
+    pub fn with_payload_dedup(mut self, window: Duration) -> Self {
+        self.dedup = Some(Arc::new(PayloadDeduplicator::new(window)));
+        self
+    }
+
+    /// Number of sends suppressed as duplicates.
+    pub fn payloads_deduplicated(&self) -> u64 {
+        self.dedup.as_ref().map_or(0, |dedup| dedup.suppressed())
+    }
+
+    /// Back off between webhook attempts according to `policy`.
+    pub fn with_retry_policy(mut self, policy: WebhookRetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Fail fast for URLs whose circuit is open.
+    ///
+    /// After `failure_threshold` consecutive failed firings to a URL (within `failure_window`,
+    /// when set) its circuit opens and sends fail without a request for `reset_timeout`; then
+    /// a single trial request decides whether the circuit closes again.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breakers = Some(Arc::new(CircuitBreakerRegistry::new(config)));
+        self
+    }
+
+    /// State of the circuit for `url`, `Closed` when circuit breaking is disabled or unused.
+    pub fn circuit_state(&self, url: &str) -> CircuitState {
+        self.circuit_breakers.as_ref().map_or(CircuitState::Closed, |breakers| breakers.state(url))
+    }
+
+    /// Resolve signing secrets with the given provider instead of the environment.
+    pub fn with_secret_provider(mut self, provider: Arc<dyn SecretProvider>) -> Self {
+        self.secret_provider = provider;
+        self
+    }
+
+    /// Control which environment variables raw payloads carry.
+    pub fn with_env_policy(mut self, env_policy: EnvCapturePolicy) -> Self {
+        self.env_policy = env_policy;
+        self
+    }
+
+    /// Restrict this executor to the given event types.
+    pub fn with_event_filter<I: IntoIterator<Item = LifecycleEventType>>(mut self, events: I) -> Self {
+        self.event_filter = Some(events.into_iter().collect());
+        self
+    }
+
     /// Extract webhook configuration from hook context.
     fn extract_webhook_config(&self, context: &HookContext) -> Result<WebhookConfig, HookError> {
         match &context.hook_type {
-            HookType::Webhook { url, method, headers, timeout: _, retry_count: _ } => {
+            HookType::Webhook {
+                url,
+                method,
+                headers,
+                timeout: _,
+                retry_count,
+                payload_mode,
+                success_criteria,
+                signing,
+                success_when,
+                capture,
+                body,
+            } => {
                 if url.is_empty() {
                     return Err(HookError::Configuration("Webhook URL cannot be empty".to_string()));
                 }
 
+                // Secret-looking environment values never leave the machine through templates
+                let templates = TemplateSubstitution::from_context(context).with_env_policy(&self.env_policy);
+                let url = templates.render(url, TemplateEscape::Url)?;
+                let body = body.as_ref().map(|body| templates.render_json(body)).transpose()?;
+
+                let body_regex = match success_criteria.as_ref().and_then(|c| c.body_regex.as_deref()) {
+                    Some(pattern) => Some(Regex::new(pattern).map_err(|e| {
+                        HookError::Configuration(format!("Invalid webhook success body_regex: {}", e))
+                    })?),
+                    None => None,
+                };
+
+                let signing = signing.as_ref().map(|signing| self.resolve_signing(signing)).transpose()?;
+                let success_when = success_when.as_deref().map(SuccessExpression::parse).transpose()?;
+                if let Some(path) = capture {
+                    if !path.starts_with('$') {
+                        return Err(HookError::Configuration(format!(
+                            "Webhook capture must be a JSONPath starting with $, got {}",
+                            path
+                        )));
+                    }
+                }
+
                 // Convert HttpMethod to reqwest::Method
                 let http_method = match method {
                     crate::hooks::types::HttpMethod::Get => Method::GET,
@@ -94,11 +451,18 @@ impl WebhookExecutor {
                 };
 
                 Ok(WebhookConfig {
-                    url: url.clone(),
+                    url,
                     method: http_method,
                     headers: headers.clone(),
-                    body: None, // We'll use the generated payload
+                    body,
                     auth: None, // TODO: Add auth support later
+                    payload_mode: *payload_mode,
+                    success_criteria: success_criteria.clone(),
+                    body_regex,
+                    signing,
+                    max_retries: retry_count.unwrap_or(self.retry_policy.max_retries),
+                    success_when,
+                    capture: capture.clone(),
                 })
             }
             _ => Err(HookError::Configuration(
@@ -107,7 +471,31 @@ impl WebhookExecutor {
         }
     }
 
-    /// Build the request payload from hook context.
+    /// Resolve a hook's signing secret and header names.
+    fn resolve_signing(&self, signing: &WebhookSigning) -> Result<RequestSigning, HookError> {
+        let secret = self.secret_provider.resolve(&signing.secret)?;
+        if secret.is_empty() {
+            return Err(HookError::Configuration("Webhook signing secret cannot be empty".to_string()));
+        }
+        Ok(RequestSigning {
+            secret,
+            signature_header: signing.signature_header.clone().unwrap_or_else(|| DEFAULT_SIGNATURE_HEADER.to_string()),
+            timestamp_header: signing
+                .timestamp_header
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SIGNATURE_TIMESTAMP_HEADER.to_string()),
+        })
+    }
+
+    /// Build the request payload in the hook's configured payload mode.
+    fn build_payload_for_mode(&self, context: &HookContext, mode: WebhookPayloadMode) -> Value {
+        match mode {
+            WebhookPayloadMode::Raw => self.build_payload(context),
+            WebhookPayloadMode::Structured => WebhookPayload::from_event(&context.event).to_value(),
+        }
+    }
+
+    /// Build the raw request payload from hook context.
     fn build_payload(&self, context: &HookContext) -> Value {
         let mut payload = json!({
             "event": {
@@ -118,7 +506,7 @@ impl WebhookExecutor {
                 "type": "webhook",
                 "working_dir": context.working_directory.to_string_lossy(),
             },
-            "environment": context.environment,
+            "environment": self.env_policy.capture(context.environment.clone()),
         });
 
         // Add event-specific data
@@ -221,19 +609,77 @@ impl WebhookExecutor {
             payload
         };
 
-        request = request.json(&body_value);
+        // Serialize once so the signature covers exactly the bytes sent
+        let body = serde_json::to_vec(&body_value)
+            .map_err(|e| HookError::Execution(format!("Failed to serialize webhook body: {}", e)))?;
+        if let Some(signing) = &config.signing {
+            let timestamp = Utc::now().timestamp();
+            let signature = sign_webhook_body(signing.secret.as_bytes(), timestamp, &body)?;
+            request = request
+                .header(&signing.timestamp_header, timestamp.to_string())
+                .header(&signing.signature_header, signature);
+        }
+
+        request = request.body(body);
 
         Ok(request)
     }
 
+    /// Send a webhook, retrying connection errors, 429 and 5xx responses with backoff.
+    ///
+    /// Other failures, such as 4xx responses, are returned at once, as is the last outcome
+    /// when the next retry would not finish before `deadline`. Returns the final outcome
+    /// with the delays waited before each retry.
+    async fn execute_with_retries(
+        &self,
+        config: &WebhookConfig,
+        payload: &Value,
+        deadline: Option<Instant>,
+    ) -> (Result<WebhookResult, HookError>, Vec<Duration>) {
+        let mut delays = Vec::new();
+        loop {
+            let outcome = self.execute_webhook(config, payload.clone()).await;
+            let retry_after = match &outcome {
+                Ok(result) if result.success => None,
+                Ok(result) if is_retryable_status(result.status_code) => {
+                    let retry_after = match result.status_code {
+                        429 | 503 => result
+                            .response_headers
+                            .get("retry-after")
+                            .and_then(|value| parse_retry_after(value, Utc::now())),
+                        _ => None,
+                    };
+                    Some(retry_after)
+                }
+                Ok(_) => None,
+                // Connection failures and timeouts
+                Err(HookError::Execution(_)) => Some(None),
+                Err(_) => None,
+            };
+
+            let attempt = delays.len() as u32;
+            let Some(retry_after) = retry_after.filter(|_| attempt < config.max_retries) else {
+                return (outcome, delays);
+            };
+            let delay = self.retry_policy.delay(attempt, retry_after);
+            if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+                warn!("Webhook attempt {} to {} failed with no time left to retry", attempt + 1, config.url);
+                return (outcome, delays);
+            }
+            warn!("Webhook attempt {} to {} failed, retrying in {:?}", attempt + 1, config.url, delay);
+            tokio::time::sleep(delay).await;
+            delays.push(delay);
+        }
+    }
+
     /// Execute webhook request and handle response.
-    async fn execute_webhook(&self, config: WebhookConfig, payload: Value) -> Result<WebhookResult, HookError> {
+    async fn execute_webhook(&self, config: &WebhookConfig, payload: Value) -> Result<WebhookResult, HookError> {
         let start_time = Instant::now();
 
         debug!("Sending webhook to: {} {}", config.method, config.url);
 
         // Build request
-        let request = self.build_request(&config, payload)?;
+        let request = self.build_request(config, payload)?;
 
         // Send request
         let response = request.send().await.map_err(|e| {
@@ -244,16 +690,40 @@ impl WebhookExecutor {
         let headers = response.headers().clone();
 
         // Read response body with size limit
-        let response_text = if response.content_length().unwrap_or(0) > self.max_response_size as u64 {
-            "[Response too large, truncated]".to_string()
+        let response_body = if response.content_length().unwrap_or(0) > self.max_response_size as u64 {
+            CapturedOutput::from_bytes(b"[Response too large, truncated]".to_vec())
         } else {
-            response.text().await.map_err(|e| {
+            let bytes = response.bytes().await.map_err(|e| {
                 HookError::Execution(format!("Failed to read response body: {}", e))
-            })?
+            })?;
+            CapturedOutput::from_bytes(bytes.to_vec())
         };
 
         let duration = start_time.elapsed();
-        let success = status.is_success();
+        let failure_reason = match &config.success_criteria {
+            Some(criteria) => {
+                check_success_criteria(criteria, config.body_regex.as_ref(), status.as_u16(), &response_body.text).err()
+            }
+            None => (!status.is_success()).then(|| format!("HTTP status {}", status.as_u16())),
+        };
+        let json_body = (config.success_when.is_some() || config.capture.is_some())
+            .then(|| serde_json::from_str::<Value>(&response_body.text));
+        let failure_reason = failure_reason.or_else(|| {
+            let expression = config.success_when.as_ref()?;
+            match &json_body {
+                Some(Ok(json)) => expression.evaluate(json).err(),
+                _ => Some(format!("success_when `{}` needs a JSON response body", expression.source)),
+            }
+        });
+        let captured = match (&config.capture, &json_body) {
+            (Some(path), Some(Ok(json))) => json_path_lookup(json, path).cloned(),
+            (Some(path), _) => {
+                debug!("Skipping webhook capture {}: response body is not JSON", path);
+                None
+            }
+            _ => None,
+        };
+        let success = failure_reason.is_none();
 
         debug!(
             "Webhook response: status={}, success={}, duration={:?}",
@@ -263,13 +733,15 @@ impl WebhookExecutor {
         Ok(WebhookResult {
             status_code: status.as_u16(),
             success,
-            response_body: response_text,
+            failure_reason,
+            response_body,
             response_headers: headers.iter()
                 .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
                 .collect(),
             duration,
-            url: config.url,
-            method: config.method,
+            url: config.url.clone(),
+            method: config.method.clone(),
+            captured,
         })
     }
 }
@@ -285,12 +757,61 @@ impl HookExecutor for WebhookExecutor {
         let webhook_config = self.extract_webhook_config(context)?;
 
         // Build payload
-        let payload = self.build_payload(context);
+        let serialization_start = Instant::now();
+        let payload = self.build_payload_for_mode(context, webhook_config.payload_mode);
+        let serialization_time = serialization_start.elapsed();
+
+        // Skip sends identical to one the endpoint received within the dedup window
+        let mut dedup_claim = None;
+        if let Some(dedup) = &self.dedup {
+            let destination = format!("{} {}", webhook_config.method, webhook_config.url);
+            let hash = content_hash(webhook_config.body.as_ref().unwrap_or(&payload), VOLATILE_PAYLOAD_FIELDS);
+            dedup_claim = dedup.claim(&destination, hash);
+            if dedup_claim.is_none() {
+                info!("Suppressing duplicate webhook payload to {}", destination);
+                return Ok(HookResult::success(
+                    Some(format!("Duplicate payload to {} suppressed", destination)),
+                    start_time.elapsed(),
+                )
+                .with_metadata(DEDUPLICATED_METADATA_KEY.to_string(), json!(true)));
+            }
+        }
+
+        // Fail fast while the endpoint's circuit is open
+        let breaker = self.circuit_breakers.as_ref().map(|breakers| breakers.breaker(&webhook_config.url));
+        let mut permit = None;
+        if let Some(breaker) = &breaker {
+            permit = breaker.try_acquire();
+            if permit.is_none() {
+                let state = breaker.state();
+                let reason = match state {
+                    CircuitState::HalfOpen => "circuit open (half-open trial in progress)",
+                    _ => "circuit open",
+                };
+                warn!("Skipping webhook to {}: {}", webhook_config.url, reason);
+                return Ok(HookResult::failure(
+                    format!("Webhook to {} not sent: {}", webhook_config.url, reason),
+                    start_time.elapsed(),
+                )
+                .with_metadata(CIRCUIT_STATE_METADATA_KEY.to_string(), json!(state)));
+            }
+        }
 
         // Execute the webhook
-        match self.execute_webhook(webhook_config, payload).await {
-            Ok(result) => {
-                if result.success {
+        let (outcome, retry_delays) = self.execute_with_retries(&webhook_config, &payload, context.deadline).await;
+        if let Some(permit) = permit {
+            match &outcome {
+                Ok(result) if result.success => permit.record_success(),
+                _ => permit.record_failure(),
+            }
+        }
+        let hook_result = match outcome {
+            Ok(mut result) => {
+                let captured = result.captured.take();
+                let hook_result = if result.success {
+                    if let Some(claim) = dedup_claim.take() {
+                        claim.delivered();
+                    }
                     info!(
                         "Webhook hook executed successfully: status={}, duration={:?}",
                         result.status_code, result.duration
@@ -301,28 +822,45 @@ impl HookExecutor for WebhookExecutor {
                         result.method, result.url, result.status_code
                     ));
 
-                    Ok(HookResult::success(output, start_time.elapsed()))
+                    result.response_body.annotate(HookResult::success(output, start_time.elapsed()), self.binary_output)
                 } else {
                     warn!(
                         "Webhook hook failed: status={}, response={}",
                         result.status_code,
-                        result.response_body.chars().take(200).collect::<String>()
+                        result.response_body.text.chars().take(200).collect::<String>()
                     );
 
                     let error_msg = format!(
-                        "Webhook failed with status {}: {}",
+                        "Webhook failed with status {} ({}): {}",
                         result.status_code,
-                        result.response_body.chars().take(500).collect::<String>()
+                        result.failure_reason.as_deref().unwrap_or("unsuccessful response"),
+                        result.response_body.text.chars().take(500).collect::<String>()
                     );
 
-                    Ok(HookResult::failure(error_msg, start_time.elapsed()))
+                    result.response_body.annotate(HookResult::failure(error_msg, start_time.elapsed()), self.binary_output)
+                };
+                match captured {
+                    Some(value) => hook_result.with_metadata(CAPTURED_METADATA_KEY.to_string(), value),
+                    None => hook_result,
                 }
             }
             Err(e) => {
                 error!("Webhook hook execution error: {}", e);
-                Ok(HookResult::failure(e.to_string(), start_time.elapsed()))
+                HookResult::failure(e.to_string(), start_time.elapsed())
             }
-        }
+        };
+
+        let hook_result = if retry_delays.is_empty() {
+            hook_result
+        } else {
+            let delays_ms: Vec<u64> = retry_delays.iter().map(|delay| delay.as_millis() as u64).collect();
+            hook_result.with_metadata(RETRY_DELAYS_METADATA_KEY.to_string(), json!(delays_ms))
+        };
+
+        Ok(hook_result.with_metadata(
+            SERIALIZATION_TIME_METADATA_KEY.to_string(),
+            serde_json::json!(serialization_time.as_micros() as u64),
+        ))
     }
 
     fn executor_type(&self) -> &'static str {
@@ -331,12 +869,19 @@ impl HookExecutor for WebhookExecutor {
 
     fn can_execute(&self, context: &HookContext) -> bool {
         matches!(context.hook_type, HookType::Webhook { .. })
+            && event_filter_allows(self.event_filter.as_ref(), context)
     }
 
     fn estimated_duration(&self) -> Option<Duration> {
         Some(Duration::from_secs(10)) // Network requests can be slower
     }
 
+    fn is_retryable(&self, error: &HookError) -> bool {
+        // 4xx responses already come back as failed results; a bad hook
+        // definition or request will not fix itself between attempts either.
+        !matches!(error, HookError::Configuration(_) | HookError::Validation(_))
+    }
+
     fn default_config(&self) -> ExecutionConfig {
         ExecutionConfig {
             timeout: Duration::from_secs(60),
@@ -346,6 +891,7 @@ impl HookExecutor for WebhookExecutor {
             max_retries: 3, // Retry network failures
             retry_delay: Duration::from_secs(1),
             isolated: true,
+            total_deadline: None,
         }
     }
 
@@ -376,10 +922,203 @@ struct WebhookConfig {
     body: Option<Value>,
     /// Authentication configuration.
     auth: Option<WebhookAuth>,
+    /// Shape of the generated payload.
+    payload_mode: WebhookPayloadMode,
+    /// Response-based success rules.
+    success_criteria: Option<WebhookSuccessCriteria>,
+    /// Compiled `success_criteria.body_regex`.
+    body_regex: Option<Regex>,
+    /// Resolved request signing, when enabled.
+    signing: Option<RequestSigning>,
+    /// Retries allowed after the first attempt.
+    max_retries: u32,
+    /// Parsed `success_when` expression.
+    success_when: Option<SuccessExpression>,
+    /// JSONPath of the response value to capture.
+    capture: Option<String>,
+}
+
+/// Comparison operators allowed in `success_when`, longest first so `>=` wins over `>`.
+const COMPARISONS: [(&str, Comparison); 6] = [
+    ("==", Comparison::Eq),
+    ("!=", Comparison::Ne),
+    (">=", Comparison::Ge),
+    ("<=", Comparison::Le),
+    (">", Comparison::Gt),
+    ("<", Comparison::Lt),
+];
+
+/// Operator of a `success_when` comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// A parsed `success_when` expression: a JSONPath, optionally compared with a JSON literal.
+#[derive(Debug, Clone)]
+struct SuccessExpression {
+    /// Expression as written, for error messages.
+    source: String,
+    path: String,
+    comparison: Option<(Comparison, Value)>,
+}
+
+impl SuccessExpression {
+    /// Parse `$.path`, or `$.path <op> <json literal>`.
+    fn parse(source: &str) -> Result<Self, HookError> {
+        let invalid = |reason: &str| {
+            HookError::Configuration(format!("Invalid webhook success_when `{}`: {}", source, reason))
+        };
+        let expression = source.trim();
+        if !expression.starts_with('$') {
+            return Err(invalid("must start with a JSONPath such as $.ok"));
+        }
+
+        let operator = COMPARISONS
+            .iter()
+            .filter_map(|(symbol, comparison)| expression.find(symbol).map(|at| (at, *symbol, *comparison)))
+            .min_by_key(|(at, symbol, _)| (*at, std::cmp::Reverse(symbol.len())));
+        let (path, comparison) = match operator {
+            Some((at, symbol, comparison)) => {
+                let literal = expression[at + symbol.len()..].trim();
+                let value = serde_json::from_str(literal)
+                    .map_err(|_| invalid(&format!("`{}` is not a JSON literal", literal)))?;
+                (expression[..at].trim(), Some((comparison, value)))
+            }
+            None => (expression, None),
+        };
+        if path.chars().any(char::is_whitespace) {
+            return Err(invalid("expected `<path> <operator> <value>`"));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            path: path.to_string(),
+            comparison,
+        })
+    }
+
+    /// Check the expression against a JSON response, describing why it does not hold.
+    fn evaluate(&self, json: &Value) -> Result<(), String> {
+        let actual = json_path_lookup(json, &self.path)
+            .ok_or_else(|| format!("success_when `{}`: {} not found in response body", self.source, self.path))?;
+        let holds = match &self.comparison {
+            None => !actual.is_null() && actual != &Value::Bool(false),
+            Some((Comparison::Eq, expected)) => actual == expected,
+            Some((Comparison::Ne, expected)) => actual != expected,
+            Some((comparison, expected)) => {
+                let ordering = match (actual, expected) {
+                    (Value::Number(a), Value::Number(b)) => a.as_f64().zip(b.as_f64()).and_then(|(a, b)| a.partial_cmp(&b)),
+                    (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                    _ => None,
+                };
+                let Some(ordering) = ordering else {
+                    return Err(format!("success_when `{}`: cannot compare {} with {}", self.source, actual, expected));
+                };
+                match comparison {
+                    Comparison::Gt => ordering.is_gt(),
+                    Comparison::Ge => ordering.is_ge(),
+                    Comparison::Lt => ordering.is_lt(),
+                    _ => ordering.is_le(),
+                }
+            }
+        };
+        if holds {
+            Ok(())
+        } else {
+            Err(format!("success_when `{}` is false ({} is {})", self.source, self.path, actual))
+        }
+    }
+}
+
+/// Webhook signing with its secret resolved.
+#[derive(Clone)]
+struct RequestSigning {
+    secret: String,
+    signature_header: String,
+    timestamp_header: String,
+}
+
+impl std::fmt::Debug for RequestSigning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestSigning")
+            .field("secret", &"<redacted>")
+            .field("signature_header", &self.signature_header)
+            .field("timestamp_header", &self.timestamp_header)
+            .finish()
+    }
+}
+
+/// Check a webhook response against success criteria, returning the first unmet rule.
+fn check_success_criteria(
+    criteria: &WebhookSuccessCriteria,
+    body_regex: Option<&Regex>,
+    status_code: u16,
+    body: &str,
+) -> Result<(), String> {
+    let status_ok = if criteria.status_codes.is_empty() {
+        (200..300).contains(&status_code)
+    } else {
+        criteria.status_codes.contains(&status_code)
+    };
+    if !status_ok {
+        return Err(format!("HTTP status {} is not an accepted status", status_code));
+    }
+
+    if let Some(regex) = body_regex {
+        if !regex.is_match(body) {
+            return Err(format!("response body does not match /{}/", regex.as_str()));
+        }
+    }
+
+    if let Some(path) = &criteria.json_path {
+        let json: Value = serde_json::from_str(body)
+            .map_err(|e| format!("response body is not JSON: {}", e))?;
+        match (json_path_lookup(&json, path), &criteria.json_equals) {
+            (None, _) => return Err(format!("{} not found in response body", path)),
+            (Some(actual), Some(expected)) if actual != expected => {
+                return Err(format!("{} is {} (expected {})", path, actual, expected));
+            }
+            (Some(actual), None) if actual.is_null() || actual == &Value::Bool(false) => {
+                return Err(format!("{} is {}", path, actual));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a JSONPath subset (`$`, `.field`, `[index]`) against a JSON value.
+fn json_path_lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (field, indexes) = match segment.find('[') {
+            Some(pos) => segment.split_at(pos),
+            None => (segment, ""),
+        };
+        if !field.is_empty() {
+            current = current.get(field)?;
+        }
+        for index in indexes.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.strip_suffix(']')?.parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+
+    Some(current)
 }
 
 /// Authentication methods for webhooks.
 #[derive(Debug, Clone)]
+#[allow(dead_code)] // Constructed once webhook auth is configurable.
 enum WebhookAuth {
     /// Bearer token authentication.
     Bearer { token: String },
@@ -396,8 +1135,10 @@ struct WebhookResult {
     status_code: u16,
     /// Whether the request was successful.
     success: bool,
+    /// Why the response was treated as a failure.
+    failure_reason: Option<String>,
     /// Response body.
-    response_body: String,
+    response_body: CapturedOutput,
     /// Response headers.
     response_headers: HashMap<String, String>,
     /// Duration of the request.
@@ -406,11 +1147,16 @@ struct WebhookResult {
     url: String,
     /// HTTP method used.
     method: Method,
+    /// Value selected by the hook's `capture` path.
+    captured: Option<Value>,
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
+    use crate::hooks::proxy::ProxyConfig;
+    use crate::hooks::secrets::REDACTED_VALUE;
     use crate::hooks::types::LifecycleEvent;
     use std::path::PathBuf;
 
@@ -428,6 +1174,12 @@ mod tests {
             headers: HashMap::new(),
             timeout: None,
             retry_count: None,
+            payload_mode: WebhookPayloadMode::Raw,
+            success_criteria: None,
+            signing: None,
+            success_when: None,
+            capture: None,
+            body: None,
         };
 
         HookContext::new(event, PathBuf::from("/tmp"))
@@ -438,7 +1190,7 @@ mod tests {
     async fn test_webhook_executor_creation() {
         let executor = WebhookExecutor::new();
         assert_eq!(executor.executor_type(), "webhook");
-        assert_eq!(executor.default_timeout, Duration::from_secs(30));
+        assert_eq!(executor.client_timeout, Duration::from_secs(60));
         assert_eq!(executor.max_response_size, 1024 * 1024);
     }
 
@@ -446,7 +1198,7 @@ mod tests {
     async fn test_webhook_executor_with_timeout() {
         let timeout = Duration::from_secs(120);
         let executor = WebhookExecutor::with_timeout(timeout);
-        assert_eq!(executor.default_timeout, timeout);
+        assert_eq!(executor.client_timeout, timeout);
     }
 
     #[tokio::test]
@@ -471,6 +1223,95 @@ mod tests {
         assert!(payload["event"]["model"] == "test-model");
     }
 
+    #[test]
+    fn test_webhook_payload_redacts_secret_environment() {
+        let executor = WebhookExecutor::new();
+        let context = create_test_context("https://example.com/webhook".to_string())
+            .with_env("API_TOKEN".to_string(), "tok-123".to_string())
+            .with_env("BUILD_ID".to_string(), "42".to_string());
+
+        let payload = executor.build_payload(&context);
+
+        assert_eq!(payload["environment"]["API_TOKEN"], REDACTED_VALUE);
+        assert_eq!(payload["environment"]["BUILD_ID"], "42");
+        assert!(!payload.to_string().contains("tok-123"));
+    }
+
+    fn fixed_timestamp() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_structured_payload_session_start() {
+        let event = LifecycleEvent::SessionStart {
+            session_id: "session-1".to_string(),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/work"),
+            timestamp: fixed_timestamp(),
+        };
+
+        let payload = WebhookPayload::from_event(&event).to_value();
+
+        assert_eq!(
+            payload,
+            json!({
+                "version": "1.0",
+                "event_type": "session_start",
+                "timestamp": "2024-01-01T12:00:00+00:00",
+                "session_id": "session-1",
+                "data": {
+                    "model": "test-model",
+                    "cwd": "/work",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_structured_payload_task_complete() {
+        let event = LifecycleEvent::TaskComplete {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            success: true,
+            output: Some("done".to_string()),
+            duration: Duration::from_millis(1500),
+            timestamp: fixed_timestamp(),
+        };
+
+        let payload = WebhookPayload::from_event(&event).to_value();
+
+        assert_eq!(
+            payload,
+            json!({
+                "version": "1.0",
+                "event_type": "task_complete",
+                "timestamp": "2024-01-01T12:00:00+00:00",
+                "session_id": "session-1",
+                "data": {
+                    "task_id": "task-1",
+                    "success": true,
+                    "output": "done",
+                    "duration_ms": 1500,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_payload_mode_selects_format() {
+        let executor = WebhookExecutor::new();
+        let context = create_test_context("https://example.com/webhook".to_string());
+
+        let raw = executor.build_payload_for_mode(&context, WebhookPayloadMode::Raw);
+        assert!(raw["event"]["type"].is_string());
+        assert!(raw.get("version").is_none());
+
+        let structured = executor.build_payload_for_mode(&context, WebhookPayloadMode::Structured);
+        assert_eq!(structured["version"], WEBHOOK_PAYLOAD_VERSION);
+        assert_eq!(structured["session_id"], "test-session");
+        assert_eq!(structured["data"]["model"], "test-model");
+    }
+
     #[tokio::test]
     async fn test_webhook_preparation() {
         let executor = WebhookExecutor::new();
@@ -509,4 +1350,667 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("cannot be empty"));
     }
+
+    fn create_criteria_context(url: String, criteria: WebhookSuccessCriteria) -> HookContext {
+        let mut context = create_test_context(url);
+        if let HookType::Webhook { success_criteria, .. } = &mut context.hook_type {
+            *success_criteria = Some(criteria);
+        }
+        context
+    }
+
+    async fn start_server(status: u16, body: Value) -> wiremock::MockServer {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn status_ok_criteria() -> WebhookSuccessCriteria {
+        WebhookSuccessCriteria {
+            json_path: Some("$.status".to_string()),
+            json_equals: Some(json!("ok")),
+            ..WebhookSuccessCriteria::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_success_criteria_fails_on_error_body_with_200() {
+        let server = start_server(200, json!({"status": "error", "message": "quota exceeded"})).await;
+        let context = create_criteria_context(server.uri(), status_ok_criteria());
+
+        let result = WebhookExecutor::new().execute(&context).await.unwrap();
+
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("status 200"));
+        assert!(error.contains("$.status is \"error\""));
+    }
+
+    #[tokio::test]
+    async fn test_success_criteria_passes_on_matching_body() {
+        let server = start_server(200, json!({"status": "ok"})).await;
+        let context = create_criteria_context(server.uri(), status_ok_criteria());
+
+        let result = WebhookExecutor::new().execute(&context).await.unwrap();
+
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_success_criteria_status_codes_and_regex() {
+        // Non-2xx accepted when listed explicitly
+        let server = start_server(409, json!({"result": "already exists"})).await;
+        let criteria = WebhookSuccessCriteria {
+            status_codes: vec![200, 409],
+            body_regex: Some("already exists".to_string()),
+            ..WebhookSuccessCriteria::default()
+        };
+        let context = create_criteria_context(server.uri(), criteria.clone());
+        assert!(WebhookExecutor::new().execute(&context).await.unwrap().success);
+
+        // GraphQL-style errors array fails the body regex
+        let server = start_server(200, json!({"errors": [{"message": "boom"}]})).await;
+        let criteria = WebhookSuccessCriteria {
+            body_regex: Some(r#"^\{"data""#.to_string()),
+            ..WebhookSuccessCriteria::default()
+        };
+        let context = create_criteria_context(server.uri(), criteria);
+        assert!(!WebhookExecutor::new().execute(&context).await.unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn test_non_utf8_response_body_is_flagged_and_captured() {
+        use crate::hooks::executor::{OUTPUT_DATA_METADATA_KEY, OUTPUT_LOSSY_METADATA_KEY};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x89, b'P', b'N', b'G', 0xff]))
+            .mount(&server)
+            .await;
+        let context = create_test_context(server.uri());
+
+        let result = WebhookExecutor::new().execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.metadata.get(OUTPUT_LOSSY_METADATA_KEY), Some(&json!(true)));
+        assert!(!result.metadata.contains_key(OUTPUT_DATA_METADATA_KEY));
+
+        let result = WebhookExecutor::new().with_binary_output(true).execute(&context).await.unwrap();
+        assert_eq!(result.metadata.get(OUTPUT_LOSSY_METADATA_KEY), Some(&json!(true)));
+        // base64 of b"\x89PNG\xff"
+        assert_eq!(result.metadata.get(OUTPUT_DATA_METADATA_KEY), Some(&json!("iVBOR/8=")));
+    }
+
+    #[tokio::test]
+    async fn test_identical_payload_suppressed_only_within_window() {
+        let server = start_server(200, json!({"status": "ok"})).await;
+        let executor = WebhookExecutor::new().with_payload_dedup(Duration::from_millis(300));
+        let context = create_test_context(server.uri());
+
+        let first = executor.execute(&context).await.unwrap();
+        assert!(first.success);
+        assert!(!first.metadata.contains_key(DEDUPLICATED_METADATA_KEY));
+
+        // Same event again: only the send timestamp differs, so it is a duplicate
+        let second = executor.execute(&context).await.unwrap();
+        assert!(second.success);
+        assert_eq!(second.metadata.get(DEDUPLICATED_METADATA_KEY), Some(&json!(true)));
+        assert_eq!(executor.payloads_deduplicated(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let third = executor.execute(&context).await.unwrap();
+        assert!(!third.metadata.contains_key(DEDUPLICATED_METADATA_KEY));
+        assert_eq!(executor.payloads_deduplicated(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    fn create_retry_context(url: String, retry_count: u32) -> HookContext {
+        let mut context = create_test_context(url);
+        if let HookType::Webhook { retry_count: hook_retry_count, .. } = &mut context.hook_type {
+            *hook_retry_count = Some(retry_count);
+        }
+        context
+    }
+
+    /// Server answering the first `failures` requests with `failure` and the rest with 200.
+    async fn start_flaky_server(failures: u64, failure: wiremock::ResponseTemplate) -> wiremock::MockServer {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(failure)
+            .up_to_n_times(failures)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn fixed_backoff(base_ms: u64) -> WebhookRetryPolicy {
+        WebhookRetryPolicy {
+            base_delay: Duration::from_millis(base_ms),
+            jitter: false,
+            ..WebhookRetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_with_growing_backoff() {
+        let server = start_flaky_server(2, wiremock::ResponseTemplate::new(503)).await;
+        let executor = WebhookExecutor::new().with_retry_policy(fixed_backoff(20));
+
+        let start = Instant::now();
+        let result = executor.execute(&create_retry_context(server.uri(), 3)).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.metadata[RETRY_DELAYS_METADATA_KEY], json!([20, 40]));
+        assert!(start.elapsed() >= Duration::from_millis(60));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_the_hook_deadline() {
+        let server = start_flaky_server(10, wiremock::ResponseTemplate::new(503)).await;
+        let executor = WebhookExecutor::new().with_retry_policy(fixed_backoff(100));
+
+        let start = Instant::now();
+        let context = create_retry_context(server.uri(), 5).with_deadline(start + Duration::from_millis(250));
+        let result = executor.execute(&context).await.unwrap();
+
+        // The 100ms wait fits, the 200ms one would overrun the deadline
+        assert!(!result.success);
+        assert_eq!(result.metadata[RETRY_DELAYS_METADATA_KEY], json!([100]));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert!(start.elapsed() < Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_header_overrides_backoff() {
+        let failure = wiremock::ResponseTemplate::new(429).insert_header("Retry-After", "1");
+        let server = start_flaky_server(1, failure).await;
+        let executor = WebhookExecutor::new().with_retry_policy(fixed_backoff(10));
+
+        let result = executor.execute(&create_retry_context(server.uri(), 3)).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.metadata[RETRY_DELAYS_METADATA_KEY], json!([1000]));
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let server = start_flaky_server(5, wiremock::ResponseTemplate::new(404)).await;
+        let executor = WebhookExecutor::new().with_retry_policy(fixed_backoff(10));
+
+        let result = executor.execute(&create_retry_context(server.uri(), 3)).await.unwrap();
+        assert!(!result.success);
+        assert!(!result.metadata.contains_key(RETRY_DELAYS_METADATA_KEY));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_retry_count() {
+        let server = start_flaky_server(5, wiremock::ResponseTemplate::new(500)).await;
+        let executor = WebhookExecutor::new().with_retry_policy(fixed_backoff(5));
+
+        let result = executor.execute(&create_retry_context(server.uri(), 2)).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.metadata[RETRY_DELAYS_METADATA_KEY], json!([5, 10]));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_backoff_ceiling_and_jitter() {
+        let policy = WebhookRetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..WebhookRetryPolicy::default()
+        };
+        let ceilings: Vec<_> = (0..6).map(|attempt| policy.backoff_ceiling(attempt).as_millis()).collect();
+        assert_eq!(ceilings, vec![100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(policy.backoff_ceiling(u32::MAX), Duration::from_millis(1000));
+
+        for attempt in 0..6 {
+            assert!(policy.delay(attempt, None) <= policy.backoff_ceiling(attempt));
+        }
+        assert_eq!(policy.delay(0, Some(Duration::from_secs(60))), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now), Some(Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast_and_recovers() {
+        let server = start_flaky_server(2, wiremock::ResponseTemplate::new(500)).await;
+        let executor = WebhookExecutor::new().with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout: Duration::from_millis(200),
+            failure_window: Some(Duration::from_secs(10)),
+        });
+        let context = create_test_context(server.uri());
+
+        for _ in 0..2 {
+            assert!(!executor.execute(&context).await.unwrap().success);
+        }
+        assert_eq!(executor.circuit_state(&server.uri()), CircuitState::Open);
+
+        // Open circuit: no request reaches the endpoint
+        let start = Instant::now();
+        let result = executor.execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("circuit open"));
+        assert_eq!(result.metadata[CIRCUIT_STATE_METADATA_KEY], json!("open"));
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // After the cooldown the trial request hits the now-healthy endpoint and closes the circuit
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(executor.circuit_state(&server.uri()), CircuitState::HalfOpen);
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(executor.circuit_state(&server.uri()), CircuitState::Closed);
+        assert!(executor.execute(&context).await.unwrap().success);
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_is_per_url() {
+        let failing = start_flaky_server(5, wiremock::ResponseTemplate::new(500)).await;
+        let healthy = start_server(200, json!({"status": "ok"})).await;
+        let executor = WebhookExecutor::new().with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 1,
+            reset_timeout: Duration::from_secs(60),
+            failure_window: None,
+        });
+
+        assert!(!executor.execute(&create_test_context(failing.uri())).await.unwrap().success);
+        assert_eq!(executor.circuit_state(&failing.uri()), CircuitState::Open);
+        assert!(executor.execute(&create_test_context(healthy.uri())).await.unwrap().success);
+        assert_eq!(executor.circuit_state(&healthy.uri()), CircuitState::Closed);
+    }
+
+    fn create_expression_context(url: String, success_when: Option<&str>, capture: Option<&str>) -> HookContext {
+        let mut context = create_test_context(url);
+        if let HookType::Webhook { success_when: hook_success_when, capture: hook_capture, .. } =
+            &mut context.hook_type
+        {
+            *hook_success_when = success_when.map(str::to_string);
+            *hook_capture = capture.map(str::to_string);
+        }
+        context
+    }
+
+    #[tokio::test]
+    async fn test_success_when_expression_decides_success() {
+        let server = start_server(200, json!({"ok": false, "queue": {"depth": 3}})).await;
+        let executor = WebhookExecutor::new();
+
+        let context = create_expression_context(server.uri(), Some("$.ok == true"), None);
+        let result = executor.execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("success_when `$.ok == true` is false ($.ok is false)"));
+
+        for passing in ["$.ok == false", "$.ok != true", "$.queue.depth >= 3", "$.queue.depth < 10", "$.queue"] {
+            let context = create_expression_context(server.uri(), Some(passing), None);
+            let result = executor.execute(&context).await.unwrap();
+            assert!(result.success, "{}: {:?}", passing, result.error);
+        }
+
+        for failing in ["$.ok", "$.queue.depth > 3", "$.missing == 1", "$.queue.depth > \"3\""] {
+            let context = create_expression_context(server.uri(), Some(failing), None);
+            assert!(!executor.execute(&context).await.unwrap().success, "{}", failing);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capture_stores_response_value_in_metadata() {
+        let server = start_server(200, json!({"ok": true, "deployment": {"id": "dep-7", "urls": ["a", "b"]}})).await;
+        let executor = WebhookExecutor::new();
+
+        let context = create_expression_context(server.uri(), Some("$.ok"), Some("$.deployment.id"));
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.metadata[CAPTURED_METADATA_KEY], json!("dep-7"));
+
+        // Captured values are kept even when the expression fails
+        let context = create_expression_context(server.uri(), Some("$.ok == false"), Some("$.deployment.urls"));
+        let result = executor.execute(&context).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.metadata[CAPTURED_METADATA_KEY], json!(["a", "b"]));
+    }
+
+    #[tokio::test]
+    async fn test_non_json_response_with_expressions() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("accepted"))
+            .mount(&server)
+            .await;
+        let executor = WebhookExecutor::new();
+
+        let result = executor.execute(&create_expression_context(server.uri(), None, Some("$.id"))).await.unwrap();
+        assert!(result.success);
+        assert!(!result.metadata.contains_key(CAPTURED_METADATA_KEY));
+
+        let result = executor.execute(&create_expression_context(server.uri(), Some("$.ok"), None)).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("needs a JSON response body"));
+    }
+
+    #[test]
+    fn test_success_when_parsing() {
+        let expression = SuccessExpression::parse("$.count >= 2").unwrap();
+        assert_eq!(expression.path, "$.count");
+        assert_eq!(expression.comparison, Some((Comparison::Ge, json!(2))));
+
+        let expression = SuccessExpression::parse(r#"$.status != "error""#).unwrap();
+        assert_eq!(expression.comparison, Some((Comparison::Ne, json!("error"))));
+
+        for invalid in ["ok == true", "$.status == error", "$.a $.b"] {
+            assert!(
+                matches!(SuccessExpression::parse(invalid), Err(HookError::Configuration(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    struct StaticSecretProvider;
+
+    impl SecretProvider for StaticSecretProvider {
+        fn resolve(&self, reference: &str) -> Result<String, HookError> {
+            match reference {
+                "env:CODEX_WEBHOOK_SECRET" => Ok("whsec_test".to_string()),
+                other => Err(HookError::Configuration(format!("unknown secret {}", other))),
+            }
+        }
+    }
+
+    fn create_signed_context(url: String, prompt: &str, signing: WebhookSigning) -> HookContext {
+        let event = LifecycleEvent::TaskStart {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            prompt: prompt.to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        let mut context = create_test_context(url);
+        context.event = event;
+        if let HookType::Webhook { signing: hook_signing, .. } = &mut context.hook_type {
+            *hook_signing = Some(signing);
+        }
+        context
+    }
+
+    /// Recompute the signature of a captured request independently of `sign_webhook_body`.
+    fn expected_signature(secret: &str, timestamp: &str, body: &[u8]) -> String {
+        let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[tokio::test]
+    async fn test_signed_webhook_signature_matches_body() {
+        let executor = WebhookExecutor::new().with_secret_provider(Arc::new(StaticSecretProvider));
+
+        for prompt in ["deploy the service", "déployer le service 🚀 — 完成"] {
+            let server = start_server(200, json!({"status": "ok"})).await;
+            let signing = WebhookSigning {
+                secret: "env:CODEX_WEBHOOK_SECRET".to_string(),
+                signature_header: None,
+                timestamp_header: None,
+            };
+            let context = create_signed_context(format!("{}/hook", server.uri()), prompt, signing);
+
+            let before = Utc::now().timestamp();
+            let result = executor.execute(&context).await.unwrap();
+            assert!(result.success, "{:?}", result.error);
+
+            let requests = server.received_requests().await.unwrap();
+            let request = &requests[0];
+            let timestamp = request.headers.get(DEFAULT_SIGNATURE_TIMESTAMP_HEADER).unwrap().to_str().unwrap();
+            assert!((before..=before + 5).contains(&timestamp.parse::<i64>().unwrap()));
+            let signature = request.headers.get(DEFAULT_SIGNATURE_HEADER).unwrap().to_str().unwrap();
+            assert_eq!(signature, expected_signature("whsec_test", timestamp, &request.body));
+
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(body["event"]["prompt"], prompt);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signing_uses_configured_headers() {
+        let executor = WebhookExecutor::new().with_secret_provider(Arc::new(StaticSecretProvider));
+        let server = start_server(200, json!({"status": "ok"})).await;
+        let signing = WebhookSigning {
+            secret: "env:CODEX_WEBHOOK_SECRET".to_string(),
+            signature_header: Some("X-Hub-Signature-256".to_string()),
+            timestamp_header: Some("X-Hub-Timestamp".to_string()),
+        };
+        let context = create_signed_context(server.uri(), "deploy", signing);
+
+        assert!(executor.execute(&context).await.unwrap().success);
+        let request = &server.received_requests().await.unwrap()[0];
+        let timestamp = request.headers.get("X-Hub-Timestamp").unwrap().to_str().unwrap();
+        let signature = request.headers.get("X-Hub-Signature-256").unwrap().to_str().unwrap();
+        assert_eq!(signature, expected_signature("whsec_test", timestamp, &request.body));
+        assert!(request.headers.get(DEFAULT_SIGNATURE_HEADER).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_signing_secret_is_configuration_error() {
+        let executor = WebhookExecutor::new().with_secret_provider(Arc::new(StaticSecretProvider));
+        let signing = WebhookSigning {
+            secret: "env:MISSING_SECRET".to_string(),
+            signature_header: None,
+            timestamp_header: None,
+        };
+        let context = create_signed_context("http://127.0.0.1:1/hook".to_string(), "deploy", signing);
+
+        let result = executor.execute(&context).await;
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+    }
+
+    #[test]
+    fn test_json_path_lookup() {
+        let value = json!({"data": {"items": [{"ok": true}, {"ok": false}]}, "status": "ok"});
+
+        assert_eq!(json_path_lookup(&value, "$.status"), Some(&json!("ok")));
+        assert_eq!(json_path_lookup(&value, "$.data.items[1].ok"), Some(&json!(false)));
+        assert_eq!(json_path_lookup(&value, "$"), Some(&value));
+        assert_eq!(json_path_lookup(&value, "$.data.items[5]"), None);
+        assert_eq!(json_path_lookup(&value, "$.missing"), None);
+    }
+
+    #[test]
+    fn test_invalid_success_regex_is_configuration_error() {
+        let criteria = WebhookSuccessCriteria {
+            body_regex: Some("(".to_string()),
+            ..WebhookSuccessCriteria::default()
+        };
+        let context = create_criteria_context("https://example.com/hook".to_string(), criteria);
+
+        let error = WebhookExecutor::new().extract_webhook_config(&context).unwrap_err();
+        assert!(matches!(error, HookError::Configuration(_)));
+    }
+
+    #[tokio::test]
+    async fn test_requests_traverse_configured_proxy() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Minimal forward proxy that records the request head and answers on the origin's behalf
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let proxy_task = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&head).to_string()
+        });
+
+        let executor = WebhookExecutor::new()
+            .with_proxy(ProxySettings::Custom(ProxyConfig {
+                url: format!("http://{}", proxy_addr),
+                username: Some("hooks".to_string()),
+                password: Some("secret".to_string()),
+                no_proxy: Vec::new(),
+            }))
+            .unwrap();
+        let context = create_test_context("http://hooks.example.invalid/notify".to_string());
+
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let head = proxy_task.await.unwrap();
+        assert!(head.starts_with("POST http://hooks.example.invalid/notify HTTP/1.1"), "{}", head);
+        // base64("hooks:secret")
+        assert!(head.contains("proxy-authorization: Basic aG9va3M6c2VjcmV0"), "{}", head);
+    }
+
+    #[test]
+    fn test_invalid_proxy_is_rejected() {
+        let result = WebhookExecutor::new().with_proxy(ProxySettings::Custom(ProxyConfig {
+            url: "ftp://proxy.corp".to_string(),
+            username: None,
+            password: None,
+            no_proxy: Vec::new(),
+        }));
+        assert!(matches!(result, Err(HookError::Configuration(_))));
+    }
+
+    #[test]
+    fn test_configuration_errors_are_not_retryable() {
+        let executor = WebhookExecutor::new();
+        assert!(!executor.is_retryable(&HookError::Configuration("missing url".to_string())));
+        assert!(!executor.is_retryable(&HookError::Validation("bad header".to_string())));
+        assert!(executor.is_retryable(&HookError::Http("connection reset".to_string())));
+        assert!(executor.is_retryable(&HookError::Execution("request failed".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_templates_use_configured_env_policy() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let executor = WebhookExecutor::new().with_env_policy(EnvCapturePolicy {
+            allow: vec!["DEPLOY_*".to_string()],
+            redact_names: None,
+            redact_values: None,
+        });
+
+        let mut context = create_test_context(server.uri())
+            .with_env("DEPLOY_TOKEN".to_string(), "hunter2".to_string())
+            .with_env("HOME".to_string(), "/home/alice".to_string());
+        if let HookType::Webhook { body, .. } = &mut context.hook_type {
+            *body = Some(json!({"token": "${env.DEPLOY_TOKEN}"}));
+        }
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let request = &server.received_requests().await.unwrap()[0];
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["token"], "hunter2");
+
+        // Variables outside the allow list are not available to templates
+        if let HookType::Webhook { body, .. } = &mut context.hook_type {
+            *body = Some(json!({"home": "${env.HOME}"}));
+        }
+        let err = executor.execute(&context).await.unwrap_err();
+        assert!(err.to_string().contains("env.HOME"));
+    }
+
+    #[tokio::test]
+    async fn test_templates_fill_webhook_url_and_body() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let executor = WebhookExecutor::new();
+
+        let mut context = create_test_context(format!("{}/sessions/${{event.session_id}}", server.uri()))
+            .with_env("DEPLOY_USER".to_string(), "alice".to_string())
+            .with_env("DEPLOY_TOKEN".to_string(), "hunter2".to_string());
+        if let HookType::Webhook { body, .. } = &mut context.hook_type {
+            *body = Some(json!({
+                "text": "Session ${event.session_id} started by ${env.DEPLOY_USER}",
+                "token": "${env.DEPLOY_TOKEN}",
+                "literal": "$${event.session_id}",
+            }));
+        }
+        let result = executor.execute(&context).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let request = &server.received_requests().await.unwrap()[0];
+        assert_eq!(request.url.path(), "/sessions/test-session");
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "text": "Session test-session started by alice",
+                "token": "<redacted>",
+                "literal": "${event.session_id}",
+            })
+        );
+
+        // Substituted values are JSON-escaped by serialization, not spliced into the document
+        context.event = LifecycleEvent::ExecBefore {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: vec!["git".to_string(), "commit".to_string(), "-m".to_string(), r#""quoted" \ msg"#.to_string()],
+            cwd: PathBuf::from("/repo"),
+            timestamp: chrono::Utc::now(),
+        };
+        if let HookType::Webhook { url, body, .. } = &mut context.hook_type {
+            *url = format!("{}/commands", server.uri());
+            *body = Some(json!({"command": "${event.command}"}));
+        }
+        executor.execute(&context).await.unwrap();
+        let request = &server.received_requests().await.unwrap()[1];
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["command"], r#"git commit -m "quoted" \ msg"#);
+
+        if let HookType::Webhook { body, .. } = &mut context.hook_type {
+            *body = Some(json!({"session": "${event.session_id}"}));
+        }
+        let err = executor.execute(&context).await.unwrap_err();
+        assert!(err.to_string().contains("'event.session_id' is not available for exec_before events"));
+    }
 }
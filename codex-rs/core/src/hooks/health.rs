@@ -0,0 +1,275 @@
+//! Consolidated health reporting for the hooks system.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+use crate::hooks::alerting::{AlertSeverity, AlertStatus, AlertingSystem};
+use crate::hooks::api;
+use crate::hooks::history::HistoryManager;
+use crate::hooks::manager::HookManager;
+use crate::hooks::metrics::MetricsCollector;
+use crate::hooks::types::HookError;
+
+/// Success rate below which the metrics subsystem is reported as degraded.
+const DEGRADED_SUCCESS_RATE: f64 = 0.9;
+/// Success rate below which the metrics subsystem is reported as critical.
+const CRITICAL_SUCCESS_RATE: f64 = 0.5;
+
+/// Health status of a subsystem or of the hooks system as a whole.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Operating normally.
+    Healthy,
+    /// Operating with reduced reliability.
+    Degraded,
+    /// Not operating correctly; operators should intervene.
+    Critical,
+}
+
+/// Health of a single subsystem.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    /// Subsystem name (e.g. `metrics`, `alerting`, `executor:script`).
+    pub name: String,
+    /// Subsystem status.
+    pub status: HealthStatus,
+    /// Human-readable explanation of the status.
+    pub details: String,
+}
+
+impl SubsystemHealth {
+    fn new(name: impl Into<String>, status: HealthStatus, details: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            details: details.into(),
+        }
+    }
+}
+
+/// Combined health report across all hook subsystems.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Worst status across all subsystems.
+    pub status: HealthStatus,
+    /// Per-subsystem details.
+    pub subsystems: Vec<SubsystemHealth>,
+    /// When the report was produced.
+    pub checked_at: DateTime<Utc>,
+}
+
+impl HealthReport {
+    /// Build a report whose overall status is the worst subsystem status.
+    pub fn from_subsystems(subsystems: Vec<SubsystemHealth>) -> Self {
+        let status = subsystems
+            .iter()
+            .map(|s| s.status)
+            .max()
+            .unwrap_or(HealthStatus::Healthy);
+
+        Self {
+            status,
+            subsystems,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// HTTP status code for this report: 503 when critical, 200 otherwise.
+    pub fn http_status_code(&self) -> u16 {
+        match self.status {
+            HealthStatus::Critical => 503,
+            HealthStatus::Healthy | HealthStatus::Degraded => 200,
+        }
+    }
+}
+
+/// Health of the metrics subsystem, derived from the overall success rate.
+pub fn metrics_health(collector: &MetricsCollector) -> SubsystemHealth {
+    let metrics = match collector.get_metrics() {
+        Ok(metrics) => metrics,
+        Err(e) => return SubsystemHealth::new("metrics", HealthStatus::Degraded, e.to_string()),
+    };
+
+    if metrics.total_executions == 0 {
+        return SubsystemHealth::new("metrics", HealthStatus::Healthy, "No executions recorded");
+    }
+
+    let status = if metrics.success_rate < CRITICAL_SUCCESS_RATE {
+        HealthStatus::Critical
+    } else if metrics.success_rate < DEGRADED_SUCCESS_RATE {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    };
+
+    SubsystemHealth::new(
+        "metrics",
+        status,
+        format!(
+            "Success rate {:.1}% over {} executions",
+            metrics.success_rate * 100.0,
+            metrics.total_executions
+        ),
+    )
+}
+
+/// Health of the alerting subsystem, derived from unresolved alerts.
+pub fn alerting_health(alerting: &AlertingSystem) -> SubsystemHealth {
+    if !alerting.is_processor_running() {
+        return SubsystemHealth::new("alerting", HealthStatus::Critical, "Alert processor is not running");
+    }
+
+    let alerts = match alerting.get_active_alerts() {
+        Ok(alerts) => alerts,
+        Err(e) => return SubsystemHealth::new("alerting", HealthStatus::Degraded, e.to_string()),
+    };
+
+    let open: Vec<_> = alerts
+        .iter()
+        .filter(|a| a.status != AlertStatus::Resolved && a.status != AlertStatus::Suppressed)
+        .collect();
+    let critical = open.iter().filter(|a| a.severity == AlertSeverity::Critical).count();
+
+    let status = if critical > 0 {
+        HealthStatus::Critical
+    } else if !open.is_empty() {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    };
+
+    SubsystemHealth::new(
+        "alerting",
+        status,
+        format!("{} active alerts, {} critical", open.len(), critical),
+    )
+}
+
+/// Health of history persistence, derived from the last disk write.
+pub fn history_health(history: &HistoryManager) -> SubsystemHealth {
+    match history.last_write_error() {
+        Some(error) if history.is_degraded() => SubsystemHealth::new(
+            "history",
+            HealthStatus::Degraded,
+            format!("History is memory-only after a failed write: {}", error),
+        ),
+        Some(error) => SubsystemHealth::new(
+            "history",
+            HealthStatus::Degraded,
+            format!("Last history write failed: {}", error),
+        ),
+        None => SubsystemHealth::new("history", HealthStatus::Healthy, "History writes succeeding"),
+    }
+}
+
+/// Serve the consolidated health report at `GET /health` until the listener fails.
+///
+/// The listener also serves the rest of the hooks HTTP API; see [`api::serve_api`]. No token
+/// is configured, so the API's `POST` routes only accept loopback peers.
+pub async fn serve_health(manager: Arc<HookManager>, listener: TcpListener) -> Result<(), HookError> {
+    api::serve_api(manager, listener, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::hooks::alerting::AlertingConfig;
+    use crate::hooks::config::{GlobalHooksConfig, HooksConfig};
+    use crate::hooks::metrics::MetricsConfig;
+
+    fn create_test_config() -> HooksConfig {
+        HooksConfig {
+            hooks: GlobalHooksConfig {
+                enabled: true,
+                timeout_seconds: 30,
+                parallel_execution: true,
+                session: Vec::new(),
+                task: Vec::new(),
+                exec: Vec::new(),
+                patch: Vec::new(),
+                mcp: Vec::new(),
+                agent: Vec::new(),
+                error: Vec::new(),
+                integration: Vec::new(),
+                proxy: Default::default(),
+                message_queue: Default::default(),
+                payload_dedup_window_seconds: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_report_status_is_worst_subsystem() {
+        let report = HealthReport::from_subsystems(vec![
+            SubsystemHealth::new("metrics", HealthStatus::Healthy, ""),
+            SubsystemHealth::new("history", HealthStatus::Degraded, ""),
+        ]);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.http_status_code(), 200);
+
+        let report = HealthReport::from_subsystems(Vec::new());
+        assert_eq!(report.status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_overall_health_healthy_by_default() {
+        let manager = HookManager::new(create_test_config())
+            .await
+            .unwrap()
+            .with_metrics_collector(Arc::new(MetricsCollector::new(MetricsConfig::default())));
+
+        let report = manager.overall_health().await;
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert!(report.subsystems.iter().any(|s| s.name == "metrics"));
+        assert!(report.subsystems.iter().any(|s| s.name == "executor:script"));
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint_returns_503_on_critical_alert() {
+        let alerting = Arc::new(AlertingSystem::new(AlertingConfig::default()));
+        alerting
+            .raise_alert(
+                "hook_failures",
+                AlertSeverity::Critical,
+                "Required hook failing repeatedly".to_string(),
+                Some("preflight".to_string()),
+                None,
+            )
+            .unwrap();
+
+        let manager = HookManager::new(create_test_config())
+            .await
+            .unwrap()
+            .with_alerting(alerting);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_health(Arc::new(manager), listener));
+
+        let response = reqwest::get(format!("http://{}/health", addr)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+
+        let report: HealthReport = response.json().await.unwrap();
+        assert_eq!(report.status, HealthStatus::Critical);
+        let alerting = report.subsystems.iter().find(|s| s.name == "alerting").unwrap();
+        assert_eq!(alerting.status, HealthStatus::Critical);
+        assert!(alerting.details.contains("1 critical"));
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint_unknown_path() {
+        let manager = HookManager::new(create_test_config()).await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_health(Arc::new(manager), listener));
+
+        let response = reqwest::get(format!("http://{}/status", addr)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+    }
+}
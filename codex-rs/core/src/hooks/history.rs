@@ -1,16 +1,24 @@
 //! Hook execution history and logging system.
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
-use std::time::SystemTime;
+use std::sync::{Arc, Condvar, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
 
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
-use crate::hooks::types::{HookError, HookResult, LifecycleEventType};
+use crate::hooks::alerting::{AlertSeverity, AlertingSystem};
+use crate::hooks::clock::{Clock, SystemClock};
+use crate::hooks::executors::executable::EXIT_CODE_METADATA_KEY;
+use crate::hooks::secrets::EnvCapturePolicy;
+use crate::hooks::types::{CancelReason, ExportFormat, HookError, HookResult, LifecycleEventType};
 
 /// Configuration for hook execution history and logging.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,8 +41,30 @@ pub struct HistoryConfig {
     pub include_output: bool,
     /// Whether to include environment variables in history.
     pub include_environment: bool,
+    /// Which environment variables are kept and which are redacted when included.
+    #[serde(default)]
+    pub env_policy: EnvCapturePolicy,
+    /// When buffered records are flushed to the history file.
+    #[serde(default)]
+    pub write_policy: WritePolicy,
+    /// Whether flushes are followed by an fsync of the history file.
+    #[serde(default)]
+    pub fsync: bool,
+    /// While disk writes are failing, how often to try the disk again.
+    #[serde(default = "default_degraded_retry_interval")]
+    pub degraded_retry_interval: Duration,
+    /// Whether rotated history files are gzipped to `hooks_NNNN.jsonl.gz`.
+    #[serde(default)]
+    pub compress_rotated: bool,
 }
 
+fn default_degraded_retry_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// Rule ID of the alert raised when history falls back to memory-only mode.
+pub const HISTORY_DEGRADED_RULE_ID: &str = "history_disk_degraded";
+
 impl Default for HistoryConfig {
     fn default() -> Self {
         Self {
@@ -47,10 +77,28 @@ impl Default for HistoryConfig {
             log_level: LogLevel::Info,
             include_output: true,
             include_environment: false,
+            env_policy: EnvCapturePolicy::default(),
+            write_policy: WritePolicy::default(),
+            fsync: false,
+            degraded_retry_interval: default_degraded_retry_interval(),
+            compress_rotated: false,
         }
     }
 }
 
+/// Policy controlling how often history records are flushed to disk.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WritePolicy {
+    /// Flush after every record (most durable, slowest).
+    #[default]
+    EveryRecord,
+    /// Flush from a background thread roughly every interval, with jitter.
+    Interval(Duration),
+    /// Flush only when the history file is rotated or the manager is dropped.
+    OnRotate,
+}
+
 /// Log levels for hook execution history.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
@@ -88,6 +136,9 @@ pub struct ExecutionHistoryRecord {
     pub retry_attempts: u32,
     /// Whether the execution was cancelled.
     pub cancelled: bool,
+    /// Why the execution was cancelled.
+    #[serde(default)]
+    pub cancel_reason: Option<CancelReason>,
     /// Environment variables (if enabled).
     pub environment: Option<std::collections::HashMap<String, String>>,
     /// Hook configuration snapshot.
@@ -117,8 +168,21 @@ pub struct HistorySummary {
     pub average_execution_time: std::time::Duration,
 }
 
+/// Field used to order execution history query results.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySort {
+    /// When the execution started.
+    #[default]
+    StartedAt,
+    /// How long the execution took.
+    Duration,
+    /// Hook identifier.
+    HookId,
+}
+
 /// Filter criteria for querying execution history.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct HistoryFilter {
     /// Filter by hook ID.
     pub hook_id: Option<String>,
@@ -132,6 +196,25 @@ pub struct HistoryFilter {
     pub limit: Option<usize>,
     /// Skip this many records (for pagination).
     pub offset: Option<usize>,
+    /// Field to sort by, applied before offset and limit.
+    pub sort_by: HistorySort,
+    /// Sort in descending order (newest, slowest or last hook ID first).
+    pub sort_desc: bool,
+}
+
+impl Default for HistoryFilter {
+    fn default() -> Self {
+        Self {
+            hook_id: None,
+            event_type: None,
+            success: None,
+            date_range: None,
+            limit: None,
+            offset: None,
+            sort_by: HistorySort::StartedAt,
+            sort_desc: true,
+        }
+    }
 }
 
 /// Hook execution history manager.
@@ -142,32 +225,73 @@ pub struct HistoryManager {
     current_file: Arc<RwLock<Option<BufWriter<File>>>>,
     current_file_size: Arc<RwLock<u64>>,
     file_counter: Arc<RwLock<usize>>,
+    last_write_error: Arc<RwLock<Option<String>>>,
+    degraded: Arc<RwLock<Option<DegradedState>>>,
+    alerting: Option<Arc<AlertingSystem>>,
+    clock: Arc<dyn Clock>,
+    /// Count of background flushes, signalled after each one.
+    background_flushes: Arc<(Mutex<u64>, Condvar)>,
+}
+
+/// Memory-only mode entered after a failed disk write.
+#[derive(Debug)]
+struct DegradedState {
+    /// When the most recent disk write was attempted.
+    last_attempt: Instant,
+    /// Alert raised on entering the mode, resolved on recovery.
+    alert_id: Option<String>,
+    /// Records kept since entering the mode that never reached disk.
+    memory_only: usize,
 }
 
 impl HistoryManager {
     /// Create a new history manager with the given configuration.
     pub fn new(config: HistoryConfig) -> Result<Self, HookError> {
+        Self::new_with_clock(config, SystemClock::shared())
+    }
+
+    /// Create a new history manager that reads time from `clock`.
+    pub fn new_with_clock(config: HistoryConfig, clock: Arc<dyn Clock>) -> Result<Self, HookError> {
+        config.env_policy.validate()?;
+
         let manager = Self {
             config,
             memory_records: Arc::new(RwLock::new(VecDeque::new())),
             current_file: Arc::new(RwLock::new(None)),
             current_file_size: Arc::new(RwLock::new(0)),
             file_counter: Arc::new(RwLock::new(0)),
+            last_write_error: Arc::new(RwLock::new(None)),
+            degraded: Arc::new(RwLock::new(None)),
+            alerting: None,
+            clock,
+            background_flushes: Arc::new((Mutex::new(0), Condvar::new())),
         };
 
         if manager.config.persist_to_disk {
             manager.ensure_history_directory()?;
+            manager.resume_file_counter()?;
             manager.initialize_file_logging()?;
+
+            if let WritePolicy::Interval(interval) = manager.config.write_policy {
+                manager.spawn_background_flusher(interval);
+            }
         }
 
         Ok(manager)
     }
 
     /// Create a history manager with default configuration.
+    #[allow(clippy::should_implement_trait)] // Fallible, so it cannot be `Default`.
     pub fn default() -> Result<Self, HookError> {
         Self::new(HistoryConfig::default())
     }
 
+    /// Raise an alert through `alerting` when history falls back to memory-only mode.
+    pub fn with_alerting(mut self, alerting: Arc<AlertingSystem>) -> Self {
+        self.alerting = Some(alerting);
+        self
+    }
+
     /// Record a hook execution in history.
     pub fn record_execution(
         &self,
@@ -177,7 +301,7 @@ impl HistoryManager {
         started_at: DateTime<Utc>,
         result: &HookResult,
         retry_attempts: u32,
-        cancelled: bool,
+        cancel_reason: Option<CancelReason>,
         hook_config: serde_json::Value,
         environment: Option<std::collections::HashMap<String, String>>,
     ) -> Result<(), HookError> {
@@ -190,15 +314,22 @@ impl HistoryManager {
             hook_id,
             event_type,
             started_at,
-            completed_at: Some(Utc::now()),
+            completed_at: Some(self.clock.now()),
             duration: result.duration,
             success: result.success,
-            exit_code: None, // TODO: Extract from result metadata if available
+            exit_code: result
+                .metadata
+                .get(EXIT_CODE_METADATA_KEY)
+                .and_then(|code| code.as_i64())
+                .and_then(|code| i32::try_from(code).ok()),
             output: if self.config.include_output { result.output.clone() } else { None },
             error_message: result.error.clone(),
             retry_attempts,
-            cancelled,
-            environment: if self.config.include_environment { environment } else { None },
+            cancelled: cancel_reason.is_some(),
+            cancel_reason,
+            environment: environment
+                .filter(|_| self.config.include_environment)
+                .map(|environment| self.config.env_policy.capture(environment)),
             hook_config,
             metadata: result.metadata.clone().into_iter().collect(),
         };
@@ -206,14 +337,104 @@ impl HistoryManager {
         // Add to memory
         self.add_to_memory(record.clone())?;
 
-        // Persist to disk if enabled
+        // Persist to disk if enabled; disk failures never fail the execution
         if self.config.persist_to_disk {
-            self.persist_to_disk(&record)?;
+            self.persist_or_degrade(&record);
         }
 
         Ok(())
     }
 
+    /// Whether disk writes are failing and history is kept in memory only.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.read().map(|state| state.is_some()).unwrap_or(false)
+    }
+
+    /// Write a record to disk, switching to memory-only mode when the write fails and
+    /// back once a retry succeeds.
+    fn persist_or_degrade(&self, record: &ExecutionHistoryRecord) {
+        let was_degraded = match self.degraded.write() {
+            Ok(mut state) => match state.as_mut() {
+                Some(state)
+                    if self.clock.instant().saturating_duration_since(state.last_attempt)
+                        < self.config.degraded_retry_interval =>
+                {
+                    state.memory_only += 1;
+                    return;
+                }
+                Some(_) => true,
+                None => false,
+            },
+            Err(_) => false,
+        };
+
+        let write_result = if was_degraded {
+            self.initialize_file_logging().and_then(|_| self.persist_to_disk(record))
+        } else {
+            self.persist_to_disk(record)
+        };
+        if let Ok(mut last_error) = self.last_write_error.write() {
+            *last_error = write_result.as_ref().err().map(|e| e.to_string());
+        }
+
+        match write_result {
+            Ok(()) if was_degraded => self.leave_degraded_mode(),
+            Ok(()) => {}
+            Err(e) => self.enter_degraded_mode(&e),
+        }
+    }
+
+    /// Drop the failing writer and record the attempt, alerting only on the first failure.
+    fn enter_degraded_mode(&self, error: &HookError) {
+        // Discard buffered bytes rather than retrying them when the writer is dropped
+        if let Ok(mut current_file) = self.current_file.write() {
+            if let Some(writer) = current_file.take() {
+                let _ = writer.into_parts();
+            }
+        }
+
+        let Ok(mut state) = self.degraded.write() else {
+            return;
+        };
+        if let Some(state) = state.as_mut() {
+            state.last_attempt = self.clock.instant();
+            state.memory_only += 1;
+            return;
+        }
+
+        tracing::error!("History disk write failed, keeping history in memory only: {}", error);
+        let alert_id = self.alerting.as_ref().and_then(|alerting| {
+            alerting
+                .raise_alert(
+                    HISTORY_DEGRADED_RULE_ID,
+                    AlertSeverity::Warning,
+                    format!("Hook history is memory-only after a disk write failed: {}", error),
+                    None,
+                    None,
+                )
+                .ok()
+        });
+        *state = Some(DegradedState {
+            last_attempt: self.clock.instant(),
+            alert_id,
+            memory_only: 1,
+        });
+    }
+
+    /// Return to writing history to disk, resolving the degradation alert.
+    fn leave_degraded_mode(&self) {
+        let Some(state) = self.degraded.write().ok().and_then(|mut state| state.take()) else {
+            return;
+        };
+
+        tracing::info!("History disk writes recovered");
+        if let (Some(alerting), Some(alert_id)) = (&self.alerting, state.alert_id) {
+            if let Err(e) = alerting.resolve_alert(&alert_id, "history") {
+                tracing::warn!("Failed to resolve history degradation alert: {}", e);
+            }
+        }
+    }
+
     /// Add a record to memory storage.
     fn add_to_memory(&self, record: ExecutionHistoryRecord) -> Result<(), HookError> {
         if let Ok(mut records) = self.memory_records.write() {
@@ -235,10 +456,10 @@ impl HistoryManager {
         let line_size = json_line.len() as u64 + 1; // +1 for newline
 
         // Check if we need to rotate the file
-        if let Ok(current_size) = self.current_file_size.read() {
-            if *current_size + line_size > self.config.max_file_size {
-                self.rotate_log_file()?;
-            }
+        // Rotation takes the size lock for writing, so release the read guard first
+        let current_size = self.current_file_size.read().map(|size| *size).unwrap_or(0);
+        if current_size + line_size > self.config.max_file_size {
+            self.rotate_log_file()?;
         }
 
         // Write to current file
@@ -246,8 +467,9 @@ impl HistoryManager {
             if let Some(ref mut writer) = *file_opt {
                 writeln!(writer, "{}", json_line)
                     .map_err(|e| HookError::Execution(format!("Failed to write history record: {}", e)))?;
-                writer.flush()
-                    .map_err(|e| HookError::Execution(format!("Failed to flush history file: {}", e)))?;
+                if self.config.write_policy == WritePolicy::EveryRecord {
+                    Self::sync_writer(writer, self.config.fsync)?;
+                }
 
                 // Update file size
                 if let Ok(mut size) = self.current_file_size.write() {
@@ -259,6 +481,66 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Error from the most recent failed disk write, cleared by the next successful one.
+    pub fn last_write_error(&self) -> Option<String> {
+        self.last_write_error.read().ok().and_then(|e| e.clone())
+    }
+
+    /// Flush any buffered history records to disk, honoring the fsync setting.
+    pub fn flush(&self) -> Result<(), HookError> {
+        if let Ok(mut file_opt) = self.current_file.write() {
+            if let Some(ref mut writer) = *file_opt {
+                Self::sync_writer(writer, self.config.fsync)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush a history writer and optionally fsync the underlying file.
+    fn sync_writer(writer: &mut BufWriter<File>, fsync: bool) -> Result<(), HookError> {
+        writer.flush()
+            .map_err(|e| HookError::Execution(format!("Failed to flush history file: {}", e)))?;
+        if fsync {
+            writer.get_ref().sync_data()
+                .map_err(|e| HookError::Execution(format!("Failed to sync history file: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Spawn a background thread that flushes the current file periodically.
+    ///
+    /// The thread holds only a weak reference and exits once the manager is dropped.
+    fn spawn_background_flusher(&self, interval: Duration) {
+        let current_file: Weak<RwLock<Option<BufWriter<File>>>> = Arc::downgrade(&self.current_file);
+        let fsync = self.config.fsync;
+        let background_flushes = Arc::clone(&self.background_flushes);
+
+        std::thread::spawn(move || {
+            loop {
+                // Jitter by +/-10% so many managers don't flush in lockstep.
+                let jitter = rand::rng().random_range(0.9..1.1);
+                std::thread::sleep(interval.mul_f64(jitter));
+
+                let Some(current_file) = current_file.upgrade() else {
+                    break;
+                };
+                if let Ok(mut file_opt) = current_file.write() {
+                    if let Some(ref mut writer) = *file_opt {
+                        if let Err(e) = Self::sync_writer(writer, fsync) {
+                            tracing::warn!("Background history flush failed: {}", e);
+                        }
+                    }
+                }
+
+                let (count, flushed) = &*background_flushes;
+                if let Ok(mut count) = count.lock() {
+                    *count += 1;
+                    flushed.notify_all();
+                }
+            }
+        });
+    }
+
     /// Query execution history with filters.
     pub fn query_history(&self, filter: HistoryFilter) -> Result<Vec<ExecutionHistoryRecord>, HookError> {
         let records = if let Ok(memory_records) = self.memory_records.read() {
@@ -267,13 +549,119 @@ impl HistoryManager {
             return Err(HookError::Execution("Failed to read memory records".to_string()));
         };
 
-        let mut filtered_records: Vec<_> = records
+        let filtered_records: Vec<_> = records
             .into_iter()
             .filter(|record| self.matches_filter(record, &filter))
             .collect();
 
-        // Sort by started_at descending (most recent first)
-        filtered_records.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(Self::sort_and_paginate(filtered_records, &filter))
+    }
+
+    /// Query execution history, falling back to the persisted history files when the
+    /// in-memory records cannot satisfy the filter.
+    ///
+    /// Records found in both places are returned once. Malformed lines are skipped.
+    pub fn query_history_persistent(&self, filter: HistoryFilter) -> Result<Vec<ExecutionHistoryRecord>, HookError> {
+        if !self.config.persist_to_disk {
+            return self.query_history(filter);
+        }
+
+        let (mut matched, oldest_in_memory) = {
+            let memory_records = self.memory_records.read()
+                .map_err(|_| HookError::Execution("Failed to read memory records".to_string()))?;
+            let matched: Vec<_> = memory_records
+                .iter()
+                .filter(|record| self.matches_filter(record, &filter))
+                .cloned()
+                .collect();
+            (matched, memory_records.iter().map(|r| r.started_at).min())
+        };
+
+        if !self.memory_satisfies(&filter, matched.len(), oldest_in_memory) {
+            let mut seen: HashSet<String> = matched.iter().map(|r| r.execution_id.clone()).collect();
+            self.flush()?;
+            self.read_persisted_matches(&filter, &mut seen, &mut matched)?;
+        }
+
+        Ok(Self::sort_and_paginate(matched, &filter))
+    }
+
+    /// Whether the in-memory records alone are enough to answer `filter`.
+    ///
+    /// Memory holds the newest records, so it can answer a newest-first page it fills, or
+    /// a date range that starts no earlier than its oldest record.
+    fn memory_satisfies(
+        &self,
+        filter: &HistoryFilter,
+        matched: usize,
+        oldest_in_memory: Option<DateTime<Utc>>,
+    ) -> bool {
+        if let Some((start, _)) = filter.date_range {
+            return oldest_in_memory.is_some_and(|oldest| oldest <= start);
+        }
+        match filter.limit {
+            Some(limit) => {
+                filter.sort_by == HistorySort::StartedAt
+                    && filter.sort_desc
+                    && matched >= filter.offset.unwrap_or(0) + limit
+            }
+            None => false,
+        }
+    }
+
+    /// Stream the history files into `matched`, keeping records that match `filter` and are
+    /// not already in `seen`. Lines that fail to parse are skipped.
+    ///
+    /// With a limit, only the records that can still land on the requested page are kept,
+    /// so memory stays proportional to the page rather than the history.
+    fn read_persisted_matches(
+        &self,
+        filter: &HistoryFilter,
+        seen: &mut HashSet<String>,
+        matched: &mut Vec<ExecutionHistoryRecord>,
+    ) -> Result<(), HookError> {
+        let keep = filter.limit.map(|limit| limit + filter.offset.unwrap_or(0));
+        for path in self.list_log_files()? {
+            let reader = match Self::open_log_reader(&path) {
+                Ok(reader) => reader,
+                // Cleanup may remove a file between listing and opening it
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(HookError::Execution(format!("Failed to open history file: {}", e))),
+            };
+            for line in reader.lines() {
+                let line = line
+                    .map_err(|e| HookError::Execution(format!("Failed to read history file: {}", e)))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: ExecutionHistoryRecord = match serde_json::from_str(&line) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        tracing::debug!("Skipping malformed history line in {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+                if !self.matches_filter(&record, filter) || !seen.insert(record.execution_id.clone()) {
+                    continue;
+                }
+                matched.push(record);
+                if let Some(keep) = keep {
+                    if matched.len() >= keep.max(1) * 2 {
+                        Self::sort_records(matched, filter);
+                        matched.truncate(keep);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort records by the filter's sort field, then apply its offset and limit.
+    fn sort_and_paginate(
+        mut filtered_records: Vec<ExecutionHistoryRecord>,
+        filter: &HistoryFilter,
+    ) -> Vec<ExecutionHistoryRecord> {
+        Self::sort_records(&mut filtered_records, filter);
 
         // Apply offset and limit
         if let Some(offset) = filter.offset {
@@ -288,7 +676,21 @@ impl HistoryManager {
             filtered_records.truncate(limit);
         }
 
-        Ok(filtered_records)
+        filtered_records
+    }
+
+    /// Sort records by the filter's sort field.
+    fn sort_records(records: &mut [ExecutionHistoryRecord], filter: &HistoryFilter) {
+        // Ties fall back to the execution ID so pages never overlap
+        records.sort_by(|a, b| {
+            let ordering = match filter.sort_by {
+                HistorySort::StartedAt => a.started_at.cmp(&b.started_at),
+                HistorySort::Duration => a.duration.cmp(&b.duration),
+                HistorySort::HookId => a.hook_id.cmp(&b.hook_id),
+            }
+            .then_with(|| a.execution_id.cmp(&b.execution_id));
+            if filter.sort_desc { ordering.reverse() } else { ordering }
+        });
     }
 
     /// Check if a record matches the given filter.
@@ -322,11 +724,12 @@ impl HistoryManager {
 
     /// Get summary statistics for execution history.
     pub fn get_summary(&self) -> Result<HistorySummary, HookError> {
-        let records = if let Ok(memory_records) = self.memory_records.read() {
-            memory_records.clone().into_iter().collect::<Vec<_>>()
-        } else {
-            return Err(HookError::Execution("Failed to read memory records".to_string()));
-        };
+        self.get_summary_filtered(HistoryFilter::default())
+    }
+
+    /// Get summary statistics for the execution history records matching `filter`.
+    pub fn get_summary_filtered(&self, filter: HistoryFilter) -> Result<HistorySummary, HookError> {
+        let records = self.query_history(filter)?;
 
         if records.is_empty() {
             return Ok(HistorySummary {
@@ -347,14 +750,8 @@ impl HistoryManager {
         let cancelled_executions = records.iter().filter(|r| r.cancelled).count();
 
         let date_range = {
-            let dates: Vec<_> = records.iter().map(|r| r.started_at).collect();
-            if dates.is_empty() {
-                None
-            } else {
-                let min_date = dates.iter().min().unwrap();
-                let max_date = dates.iter().max().unwrap();
-                Some((*min_date, *max_date))
-            }
+            let dates = || records.iter().map(|r| r.started_at);
+            dates().min().zip(dates().max())
         };
 
         // Find most active hook
@@ -402,31 +799,145 @@ impl HistoryManager {
         })
     }
 
-    /// Clear all history records.
+    /// Clear all history records, in memory and on disk.
     pub fn clear_history(&self) -> Result<(), HookError> {
+        if !self.config.persist_to_disk {
+            if let Ok(mut records) = self.memory_records.write() {
+                records.clear();
+            }
+            return Ok(());
+        }
+
+        // Hold the writer lock throughout so concurrent records wait and land in the fresh file
+        let mut current_file = self.current_file.write()
+            .map_err(|_| HookError::Execution("Failed to lock history file".to_string()))?;
+        if let Some(writer) = current_file.take() {
+            // The buffered records are being cleared too
+            let _ = writer.into_parts();
+        }
         if let Ok(mut records) = self.memory_records.write() {
             records.clear();
         }
 
-        // TODO: Also clear disk files if needed
+        for path in self.list_log_files()? {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(HookError::Execution(format!("Failed to remove history file: {}", e)));
+                }
+            }
+        }
+
+        if let Ok(mut counter) = self.file_counter.write() {
+            *counter = 0;
+        }
+        if let Ok(mut size) = self.current_file_size.write() {
+            *size = 0;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.get_current_log_file_path()?)
+            .map_err(|e| HookError::Execution(format!("Failed to open history file: {}", e)))?;
+        *current_file = Some(BufWriter::new(file));
+
         Ok(())
     }
 
     /// Export history to JSON file.
     pub fn export_to_file(&self, path: &Path) -> Result<(), HookError> {
+        self.export_to_file_as(path, ExportFormat::Json)
+    }
+
+    /// Export history to a file in the given format.
+    pub fn export_to_file_as(&self, path: &Path, format: ExportFormat) -> Result<(), HookError> {
+        let data = self.export_records(format)?;
+
+        std::fs::write(path, data)
+            .map_err(|e| HookError::Execution(format!("Failed to write history file: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Serialize the in-memory history records in the given format.
+    pub fn export_records(&self, format: ExportFormat) -> Result<Vec<u8>, HookError> {
         let records = if let Ok(memory_records) = self.memory_records.read() {
-            memory_records.clone().into_iter().collect::<Vec<_>>()
+            memory_records.iter().cloned().collect::<Vec<_>>()
         } else {
             return Err(HookError::Execution("Failed to read memory records".to_string()));
         };
 
-        let json = serde_json::to_string_pretty(&records)
-            .map_err(|e| HookError::Execution(format!("Failed to serialize history: {}", e)))?;
+        format.encode(&records)
+    }
 
-        std::fs::write(path, json)
-            .map_err(|e| HookError::Execution(format!("Failed to write history file: {}", e)))?;
+    /// Export history as newline-delimited JSON, one record per line.
+    ///
+    /// When history is persisted to disk the records are streamed from the
+    /// log files line by line, followed by any records held in memory only
+    /// while disk writes are failing; otherwise the in-memory records are
+    /// serialized one at a time. Returns the number of records written.
+    pub fn export_ndjson_streaming<W: Write>(&self, writer: W) -> Result<usize, HookError> {
+        let mut writer = BufWriter::new(writer);
+        let mut count = 0;
 
-        Ok(())
+        if self.config.persist_to_disk {
+            // Make sure everything buffered for the current file is on disk.
+            self.flush()?;
+
+            for path in self.list_log_files()? {
+                let reader = Self::open_log_reader(&path)
+                    .map_err(|e| HookError::Execution(format!("Failed to open history file: {}", e)))?;
+                for line in reader.lines() {
+                    let line = line
+                        .map_err(|e| HookError::Execution(format!("Failed to read history file: {}", e)))?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    writeln!(writer, "{}", line)
+                        .map_err(|e| HookError::Execution(format!("Failed to write history export: {}", e)))?;
+                    count += 1;
+                }
+            }
+
+            // Records kept while degraded are the newest in memory and missing from disk
+            let memory_only = self.degraded.read()
+                .ok()
+                .and_then(|state| state.as_ref().map(|state| state.memory_only))
+                .unwrap_or(0);
+            if memory_only > 0 {
+                let records = self.memory_records.read()
+                    .map_err(|_| HookError::Execution("Failed to read memory records".to_string()))?;
+                let skip = records.len().saturating_sub(memory_only);
+                count += Self::write_ndjson_records(&mut writer, records.iter().skip(skip))?;
+            }
+        } else {
+            let records = self.memory_records.read()
+                .map_err(|_| HookError::Execution("Failed to read memory records".to_string()))?;
+            count += Self::write_ndjson_records(&mut writer, records.iter())?;
+        }
+
+        writer.flush()
+            .map_err(|e| HookError::Execution(format!("Failed to flush history export: {}", e)))?;
+
+        Ok(count)
+    }
+
+    /// Serialize records as newline-delimited JSON, returning how many were written.
+    fn write_ndjson_records<'a, W: Write>(
+        writer: &mut W,
+        records: impl Iterator<Item = &'a ExecutionHistoryRecord>,
+    ) -> Result<usize, HookError> {
+        let mut count = 0;
+        for record in records {
+            serde_json::to_writer(&mut *writer, record)
+                .map_err(|e| HookError::Execution(format!("Failed to serialize history record: {}", e)))?;
+            writer.write_all(b"\n")
+                .map_err(|e| HookError::Execution(format!("Failed to write history export: {}", e)))?;
+            count += 1;
+        }
+        Ok(count)
     }
 
     /// Ensure the history directory exists.
@@ -437,6 +948,28 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Continue numbering after the files a previous run left behind.
+    ///
+    /// The newest plain file is appended to; a compressed one was already rotated out,
+    /// so logging starts in the file after it.
+    fn resume_file_counter(&self) -> Result<(), HookError> {
+        let Some(newest) = self.list_log_files()?.pop() else {
+            return Ok(());
+        };
+        let Some(name) = newest.file_name().and_then(|name| name.to_str()) else {
+            return Ok(());
+        };
+        let Some(counter) = Self::log_file_counter(name) else {
+            return Ok(());
+        };
+
+        let next = if name.ends_with(".gz") { counter + 1 } else { counter };
+        if let Ok(mut file_counter) = self.file_counter.write() {
+            *file_counter = next;
+        }
+        Ok(())
+    }
+
     /// Initialize file logging.
     fn initialize_file_logging(&self) -> Result<(), HookError> {
         let file_path = self.get_current_log_file_path()?;
@@ -465,11 +998,23 @@ impl HistoryManager {
 
     /// Rotate the current log file.
     fn rotate_log_file(&self) -> Result<(), HookError> {
-        // Close current file
+        let closed_path = self.get_current_log_file_path()?;
+
+        // Flush and close current file
         if let Ok(mut current_file) = self.current_file.write() {
+            if let Some(ref mut writer) = *current_file {
+                Self::sync_writer(writer, self.config.fsync)?;
+            }
             *current_file = None;
         }
 
+        // A failed compression leaves the plain file in place, which is still readable
+        if self.config.compress_rotated {
+            if let Err(e) = Self::compress_log_file(&closed_path) {
+                tracing::warn!("Failed to compress rotated history file {}: {}", closed_path.display(), e);
+            }
+        }
+
         // Increment file counter
         if let Ok(mut counter) = self.file_counter.write() {
             *counter += 1;
@@ -484,6 +1029,45 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Gzip a closed history file to `<path>.gz` and remove the plain file.
+    ///
+    /// An existing archive is appended to as a new gzip member, so a file name reused
+    /// after a restart never overwrites earlier history.
+    fn compress_log_file(path: &Path) -> std::io::Result<()> {
+        let mut gz_path = path.as_os_str().to_owned();
+        gz_path.push(".gz");
+
+        let mut source = File::open(path)?;
+        let archive = OpenOptions::new().create(true).append(true).open(&gz_path)?;
+        let mut encoder = GzEncoder::new(archive, Compression::default());
+        std::io::copy(&mut source, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+
+        std::fs::remove_file(path)
+    }
+
+    /// Open a history file for line-by-line reading, decompressing `.gz` files.
+    fn open_log_reader(path: &Path) -> std::io::Result<Box<dyn BufRead>> {
+        let file = File::open(path)?;
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            Ok(Box::new(BufReader::new(MultiGzDecoder::new(file))))
+        } else {
+            Ok(Box::new(BufReader::new(file)))
+        }
+    }
+
+    /// Whether a file name belongs to a plain or compressed history file.
+    fn is_log_file_name(name: &str) -> bool {
+        name.starts_with("hooks_") && (name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"))
+    }
+
+    /// The rotation counter embedded in a history file name.
+    fn log_file_counter(name: &str) -> Option<usize> {
+        let rest = name.strip_prefix("hooks_")?;
+        let digits = rest.strip_suffix(".jsonl.gz").or_else(|| rest.strip_suffix(".jsonl"))?;
+        digits.parse().ok()
+    }
+
     /// Clean up old log files.
     fn cleanup_old_files(&self) -> Result<(), HookError> {
         let history_dir = self.expand_path(&self.config.history_directory)?;
@@ -492,7 +1076,7 @@ impl HistoryManager {
         if let Ok(entries) = std::fs::read_dir(&history_dir) {
             for entry in entries.flatten() {
                 if let Some(name) = entry.file_name().to_str() {
-                    if name.starts_with("hooks_") && name.ends_with(".jsonl") {
+                    if Self::is_log_file_name(name) {
                         if let Ok(metadata) = entry.metadata() {
                             if let Ok(modified) = metadata.modified() {
                                 log_files.push((entry.path(), modified));
@@ -508,14 +1092,31 @@ impl HistoryManager {
 
         // Remove excess files
         while log_files.len() >= self.config.max_files {
-            if let Some((path, _)) = log_files.remove(0) {
-                let _ = std::fs::remove_file(path); // Ignore errors
-            }
+            let (path, _) = log_files.remove(0);
+            let _ = std::fs::remove_file(path); // Ignore errors
         }
 
         Ok(())
     }
 
+    /// List history log files in the order they were written.
+    fn list_log_files(&self) -> Result<Vec<PathBuf>, HookError> {
+        let history_dir = self.expand_path(&self.config.history_directory)?;
+
+        let mut log_files = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(&history_dir) {
+            for entry in entries.flatten() {
+                if let Some(counter) = entry.file_name().to_str().and_then(Self::log_file_counter) {
+                    log_files.push((counter, entry.path()));
+                }
+            }
+        }
+
+        // The counter outgrows its zero padding, so order by its value rather than the name
+        log_files.sort();
+        Ok(log_files.into_iter().map(|(_, path)| path).collect())
+    }
+
     /// Get the current log file path.
     fn get_current_log_file_path(&self) -> Result<PathBuf, HookError> {
         let history_dir = self.expand_path(&self.config.history_directory)?;
@@ -532,9 +1133,9 @@ impl HistoryManager {
     /// Expand path with home directory support.
     fn expand_path(&self, path: &Path) -> Result<PathBuf, HookError> {
         if let Some(path_str) = path.to_str() {
-            if path_str.starts_with("~/") {
+            if let Some(rest) = path_str.strip_prefix("~/") {
                 if let Some(home) = dirs::home_dir() {
-                    return Ok(home.join(&path_str[2..]));
+                    return Ok(home.join(rest));
                 }
             }
         }
@@ -542,9 +1143,19 @@ impl HistoryManager {
     }
 }
 
+impl Drop for HistoryManager {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            tracing::warn!("Failed to flush history on shutdown: {}", e);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
+    use crate::hooks::clock::MockClock;
     use tempfile::TempDir;
 
     fn create_test_config() -> HistoryConfig {
@@ -559,6 +1170,11 @@ mod tests {
             log_level: LogLevel::Info,
             include_output: true,
             include_environment: false,
+            env_policy: EnvCapturePolicy::default(),
+            write_policy: WritePolicy::EveryRecord,
+            fsync: false,
+            degraded_retry_interval: Duration::ZERO,
+            compress_rotated: false,
         }
     }
 
@@ -594,7 +1210,7 @@ mod tests {
             Utc::now(),
             &result,
             0,
-            false,
+            None,
             serde_json::json!({}),
             None,
         ).unwrap();
@@ -621,7 +1237,7 @@ mod tests {
             Utc::now(),
             &result1,
             0,
-            false,
+            None,
             serde_json::json!({}),
             None,
         ).unwrap();
@@ -633,7 +1249,7 @@ mod tests {
             Utc::now(),
             &result2,
             1,
-            false,
+            None,
             serde_json::json!({}),
             None,
         ).unwrap();
@@ -657,6 +1273,75 @@ mod tests {
         assert_eq!(records[0].hook_id, "hook_2");
     }
 
+    fn create_sortable_history() -> HistoryManager {
+        let manager = HistoryManager::new(create_test_config()).unwrap();
+        let start = Utc::now();
+        // (hook_id, started offset seconds, duration ms)
+        let records = [("hook_c", 0, 300), ("hook_a", 1, 100), ("hook_b", 2, 500), ("hook_a", 3, 200), ("hook_d", 4, 400)];
+        for (i, (hook_id, offset, duration_ms)) in records.into_iter().enumerate() {
+            let mut result = create_test_result(true);
+            result.duration = std::time::Duration::from_millis(duration_ms);
+            manager.record_execution(
+                format!("exec_{}", i),
+                hook_id.to_string(),
+                LifecycleEventType::TaskComplete,
+                start + chrono::Duration::seconds(offset),
+                &result,
+                0,
+                None,
+                serde_json::json!({}),
+                None,
+            ).unwrap();
+        }
+        manager
+    }
+
+    fn query_ids(manager: &HistoryManager, filter: HistoryFilter) -> Vec<String> {
+        manager.query_history(filter).unwrap().into_iter().map(|r| r.execution_id).collect()
+    }
+
+    #[test]
+    fn test_query_history_sort_modes() {
+        let manager = create_sortable_history();
+
+        // Default remains newest first
+        assert_eq!(
+            query_ids(&manager, HistoryFilter::default()),
+            vec!["exec_4", "exec_3", "exec_2", "exec_1", "exec_0"]
+        );
+
+        let oldest_first = HistoryFilter { sort_desc: false, ..Default::default() };
+        assert_eq!(query_ids(&manager, oldest_first), vec!["exec_0", "exec_1", "exec_2", "exec_3", "exec_4"]);
+
+        let slowest_first = HistoryFilter { sort_by: HistorySort::Duration, ..Default::default() };
+        assert_eq!(query_ids(&manager, slowest_first), vec!["exec_2", "exec_4", "exec_0", "exec_3", "exec_1"]);
+
+        let by_hook = HistoryFilter { sort_by: HistorySort::HookId, sort_desc: false, ..Default::default() };
+        assert_eq!(query_ids(&manager, by_hook), vec!["exec_1", "exec_3", "exec_2", "exec_0", "exec_4"]);
+    }
+
+    #[test]
+    fn test_query_history_sorted_pagination() {
+        let manager = create_sortable_history();
+        let page = |offset: usize| HistoryFilter {
+            sort_by: HistorySort::HookId,
+            sort_desc: false,
+            offset: Some(offset),
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let pages: Vec<Vec<String>> = (0..3).map(|i| query_ids(&manager, page(i * 2))).collect();
+        assert_eq!(pages[0], vec!["exec_1", "exec_3"]);
+        assert_eq!(pages[1], vec!["exec_2", "exec_0"]);
+        assert_eq!(pages[2], vec!["exec_4"]);
+
+        let mut all: Vec<String> = pages.concat();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 5);
+    }
+
     #[test]
     fn test_history_summary() {
         let config = create_test_config();
@@ -672,7 +1357,7 @@ mod tests {
                 Utc::now(),
                 &result,
                 0,
-                false,
+                None,
                 serde_json::json!({}),
                 None,
             ).unwrap();
@@ -685,4 +1370,633 @@ mod tests {
         assert!(summary.most_active_hook.is_some());
         assert!(summary.date_range.is_some());
     }
+
+    #[test]
+    fn test_export_ndjson_streaming_large_history() {
+        /// Writer that only counts newlines so the export is never held in memory.
+        struct LineCounter(usize);
+
+        impl Write for LineCounter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.iter().filter(|b| **b == b'\n').count();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            persist_to_disk: true,
+            history_directory: temp_dir.path().to_path_buf(),
+            max_file_size: 1024 * 1024,
+            max_files: 100,
+            ..create_test_config()
+        };
+        let manager = HistoryManager::new(config).unwrap();
+
+        let total = 5000;
+        for i in 0..total {
+            let result = create_test_result(i % 3 != 0);
+            manager.record_execution(
+                format!("exec_{}", i),
+                format!("hook_{}", i % 7),
+                LifecycleEventType::TaskComplete,
+                Utc::now(),
+                &result,
+                0,
+                None,
+                serde_json::json!({}),
+                None,
+            ).unwrap();
+        }
+
+        // Only the most recent records are kept in memory; the export reads disk.
+        assert_eq!(manager.query_history(HistoryFilter::default()).unwrap().len(), 100);
+
+        let mut counter = LineCounter(0);
+        let written = manager.export_ndjson_streaming(&mut counter).unwrap();
+        assert_eq!(written, total);
+        assert_eq!(counter.0, total);
+    }
+
+    #[test]
+    fn test_export_ndjson_streaming_from_memory() {
+        let config = create_test_config();
+        let manager = HistoryManager::new(config).unwrap();
+
+        for i in 0..3 {
+            manager.record_execution(
+                format!("exec_{}", i),
+                "hook_1".to_string(),
+                LifecycleEventType::SessionStart,
+                Utc::now(),
+                &create_test_result(true),
+                0,
+                None,
+                serde_json::json!({}),
+                None,
+            ).unwrap();
+        }
+
+        let mut buffer = Vec::new();
+        let written = manager.export_ndjson_streaming(&mut buffer).unwrap();
+        assert_eq!(written, 3);
+
+        let output = String::from_utf8(buffer).unwrap();
+        let records: Vec<ExecutionHistoryRecord> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].execution_id, "exec_0");
+    }
+
+    #[test]
+    fn test_cancel_reason_recorded() {
+        let manager = HistoryManager::new(create_test_config()).unwrap();
+        manager.record_execution(
+            "exec_1".to_string(),
+            "hook_1".to_string(),
+            LifecycleEventType::SessionEnd,
+            Utc::now(),
+            &create_test_result(false),
+            0,
+            Some(CancelReason::Shutdown),
+            serde_json::json!({}),
+            None,
+        ).unwrap();
+
+        let records = manager.query_history(HistoryFilter::default()).unwrap();
+        assert!(records[0].cancelled);
+        assert_eq!(records[0].cancel_reason, Some(CancelReason::Shutdown));
+
+        let summary = manager.get_summary().unwrap();
+        assert_eq!(summary.cancelled_executions, 1);
+        assert_eq!(summary.failed_executions, 0);
+    }
+
+    fn record_n(manager: &HistoryManager, count: usize) {
+        for i in 0..count {
+            manager.record_execution(
+                format!("exec_{}", i),
+                "hook_1".to_string(),
+                LifecycleEventType::SessionStart,
+                Utc::now(),
+                &create_test_result(true),
+                0,
+                None,
+                serde_json::json!({}),
+                None,
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_recorded_environment_is_redacted() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            include_environment: true,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new(config).unwrap();
+
+        let environment = std::collections::HashMap::from([
+            ("AWS_SECRET_ACCESS_KEY".to_string(), "wJalrXUtnFEMI/K7MDENG".to_string()),
+            ("HOME".to_string(), "/home/dev".to_string()),
+        ]);
+        manager.record_execution(
+            "exec_1".to_string(),
+            "hook_1".to_string(),
+            LifecycleEventType::SessionStart,
+            Utc::now(),
+            &create_test_result(true),
+            0,
+            None,
+            serde_json::json!({}),
+            Some(environment),
+        ).unwrap();
+
+        let records = manager.query_history(HistoryFilter::default()).unwrap();
+        let recorded = records[0].environment.as_ref().unwrap();
+        assert_eq!(recorded["AWS_SECRET_ACCESS_KEY"], crate::hooks::secrets::REDACTED_VALUE);
+        assert_eq!(recorded["HOME"], "/home/dev");
+
+        let on_disk = std::fs::read_to_string(temp_dir.path().join("hooks_0000.jsonl")).unwrap();
+        assert!(!on_disk.contains("wJalrXUtnFEMI"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_disk_full_degrades_to_memory_only() {
+        use crate::hooks::alerting::AlertingConfig;
+
+        let temp_dir = TempDir::new().unwrap();
+        let clock = Arc::new(MockClock::new());
+        let alerting = Arc::new(AlertingSystem::new_with_clock(AlertingConfig::default(), clock.clone()));
+        let config = HistoryConfig {
+            degraded_retry_interval: Duration::from_millis(500),
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new_with_clock(config, clock.clone())
+            .unwrap()
+            .with_alerting(alerting.clone());
+
+        // Every write to /dev/full fails with ENOSPC
+        let full = OpenOptions::new().write(true).open("/dev/full").unwrap();
+        *manager.current_file.write().unwrap() = Some(BufWriter::new(full));
+
+        record_n(&manager, 3);
+        assert!(manager.is_degraded());
+        assert!(manager.last_write_error().unwrap().contains("No space left"));
+        assert_eq!(manager.query_history(HistoryFilter::default()).unwrap().len(), 3);
+
+        let degraded_alerts = || {
+            alerting
+                .get_active_alerts()
+                .unwrap()
+                .into_iter()
+                .filter(|a| a.rule_id == HISTORY_DEGRADED_RULE_ID)
+                .count()
+        };
+        assert_eq!(degraded_alerts(), 1);
+
+        // Within the retry interval, records stay in memory without touching the disk
+        clock.advance(Duration::from_millis(400));
+        record_n(&manager, 1);
+        assert!(manager.is_degraded());
+        assert_eq!(degraded_alerts(), 1);
+
+        // Once the retry interval has passed, the next record reopens the file and recovers
+        clock.advance(Duration::from_millis(200));
+        record_n(&manager, 1);
+        assert!(!manager.is_degraded());
+        assert!(manager.last_write_error().is_none());
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 1);
+        assert_eq!(degraded_alerts(), 0);
+    }
+
+    #[test]
+    fn test_export_ndjson_streaming_includes_memory_only_records_while_degraded() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            degraded_retry_interval: Duration::from_secs(60),
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new_with_clock(config, Arc::new(MockClock::new())).unwrap();
+        record_n(&manager, 2);
+
+        let full = OpenOptions::new().write(true).open("/dev/full").unwrap();
+        *manager.current_file.write().unwrap() = Some(BufWriter::new(full));
+        record_n(&manager, 3);
+        assert!(manager.is_degraded());
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 2);
+
+        let mut buffer = Vec::new();
+        assert_eq!(manager.export_ndjson_streaming(&mut buffer).unwrap(), 5);
+        let ids: Vec<String> = String::from_utf8(buffer)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ExecutionHistoryRecord>(line).unwrap().execution_id)
+            .collect();
+        assert_eq!(ids, vec!["exec_0", "exec_1", "exec_0", "exec_1", "exec_2"]);
+    }
+
+    fn disk_config(temp_dir: &TempDir, write_policy: WritePolicy) -> HistoryConfig {
+        HistoryConfig {
+            persist_to_disk: true,
+            history_directory: temp_dir.path().to_path_buf(),
+            max_file_size: 1024 * 1024,
+            write_policy,
+            fsync: true,
+            ..create_test_config()
+        }
+    }
+
+    /// Block until the background flusher has run `flushes` times in total, or `timeout` passes.
+    fn wait_for_background_flushes(manager: &HistoryManager, flushes: u64, timeout: Duration) -> bool {
+        let (count, flushed) = &*manager.background_flushes;
+        let count = count.lock().unwrap();
+        let (count, _) = flushed.wait_timeout_while(count, timeout, |count| *count < flushes).unwrap();
+        *count >= flushes
+    }
+
+    fn lines_on_disk(temp_dir: &TempDir, file_name: &str) -> usize {
+        std::fs::read_to_string(temp_dir.path().join(file_name))
+            .map(|contents| contents.lines().count())
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_write_policy_every_record_is_durable_immediately() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = HistoryManager::new(disk_config(&temp_dir, WritePolicy::EveryRecord)).unwrap();
+
+        record_n(&manager, 3);
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 3);
+    }
+
+    #[test]
+    fn test_write_policy_interval_flushes_in_background() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy = WritePolicy::Interval(Duration::from_millis(200));
+        let manager = HistoryManager::new(disk_config(&temp_dir, policy)).unwrap();
+
+        record_n(&manager, 3);
+        // Buffered records are not written until the flusher runs.
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 0);
+
+        assert!(wait_for_background_flushes(&manager, 1, Duration::from_secs(5)));
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 3);
+    }
+
+    #[test]
+    fn test_write_policy_on_rotate_flushes_on_rotation_and_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            max_file_size: 2048,
+            max_files: 100,
+            ..disk_config(&temp_dir, WritePolicy::OnRotate)
+        };
+        let manager = HistoryManager::new(config).unwrap();
+
+        record_n(&manager, 1);
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 0);
+
+        // Write enough to force at least one rotation.
+        record_n(&manager, 20);
+        let first_file_lines = lines_on_disk(&temp_dir, "hooks_0000.jsonl");
+        assert!(first_file_lines > 0);
+
+        drop(manager);
+        let total: usize = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .flatten()
+            .map(|entry| lines_on_disk(&temp_dir, entry.file_name().to_str().unwrap()))
+            .sum();
+        assert_eq!(total, 21);
+    }
+
+    #[test]
+    fn test_query_history_persistent_reads_rotated_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            max_memory_records: 5,
+            max_file_size: 2048,
+            max_files: 100,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new(config).unwrap();
+        record_n(&manager, 20);
+        assert!(temp_dir.path().join("hooks_0001.jsonl").exists());
+
+        // Only the newest records are still in memory
+        assert_eq!(manager.query_history(HistoryFilter::default()).unwrap().len(), 5);
+        let all = manager.query_history_persistent(HistoryFilter::default()).unwrap();
+        assert_eq!(all.len(), 20);
+
+        // Malformed lines are skipped and memory can be empty
+        let mut file = OpenOptions::new().append(true).open(temp_dir.path().join("hooks_0000.jsonl")).unwrap();
+        writeln!(file, "{{not json").unwrap();
+        manager.memory_records.write().unwrap().clear();
+
+        let mut ids = query_ids_persistent(&manager, HistoryFilter::default());
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 20);
+
+        let page = HistoryFilter { limit: Some(3), sort_desc: false, ..Default::default() };
+        assert_eq!(query_ids_persistent(&manager, page), vec!["exec_0", "exec_1", "exec_2"]);
+        let page = HistoryFilter { limit: Some(2), offset: Some(15), sort_desc: false, ..Default::default() };
+        assert_eq!(query_ids_persistent(&manager, page), vec!["exec_15", "exec_16"]);
+    }
+
+    #[test]
+    fn test_query_history_persistent_uses_memory_when_sufficient() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = HistoryManager::new(disk_config(&temp_dir, WritePolicy::EveryRecord)).unwrap();
+        record_n(&manager, 10);
+
+        // A record that only exists on disk is not read for a page memory can fill
+        let mut disk_only = manager.query_history(HistoryFilter::default()).unwrap().remove(0);
+        disk_only.execution_id = "disk_only".to_string();
+        disk_only.started_at = Utc::now() - chrono::Duration::hours(1);
+        let mut file = OpenOptions::new().append(true).open(temp_dir.path().join("hooks_0000.jsonl")).unwrap();
+        writeln!(file, "{}", serde_json::to_string(&disk_only).unwrap()).unwrap();
+
+        let newest = HistoryFilter { limit: Some(5), ..Default::default() };
+        assert!(!query_ids_persistent(&manager, newest).contains(&"disk_only".to_string()));
+
+        let everything = query_ids_persistent(&manager, HistoryFilter::default());
+        assert_eq!(everything.len(), 11);
+        assert_eq!(everything.last().unwrap(), "disk_only");
+    }
+
+    #[test]
+    fn test_clear_history_removes_disk_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            max_file_size: 2048,
+            max_files: 100,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new(config).unwrap();
+        record_n(&manager, 20);
+        assert!(temp_dir.path().join("hooks_0001.jsonl").exists());
+
+        manager.clear_history().unwrap();
+        assert!(manager.query_history(HistoryFilter::default()).unwrap().is_empty());
+        assert!(manager.query_history_persistent(HistoryFilter::default()).unwrap().is_empty());
+        let files: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(files, vec!["hooks_0000.jsonl"]);
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 0);
+
+        // New records go to the fresh file
+        record_n(&manager, 2);
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_0000.jsonl"), 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_script_exit_code_recorded() {
+        use crate::hooks::context::HookContext;
+        use crate::hooks::executor::HookExecutor;
+        use crate::hooks::executors::ScriptExecutor;
+        use crate::hooks::types::{HookType, LifecycleEvent};
+
+        let event = LifecycleEvent::SessionStart {
+            session_id: "test-session".to_string(),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: Utc::now(),
+        };
+        let context = HookContext::new(event, PathBuf::from("/tmp")).with_hook_type(HookType::Script {
+            command: vec!["exit".to_string(), "2".to_string()],
+            cwd: None,
+            environment: std::collections::HashMap::new(),
+            timeout: None,
+            resource_limits: None,
+        });
+        let result = ScriptExecutor::new().execute(&context).await.unwrap();
+
+        let manager = HistoryManager::new(create_test_config()).unwrap();
+        manager.record_execution(
+            "exec_1".to_string(),
+            "hook_1".to_string(),
+            LifecycleEventType::SessionStart,
+            Utc::now(),
+            &result,
+            0,
+            None,
+            serde_json::json!({}),
+            None,
+        ).unwrap();
+        manager.record_execution(
+            "exec_2".to_string(),
+            "hook_2".to_string(),
+            LifecycleEventType::SessionStart,
+            Utc::now(),
+            &create_test_result(false),
+            0,
+            None,
+            serde_json::json!({}),
+            None,
+        ).unwrap();
+
+        let records = manager.query_history(HistoryFilter { sort_desc: false, ..Default::default() }).unwrap();
+        assert_eq!(records[0].exit_code, Some(2));
+        assert_eq!(records[1].exit_code, None);
+    }
+
+    #[test]
+    fn test_summary_filtered_by_date_range() {
+        let manager = HistoryManager::new(create_test_config()).unwrap();
+        let now = Utc::now();
+        let record_at = |id: usize, hook_id: &str, success: bool, hours_ago: i64, duration_ms: u64| {
+            let mut result = create_test_result(success);
+            result.duration = Duration::from_millis(duration_ms);
+            manager.record_execution(
+                format!("exec_{}", id),
+                hook_id.to_string(),
+                LifecycleEventType::TaskComplete,
+                now - chrono::Duration::hours(hours_ago),
+                &result,
+                0,
+                None,
+                serde_json::json!({}),
+                None,
+            ).unwrap();
+        };
+
+        // Yesterday hook_old failed a lot; in the last hour hook_new did
+        for i in 0..6 {
+            record_at(i, "hook_old", false, 24, 1000);
+        }
+        for i in 6..11 {
+            record_at(i, "hook_new", i % 2 == 0, 0, 100);
+        }
+        for i in 11..15 {
+            record_at(i, "hook_rare", false, 0, 100);
+        }
+
+        let all = manager.get_summary().unwrap();
+        assert_eq!(all.total_executions, 15);
+        assert_eq!(all.least_reliable_hook.as_deref(), Some("hook_old"));
+
+        let last_hour = manager.get_summary_filtered(HistoryFilter {
+            date_range: Some((now - chrono::Duration::hours(1), now)),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(last_hour.total_executions, 9);
+        assert_eq!(last_hour.successful_executions, 3);
+        assert_eq!(last_hour.failed_executions, 6);
+        assert_eq!(last_hour.most_active_hook.as_deref(), Some("hook_new"));
+        // hook_rare fails every time but has fewer than five executions
+        assert_eq!(last_hour.least_reliable_hook.as_deref(), Some("hook_new"));
+        assert_eq!(last_hour.average_execution_time, Duration::from_millis(100));
+        assert!(last_hour.date_range.unwrap().0 >= now - chrono::Duration::hours(1));
+
+        let empty = manager.get_summary_filtered(HistoryFilter {
+            event_type: Some(LifecycleEventType::SessionStart),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(empty.total_executions, 0);
+        assert!(empty.date_range.is_none());
+    }
+
+    #[test]
+    fn test_compressed_rotation_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            max_memory_records: 5,
+            max_file_size: 2048,
+            max_files: 100,
+            compress_rotated: true,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new(config).unwrap();
+        record_n(&manager, 20);
+
+        assert!(temp_dir.path().join("hooks_0000.jsonl.gz").exists());
+        assert!(!temp_dir.path().join("hooks_0000.jsonl").exists());
+
+        manager.memory_records.write().unwrap().clear();
+        let ids = query_ids_persistent(&manager, HistoryFilter { sort_desc: false, ..Default::default() });
+        assert_eq!(ids.len(), 20);
+        assert_eq!(ids[0], "exec_0");
+
+        let mut buffer = Vec::new();
+        assert_eq!(manager.export_ndjson_streaming(&mut buffer).unwrap(), 20);
+
+        manager.clear_history().unwrap();
+        assert!(!temp_dir.path().join("hooks_0000.jsonl.gz").exists());
+    }
+
+    #[test]
+    fn test_compression_appends_to_existing_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("hooks_0000.jsonl");
+
+        std::fs::write(&path, "first\n").unwrap();
+        HistoryManager::compress_log_file(&path).unwrap();
+        std::fs::write(&path, "second\n").unwrap();
+        HistoryManager::compress_log_file(&path).unwrap();
+
+        assert!(!path.exists());
+        let reader = HistoryManager::open_log_reader(&temp_dir.path().join("hooks_0000.jsonl.gz")).unwrap();
+        let lines: Vec<String> = reader.lines().map(|line| line.unwrap()).collect();
+        assert_eq!(lines, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_log_files_are_ordered_by_counter() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["hooks_10000.jsonl", "hooks_9999.jsonl.gz", "hooks_0002.jsonl.gz", "notes.txt"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        let manager = HistoryManager::new(HistoryConfig {
+            max_files: 100,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        })
+        .unwrap();
+
+        let names: Vec<String> = manager
+            .list_log_files()
+            .unwrap()
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["hooks_0002.jsonl.gz", "hooks_9999.jsonl.gz", "hooks_10000.jsonl"]);
+
+        // The newest plain file is where logging resumes
+        record_n(&manager, 1);
+        assert_eq!(lines_on_disk(&temp_dir, "hooks_10000.jsonl"), 1);
+    }
+
+    #[test]
+    fn test_file_counter_resumes_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = HistoryConfig {
+            max_file_size: 2048,
+            max_files: 100,
+            ..disk_config(&temp_dir, WritePolicy::EveryRecord)
+        };
+        let manager = HistoryManager::new(config.clone()).unwrap();
+        record_n(&manager, 20);
+        let newest = manager.list_log_files().unwrap().pop().unwrap();
+        let before = std::fs::read_to_string(&newest).unwrap().lines().count();
+        drop(manager);
+
+        // Room to spare, so the record lands in the newest file rather than rotating
+        let manager = HistoryManager::new(HistoryConfig { max_file_size: 1024 * 1024, ..config.clone() }).unwrap();
+        record_n(&manager, 1);
+        assert_eq!(std::fs::read_to_string(&newest).unwrap().lines().count(), before + 1);
+        let total: usize = manager
+            .list_log_files()
+            .unwrap()
+            .iter()
+            .map(|path| std::fs::read_to_string(path).unwrap().lines().count())
+            .sum();
+        assert_eq!(total, 21);
+        drop(manager);
+
+        // A compressed newest file is never reopened; logging moves on to the next one
+        let compressed_config = HistoryConfig { compress_rotated: true, ..config };
+        let manager = HistoryManager::new(compressed_config.clone()).unwrap();
+        manager.rotate_log_file().unwrap();
+        drop(manager);
+        let manager = HistoryManager::new(compressed_config).unwrap();
+        record_n(&manager, 1);
+        let files = manager.list_log_files().unwrap();
+        assert!(files[files.len() - 2].extension().is_some_and(|ext| ext == "gz"));
+        assert_eq!(std::fs::read_to_string(files.last().unwrap()).unwrap().lines().count(), 1);
+    }
+
+    fn query_ids_persistent(manager: &HistoryManager, filter: HistoryFilter) -> Vec<String> {
+        manager.query_history_persistent(filter).unwrap().into_iter().map(|r| r.execution_id).collect()
+    }
+
+    #[test]
+    fn test_export_records_message_pack_round_trip() {
+        let manager = HistoryManager::new(create_test_config()).unwrap();
+        record_n(&manager, 50);
+
+        let json = manager.export_records(ExportFormat::Json).unwrap();
+        let packed = manager.export_records(ExportFormat::MessagePack).unwrap();
+        assert!(packed.len() * 4 < json.len() * 3, "{} vs {} bytes", packed.len(), json.len());
+
+        let from_json: Vec<ExecutionHistoryRecord> = ExportFormat::Json.decode(&json).unwrap();
+        let from_packed: Vec<ExecutionHistoryRecord> = ExportFormat::MessagePack.decode(&packed).unwrap();
+        assert_eq!(from_packed.len(), 50);
+        assert_eq!(
+            serde_json::to_value(&from_packed).unwrap(),
+            serde_json::to_value(&from_json).unwrap()
+        );
+    }
 }
@@ -1,5 +1,7 @@
 //! Integration tests for the complete hooks system.
 
+#![allow(clippy::unwrap_used, clippy::module_inception)]
+
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -9,14 +11,14 @@ use tempfile::TempDir;
 use crate::hooks::config::{HookConfig, HooksConfig, GlobalHooksConfig};
 use crate::hooks::manager::HookManager;
 use crate::hooks::types::{
-    HookExecutionMode, HookPriority, HookType, LifecycleEvent, HttpMethod,
+    ErrorContext, HookExecutionMode, HookPriority, HookType, LifecycleEvent, LifecycleEventType, HttpMethod,
 };
 
 /// Helper function to create a comprehensive test configuration.
 fn create_comprehensive_test_config() -> HooksConfig {
     let mut env = HashMap::new();
     env.insert("TEST_SESSION_ID".to_string(), "${session_id}".to_string());
-    env.insert("TEST_USER_ID".to_string(), "${user_id}".to_string());
+    env.insert("TEST_MODEL".to_string(), "${model}".to_string());
 
     HooksConfig {
         hooks: GlobalHooksConfig {
@@ -26,61 +28,85 @@ fn create_comprehensive_test_config() -> HooksConfig {
             session: vec![
                 // Script hook for session start
                 HookConfig {
-                    event: "session.start".to_string(),
+                    id: None,
+                    event: LifecycleEventType::SessionStart,
                     hook_type: HookType::Script {
                         command: vec!["echo".to_string(), "Session started: ${session_id}".to_string()],
                         cwd: None,
                         environment: env.clone(),
                         timeout: Some(Duration::from_secs(5)),
+                        resource_limits: None,
                     },
                     description: Some("Log session start".to_string()),
-                    enabled: true,
                     required: false,
                     timeout: Some(Duration::from_secs(10)),
                     mode: HookExecutionMode::Async,
                     priority: HookPriority::NORMAL,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
                 // MCP hook for session start
                 HookConfig {
-                    event: "session.start".to_string(),
+                    id: None,
+                    event: LifecycleEventType::SessionStart,
                     hook_type: HookType::McpTool {
                         server: "session_tracker".to_string(),
                         tool: "track_session_start".to_string(),
                         timeout: Some(Duration::from_secs(10)),
                     },
                     description: Some("Track session start via MCP".to_string()),
-                    enabled: true,
                     required: false,
                     timeout: Some(Duration::from_secs(15)),
                     mode: HookExecutionMode::Async,
                     priority: HookPriority::HIGH,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             task: vec![
                 // Script hook for task completion
                 HookConfig {
-                    event: "task.complete".to_string(),
+                    id: None,
+                    event: LifecycleEventType::TaskComplete,
                     hook_type: HookType::Script {
                         command: vec!["echo".to_string(), "Task completed: ${task_id}".to_string()],
                         cwd: None,
                         environment: HashMap::new(),
                         timeout: Some(Duration::from_secs(5)),
+                        resource_limits: None,
                     },
                     description: Some("Log task completion".to_string()),
-                    enabled: true,
                     required: true, // This is a critical hook
                     timeout: Some(Duration::from_secs(10)),
-                    mode: HookExecutionMode::Sync,
+                    mode: HookExecutionMode::Blocking,
                     priority: HookPriority::HIGH,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             exec: vec![
                 // Webhook hook for command execution
                 HookConfig {
-                    event: "exec.before".to_string(),
+                    id: None,
+                    event: LifecycleEventType::ExecBefore,
                     hook_type: HookType::Webhook {
                         url: "https://httpbin.org/post".to_string(), // Test endpoint
                         method: HttpMethod::Post,
@@ -92,14 +118,26 @@ fn create_comprehensive_test_config() -> HooksConfig {
                         },
                         timeout: Some(Duration::from_secs(10)),
                         retry_count: Some(2),
+                        payload_mode: crate::hooks::types::WebhookPayloadMode::Raw,
+                        success_criteria: None,
+                        signing: None,
+                        success_when: None,
+                        capture: None,
+                        body: None,
                     },
                     description: Some("Notify external system of command execution".to_string()),
-                    enabled: true,
                     required: false,
                     timeout: Some(Duration::from_secs(15)),
                     mode: HookExecutionMode::Async,
                     priority: HookPriority::LOW,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             patch: Vec::new(),
@@ -108,23 +146,34 @@ fn create_comprehensive_test_config() -> HooksConfig {
             error: vec![
                 // Error handling hook
                 HookConfig {
-                    event: "error.occurred".to_string(),
+                    id: None,
+                    event: LifecycleEventType::ErrorOccurred,
                     hook_type: HookType::Script {
-                        command: vec!["echo".to_string(), "Error occurred: ${error_message}".to_string()],
+                        command: vec!["echo".to_string(), "Error occurred: ${error}".to_string()],
                         cwd: None,
                         environment: HashMap::new(),
                         timeout: Some(Duration::from_secs(3)),
+                        resource_limits: None,
                     },
                     description: Some("Log errors".to_string()),
-                    enabled: true,
                     required: false,
                     timeout: Some(Duration::from_secs(5)),
                     mode: HookExecutionMode::Async,
                     priority: HookPriority::HIGH,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             integration: Vec::new(),
+            proxy: Default::default(),
+            message_queue: Default::default(),
+            payload_dedup_window_seconds: None,
         },
     }
 }
@@ -145,20 +194,28 @@ fn create_timeout_test_config() -> HooksConfig {
             parallel_execution: false,
             session: vec![
                 HookConfig {
-                    event: "session.start".to_string(),
+                    id: None,
+                    event: LifecycleEventType::SessionStart,
                     hook_type: HookType::Script {
                         command: vec!["sleep".to_string(), "5".to_string()], // Will timeout
                         cwd: None,
                         environment: HashMap::new(),
-                        timeout: Some(Duration::from_secs(10)), // Longer than global timeout
+                        timeout: None,
+                        resource_limits: None,
                     },
                     description: Some("Slow hook that will timeout".to_string()),
-                    enabled: true,
                     required: true,
-                    timeout: Some(Duration::from_secs(10)),
-                    mode: HookExecutionMode::Sync,
+                    timeout: None, // Falls back to the global timeout
+                    mode: HookExecutionMode::Blocking,
                     priority: HookPriority::NORMAL,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             task: Vec::new(),
@@ -168,6 +225,9 @@ fn create_timeout_test_config() -> HooksConfig {
             agent: Vec::new(),
             error: Vec::new(),
             integration: Vec::new(),
+            proxy: Default::default(),
+            message_queue: Default::default(),
+            payload_dedup_window_seconds: None,
         },
     }
 }
@@ -179,16 +239,15 @@ mod integration_tests {
     #[tokio::test]
     async fn test_hook_manager_with_comprehensive_config() {
         let config = create_comprehensive_test_config();
-        let manager = HookManager::new(config).await.unwrap();
+        let manager = HookManager::new(config.clone()).await.unwrap();
 
         assert!(manager.is_enabled());
         
-        // Verify all executor types are available
-        assert_eq!(manager.executors.len(), 4);
-        assert!(manager.executors.contains_key("script"));
-        assert!(manager.executors.contains_key("webhook"));
-        assert!(manager.executors.contains_key("mcp_tool"));
-        assert!(manager.executors.contains_key("executable"));
+        // Verify an executor is available for every configured hook type
+        let groups = [&config.hooks.session, &config.hooks.task, &config.hooks.exec, &config.hooks.error];
+        for hook in groups.into_iter().flatten() {
+            assert!(manager.get_executor_for_hook(&hook.hook_type).is_ok());
+        }
     }
 
     #[tokio::test]
@@ -198,15 +257,17 @@ mod integration_tests {
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session_123".to_string(),
-            user_id: Some("test_user_456".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
-        let result = manager.trigger_event(event).await;
+        let result = manager.trigger_event(event.clone()).await;
         assert!(result.is_ok());
         
-        // Check that metrics were collected
-        let metrics = manager.get_metrics();
-        assert!(metrics.total_executions > 0);
+        // Check that the hooks ran
+        let results = manager.dispatch_event_collected(event).await.unwrap();
+        assert!(!results.results.is_empty());
     }
 
     #[tokio::test]
@@ -216,8 +277,11 @@ mod integration_tests {
 
         let event = LifecycleEvent::TaskComplete {
             task_id: "task_789".to_string(),
+            session_id: "test_session".to_string(),
             success: true,
+            output: None,
             duration: Duration::from_secs(5),
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -230,8 +294,11 @@ mod integration_tests {
         let manager = HookManager::new(config).await.unwrap();
 
         let event = LifecycleEvent::ExecBefore {
-            command: "ls -la".to_string(),
-            working_dir: PathBuf::from("/tmp"),
+            call_id: "call_1".to_string(),
+            task_id: "task_1".to_string(),
+            command: vec!["ls".to_string(), "-la".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -247,9 +314,14 @@ mod integration_tests {
         let manager = HookManager::new(config).await.unwrap();
 
         let event = LifecycleEvent::ErrorOccurred {
-            error_type: "test_error".to_string(),
-            error_message: "This is a test error".to_string(),
-            context: HashMap::new(),
+            task_id: None,
+            error: "This is a test error".to_string(),
+            context: ErrorContext {
+                component: "test".to_string(),
+                operation: None,
+                details: HashMap::new(),
+            },
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -265,15 +337,17 @@ mod integration_tests {
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
-        let result = manager.trigger_event(event).await;
+        let result = manager.trigger_event(event.clone()).await;
         assert!(result.is_ok());
         
         // No hooks should have been executed
-        let metrics = manager.get_metrics();
-        assert_eq!(metrics.total_executions, 0);
+        let results = manager.dispatch_event_collected(event).await.unwrap();
+        assert!(results.results.is_empty());
     }
 
     #[tokio::test]
@@ -288,7 +362,9 @@ mod integration_tests {
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -305,7 +381,9 @@ mod integration_tests {
         
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session_parallel".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let start_time = std::time::Instant::now();
@@ -320,7 +398,9 @@ mod integration_tests {
         
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session_sequential".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let start_time = std::time::Instant::now();
@@ -331,7 +411,7 @@ mod integration_tests {
 
         // Note: In a real test, parallel execution should be faster
         // But for simple echo commands, the difference might be negligible
-        println!("Parallel: {:?}, Sequential: {:?}", parallel_duration, sequential_duration);
+        tracing::debug!("Parallel: {:?}, Sequential: {:?}", parallel_duration, sequential_duration);
     }
 
     #[tokio::test]
@@ -343,33 +423,44 @@ mod integration_tests {
         let events = vec![
             LifecycleEvent::SessionStart {
                 session_id: "session_1".to_string(),
-                user_id: Some("user_1".to_string()),
+                model: "test-model".to_string(),
+                cwd: PathBuf::from("/tmp"),
+                timestamp: chrono::Utc::now(),
             },
             LifecycleEvent::TaskComplete {
                 task_id: "task_1".to_string(),
+                session_id: "test_session".to_string(),
                 success: true,
+                output: None,
                 duration: Duration::from_secs(2),
+                timestamp: chrono::Utc::now(),
             },
             LifecycleEvent::ErrorOccurred {
-                error_type: "test_error".to_string(),
-                error_message: "Test error message".to_string(),
-                context: HashMap::new(),
+                task_id: None,
+                error: "Test error message".to_string(),
+                context: ErrorContext {
+                    component: "test".to_string(),
+                    operation: None,
+                    details: HashMap::new(),
+                },
+                timestamp: chrono::Utc::now(),
             },
         ];
 
+        let mut results = Vec::new();
         for event in events {
-            let _ = manager.trigger_event(event).await;
+            results.push(manager.dispatch_event_collected(event).await.unwrap());
         }
 
-        let metrics = manager.get_metrics();
-        assert!(metrics.total_executions > 0);
-        assert!(metrics.total_duration > Duration::from_secs(0));
+        let total_executions: usize = results.iter().map(|r| r.results.len()).sum();
+        assert!(total_executions > 0);
+        assert!(results.iter().any(|r| r.total_duration > Duration::from_secs(0)));
         
         // Should have some successful executions
-        assert!(metrics.successful_executions > 0);
+        assert!(results.iter().any(|r| !r.successful.is_empty()));
         
         // Success rate should be reasonable
-        assert!(metrics.success_rate >= 0.0 && metrics.success_rate <= 1.0);
+        assert!(results.iter().all(|r| r.success_rate >= 0.0 && r.success_rate <= 1.0));
     }
 
     #[tokio::test]
@@ -378,14 +469,16 @@ mod integration_tests {
         
         // Add a condition to one of the hooks
         if let Some(hook) = config.hooks.session.get_mut(0) {
-            hook.conditions.insert("user_type".to_string(), "admin".to_string());
+            hook.condition = Some("model == 'admin-model'".to_string());
         }
 
         let manager = HookManager::new(config).await.unwrap();
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -411,7 +504,9 @@ mod integration_tests {
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let result = manager.trigger_event(event).await;
@@ -432,34 +527,44 @@ mod error_handling_tests {
         
         // Add a hook that will fail
         config.hooks.session.push(HookConfig {
-            event: "session.start".to_string(),
+            id: None,
+            event: LifecycleEventType::SessionStart,
             hook_type: HookType::Script {
                 command: vec!["false".to_string()], // Command that always fails
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: Some(Duration::from_secs(5)),
+                resource_limits: None,
             },
             description: Some("Hook that always fails".to_string()),
-            enabled: true,
             required: false, // Not required, so failure shouldn't stop execution
             timeout: Some(Duration::from_secs(10)),
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
-            conditions: HashMap::new(),
+            condition: None,
+            command_pattern: None,
+            blocking: false,
+            tags: Vec::new(),
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            fallback: None,
         });
 
         let manager = HookManager::new(config).await.unwrap();
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
-        let result = manager.trigger_event(event).await;
+        let result = manager.trigger_event(event.clone()).await;
         assert!(result.is_ok()); // Should succeed despite hook failure
         
-        let metrics = manager.get_metrics();
-        assert!(metrics.failed_executions > 0);
+        let results = manager.dispatch_event_collected(event).await.unwrap();
+        assert!(!results.failed.is_empty());
     }
 
     #[tokio::test]
@@ -473,6 +578,7 @@ mod error_handling_tests {
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: Some(Duration::from_secs(5)),
+                resource_limits: None,
             };
             hook.required = true; // This is critical
         }
@@ -481,16 +587,20 @@ mod error_handling_tests {
 
         let event = LifecycleEvent::TaskComplete {
             task_id: "test_task".to_string(),
+            session_id: "test_session".to_string(),
             success: true,
+            output: None,
             duration: Duration::from_secs(1),
+            timestamp: chrono::Utc::now(),
         };
 
-        let result = manager.trigger_event(event).await;
-        // Should still succeed at the manager level, but the hook execution should be marked as failed
-        assert!(result.is_ok());
+        let result = manager.trigger_event(event.clone()).await;
+        // A failing required blocking hook fails the event at the manager level
+        assert!(result.is_err());
         
-        let metrics = manager.get_metrics();
-        assert!(metrics.failed_executions > 0);
+        let results = manager.dispatch_event_collected(event).await.unwrap();
+        assert!(!results.failed.is_empty());
+        assert!(results.has_critical_failures());
     }
 
     #[tokio::test]
@@ -500,21 +610,22 @@ mod error_handling_tests {
 
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
 
         let start_time = std::time::Instant::now();
-        let result = manager.trigger_event(event).await;
+        let results = manager.dispatch_event_collected(event).await;
         let duration = start_time.elapsed();
 
-        assert!(result.is_ok());
+        assert!(results.is_ok());
         
         // Should not take much longer than the timeout
         assert!(duration < Duration::from_secs(5));
         
-        let metrics = manager.get_metrics();
         // The hook should have timed out and been marked as failed
-        assert!(metrics.failed_executions > 0);
+        assert!(!results.unwrap().failed.is_empty());
     }
 
     #[tokio::test]
@@ -523,31 +634,32 @@ mod error_handling_tests {
         
         // Add a hook with invalid configuration
         config.hooks.session.push(HookConfig {
-            event: "session.start".to_string(),
+            id: None,
+            event: LifecycleEventType::SessionStart,
             hook_type: HookType::Script {
                 command: vec![], // Empty command - invalid
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: Some(Duration::from_secs(5)),
+                resource_limits: None,
             },
             description: Some("Invalid hook".to_string()),
-            enabled: true,
             required: false,
             timeout: Some(Duration::from_secs(10)),
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
-            conditions: HashMap::new(),
+            condition: None,
+            command_pattern: None,
+            blocking: false,
+            tags: Vec::new(),
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            fallback: None,
         });
 
-        let manager = HookManager::new(config).await.unwrap();
-
-        let event = LifecycleEvent::SessionStart {
-            session_id: "test_session".to_string(),
-            user_id: Some("test_user".to_string()),
-        };
-
-        let result = manager.trigger_event(event).await;
-        // Should handle invalid configuration gracefully
-        assert!(result.is_ok());
+        // Invalid hooks are rejected when the manager loads its configuration
+        let result = HookManager::new(config).await;
+        assert!(matches!(result, Err(crate::hooks::types::HookError::Registry(_))));
     }
 }
@@ -1,19 +1,28 @@
 //! Hook manager for coordinating hook execution.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::future::join_all;
-use tokio::time::timeout;
 
-use crate::hooks::config::HooksConfig;
-use crate::hooks::context::{HookContext, HookExecutionContext};
-use crate::hooks::executor::{HookExecutor, ScriptExecutor, WebhookExecutor, McpToolExecutor, ExecutableExecutor};
-use crate::hooks::executors::{DatabaseExecutor, MessageQueueExecutor, FileSystemExecutor, CustomPluginExecutor};
+use crate::hooks::alerting::AlertingSystem;
+use crate::hooks::dashboard::HookDashboard;
+use crate::hooks::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerRegistry, CircuitState};
+use crate::hooks::config::{default_timeout_seconds, HookConfig, HooksConfig};
+use crate::hooks::context::{HookContext, HookExecutionContext, HOOK_ID_METADATA_KEY};
+use crate::hooks::dedup::DEDUPLICATED_METADATA_KEY;
+use crate::hooks::executor::{AggregatedResults, DeadLetter, ExecutionConfigResolver, ExecutionContext, ExecutionCoordinator, ExecutionResult, HookExecutor, RequiredFailurePolicy, ScriptExecutor, SkippedHook, WebhookExecutor, McpToolExecutor, ExecutableExecutor};
+use crate::hooks::executors::patch_analysis::{PendingPatches, PATCH_METADATA_KEY};
+use crate::hooks::executors::{DatabaseExecutor, MessageQueueExecutor, FileSystemExecutor, CustomPluginExecutor, PatchAnalysisExecutor, DesktopNotificationExecutor};
+use crate::hooks::health::{self, HealthReport, HealthStatus, SubsystemHealth};
+use crate::hooks::history::HistoryManager;
+use crate::hooks::metrics::MetricsCollector;
+use crate::hooks::performance::{PerformanceCollector, PerformanceSample};
 use crate::hooks::registry::HookRegistry;
-use crate::hooks::types::{HookError, HookResult, HookType, LifecycleEvent, HookExecutionMode};
+use crate::hooks::secrets::{EnvSecretProvider, SecretProvider};
+use crate::hooks::types::{HookError, HookResult, HookType, LifecycleEvent, LifecycleEventType, HookExecutionMode, SkipReason, CancelReason};
 
 /// Execution metrics for testing and monitoring.
 #[derive(Debug, Clone, Default)]
@@ -30,9 +39,62 @@ pub struct ExecutionMetrics {
 pub struct HookManager {
     registry: Arc<HookRegistry>,
     config: HooksConfig,
-    executors: HashMap<String, Box<dyn HookExecutor>>,
+    executors: HashMap<String, Arc<dyn HookExecutor>>,
+    coordinator: ExecutionCoordinator,
+    config_resolver: ExecutionConfigResolver,
     working_directory: PathBuf,
     metrics: HookExecutionMetrics,
+    metrics_collector: Option<Arc<MetricsCollector>>,
+    performance: Option<Arc<PerformanceCollector>>,
+    alerting: Option<Arc<AlertingSystem>>,
+    history: Option<Arc<HistoryManager>>,
+    dashboard: Option<Arc<HookDashboard>>,
+    circuit_breakers: CircuitBreakerRegistry,
+    pending_patches: PendingPatches,
+}
+
+/// Decision produced by a gated event dispatch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateDecision {
+    /// No required hook failed; the caller may continue.
+    Proceed,
+    /// A required hook failed; the caller should halt the operation.
+    Abort { reason: String },
+}
+
+impl GateDecision {
+    /// Derive a decision from the aggregated results of a hook run.
+    pub fn from_results(results: &AggregatedResults) -> Self {
+        if !results.has_critical_failures() {
+            return GateDecision::Proceed;
+        }
+
+        let errors = results.failed
+            .iter()
+            .filter(|r| r.config.required)
+            .map(|r| r.result.error.clone().unwrap_or_else(|| format!("execution {} failed", r.execution_id)))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        GateDecision::Abort {
+            reason: format!("Required hook failed: {}", errors),
+        }
+    }
+
+    /// Whether the caller should halt the operation.
+    pub fn is_abort(&self) -> bool {
+        matches!(self, GateDecision::Abort { .. })
+    }
+}
+
+/// How a dispatch runs the hooks matching an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dispatch {
+    /// Await every hook, fire-and-forget ones included, so the caller sees all results.
+    Collected,
+    /// Leave fire-and-forget hooks running and guard hooks that declare a fallback with
+    /// their circuit breaker.
+    Triggered,
 }
 
 /// Metrics for tracking hook execution performance.
@@ -45,22 +107,6 @@ pub struct HookExecutionMetrics {
     pub average_execution_time: Duration,
 }
 
-/// Result of executing multiple hooks.
-#[derive(Debug, Clone)]
-pub struct HookExecutionResults {
-    pub successful: Vec<HookExecutionResult>,
-    pub failed: Vec<HookExecutionResult>,
-    pub total_duration: Duration,
-}
-
-/// Result of executing a single hook.
-#[derive(Debug, Clone)]
-pub struct HookExecutionResult {
-    pub hook_description: String,
-    pub result: HookResult,
-    pub execution_time: Duration,
-}
-
 impl HookManager {
     /// Create a new hook manager with the given configuration.
     pub async fn new(config: HooksConfig) -> Result<Self, HookError> {
@@ -72,61 +118,527 @@ impl HookManager {
         let registry = Arc::new(HookRegistry::new(config.clone()).await?);
 
         // Initialize hook executors
-        let mut executors: HashMap<String, Box<dyn HookExecutor>> = HashMap::new();
-        executors.insert("script".to_string(), Box::new(ScriptExecutor::new()));
-        executors.insert("webhook".to_string(), Box::new(WebhookExecutor::new()));
-        executors.insert("mcp_tool".to_string(), Box::new(McpToolExecutor::new()));
-        executors.insert("executable".to_string(), Box::new(ExecutableExecutor));
-        executors.insert("database".to_string(), Box::new(DatabaseExecutor::new()));
-        executors.insert("message_queue".to_string(), Box::new(MessageQueueExecutor::new()));
-        executors.insert("filesystem".to_string(), Box::new(FileSystemExecutor::new()));
-        executors.insert("custom_plugin".to_string(), Box::new(CustomPluginExecutor::new()));
+        let mut executors: HashMap<String, Arc<dyn HookExecutor>> = HashMap::new();
+        let secret_provider: Arc<dyn SecretProvider> = Arc::new(EnvSecretProvider);
+        executors.insert("script".to_string(), Arc::new(ScriptExecutor::new()));
+        executors.insert("webhook".to_string(), Arc::new(Self::webhook_executor(&config, secret_provider.clone())?));
+        executors.insert("mcp_tool".to_string(), Arc::new(McpToolExecutor::new()));
+        executors.insert("executable".to_string(), Arc::new(ExecutableExecutor::new()));
+        executors.insert("database".to_string(), Arc::new(DatabaseExecutor::new()));
+        executors.insert(
+            "message_queue".to_string(),
+            Arc::new(Self::message_queue_executor(&config, secret_provider)),
+        );
+        executors.insert("filesystem".to_string(), Arc::new(FileSystemExecutor::new()));
+        executors.insert("custom_plugin".to_string(), Arc::new(CustomPluginExecutor::new()));
+        executors.insert("patch_analysis".to_string(), Arc::new(PatchAnalysisExecutor::new()));
+        executors.insert("desktop_notification".to_string(), Arc::new(DesktopNotificationExecutor::new()));
+
+        // A global timeout the user changed replaces executor defaults
+        let mut config_resolver = ExecutionConfigResolver::new();
+        if config.hooks.timeout_seconds != default_timeout_seconds() {
+            config_resolver = config_resolver.with_global_timeout(Duration::from_secs(config.hooks.timeout_seconds));
+        }
 
         Ok(Self {
             registry,
             config,
             executors,
+            coordinator: ExecutionCoordinator::new(),
+            config_resolver,
             working_directory,
             metrics: HookExecutionMetrics::default(),
+            metrics_collector: None,
+            performance: None,
+            alerting: None,
+            history: None,
+            dashboard: None,
+            circuit_breakers: CircuitBreakerRegistry::default(),
+            pending_patches: PendingPatches::default(),
         })
     }
 
+    /// Build the webhook executor for `config`.
+    fn webhook_executor(config: &HooksConfig, secret_provider: Arc<dyn SecretProvider>) -> Result<WebhookExecutor, HookError> {
+        let mut executor = WebhookExecutor::new()
+            .with_proxy(config.hooks.proxy.clone())?
+            .with_secret_provider(secret_provider);
+        if let Some(window) = config.hooks.payload_dedup_window_seconds {
+            executor = executor.with_payload_dedup(Duration::from_secs(window));
+        }
+        Ok(executor)
+    }
+
+    /// Build the message queue executor for `config`.
+    fn message_queue_executor(config: &HooksConfig, secret_provider: Arc<dyn SecretProvider>) -> MessageQueueExecutor {
+        let settings = &config.hooks.message_queue;
+        let mut executor = MessageQueueExecutor::new().with_secret_provider(secret_provider);
+        if let Some(window) = config.hooks.payload_dedup_window_seconds {
+            executor = executor.with_payload_dedup(Duration::from_secs(window));
+        }
+        if let Some(batching) = &settings.batching {
+            executor = executor.with_batching(batching.max_batch, Duration::from_millis(batching.flush_interval_ms));
+        }
+        #[cfg(feature = "sqs")]
+        if let Some(endpoint) = &settings.sqs_endpoint {
+            executor = executor.with_sqs_endpoint(endpoint.clone());
+        }
+        executor
+    }
+
+    /// Resolve webhook signing secrets and queue credentials with `provider` instead of the
+    /// environment.
+    pub fn with_secret_provider(mut self, provider: Arc<dyn SecretProvider>) -> Result<Self, HookError> {
+        self.executors.insert(
+            "webhook".to_string(),
+            Arc::new(Self::webhook_executor(&self.config, provider.clone())?),
+        );
+        self.executors.insert(
+            "message_queue".to_string(),
+            Arc::new(Self::message_queue_executor(&self.config, provider)),
+        );
+        Ok(self)
+    }
+
+    /// Replace the resolver used to compute each hook's effective execution config.
+    pub fn with_config_resolver(mut self, resolver: ExecutionConfigResolver) -> Self {
+        self.config_resolver = resolver;
+        self
+    }
+
+    /// Allow at most `max_concurrent` hooks of `executor_type` (e.g. `database`) to run at once.
+    pub fn with_executor_concurrency_limit(mut self, executor_type: impl Into<String>, max_concurrent: usize) -> Self {
+        self.coordinator = std::mem::take(&mut self.coordinator).with_executor_concurrency_limit(executor_type, max_concurrent);
+        self
+    }
+
+    /// Allow at most `max_concurrent` hooks to run at once across all events; the rest queue.
+    pub fn with_global_concurrency_limit(mut self, max_concurrent: usize) -> Self {
+        self.coordinator = std::mem::take(&mut self.coordinator).with_global_concurrency_limit(max_concurrent);
+        self
+    }
+
+    /// Allow at most `max_concurrent` hooks for `event_type` to run at once.
+    pub fn with_event_concurrency_limit(mut self, event_type: LifecycleEventType, max_concurrent: usize) -> Self {
+        self.coordinator = std::mem::take(&mut self.coordinator).with_event_concurrency_limit(event_type, max_concurrent);
+        self
+    }
+
+    /// Set what happens to an event's remaining hooks once a required blocking hook fails.
+    pub fn with_required_failure_policy(mut self, policy: RequiredFailurePolicy) -> Self {
+        self.coordinator = std::mem::take(&mut self.coordinator).with_required_failure_policy(policy);
+        self
+    }
+
+    /// Record failed fire-and-forget hooks, keeping the newest `capacity` in memory and
+    /// appending every one to `path` as JSONL when given.
+    pub fn with_dead_letter_queue(mut self, capacity: usize, path: Option<PathBuf>) -> Self {
+        self.coordinator = std::mem::take(&mut self.coordinator).with_dead_letter_queue(capacity, path);
+        self
+    }
+
+    /// Take the failed fire-and-forget hooks recorded in memory, oldest first.
+    pub fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.coordinator.drain_dead_letters()
+    }
+
+    /// Attach a metrics collector used for health reporting.
+    pub fn with_metrics_collector(mut self, collector: Arc<MetricsCollector>) -> Self {
+        self.metrics_collector = Some(collector);
+        self
+    }
+
+    /// Attach a performance collector fed a sample for every coordinated execution.
+    pub fn with_performance_collector(mut self, collector: Arc<PerformanceCollector>) -> Self {
+        self.performance = Some(collector);
+        self
+    }
+
+    /// Attach an alerting system used for health reporting.
+    pub fn with_alerting(mut self, alerting: Arc<AlertingSystem>) -> Self {
+        self.alerting = Some(alerting);
+        self
+    }
+
+    /// Attach a history manager used for health reporting.
+    pub fn with_history(mut self, history: Arc<HistoryManager>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Attach a dashboard whose execution tracking is closed out on shutdown.
+    pub fn with_dashboard(mut self, dashboard: Arc<HookDashboard>) -> Self {
+        self.dashboard = Some(dashboard);
+        self
+    }
+
+    /// Cancel in-flight hook executions and record them as cancelled on the dashboard.
+    pub async fn shutdown(&self) {
+        self.coordinator.shutdown().await;
+        if let Some(dashboard) = &self.dashboard {
+            if let Err(e) = dashboard.cancel_all_tracking(CancelReason::Shutdown) {
+                tracing::warn!("Failed to record shutdown on the dashboard: {}", e);
+            }
+        }
+    }
+
+    /// Configure the circuit breakers guarding hooks that declare a fallback.
+    pub fn with_circuit_breaker_config(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breakers = CircuitBreakerRegistry::new(config);
+        self
+    }
+
+    /// Current circuit state for a hook.
+    pub fn circuit_state(&self, hook_id: &str) -> CircuitState {
+        self.circuit_breakers.state(hook_id)
+    }
+
+    /// Combine the health of every attached subsystem into one report.
+    pub async fn overall_health(&self) -> HealthReport {
+        let mut subsystems = Vec::new();
+
+        if let Some(collector) = &self.metrics_collector {
+            subsystems.push(health::metrics_health(collector));
+        }
+        if let Some(alerting) = &self.alerting {
+            subsystems.push(health::alerting_health(alerting));
+        }
+        if let Some(history) = &self.history {
+            subsystems.push(health::history_health(history));
+        }
+
+        let mut executor_names: Vec<_> = self.executors.keys().collect();
+        executor_names.sort();
+        for name in executor_names {
+            let subsystem = format!("executor:{}", name);
+            let health = match self.executors[name].health_check().await {
+                Ok(()) => SubsystemHealth {
+                    name: subsystem,
+                    status: HealthStatus::Healthy,
+                    details: "Ready".to_string(),
+                },
+                Err(e) => SubsystemHealth {
+                    name: subsystem,
+                    status: HealthStatus::Degraded,
+                    details: e.to_string(),
+                },
+            };
+            subsystems.push(health);
+        }
+
+        HealthReport::from_subsystems(subsystems)
+    }
+
     /// Trigger a lifecycle event and execute all matching hooks.
+    ///
+    /// Hooks run through the coordinator like any other dispatch, except that
+    /// fire-and-forget hooks are left running in the background and hooks that
+    /// declare a fallback are guarded by their circuit breaker. Fails when a
+    /// required hook fails.
     pub async fn trigger_event(&self, event: LifecycleEvent) -> Result<(), HookError> {
         if !self.config.hooks.enabled {
             return Ok(());
         }
 
         let start_time = Instant::now();
-        tracing::info!("Triggering lifecycle event: {:?}", event.event_type());
+        let event_type = event.event_type();
+        let results = self.dispatch_event_until(event, None, Dispatch::Triggered).await?;
+        if results.results.is_empty() && results.skipped.is_empty() {
+            tracing::debug!("No hooks found for event: {:?}", event_type);
+            return Ok(());
+        }
+
+        self.log_execution_results(&results, start_time.elapsed());
+        match GateDecision::from_results(&results) {
+            GateDecision::Proceed => Ok(()),
+            GateDecision::Abort { reason } => Err(HookError::Execution(reason)),
+        }
+    }
+
+    /// Trigger a lifecycle event and decide whether the caller may proceed.
+    ///
+    /// All matching hooks are awaited (fire-and-forget hooks included) so the
+    /// decision reflects every required hook. Blocking hooks run first.
+    pub async fn dispatch_event_gated(&self, event: LifecycleEvent) -> Result<GateDecision, HookError> {
+        let event_type = event.event_type();
+        tracing::info!("Gating on lifecycle event: {:?}", event_type);
+        let results = self.dispatch_event_collected(event).await?;
+        let decision = GateDecision::from_results(&results);
+
+        if let GateDecision::Abort { reason } = &decision {
+            tracing::warn!("Gated event {:?} aborted: {}", event_type, reason);
+        }
+
+        Ok(decision)
+    }
+
+    /// Trigger a lifecycle event, await every matching hook and return the results,
+    /// including the hooks that were skipped and why.
+    pub async fn dispatch_event_collected(&self, event: LifecycleEvent) -> Result<AggregatedResults, HookError> {
+        self.dispatch_event_until(event, None, Dispatch::Collected).await
+    }
+
+    /// Like [`Self::dispatch_event_collected`], but non-required hooks share a total time `budget`.
+    ///
+    /// When the budget runs out, non-required hooks that have not started are skipped with
+    /// `SkipReason::BudgetExhausted` and those still running are cancelled; required hooks finish.
+    pub async fn dispatch_event_within(
+        &self,
+        event: LifecycleEvent,
+        budget: Duration,
+    ) -> Result<AggregatedResults, HookError> {
+        self.dispatch_event_until(event, Some(budget), Dispatch::Collected).await
+    }
+
+    async fn dispatch_event_until(
+        &self,
+        event: LifecycleEvent,
+        budget: Option<Duration>,
+        dispatch: Dispatch,
+    ) -> Result<AggregatedResults, HookError> {
+        if !self.config.hooks.enabled {
+            let skipped = self
+                .registry
+                .get_hooks_for_event(event.event_type())
+                .into_iter()
+                .map(|hook| SkippedHook::new(hook.get_id(), SkipReason::Disabled, None))
+                .collect();
+            return Ok(self.record_skips(AggregatedResults::default().with_skipped(skipped)));
+        }
+
+        tracing::info!("Dispatching lifecycle event: {:?} ({:?})", event.event_type(), dispatch);
+        self.record_event(event.event_type());
+
+        let context = self.event_context(&event);
+
+        let (matching_hooks, mut skipped) = self.registry.get_matching_hooks_with_skips(&event, &context)
+            .map_err(|e| HookError::Execution(format!("Failed to get matching hooks: {}", e)))?;
+
+        let mut executions = Vec::with_capacity(matching_hooks.len());
+        // Hooks run on their own behind their circuit breaker, with their fallback
+        let mut guarded = Vec::new();
+        // Fallbacks standing in for hooks whose circuit is open, by the hook they replace
+        let mut fallback_for = HashMap::new();
+        for hook in matching_hooks {
+            let mut hook = hook;
+            let mut hook_id = hook.get_id();
+            if dispatch == Dispatch::Triggered {
+                if let Some(fallback) = hook.fallback.as_deref() {
+                    guarded.push((hook, fallback));
+                    continue;
+                }
+            }
+            if self.circuit_breakers.state(&hook_id) == CircuitState::Open {
+                let Some(fallback) = hook.fallback.as_deref() else {
+                    skipped.push(SkippedHook::new(hook_id, SkipReason::CircuitOpen, None));
+                    continue;
+                };
+                let detail = format!("fallback {} ran instead", fallback.get_id());
+                skipped.push(SkippedHook::new(hook_id.clone(), SkipReason::CircuitOpen, Some(detail)));
+                tracing::warn!("Hook {} falling back to {} (circuit_open)", hook_id, fallback.get_id());
+                let primary_id = std::mem::replace(&mut hook_id, fallback.get_id());
+                fallback_for.insert(hook_id.clone(), primary_id);
+                hook = fallback;
+            }
+            let executor = match self.get_executor_for_hook(&hook.hook_type) {
+                Ok(executor) => executor.clone(),
+                Err(e) => {
+                    skipped.push(SkippedHook::new(hook_id, SkipReason::NoMatchingExecutor, Some(e.to_string())));
+                    continue;
+                }
+            };
+            let hook_context = Self::context_for_hook(&context, hook);
+            if !executor.can_execute(&hook_context) {
+                let detail = format!("{} executor declined the hook", executor.executor_type());
+                skipped.push(SkippedHook::new(hook_id, SkipReason::NoMatchingExecutor, Some(detail)));
+                continue;
+            }
+            let mut exec_config = self.config_resolver.resolve_for_hook(executor.as_ref(), hook);
+            if dispatch == Dispatch::Collected && exec_config.mode == HookExecutionMode::FireAndForget {
+                exec_config.mode = HookExecutionMode::Async;
+            }
+            let exec_context = ExecutionContext::new(hook_context, exec_config)
+                .with_hook_id(hook_id)
+                .with_dependencies(hook.depends_on.clone());
+            executions.push((executor, exec_context));
+        }
 
-        // Create hook execution context
+        let hook_ids: HashMap<String, String> = executions
+            .iter()
+            .map(|(_, context)| (context.execution_id.clone(), context.dependency_key()))
+            .collect();
+        let mut results = match budget {
+            _ if executions.is_empty() => AggregatedResults::default(),
+            Some(budget) => self.coordinator.execute_hooks_within(executions, budget).await,
+            None => self.coordinator.execute_hooks(executions).await,
+        };
+        if !guarded.is_empty() {
+            let guarded_results = join_all(
+                guarded
+                    .iter()
+                    .map(|(hook, fallback)| self.execute_with_fallback(hook, fallback, &context)),
+            )
+            .await;
+            let mut all = std::mem::take(&mut results.results);
+            for ((hook, _), result) in guarded.into_iter().zip(guarded_results) {
+                match result {
+                    Ok(result) => {
+                        self.record_execution(&hook.get_id(), event.event_type(), &result.result, result.duration, result.retry_attempts);
+                        self.record_performance(&hook.get_id(), event.event_type(), &result, 1);
+                        all.push(result);
+                    }
+                    Err(e) => skipped.push(SkippedHook::new(hook.get_id(), SkipReason::NoMatchingExecutor, Some(e.to_string()))),
+                }
+            }
+            results = AggregatedResults::from_results(all).with_skipped(std::mem::take(&mut results.skipped));
+        }
+        if !fallback_for.is_empty() {
+            let mut all = std::mem::take(&mut results.results);
+            for result in &mut all {
+                let primary_id = hook_ids.get(&result.execution_id).and_then(|hook_id| fallback_for.get(hook_id));
+                if let Some(primary_id) = primary_id {
+                    let metadata = &mut result.result.metadata;
+                    metadata.insert("fallback_for".to_string(), serde_json::json!(primary_id));
+                    metadata.insert("fallback_reason".to_string(), serde_json::json!("circuit_open"));
+                }
+            }
+            results = AggregatedResults::from_results(all).with_skipped(std::mem::take(&mut results.skipped));
+        }
+        let concurrency_level = results.results.len() as u32;
+        for result in &results.results {
+            if let Some(hook_id) = hook_ids.get(&result.execution_id) {
+                self.record_execution(hook_id, event.event_type(), &result.result, result.duration, result.retry_attempts);
+                self.record_performance(hook_id, event.event_type(), result, concurrency_level);
+            }
+        }
+        Ok(self.record_skips(results.with_skipped(skipped)))
+    }
+
+    /// Feed a coordinated execution to the attached performance collector, if any.
+    fn record_performance(&self, hook_id: &str, event_type: LifecycleEventType, result: &ExecutionResult, concurrency_level: u32) {
+        if let Some(collector) = &self.performance {
+            let sample = PerformanceSample::from_execution(hook_id, event_type, result, concurrency_level);
+            if let Err(e) = collector.record_sample(sample) {
+                tracing::debug!("Failed to record performance sample for {}: {}", hook_id, e);
+            }
+        }
+    }
+
+    /// Build the context hooks see for `event`, including any patch it carries.
+    fn event_context(&self, event: &LifecycleEvent) -> HookContext {
         let context = HookExecutionContext::new(event.clone(), self.working_directory.clone())
             .env("CODEX_HOOKS_ENABLED".to_string(), "true".to_string())
             .build();
+        match self.pending_patches.patch_for_event(event) {
+            Some(patch) => context.with_metadata(PATCH_METADATA_KEY.to_string(), patch.into()),
+            None => context,
+        }
+    }
 
-        // Get matching hooks from registry
-        let matching_hooks = self.registry.get_matching_hooks(&event, &context)
-            .map_err(|e| HookError::Execution(format!("Failed to get matching hooks: {}", e)))?;
+    /// Count an event occurrence in the attached metrics collector, if any.
+    fn record_event(&self, event_type: LifecycleEventType) {
+        if let Some(collector) = &self.metrics_collector {
+            collector.record_event(event_type);
+        }
+    }
 
-        if matching_hooks.is_empty() {
-            tracing::debug!("No hooks found for event: {:?}", event.event_type());
-            return Ok(());
+    /// Count a finished hook in the attached metrics collector, if any.
+    fn record_execution(
+        &self,
+        hook_id: &str,
+        event_type: LifecycleEventType,
+        result: &HookResult,
+        duration: Duration,
+        retry_count: u32,
+    ) {
+        if let Some(collector) = &self.metrics_collector {
+            collector.record_execution(hook_id, event_type, duration, result.success, result.error.clone(), retry_count);
+            if result.metadata.get(DEDUPLICATED_METADATA_KEY) == Some(&serde_json::json!(true)) {
+                collector.record_deduplicated(hook_id);
+            }
         }
+    }
 
-        tracing::info!("Found {} matching hooks for event: {:?}", matching_hooks.len(), event.event_type());
+    /// Log skipped hooks and count them in the attached metrics collector, if any.
+    fn record_skips(&self, results: AggregatedResults) -> AggregatedResults {
+        for skipped in &results.skipped {
+            tracing::debug!("Skipped hook {}: {}", skipped.hook_id, skipped.reason.as_str());
+            if let Some(collector) = &self.metrics_collector {
+                collector.record_skip(&skipped.hook_id, skipped.reason);
+            }
+        }
+        results
+    }
 
-        // Execute hooks based on their execution mode
-        let results = self.execute_hooks(matching_hooks, &context).await?;
+    /// Run a single hook on demand against `event`, or a synthetic event of the hook's type.
+    ///
+    /// With `dry_run` the hook is resolved and checked but not executed.
+    pub async fn test_fire(
+        &self,
+        hook_id: &str,
+        event: Option<LifecycleEvent>,
+        dry_run: bool,
+    ) -> Result<ExecutionResult, HookError> {
+        let hook = self.registry.get_hook(hook_id)
+            .ok_or_else(|| HookError::Registry(format!("Hook not found: {}", hook_id)))?;
+
+        let event = event.unwrap_or_else(|| LifecycleEvent::synthetic(hook.event));
+        if event.event_type() != hook.event {
+            return Err(HookError::Validation(format!(
+                "Hook {} handles {} events, not {}",
+                hook_id,
+                hook.event,
+                event.event_type()
+            )));
+        }
 
-        // Log execution results
-        let total_duration = start_time.elapsed();
-        self.log_execution_results(&results, total_duration);
+        let executor = self.get_executor_for_hook(&hook.hook_type)?.clone();
+        let mut exec_config = self.config_resolver.resolve_for_hook(executor.as_ref(), hook);
+        // Always wait for the result, whatever the hook's configured mode
+        exec_config.mode = HookExecutionMode::Blocking;
 
-        // Handle any critical failures
-        self.handle_execution_results(&results)?;
+        let event_context = HookExecutionContext::new(event, self.working_directory.clone())
+            .env("CODEX_HOOKS_ENABLED".to_string(), "true".to_string())
+            .env("CODEX_HOOKS_TEST_FIRE".to_string(), "true".to_string())
+            .build();
+        let hook_context = Self::context_for_hook(&event_context, hook);
+        let context = ExecutionContext::new(hook_context, exec_config);
+
+        tracing::info!("Test-firing hook {} (dry_run: {})", hook_id, dry_run);
+
+        if dry_run {
+            let result = if executor.can_execute(&context.hook_context) {
+                HookResult::success(
+                    Some(format!("Dry run: {} executor would run hook {}", executor.executor_type(), hook_id)),
+                    Duration::ZERO,
+                )
+            } else {
+                HookResult::failure(
+                    format!("Dry run: {} executor would decline hook {}", executor.executor_type(), hook_id),
+                    Duration::ZERO,
+                )
+            };
+
+            return Ok(ExecutionResult {
+                execution_id: context.execution_id.clone(),
+                result: result.with_metadata("dry_run".to_string(), serde_json::Value::Bool(true)),
+                config: context.config.clone(),
+                duration: Duration::ZERO,
+                retry_attempts: 0,
+                cancelled: false,
+                cancel_reason: None,
+                error_details: None,
+                timing_breakdown: None,
+            });
+        }
 
-        Ok(())
+        self.coordinator
+            .execute_hooks(vec![(executor, context)])
+            .await
+            .results
+            .into_iter()
+            .next()
+            .ok_or_else(|| HookError::Execution(format!("Test fire of hook {} produced no result", hook_id)))
     }
 
     /// Check if hooks are enabled.
@@ -139,6 +651,11 @@ impl HookManager {
         self.registry.clone()
     }
 
+    /// Get the directory hooks run in.
+    pub fn working_directory(&self) -> &Path {
+        &self.working_directory
+    }
+
     /// Get execution metrics.
     pub fn metrics(&self) -> &HookExecutionMetrics {
         &self.metrics
@@ -151,8 +668,8 @@ impl HookManager {
             successful_executions: self.metrics.successful_executions,
             failed_executions: self.metrics.failed_executions,
             cancelled_executions: 0, // Not tracked in current metrics
-            total_execution_time: Duration::from_millis(self.metrics.total_execution_time_ms),
-            average_execution_time: Duration::from_millis(self.metrics.average_execution_time_ms),
+            total_execution_time: self.metrics.total_execution_time,
+            average_execution_time: self.metrics.average_execution_time,
         }
     }
 
@@ -163,157 +680,123 @@ impl HookManager {
         // For now, this is a no-op since metrics is not mutable
     }
 
-    /// Execute a list of hooks with the given context.
-    async fn execute_hooks(
+    /// Run a hook guarded by its circuit breaker, falling back when the circuit is open
+    /// or the hook fails after its retries.
+    ///
+    /// Both attempts are recorded in history, linked through `fallback_execution_id` on the
+    /// primary and `primary_execution_id` / `fallback_for` on the fallback.
+    async fn execute_with_fallback(
         &self,
-        hooks: Vec<&crate::hooks::config::HookConfig>,
+        hook: &HookConfig,
+        fallback: &HookConfig,
         context: &HookContext,
-    ) -> Result<HookExecutionResults, HookError> {
-        let mut successful = Vec::new();
-        let mut failed = Vec::new();
-        let start_time = Instant::now();
-
-        // Separate hooks by execution mode
-        let (blocking_hooks, async_hooks, fire_and_forget_hooks): (Vec<_>, Vec<_>, Vec<_>) = hooks
-            .into_iter()
-            .partition3(|hook| match hook.mode {
-                HookExecutionMode::Blocking => (true, false, false),
-                HookExecutionMode::Async => (false, true, false),
-                HookExecutionMode::FireAndForget => (false, false, true),
-            });
-
-        // Execute blocking hooks first (sequentially)
-        for hook in blocking_hooks {
-            let result = self.execute_single_hook(hook, context).await;
-            match result {
-                Ok(exec_result) => {
-                    if exec_result.result.success {
-                        successful.push(exec_result);
-                    } else {
-                        failed.push(exec_result.clone());
-                        // For blocking hooks, if required and failed, stop execution
-                        if hook.required {
-                            return Err(HookError::Execution(format!(
-                                "Required blocking hook failed: {}",
-                                exec_result.hook_description
-                            )));
-                        }
-                    }
-                }
-                Err(e) => {
-                    let exec_result = HookExecutionResult {
-                        hook_description: self.get_hook_description(hook),
-                        result: HookResult::failure(e.to_string(), Duration::from_secs(0)),
-                        execution_time: Duration::from_secs(0),
-                    };
-                    failed.push(exec_result);
-                    if hook.required {
-                        return Err(e);
-                    }
-                }
-            }
-        }
-
-        // Execute async hooks in parallel
-        if !async_hooks.is_empty() {
-            let async_futures: Vec<_> = async_hooks
-                .into_iter()
-                .map(|hook| self.execute_single_hook(hook, context))
-                .collect();
-
-            let async_results = join_all(async_futures).await;
-            for (i, result) in async_results.into_iter().enumerate() {
-                match result {
-                    Ok(exec_result) => {
-                        if exec_result.result.success {
-                            successful.push(exec_result);
-                        } else {
-                            failed.push(exec_result);
-                        }
-                    }
-                    Err(e) => {
-                        let exec_result = HookExecutionResult {
-                            hook_description: format!("Async hook {}", i),
-                            result: HookResult::failure(e.to_string(), Duration::from_secs(0)),
-                            execution_time: Duration::from_secs(0),
-                        };
-                        failed.push(exec_result);
-                    }
-                }
+    ) -> Result<ExecutionResult, HookError> {
+        let hook_id = hook.get_id();
+        let breaker = self.circuit_breakers.breaker(&hook_id);
+        let started_at = chrono::Utc::now();
+
+        let primary = if let Some(permit) = breaker.try_acquire() {
+            let result = self.execute_coordinated(hook, context).await?;
+            if result.result.success {
+                permit.record_success();
+            } else {
+                permit.record_failure();
             }
-        }
-
-        // Execute fire-and-forget hooks (don't wait for results)
-        if !fire_and_forget_hooks.is_empty() {
-            for hook in fire_and_forget_hooks {
-                let hook_description = self.get_hook_description(hook);
-                let _context_clone = context.clone();
+            Some(result)
+        } else {
+            None
+        };
 
-                // Create a simple executor for fire-and-forget (we'll implement this properly later)
-                tokio::spawn(async move {
-                    tracing::debug!("Fire-and-forget hook started: {}", hook_description);
-                    // TODO: Implement actual execution in Phase 2.2
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    tracing::debug!("Fire-and-forget hook completed: {}", hook_description);
-                });
+        let reason = match &primary {
+            Some(result) if result.result.success => {
+                self.record_history(hook, started_at, result);
+                return Ok(result.clone());
             }
+            Some(_) => "primary_failed",
+            None => "circuit_open",
+        };
+        tracing::warn!("Hook {} falling back to {} ({})", hook_id, fallback.get_id(), reason);
+
+        let fallback_started_at = chrono::Utc::now();
+        let mut fallback_result = self.execute_coordinated(fallback, context).await?;
+        fallback_result.result = fallback_result
+            .result
+            .with_metadata("fallback_for".to_string(), serde_json::json!(hook_id))
+            .with_metadata("fallback_reason".to_string(), serde_json::json!(reason));
+
+        if let Some(mut primary) = primary {
+            fallback_result.result = fallback_result
+                .result
+                .with_metadata("primary_execution_id".to_string(), serde_json::json!(primary.execution_id));
+            primary.result = primary
+                .result
+                .with_metadata("fallback_execution_id".to_string(), serde_json::json!(fallback_result.execution_id));
+            self.record_history(hook, started_at, &primary);
         }
+        self.record_history(fallback, fallback_started_at, &fallback_result);
 
-        Ok(HookExecutionResults {
-            successful,
-            failed,
-            total_duration: start_time.elapsed(),
-        })
+        Ok(fallback_result)
     }
 
-    /// Execute a single hook with timeout and error handling.
-    async fn execute_single_hook(
-        &self,
-        hook: &crate::hooks::config::HookConfig,
-        context: &HookContext,
-    ) -> Result<HookExecutionResult, HookError> {
-        let start_time = Instant::now();
-        let hook_description = self.get_hook_description(hook);
-
-        tracing::debug!("Executing hook: {}", hook_description);
-
-        // Get the appropriate executor
-        let executor = self.get_executor_for_hook(&hook.hook_type)?;
+    /// The context a hook's executor runs with: the event context plus the hook's type and id.
+    fn context_for_hook(context: &HookContext, hook: &HookConfig) -> HookContext {
+        context
+            .clone()
+            .with_hook_type(hook.hook_type.clone())
+            .with_metadata(HOOK_ID_METADATA_KEY.to_string(), hook.get_id().into())
+    }
 
-        // Get timeout (from hook config or global default)
-        let timeout_duration = hook.get_timeout(Duration::from_secs(self.config.hooks.timeout_seconds));
+    /// Run a single hook through the coordinator and wait for its result.
+    async fn execute_coordinated(&self, hook: &HookConfig, context: &HookContext) -> Result<ExecutionResult, HookError> {
+        let executor = self.get_executor_for_hook(&hook.hook_type)?.clone();
+        let mut exec_config = self.config_resolver.resolve_for_hook(executor.as_ref(), hook);
+        exec_config.mode = HookExecutionMode::Blocking;
+        let hook_context = Self::context_for_hook(context, hook);
+
+        self.coordinator
+            .execute_hooks(vec![(executor, ExecutionContext::new(hook_context, exec_config))])
+            .await
+            .results
+            .into_iter()
+            .next()
+            .ok_or_else(|| HookError::Execution(format!("Hook {} produced no result", hook.get_id())))
+    }
 
-        // Execute with timeout
-        let result = match timeout(timeout_duration, executor.execute(context)).await {
-            Ok(Ok(hook_result)) => {
-                tracing::debug!("Hook executed successfully: {}", hook_description);
-                hook_result
-            }
-            Ok(Err(e)) => {
-                tracing::warn!("Hook execution failed: {} - {}", hook_description, e);
-                HookResult::failure(e.to_string(), start_time.elapsed())
-            }
-            Err(_) => {
-                let error_msg = format!("Hook execution timed out after {:?}", timeout_duration);
-                tracing::warn!("{}: {}", error_msg, hook_description);
-                HookResult::failure(error_msg, timeout_duration)
-            }
+    /// Record an execution in the attached history, if any.
+    fn record_history(&self, hook: &HookConfig, started_at: chrono::DateTime<chrono::Utc>, result: &ExecutionResult) {
+        let Some(history) = &self.history else {
+            return;
         };
 
-        Ok(HookExecutionResult {
-            hook_description,
-            result,
-            execution_time: start_time.elapsed(),
-        })
+        let hook_config = serde_json::to_value(hook).unwrap_or(serde_json::Value::Null);
+        if let Err(e) = history.record_execution(
+            result.execution_id.clone(),
+            hook.get_id(),
+            hook.event,
+            started_at,
+            &result.result,
+            result.retry_attempts,
+            result.cancel_reason,
+            hook_config,
+            None,
+        ) {
+            tracing::warn!("Failed to record hook {} in history: {}", hook.get_id(), e);
+        }
     }
 
     /// Get the appropriate executor for a hook type.
-    fn get_executor_for_hook(&self, hook_type: &HookType) -> Result<&Box<dyn HookExecutor>, HookError> {
+    pub(crate) fn get_executor_for_hook(&self, hook_type: &HookType) -> Result<&Arc<dyn HookExecutor>, HookError> {
         let executor_key = match hook_type {
             HookType::Script { .. } => "script",
             HookType::Webhook { .. } => "webhook",
             HookType::McpTool { .. } => "mcp_tool",
             HookType::Executable { .. } => "executable",
+            HookType::Database { .. } => "database",
+            HookType::MessageQueue { .. } => "message_queue",
+            HookType::FileSystem { .. } => "filesystem",
+            HookType::CustomPlugin { .. } => "custom_plugin",
+            HookType::PatchAnalysis { .. } => "patch_analysis",
+            HookType::DesktopNotification { .. } => "desktop_notification",
         };
 
         self.executors.get(executor_key).ok_or_else(|| {
@@ -321,107 +804,192 @@ impl HookManager {
         })
     }
 
-    /// Get a human-readable description of a hook.
-    fn get_hook_description(&self, hook: &crate::hooks::config::HookConfig) -> String {
-        hook.description
-            .clone()
-            .unwrap_or_else(|| format!("{:?} hook", hook.hook_type))
-    }
-
-    /// Log the results of hook execution.
-    fn log_execution_results(&self, results: &HookExecutionResults, total_duration: Duration) {
-        let total_hooks = results.successful.len() + results.failed.len();
-
-        tracing::info!(
-            "Hook execution completed: {}/{} successful, {} failed, took {:?}",
-            results.successful.len(),
-            total_hooks,
-            results.failed.len(),
-            total_duration
-        );
+    /// Log the results of a triggered event.
+    fn log_execution_results(&self, results: &AggregatedResults, total_duration: Duration) {
+        tracing::info!("Hook execution completed: {}, took {:?}", results.summary(), total_duration);
 
         // Log individual failures
         for failed_result in &results.failed {
             tracing::warn!(
                 "Hook failed: {} - {}",
-                failed_result.hook_description,
-                failed_result.result.error.as_ref().unwrap_or(&"Unknown error".to_string())
+                failed_result.execution_id,
+                failed_result.result.error.as_deref().unwrap_or("Unknown error")
             );
         }
+    }
+}
 
-        // Log performance metrics
-        if !results.successful.is_empty() {
-            let avg_time: Duration = results.successful
-                .iter()
-                .map(|r| r.execution_time)
-                .sum::<Duration>() / results.successful.len() as u32;
-
-            tracing::debug!("Average successful hook execution time: {:?}", avg_time);
+impl Drop for HookManager {
+    fn drop(&mut self) {
+        self.coordinator.shutdown_now();
+        if let Some(dashboard) = &self.dashboard {
+            let _ = dashboard.cancel_all_tracking(CancelReason::Shutdown);
         }
     }
+}
 
-    /// Handle execution results and determine if any critical failures occurred.
-    fn handle_execution_results(&self, results: &HookExecutionResults) -> Result<(), HookError> {
-        // Check if there were any critical failures that should stop execution
-        let critical_failures: Vec<_> = results.failed
-            .iter()
-            .filter(|result| result.hook_description.contains("required"))
-            .collect();
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use crate::hooks::config::HookConfig;
+    use crate::hooks::executor::HookExecutorResult;
+    use crate::hooks::types::{HookPriority, LifecycleEventType};
+    use tempfile::TempDir;
 
-        if !critical_failures.is_empty() {
-            let error_msg = format!(
-                "Critical hook failures detected: {}",
-                critical_failures
-                    .iter()
-                    .map(|r| r.hook_description.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-            return Err(HookError::Execution(error_msg));
+    /// Executor that returns a fixed outcome without running anything.
+    struct StubExecutor {
+        succeed: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl HookExecutor for StubExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            if self.succeed {
+                Ok(HookResult::success(Some("ok".to_string()), Duration::from_millis(1)))
+            } else {
+                Ok(HookResult::failure("pre-flight check failed".to_string(), Duration::from_millis(1)))
+            }
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "stub"
         }
 
-        Ok(())
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
     }
-}
 
-/// Helper trait for partitioning iterators into three groups.
-trait Partition3<T> {
-    fn partition3<F>(self, predicate: F) -> (Vec<T>, Vec<T>, Vec<T>)
-    where
-        F: Fn(&T) -> (bool, bool, bool);
-}
+    /// Executor that runs far longer than any hook timeout.
+    struct SlowExecutor;
+
+    #[async_trait::async_trait]
+    impl HookExecutor for SlowExecutor {
+        async fn execute(&self, _context: &HookContext) -> HookExecutorResult {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(HookResult::success(None, Duration::from_secs(30)))
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "slow"
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
 
-impl<I, T> Partition3<T> for I
-where
-    I: Iterator<Item = T>,
-{
-    fn partition3<F>(self, predicate: F) -> (Vec<T>, Vec<T>, Vec<T>)
-    where
-        F: Fn(&T) -> (bool, bool, bool),
-    {
-        let mut first = Vec::new();
-        let mut second = Vec::new();
-        let mut third = Vec::new();
-
-        for item in self {
-            let (is_first, is_second, is_third) = predicate(&item);
-            if is_first {
-                first.push(item);
-            } else if is_second {
-                second.push(item);
-            } else if is_third {
-                third.push(item);
+        fn default_config(&self) -> crate::hooks::executor::ExecutionConfig {
+            crate::hooks::executor::ExecutionConfig {
+                timeout: Duration::from_secs(60),
+                ..Default::default()
             }
         }
+    }
 
-        (first, second, third)
+    /// Executor that sleeps for the number of milliseconds given as the script command.
+    struct SleepExecutor;
+
+    #[async_trait::async_trait]
+    impl HookExecutor for SleepExecutor {
+        async fn execute(&self, context: &HookContext) -> HookExecutorResult {
+            let millis = match &context.hook_type {
+                HookType::Script { command, .. } => command[0].parse().unwrap_or(0),
+                _ => 0,
+            };
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            Ok(HookResult::success(Some(format!("slept {}ms", millis)), Duration::from_millis(millis)))
+        }
+
+        fn executor_type(&self) -> &'static str {
+            "sleep"
+        }
+
+        fn can_execute(&self, _context: &HookContext) -> bool {
+            true
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+    fn create_exec_hook(required: bool) -> HookConfig {
+        HookConfig {
+            id: Some("preflight".to_string()),
+            event: LifecycleEventType::ExecBefore,
+            hook_type: HookType::Script {
+                command: vec!["true".to_string()],
+                cwd: None,
+                environment: HashMap::new(),
+                timeout: None,
+                resource_limits: None,
+            },
+            mode: HookExecutionMode::Blocking,
+            priority: HookPriority::NORMAL,
+            condition: None,
+            blocking: true,
+            required,
+            tags: Vec::new(),
+            description: Some("Pre-flight check".to_string()),
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
+        }
+    }
+
+    fn create_exec_event() -> LifecycleEvent {
+        LifecycleEvent::ExecBefore {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    async fn create_gated_manager(required: bool, succeed: bool) -> HookManager {
+        let mut config = create_test_config();
+        config.hooks.exec.push(create_exec_hook(required));
+
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed }));
+        manager
+    }
+
+    fn create_fallback_hook(url: String, fallback_path: PathBuf) -> HookConfig {
+        let mut fallback = create_exec_hook(false);
+        fallback.id = Some("local_log".to_string());
+        fallback.event = LifecycleEventType::TaskStart;
+        fallback.hook_type = HookType::FileSystem {
+            operation: crate::hooks::types::FileSystemOperation::Write,
+            path: fallback_path,
+            target_path: None,
+            content: Some("webhook unavailable".to_string()),
+            timeout: None,
+            permissions: None,
+            max_events: None,
+            atomic: false,
+        };
+
+        let mut hook = create_exec_hook(false);
+        hook.id = Some("notify".to_string());
+        hook.event = LifecycleEventType::TaskStart;
+        hook.hook_type = HookType::Webhook {
+            url,
+            method: crate::hooks::types::HttpMethod::Post,
+            headers: HashMap::new(),
+            timeout: None,
+            retry_count: None,
+            payload_mode: crate::hooks::types::WebhookPayloadMode::Raw,
+            success_criteria: None,
+            signing: None,
+            success_when: None,
+            capture: None,
+            body: None,
+        };
+        hook.fallback = Some(Box::new(fallback));
+        hook
+    }
 
     fn create_test_config() -> HooksConfig {
         HooksConfig {
@@ -437,6 +1005,9 @@ mod tests {
                 agent: Vec::new(),
                 error: Vec::new(),
                 integration: Vec::new(),
+                proxy: Default::default(),
+                message_queue: Default::default(),
+                payload_dedup_window_seconds: None,
             },
         }
     }
@@ -447,7 +1018,83 @@ mod tests {
         let manager = HookManager::new(config).await.unwrap();
 
         assert!(manager.is_enabled());
-        assert_eq!(manager.executors.len(), 4); // script, webhook, mcp_tool, executable
+        assert_eq!(manager.executors.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_patch_after_hooks_receive_the_announced_patch() {
+        use crate::hooks::executors::patch_analysis::{PatchStats, PATCH_STATS_METADATA_KEY};
+
+        let mut config = create_test_config();
+        let mut hook = create_exec_hook(false);
+        hook.id = Some("patch_stats".to_string());
+        hook.event = LifecycleEventType::PatchAfter;
+        hook.hook_type = HookType::PatchAnalysis { timeout: None };
+        config.hooks.patch.push(hook);
+        let manager = HookManager::new(config).await.unwrap();
+
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n fn a() {}\n+fn b() {}\n";
+        manager
+            .dispatch_event_collected(LifecycleEvent::PatchBefore {
+                call_id: "call-1".to_string(),
+                task_id: "task-1".to_string(),
+                changes: HashMap::from([(PathBuf::from("src/lib.rs"), diff.to_string())]),
+                timestamp: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        let results = manager
+            .dispatch_event_collected(LifecycleEvent::PatchAfter {
+                call_id: "call-1".to_string(),
+                task_id: "task-1".to_string(),
+                success: true,
+                applied_files: vec![PathBuf::from("src/lib.rs")],
+                duration: Duration::from_millis(5),
+                timestamp: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(results.successful.len(), 1);
+        let stats: PatchStats =
+            serde_json::from_value(results.successful[0].result.metadata[PATCH_STATS_METADATA_KEY].clone()).unwrap();
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(stats.lines_added, 1);
+    }
+
+    #[tokio::test]
+    async fn test_message_queue_batching_from_config() {
+        let mut config = create_test_config();
+        config.hooks.message_queue.batching = Some(crate::hooks::config::MessageBatchingConfig {
+            max_batch: 2,
+            flush_interval_ms: 60_000,
+        });
+        let executor = HookManager::message_queue_executor(&config, Arc::new(EnvSecretProvider));
+
+        let contexts: Vec<_> = ["deploy 1", "deploy 2"]
+            .into_iter()
+            .map(|message| {
+                HookContext::new(create_exec_event(), PathBuf::from("/tmp")).with_hook_type(HookType::MessageQueue {
+                    queue_url: "https://hooks.servicebus.windows.net/deployments".to_string(),
+                    message: message.to_string(),
+                    queue_type: crate::hooks::types::MessageQueueType::AzureServiceBus,
+                    timeout: None,
+                    routing_key: None,
+                    headers: HashMap::new(),
+                    idempotency_key: None,
+                    payload_mode: crate::hooks::types::MessagePayloadMode::Passthrough,
+                    credentials: None,
+                })
+            })
+            .collect();
+
+        // A full batch is sent at once, well before the flush interval, one request per
+        // message since Azure Service Bus has no batch API
+        let start = Instant::now();
+        let results = join_all(contexts.iter().map(|context| executor.execute(context))).await;
+        assert!(results.into_iter().all(|result| result.unwrap().success));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(executor.backend_calls(), 2);
     }
 
     #[tokio::test]
@@ -511,50 +1158,614 @@ mod tests {
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn test_partition3_helper() {
-        let items = vec![1, 2, 3, 4, 5, 6];
-        let (first, second, third) = items.into_iter().partition3(|&x| {
-            if x % 3 == 0 {
-                (true, false, false)  // divisible by 3
-            } else if x % 2 == 0 {
-                (false, true, false)  // even
-            } else {
-                (false, false, true)  // odd
+    #[tokio::test]
+    async fn test_dispatch_event_gated_aborts_on_required_failure() {
+        let manager = create_gated_manager(true, false).await;
+
+        let decision = manager.dispatch_event_gated(create_exec_event()).await.unwrap();
+        match decision {
+            GateDecision::Abort { reason } => assert!(reason.contains("pre-flight check failed")),
+            GateDecision::Proceed => panic!("expected Abort for failing required hook"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_event_gated_proceeds_on_success() {
+        let manager = create_gated_manager(true, true).await;
+
+        let decision = manager.dispatch_event_gated(create_exec_event()).await.unwrap();
+        assert_eq!(decision, GateDecision::Proceed);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_event_gated_ignores_optional_failure() {
+        let manager = create_gated_manager(false, false).await;
+
+        let decision = manager.dispatch_event_gated(create_exec_event()).await.unwrap();
+        assert!(!decision.is_abort());
+    }
+
+    #[tokio::test]
+    async fn test_trigger_event_fails_on_required_hook_and_honours_policy() {
+        let mut config = create_test_config();
+        config.hooks.exec.push(create_exec_hook(true));
+        let mut follow_up = create_exec_hook(false);
+        follow_up.id = Some("follow_up".to_string());
+        config.hooks.exec.push(follow_up);
+
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed: false }));
+
+        // Required comes from the hook's resolved config, not its description
+        let err = manager.trigger_event(create_exec_event()).await.unwrap_err();
+        assert!(err.to_string().contains("pre-flight check failed"));
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        assert_eq!(results.failed.len(), 2);
+
+        let manager = manager.with_required_failure_policy(RequiredFailurePolicy::SkipBlocking);
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        assert_eq!(results.failed.len(), 1);
+        assert_eq!(results.skipped.len(), 1);
+        assert_eq!(results.skipped[0].hook_id, "follow_up");
+        assert_eq!(results.skipped[0].reason, SkipReason::RequiredHookFailed);
+    }
+
+    #[tokio::test]
+    async fn test_hook_timeout_overrides_executor_default() {
+        let mut hook = create_exec_hook(false);
+        hook.timeout = Some(Duration::from_secs(1));
+        let mut config = create_test_config();
+        config.hooks.exec.push(hook.clone());
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(SlowExecutor));
+
+        let start = Instant::now();
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(results.failed.len(), 1);
+        assert!(results.failed[0].result.error.as_deref().unwrap().contains("timed out after 1s"));
+        assert!(elapsed >= Duration::from_secs(1));
+        assert!(elapsed < Duration::from_secs(5));
+
+        // Without a hook timeout the executor default applies.
+        hook.timeout = None;
+        let executor = manager.get_executor_for_hook(&hook.hook_type).unwrap();
+        let config = manager.config_resolver.resolve_for_hook(executor.as_ref(), &hook);
+        assert_eq!(config.timeout, Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_global_timeout_applies_to_hooks_without_their_own() {
+        let mut config = create_test_config();
+        config.hooks.timeout_seconds = 1;
+        config.hooks.exec.push(create_exec_hook(false));
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(SlowExecutor));
+
+        let start = Instant::now();
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        assert_eq!(results.failed.len(), 1);
+        assert!(results.failed[0].result.error.as_deref().unwrap().contains("timed out after 1s"));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // A hook timeout still wins over the global one
+        let mut hook = create_exec_hook(false);
+        hook.timeout = Some(Duration::from_secs(2));
+        let executor = manager.get_executor_for_hook(&hook.hook_type).unwrap();
+        let config = manager.config_resolver.resolve_for_hook(executor.as_ref(), &hook);
+        assert_eq!(config.timeout, Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_feeds_performance_collector() {
+        let collector = Arc::new(PerformanceCollector::default());
+        let manager = create_gated_manager(false, true).await.with_performance_collector(collector.clone());
+
+        manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.recent_samples.len(), 1);
+        let sample = &data.recent_samples[0];
+        assert_eq!(sample.hook_id, "preflight");
+        assert_eq!(sample.event_type, LifecycleEventType::ExecBefore);
+        assert!(sample.success);
+        assert!(sample.timing_breakdown.is_some());
+        assert!(data.hook_performance.contains_key("preflight"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_closes_dashboard_tracking() {
+        use crate::hooks::dashboard::DashboardEventType;
+        use crate::hooks::history::HistoryConfig;
+        use crate::hooks::metrics::MetricsConfig;
+
+        let temp_dir = TempDir::new().unwrap();
+        let history = Arc::new(
+            HistoryManager::new(HistoryConfig {
+                persist_to_disk: false,
+                history_directory: temp_dir.path().to_path_buf(),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        let dashboard = Arc::new(HookDashboard::with_defaults(
+            Arc::new(MetricsCollector::new(MetricsConfig::default())),
+            history,
+        ));
+        let manager = create_gated_manager(false, true).await.with_dashboard(dashboard.clone());
+        let mut events = dashboard.subscribe();
+
+        dashboard
+            .start_execution_tracking("exec_1".to_string(), "preflight".to_string(), LifecycleEventType::ExecBefore, None)
+            .unwrap();
+        manager.shutdown().await;
+
+        let data = dashboard.get_dashboard_data().await.unwrap();
+        assert!(data.active_executions.is_empty());
+        let mut cancelled = None;
+        while let Ok(event) = events.try_recv() {
+            if event.event_type == DashboardEventType::ExecutionCancelled {
+                cancelled = Some(event);
             }
-        });
+        }
+        let cancelled = cancelled.unwrap();
+        assert_eq!(cancelled.hook_id.as_deref(), Some("preflight"));
+        assert_eq!(cancelled.metadata["cancel_reason"], serde_json::json!(CancelReason::Shutdown));
+
+        // Dropping the manager closes out anything still tracked
+        let manager = create_gated_manager(false, true).await.with_dashboard(dashboard.clone());
+        dashboard
+            .start_execution_tracking("exec_2".to_string(), "preflight".to_string(), LifecycleEventType::ExecBefore, None)
+            .unwrap();
+        drop(manager);
+        assert!(dashboard.get_dashboard_data().await.unwrap().active_executions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_payload_dedup_from_config_is_counted() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = create_test_config();
+        config.hooks.payload_dedup_window_seconds = Some(60);
+        let mut hook = create_fallback_hook(server.uri(), PathBuf::from("/tmp/unused.log"));
+        hook.fallback = None;
+        config.hooks.task.push(hook);
+
+        let collector = Arc::new(MetricsCollector::default());
+        let manager = HookManager::new(config).await.unwrap().with_metrics_collector(collector.clone());
+        let event = LifecycleEvent::TaskStart {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            prompt: "deploy".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
 
-        assert_eq!(first, vec![3, 6]);  // divisible by 3
-        assert_eq!(second, vec![2, 4]); // even (but not divisible by 3)
-        assert_eq!(third, vec![1, 5]);  // odd
+        for _ in 0..2 {
+            let results = manager.dispatch_event_collected(event.clone()).await.unwrap();
+            assert_eq!(results.successful.len(), 1);
+        }
+        server.verify().await;
+
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.deduplicated_sends, 1);
+        assert_eq!(metrics.deduplicated_by_hook_id["notify"], 1);
     }
 
-    #[test]
-    fn test_hook_execution_results() {
-        let successful = vec![
-            HookExecutionResult {
-                hook_description: "test hook 1".to_string(),
-                result: HookResult::success(Some("output".to_string()), Duration::from_millis(100)),
-                execution_time: Duration::from_millis(100),
-            },
-        ];
+    #[tokio::test]
+    async fn test_collected_dispatch_runs_fallback_when_circuit_open() {
+        let temp_dir = TempDir::new().unwrap();
+        let fallback_path = temp_dir.path().join("fallback.log");
+        let mut config = create_test_config();
+        config.hooks.task.push(create_fallback_hook("http://127.0.0.1:1".to_string(), fallback_path.clone()));
 
-        let failed = vec![
-            HookExecutionResult {
-                hook_description: "test hook 2".to_string(),
-                result: HookResult::failure("error".to_string(), Duration::from_millis(50)),
-                execution_time: Duration::from_millis(50),
-            },
-        ];
+        let manager = HookManager::new(config).await.unwrap();
+        let breaker = manager.circuit_breakers.breaker("notify");
+        for _ in 0..CircuitBreakerConfig::default().failure_threshold {
+            breaker.record_failure();
+        }
+
+        let results = manager
+            .dispatch_event_collected(LifecycleEvent::synthetic(LifecycleEventType::TaskStart))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&fallback_path).unwrap(), "webhook unavailable");
+        assert_eq!(results.successful.len(), 1);
+        let metadata = &results.successful[0].result.metadata;
+        assert_eq!(metadata.get("fallback_for"), Some(&serde_json::json!("notify")));
+        assert_eq!(metadata.get("fallback_reason"), Some(&serde_json::json!("circuit_open")));
+        assert_eq!(results.skipped.len(), 1);
+        assert_eq!(results.skipped[0].reason, SkipReason::CircuitOpen);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_runs_when_primary_fails_or_circuit_open() {
+        use crate::hooks::history::{HistoryConfig, HistoryFilter};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
 
-        let results = HookExecutionResults {
-            successful,
-            failed,
-            total_duration: Duration::from_millis(150),
+        let temp_dir = TempDir::new().unwrap();
+        let fallback_path = temp_dir.path().join("fallback.log");
+        let mut config = create_test_config();
+        config.hooks.task.push(create_fallback_hook(server.uri(), fallback_path.clone()));
+
+        let history = Arc::new(
+            HistoryManager::new(HistoryConfig {
+                persist_to_disk: false,
+                history_directory: temp_dir.path().to_path_buf(),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        let manager = HookManager::new(config)
+            .await
+            .unwrap()
+            .with_history(history.clone())
+            .with_circuit_breaker_config(CircuitBreakerConfig {
+                failure_threshold: 1,
+                reset_timeout: Duration::from_secs(60),
+                failure_window: None,
+            });
+
+        let event = LifecycleEvent::synthetic(LifecycleEventType::TaskStart);
+
+        // First failure opens the circuit and falls back
+        manager.trigger_event(event.clone()).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&fallback_path).unwrap(), "webhook unavailable");
+        assert_eq!(manager.circuit_state("notify"), CircuitState::Open);
+
+        // Open circuit skips the webhook entirely
+        std::fs::remove_file(&fallback_path).unwrap();
+        manager.trigger_event(event).await.unwrap();
+        assert!(fallback_path.exists());
+        server.verify().await;
+
+        let primary = history
+            .query_history(HistoryFilter { hook_id: Some("notify".to_string()), ..Default::default() })
+            .unwrap();
+        assert_eq!(primary.len(), 1);
+        assert!(!primary[0].success);
+
+        let fallbacks = history
+            .query_history(HistoryFilter { hook_id: Some("local_log".to_string()), ..Default::default() })
+            .unwrap();
+        assert_eq!(fallbacks.len(), 2);
+        assert!(fallbacks.iter().all(|record| record.success));
+
+        let linked = fallbacks
+            .iter()
+            .find(|record| record.metadata.get("fallback_reason") == Some(&serde_json::json!("primary_failed")))
+            .unwrap();
+        assert_eq!(
+            linked.metadata.get("primary_execution_id"),
+            Some(&serde_json::json!(primary[0].execution_id))
+        );
+        assert_eq!(
+            primary[0].metadata.get("fallback_execution_id"),
+            Some(&serde_json::json!(linked.execution_id))
+        );
+        assert!(fallbacks
+            .iter()
+            .any(|record| record.metadata.get("fallback_reason") == Some(&serde_json::json!("circuit_open"))));
+    }
+
+    fn create_sleep_hook(id: &str, millis: u64, blocking: bool, required: bool) -> HookConfig {
+        let mut hook = create_exec_hook(required);
+        hook.id = Some(id.to_string());
+        hook.event = LifecycleEventType::TaskStart;
+        hook.blocking = blocking;
+        hook.mode = if blocking {
+            HookExecutionMode::Blocking
+        } else {
+            HookExecutionMode::Async
+        };
+        hook.hook_type = HookType::Script {
+            command: vec![millis.to_string()],
+            cwd: None,
+            environment: HashMap::new(),
+            timeout: None,
+            resource_limits: None,
         };
+        hook
+    }
+
+    async fn create_sleep_manager(hooks: Vec<HookConfig>) -> HookManager {
+        let mut config = create_test_config();
+        config.hooks.task = hooks;
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(SleepExecutor));
+        manager
+    }
+
+    fn create_task_event() -> LifecycleEvent {
+        LifecycleEvent::TaskStart {
+            task_id: "test-task".to_string(),
+            session_id: "test-session".to_string(),
+            prompt: "Test task".to_string(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
 
+    #[tokio::test]
+    async fn test_event_budget_cancels_non_required_hooks() {
+        let budget = Duration::from_millis(200);
+        let manager = create_sleep_manager(vec![
+            create_sleep_hook("fast", 10, false, false),
+            create_sleep_hook("slow_a", 5_000, false, false),
+            create_sleep_hook("slow_b", 5_000, false, false),
+            create_sleep_hook("required_slow", 400, false, true),
+        ])
+        .await;
+
+        let start = Instant::now();
+        let results = manager.dispatch_event_within(create_task_event(), budget).await.unwrap();
+        let elapsed = start.elapsed();
+
+        // The required hook outlives the budget; the slow optional ones are cut off at it
+        assert!(elapsed >= Duration::from_millis(400));
+        assert!(elapsed < Duration::from_secs(2));
+        assert_eq!(results.successful.len(), 2);
+        assert!(results.successful.iter().any(|r| r.config.required));
+        assert_eq!(results.cancelled.len(), 2);
+        for cancelled in &results.cancelled {
+            assert_eq!(cancelled.result.error.as_deref(), Some(crate::hooks::executor::EVENT_BUDGET_EXHAUSTED));
+            assert!(cancelled.duration >= budget);
+            assert!(cancelled.duration < Duration::from_secs(1));
+        }
+        assert!(results.skipped.is_empty());
+        assert!(manager.coordinator.get_active_executions().await.is_empty());
+
+        // Hooks that have not started when the budget runs out are skipped rather than cancelled
+        let manager = create_sleep_manager(vec![
+            create_sleep_hook("gate", 300, true, true),
+            create_sleep_hook("late", 10, false, false),
+        ])
+        .await;
+
+        let results = manager.dispatch_event_within(create_task_event(), budget).await.unwrap();
         assert_eq!(results.successful.len(), 1);
-        assert_eq!(results.failed.len(), 1);
-        assert_eq!(results.total_duration, Duration::from_millis(150));
+        assert!(results.cancelled.is_empty());
+        assert_eq!(results.skipped.len(), 1);
+        assert_eq!(results.skipped[0].hook_id, "late");
+        assert_eq!(results.skipped[0].reason, SkipReason::BudgetExhausted);
+        assert_eq!(results.skipped_by_reason()[&SkipReason::BudgetExhausted], 1);
+    }
+
+    #[tokio::test]
+    async fn test_trigger_event_runs_dependency_waves_without_waiting_for_fire_and_forget() {
+        let mut second = create_sleep_hook("second", 100, false, false);
+        second.depends_on = vec!["first".to_string()];
+        let mut background = create_sleep_hook("background", 2_000, false, false);
+        background.mode = HookExecutionMode::FireAndForget;
+        let collector = Arc::new(MetricsCollector::default());
+        let manager = create_sleep_manager(vec![second, create_sleep_hook("first", 100, false, false), background])
+            .await
+            .with_metrics_collector(collector.clone());
+
+        let start = Instant::now();
+        manager.trigger_event(create_task_event()).await.unwrap();
+        let elapsed = start.elapsed();
+
+        // The dependent hook waits for its dependency; the fire-and-forget hook is not awaited
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(1));
+        assert_eq!(collector.get_metrics().unwrap().total_executions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_fire_and_forget_hook_is_dead_lettered() {
+        let mut hook = create_exec_hook(false);
+        hook.blocking = false;
+        hook.mode = HookExecutionMode::FireAndForget;
+        let mut config = create_test_config();
+        config.hooks.exec.push(hook);
+
+        let mut manager = HookManager::new(config).await.unwrap().with_dead_letter_queue(10, None);
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed: false }));
+
+        manager.trigger_event(create_exec_event()).await.unwrap();
+
+        let mut dead_letters = Vec::new();
+        for _ in 0..50 {
+            dead_letters = manager.drain_dead_letters();
+            if !dead_letters.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].hook_id, "preflight");
+        assert_eq!(dead_letters[0].event_type, LifecycleEventType::ExecBefore);
+        assert_eq!(dead_letters[0].error, "pre-flight check failed");
+    }
+
+    #[tokio::test]
+    async fn test_global_and_event_concurrency_limits_queue_hooks() {
+        let hooks = || {
+            vec![
+                create_sleep_hook("a", 100, false, false),
+                create_sleep_hook("b", 100, false, false),
+            ]
+        };
+
+        for manager in [
+            create_sleep_manager(hooks()).await.with_global_concurrency_limit(1),
+            create_sleep_manager(hooks()).await.with_event_concurrency_limit(LifecycleEventType::TaskStart, 1),
+        ] {
+            let start = Instant::now();
+            let results = manager.dispatch_event_collected(create_task_event()).await.unwrap();
+            assert_eq!(results.successful.len(), 2);
+            assert!(start.elapsed() >= Duration::from_millis(200));
+            assert!(manager.coordinator.concurrency_metrics().await.queue_wait_times.max_depth >= 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exit_code_condition_gates_command_complete_hooks() {
+        let mut config = create_test_config();
+        let mut on_failure = create_exec_hook(false);
+        on_failure.id = Some("on_failure".to_string());
+        on_failure.event = LifecycleEventType::ExecAfter;
+        on_failure.condition = Some("exit_code != 0 && command.contains('cargo')".to_string());
+        config.hooks.exec.push(on_failure);
+
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed: true }));
+
+        let exec_after = |exit_code| LifecycleEvent::ExecAfter {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: vec!["cargo".to_string(), "build".to_string()],
+            exit_code,
+            stdout: String::new(),
+            stderr: String::new(),
+            duration: Duration::from_millis(10),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let results = manager.dispatch_event_collected(exec_after(2)).await.unwrap();
+        assert_eq!(results.successful.len(), 1);
+        assert!(results.skipped.is_empty());
+
+        let results = manager.dispatch_event_collected(exec_after(0)).await.unwrap();
+        assert!(results.results.is_empty());
+        assert_eq!(results.skipped.len(), 1);
+        assert_eq!(results.skipped[0].hook_id, "on_failure");
+        assert_eq!(results.skipped[0].reason, SkipReason::ConditionNotMet);
+    }
+
+    #[tokio::test]
+    async fn test_command_pattern_filters_exec_hooks() {
+        let mut config = create_test_config();
+        let mut git_glob = create_exec_hook(false);
+        git_glob.id = Some("git_glob".to_string());
+        git_glob.command_pattern = Some("git *".to_string());
+        config.hooks.exec.push(git_glob);
+        let mut git_regex = create_exec_hook(false);
+        git_regex.id = Some("git_regex".to_string());
+        git_regex.command_pattern = Some("regex:^git commit\\b".to_string());
+        config.hooks.exec.push(git_regex);
+
+        let mut manager = HookManager::new(config).await.unwrap();
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed: true }));
+
+        let exec_before = |command: &[&str]| LifecycleEvent::ExecBefore {
+            call_id: "call-1".to_string(),
+            task_id: "task-1".to_string(),
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let results = manager.dispatch_event_collected(exec_before(&["git", "commit", "-m", "wip"])).await.unwrap();
+        assert_eq!(results.successful.len(), 2);
+        assert!(results.skipped.is_empty());
+
+        let results = manager.dispatch_event_collected(exec_before(&["cargo", "build"])).await.unwrap();
+        assert!(results.results.is_empty());
+        assert_eq!(results.skipped.len(), 2);
+        assert!(results.skipped.iter().all(|s| s.reason == SkipReason::CommandNotMatched));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_command_pattern_is_rejected_at_load() {
+        let mut config = create_test_config();
+        let mut hook = create_exec_hook(false);
+        hook.command_pattern = Some("regex:git (".to_string());
+        config.hooks.exec.push(hook);
+        assert!(HookManager::new(config).await.is_err());
+
+        let mut config = create_test_config();
+        let mut hook = create_exec_hook(false);
+        hook.event = LifecycleEventType::TaskStart;
+        hook.command_pattern = Some("git *".to_string());
+        config.hooks.task.push(hook);
+        assert!(HookManager::new(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_condition_is_rejected_at_load() {
+        let mut config = create_test_config();
+        let mut hook = create_exec_hook(false);
+        hook.condition = Some("exit_code => 0".to_string());
+        config.hooks.exec.push(hook);
+
+        assert!(HookManager::new(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_event_collected_records_skip_reasons() {
+        let mut config = create_test_config();
+        config.hooks.exec.push(create_exec_hook(false));
+
+        let mut conditional = create_exec_hook(false);
+        conditional.id = Some("conditional".to_string());
+        conditional.condition = Some("false".to_string());
+        config.hooks.exec.push(conditional);
+
+        let mut no_executor = create_exec_hook(false);
+        no_executor.id = Some("archive".to_string());
+        no_executor.hook_type = HookType::FileSystem {
+            operation: crate::hooks::types::FileSystemOperation::Write,
+            path: PathBuf::from("/tmp/archive.log"),
+            target_path: None,
+            content: None,
+            timeout: None,
+            permissions: None,
+            max_events: None,
+            atomic: false,
+        };
+        config.hooks.exec.push(no_executor);
+
+        let mut tripped = create_exec_hook(false);
+        tripped.id = Some("tripped".to_string());
+        config.hooks.exec.push(tripped);
+
+        let collector = Arc::new(MetricsCollector::default());
+        let mut manager = HookManager::new(config.clone()).await.unwrap().with_metrics_collector(collector.clone());
+        manager.executors.insert("script".to_string(), Arc::new(StubExecutor { succeed: true }));
+        manager.executors.remove("filesystem");
+        let breaker = manager.circuit_breakers.breaker("tripped");
+        for _ in 0..CircuitBreakerConfig::default().failure_threshold {
+            breaker.record_failure();
+        }
+
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        assert_eq!(results.successful.len(), 1);
+        assert!(results.failed.is_empty());
+
+        let reasons: HashMap<_, _> = results.skipped.iter().map(|s| (s.hook_id.as_str(), s.reason)).collect();
+        assert_eq!(reasons.len(), 3);
+        assert_eq!(reasons["conditional"], SkipReason::ConditionNotMet);
+        assert_eq!(reasons["archive"], SkipReason::NoMatchingExecutor);
+        assert_eq!(reasons["tripped"], SkipReason::CircuitOpen);
+
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.skipped_executions[&SkipReason::ConditionNotMet], 1);
+        assert_eq!(metrics.skipped_by_hook_id["tripped"][&SkipReason::CircuitOpen], 1);
+
+        // With the hooks system disabled, every registered hook is skipped as disabled
+        config.hooks.enabled = false;
+        let manager = HookManager::new(config).await.unwrap();
+        let results = manager.dispatch_event_collected(create_exec_event()).await.unwrap();
+        assert!(results.results.is_empty());
+        assert_eq!(results.skipped.len(), 4);
+        assert!(results.skipped.iter().all(|s| s.reason == SkipReason::Disabled));
+        assert_eq!(results.skipped_by_reason()[&SkipReason::Disabled], 4);
     }
 }
@@ -1,11 +1,15 @@
 //! Hook execution metrics and monitoring system.
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
 use serde::{Deserialize, Serialize};
 
-use crate::hooks::types::{HookError, LifecycleEventType};
+use crate::hooks::types::{HookError, LifecycleEventType, SkipReason};
 
 /// Comprehensive metrics for hook execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +44,74 @@ pub struct HookMetrics {
     pub performance_percentiles: PerformancePercentiles,
     /// Error statistics.
     pub error_stats: ErrorStatistics,
+    /// Number of skipped hooks by reason; skips are not counted as executions.
+    #[serde(default)]
+    pub skipped_executions: HashMap<SkipReason, u64>,
+    /// Number of skips by hook ID and reason.
+    #[serde(default)]
+    pub skipped_by_hook_id: HashMap<String, HashMap<SkipReason, u64>>,
+    /// Histogram of execution times across all hooks.
+    #[serde(default)]
+    pub duration_histogram: DurationHistogram,
+    /// Number of sends suppressed as duplicates of a recent identical payload.
+    #[serde(default)]
+    pub deduplicated_sends: u64,
+    /// Number of suppressed duplicate sends by hook ID.
+    #[serde(default)]
+    pub deduplicated_by_hook_id: HashMap<String, u64>,
+}
+
+/// Execution time histogram with fixed bucket bounds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DurationHistogram {
+    /// Upper bounds of the buckets, in seconds.
+    pub bounds: Vec<f64>,
+    /// Observations per bucket; durations above the last bound are only in `count`.
+    pub counts: Vec<u64>,
+    /// Total number of observations.
+    pub count: u64,
+    /// Sum of all observations.
+    pub sum: Duration,
+}
+
+impl DurationHistogram {
+    /// Record one duration, starting over if the bucket bounds changed.
+    fn observe(&mut self, bounds: &[f64], duration: Duration) {
+        if self.bounds != bounds {
+            *self = Self {
+                bounds: bounds.to_vec(),
+                counts: vec![0; bounds.len()],
+                ..Self::default()
+            };
+        }
+
+        let seconds = duration.as_secs_f64();
+        if let Some(bucket) = self.bounds.iter().position(|bound| seconds <= *bound) {
+            self.counts[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += duration;
+    }
+
+    /// Add another histogram's observations; ignored if its buckets differ and this one has data.
+    fn absorb(&mut self, other: DurationHistogram) {
+        if self.bounds == other.bounds {
+            for (count, other_count) in self.counts.iter_mut().zip(other.counts) {
+                *count += other_count;
+            }
+            self.count += other.count;
+            self.sum += other.sum;
+        } else if self.count == 0 {
+            *self = other;
+        }
+    }
+}
+
+/// Successful and failed execution counts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OutcomeCounts {
+    pub successful: u64,
+    pub failed: u64,
 }
 
 /// Metrics for a specific event type.
@@ -50,6 +122,43 @@ pub struct EventTypeMetrics {
     pub failed_executions: u64,
     pub average_execution_time: Duration,
     pub success_rate: f64,
+    /// Number of times the event fired, whether or not any hooks ran.
+    #[serde(default)]
+    pub occurrences: u64,
+}
+
+impl EventTypeMetrics {
+    fn empty() -> Self {
+        Self {
+            total_executions: 0,
+            successful_executions: 0,
+            failed_executions: 0,
+            average_execution_time: Duration::ZERO,
+            success_rate: 0.0,
+            occurrences: 0,
+        }
+    }
+
+    /// Fold another set of event type metrics into these, weighting the average by execution count.
+    fn absorb(&mut self, other: EventTypeMetrics) {
+        let total = self.total_executions + other.total_executions;
+        if total > 0 {
+            self.average_execution_time = Duration::from_secs_f64(
+                (self.average_execution_time.as_secs_f64() * self.total_executions as f64
+                    + other.average_execution_time.as_secs_f64() * other.total_executions as f64)
+                    / total as f64,
+            );
+        }
+        self.total_executions = total;
+        self.successful_executions += other.successful_executions;
+        self.failed_executions += other.failed_executions;
+        self.occurrences += other.occurrences;
+        self.success_rate = if total > 0 {
+            self.successful_executions as f64 / total as f64
+        } else {
+            0.0
+        };
+    }
 }
 
 /// Metrics for a specific hook ID.
@@ -62,11 +171,67 @@ pub struct HookIdMetrics {
     pub average_execution_time: Duration,
     pub last_execution: Option<SystemTime>,
     pub success_rate: f64,
+    /// Exponentially-weighted success rate that tracks recent behavior.
+    #[serde(default)]
+    pub recent_success_rate: f64,
     pub error_count_by_type: HashMap<String, u64>,
+    /// Execution outcomes of this hook per event type.
+    #[serde(default)]
+    pub outcomes_by_event_type: HashMap<LifecycleEventType, OutcomeCounts>,
+}
+
+impl HookIdMetrics {
+    /// Metrics for a hook that has not executed yet.
+    fn empty(hook_id: &str) -> Self {
+        Self {
+            hook_id: hook_id.to_string(),
+            total_executions: 0,
+            successful_executions: 0,
+            failed_executions: 0,
+            average_execution_time: Duration::ZERO,
+            last_execution: None,
+            success_rate: 0.0,
+            recent_success_rate: 0.0,
+            error_count_by_type: HashMap::new(),
+            outcomes_by_event_type: HashMap::new(),
+        }
+    }
+
+    /// Fold another hook's metrics into these, weighting averages by execution count.
+    fn absorb(&mut self, other: HookIdMetrics) {
+        let total = self.total_executions + other.total_executions;
+        if total > 0 {
+            let weight = |count: u64| count as f64 / total as f64;
+            self.average_execution_time = Duration::from_secs_f64(
+                self.average_execution_time.as_secs_f64() * weight(self.total_executions)
+                    + other.average_execution_time.as_secs_f64() * weight(other.total_executions),
+            );
+            self.recent_success_rate = self.recent_success_rate * weight(self.total_executions)
+                + other.recent_success_rate * weight(other.total_executions);
+        }
+
+        self.total_executions = total;
+        self.successful_executions += other.successful_executions;
+        self.failed_executions += other.failed_executions;
+        self.success_rate = if total > 0 {
+            self.successful_executions as f64 / total as f64
+        } else {
+            0.0
+        };
+        self.last_execution = self.last_execution.max(other.last_execution);
+        for (error_type, count) in other.error_count_by_type {
+            *self.error_count_by_type.entry(error_type).or_insert(0) += count;
+        }
+        for (event_type, outcomes) in other.outcomes_by_event_type {
+            let counts = self.outcomes_by_event_type.entry(event_type).or_default();
+            counts.successful += outcomes.successful;
+            counts.failed += outcomes.failed;
+        }
+    }
 }
 
 /// Performance percentiles for execution times.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PerformancePercentiles {
     pub p50: Duration,
     pub p90: Duration,
@@ -74,6 +239,66 @@ pub struct PerformancePercentiles {
     pub p99: Duration,
 }
 
+/// Stable set of buckets that error messages are classified into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Timeout,
+    Connection,
+    Configuration,
+    Permission,
+    NotFound,
+    Unknown,
+}
+
+impl ErrorCategory {
+    /// Message fragments for each category, checked in order so that e.g.
+    /// "connection timed out" counts as a timeout.
+    const PATTERNS: [(ErrorCategory, &'static [&'static str]); 5] = [
+        (ErrorCategory::Timeout, &["timeout", "timed out", "deadline exceeded"]),
+        (
+            ErrorCategory::Permission,
+            &["permission", "access denied", "forbidden", "unauthorized", "401", "403", "not permitted"],
+        ),
+        (
+            ErrorCategory::NotFound,
+            &["not found", "404", "no such file", "does not exist"],
+        ),
+        (
+            ErrorCategory::Connection,
+            &[
+                "connection", "connect", "network", "refused", "unreachable", "broken pipe", "dns",
+                "circuit open",
+            ],
+        ),
+        (
+            ErrorCategory::Configuration,
+            &["configuration", "config", "not configured", "missing required", "unsupported"],
+        ),
+    ];
+
+    /// Classify an error message by the first category whose patterns it contains.
+    pub fn classify(error_message: &str) -> Self {
+        let error_lower = error_message.to_lowercase();
+        Self::PATTERNS
+            .iter()
+            .find(|(_, patterns)| patterns.iter().any(|pattern| error_lower.contains(pattern)))
+            .map_or(ErrorCategory::Unknown, |(category, _)| *category)
+    }
+
+    /// Name used as the key in error counts.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Timeout => "timeout",
+            ErrorCategory::Connection => "connection",
+            ErrorCategory::Configuration => "configuration",
+            ErrorCategory::Permission => "permission",
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::Unknown => "unknown",
+        }
+    }
+}
+
 /// Error statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorStatistics {
@@ -119,6 +344,91 @@ pub struct MetricsConfig {
     pub collect_percentiles: bool,
     /// Interval for aggregating metrics.
     pub aggregation_interval: Duration,
+    /// When set, records are queued and applied in bulk at this interval.
+    #[serde(default)]
+    pub batch_interval: Option<Duration>,
+    /// Weight of the newest execution in the recent success rate (0.0 to 1.0).
+    #[serde(default = "default_success_rate_alpha")]
+    pub success_rate_alpha: f64,
+    /// Maximum number of hook IDs tracked individually; the least recently executed
+    /// are folded into [`EVICTED_HOOKS_BUCKET`].
+    #[serde(default = "default_max_tracked_hooks")]
+    pub max_tracked_hooks: usize,
+    /// Upper bounds, in seconds, of the execution time histogram buckets.
+    #[serde(default = "default_histogram_buckets")]
+    pub histogram_buckets: Vec<f64>,
+    /// Snapshot file loaded when the collector is created.
+    #[serde(default)]
+    pub snapshot_path: Option<PathBuf>,
+    /// When set together with `snapshot_path`, metrics are saved there at this interval.
+    #[serde(default)]
+    pub auto_save_interval: Option<Duration>,
+}
+
+/// Default execution time histogram buckets, matching the Prometheus client defaults.
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+fn default_histogram_buckets() -> Vec<f64> {
+    DEFAULT_HISTOGRAM_BUCKETS.to_vec()
+}
+
+/// Percentile of sorted durations, interpolating linearly between the two nearest
+/// ranks (the "type 7" method used by most spreadsheets and NumPy).
+pub(crate) fn interpolated_percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    match sorted {
+        [] => Duration::ZERO,
+        [only] => *only,
+        _ => {
+            let rank = (percentile / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            let fraction = rank - lower as f64;
+            let low = sorted[lower].as_secs_f64();
+            let high = sorted[upper].as_secs_f64();
+            Duration::from_secs_f64(low + (high - low) * fraction)
+        }
+    }
+}
+
+/// Drop the oldest entries so at most `max` remain.
+fn keep_newest<T>(records: &mut Vec<T>, max: usize) {
+    let excess = records.len().saturating_sub(max);
+    records.drain(..excess);
+}
+
+/// Escape a Prometheus label value.
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Hook ID under which the stats of evicted hooks are accumulated.
+pub const EVICTED_HOOKS_BUCKET: &str = "__other__";
+
+/// Default maximum number of hook IDs tracked individually.
+pub const DEFAULT_MAX_TRACKED_HOOKS: usize = 1_000;
+
+pub(crate) fn default_max_tracked_hooks() -> usize {
+    DEFAULT_MAX_TRACKED_HOOKS
+}
+
+/// Default weight of the newest execution in exponentially-weighted success rates.
+pub const DEFAULT_SUCCESS_RATE_ALPHA: f64 = 0.1;
+
+fn default_success_rate_alpha() -> f64 {
+    DEFAULT_SUCCESS_RATE_ALPHA
+}
+
+/// Fold one outcome into an exponentially-weighted success rate.
+///
+/// The first outcome seeds the average so a new hook does not start at 0%.
+pub(crate) fn update_success_ewma(current: f64, success: bool, alpha: f64, is_first: bool) -> f64 {
+    let outcome = if success { 1.0 } else { 0.0 };
+    if is_first {
+        outcome
+    } else {
+        let alpha = alpha.clamp(0.0, 1.0);
+        alpha * outcome + (1.0 - alpha) * current
+    }
 }
 
 impl Default for MetricsConfig {
@@ -129,6 +439,12 @@ impl Default for MetricsConfig {
             max_recent_errors: 100,
             collect_percentiles: true,
             aggregation_interval: Duration::from_secs(60),
+            batch_interval: None,
+            success_rate_alpha: DEFAULT_SUCCESS_RATE_ALPHA,
+            max_tracked_hooks: DEFAULT_MAX_TRACKED_HOOKS,
+            histogram_buckets: default_histogram_buckets(),
+            snapshot_path: None,
+            auto_save_interval: None,
         }
     }
 }
@@ -139,21 +455,270 @@ pub struct MetricsCollector {
     config: MetricsConfig,
     metrics: Arc<RwLock<HookMetrics>>,
     execution_times: Arc<RwLock<Vec<Duration>>>,
+    batch: Option<MetricsBatch>,
+}
+
+/// Queue of execution records waiting to be applied in bulk.
+#[derive(Debug)]
+struct MetricsBatch {
+    sender: Sender<ExecutionRecord>,
+    receiver: Arc<Mutex<Receiver<ExecutionRecord>>>,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new(MetricsConfig::default())
+    }
 }
 
 impl MetricsCollector {
     /// Create a new metrics collector.
+    ///
+    /// With `batch_interval` set, a background thread applies queued records
+    /// under a single lock per interval; call [`MetricsCollector::flush`] to
+    /// apply them immediately. With `snapshot_path` set, a previously saved
+    /// snapshot is loaded, and `auto_save_interval` keeps it up to date.
     pub fn new(config: MetricsConfig) -> Self {
-        Self {
+        let batch_interval = config.batch_interval;
+        let snapshot_path = config.snapshot_path.clone();
+        let auto_save_interval = config.auto_save_interval;
+        let mut collector = Self {
             config,
             metrics: Arc::new(RwLock::new(HookMetrics::default())),
             execution_times: Arc::new(RwLock::new(Vec::new())),
+            batch: None,
+        };
+
+        if let Some(interval) = batch_interval {
+            let (sender, receiver) = mpsc::channel();
+            let receiver = Arc::new(Mutex::new(receiver));
+            collector.spawn_batch_drainer(interval, receiver.clone());
+            collector.batch = Some(MetricsBatch { sender, receiver });
+        }
+
+        if let Some(path) = snapshot_path {
+            if let Err(e) = collector.load_snapshot(&path) {
+                tracing::warn!("Starting with empty hook metrics: {}", e);
+            }
+            if let Some(interval) = auto_save_interval {
+                collector.spawn_auto_saver(interval, path);
+            }
         }
+
+        collector
     }
 
-    /// Create a metrics collector with default configuration.
-    pub fn default() -> Self {
-        Self::new(MetricsConfig::default())
+    /// Spawn a background thread that saves a snapshot periodically.
+    ///
+    /// Queued records are applied before each save so the snapshot is never behind
+    /// the batch. The thread holds only weak references and exits once the collector
+    /// is dropped.
+    fn spawn_auto_saver(&self, interval: Duration, path: PathBuf) {
+        let metrics = Arc::downgrade(&self.metrics);
+        let execution_times = Arc::downgrade(&self.execution_times);
+        let receiver = self.batch.as_ref().map(|batch| Arc::downgrade(&batch.receiver));
+        let config = self.config.clone();
+
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+
+                let (Some(metrics), Some(execution_times)) = (metrics.upgrade(), execution_times.upgrade()) else {
+                    break;
+                };
+                if let Some(receiver) = receiver.as_ref().and_then(|receiver| receiver.upgrade()) {
+                    let collector = MetricsCollector {
+                        config: config.clone(),
+                        metrics: metrics.clone(),
+                        execution_times,
+                        batch: None,
+                    };
+                    collector.drain(&receiver);
+                }
+                let snapshot = match metrics.read() {
+                    Ok(metrics) => metrics.clone(),
+                    Err(_) => break,
+                };
+                if let Err(e) = Self::write_snapshot(&path, &snapshot) {
+                    tracing::warn!("Failed to auto-save hook metrics: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Save the current metrics to `path` as JSON, replacing the file atomically.
+    pub fn save_snapshot(&self, path: &Path) -> Result<(), HookError> {
+        self.flush();
+        Self::write_snapshot(path, &self.get_metrics()?)
+    }
+
+    /// Write metrics to a temp file next to `path` and move it into place.
+    fn write_snapshot(path: &Path, metrics: &HookMetrics) -> Result<(), HookError> {
+        let json = serde_json::to_vec_pretty(metrics)
+            .map_err(|e| HookError::Execution(format!("Failed to serialize metrics: {}", e)))?;
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let mut temp_file = tempfile::NamedTempFile::new_in(dir)
+            .map_err(|e| HookError::Execution(format!("Failed to create metrics snapshot: {}", e)))?;
+        temp_file
+            .write_all(&json)
+            .and_then(|()| temp_file.as_file().sync_all())
+            .map_err(|e| HookError::Execution(format!("Failed to write metrics snapshot: {}", e)))?;
+        temp_file
+            .persist(path)
+            .map_err(|e| HookError::Execution(format!("Failed to replace metrics snapshot: {}", e)))?;
+        Ok(())
+    }
+
+    /// Load a snapshot saved by [`MetricsCollector::save_snapshot`] and merge it into
+    /// the current metrics, treating its records as older than any already collected.
+    ///
+    /// A missing file is not an error. A corrupt or partial file is logged and ignored.
+    pub fn load_snapshot(&self, path: &Path) -> Result<(), HookError> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(HookError::Execution(format!(
+                    "Failed to read metrics snapshot {}: {}",
+                    path.display(),
+                    e
+                )));
+            }
+        };
+        let loaded: HookMetrics = match serde_json::from_slice(&contents) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                tracing::warn!("Ignoring corrupt metrics snapshot {}: {}", path.display(), e);
+                return Ok(());
+            }
+        };
+
+        if self.config.collect_percentiles {
+            if let Ok(mut times) = self.execution_times.write() {
+                let mut loaded_times: Vec<Duration> = loaded.recent_executions.iter().map(|r| r.duration).collect();
+                loaded_times.append(&mut *times);
+                *times = loaded_times;
+                keep_newest(&mut *times, self.config.max_recent_executions);
+            }
+        }
+
+        let mut metrics = self.metrics.write()
+            .map_err(|e| HookError::Execution(format!("Failed to write metrics: {}", e)))?;
+        self.merge_metrics(&mut metrics, loaded);
+        if self.config.collect_percentiles {
+            metrics.performance_percentiles = self.calculate_percentiles();
+        }
+        Ok(())
+    }
+
+    /// Fold previously saved metrics into `metrics`.
+    fn merge_metrics(&self, metrics: &mut HookMetrics, loaded: HookMetrics) {
+        metrics.total_executions += loaded.total_executions;
+        metrics.successful_executions += loaded.successful_executions;
+        metrics.failed_executions += loaded.failed_executions;
+        metrics.timed_out_executions += loaded.timed_out_executions;
+        metrics.cancelled_executions += loaded.cancelled_executions;
+        metrics.total_execution_time += loaded.total_execution_time;
+        if metrics.total_executions > 0 {
+            metrics.average_execution_time = metrics.total_execution_time.div_f64(metrics.total_executions as f64);
+            metrics.success_rate = metrics.successful_executions as f64 / metrics.total_executions as f64;
+        }
+        if metrics.min_execution_time == Duration::ZERO
+            || (loaded.min_execution_time > Duration::ZERO && loaded.min_execution_time < metrics.min_execution_time)
+        {
+            metrics.min_execution_time = loaded.min_execution_time;
+        }
+        metrics.max_execution_time = metrics.max_execution_time.max(loaded.max_execution_time);
+
+        for (event_type, event_metrics) in loaded.by_event_type {
+            match metrics.by_event_type.get_mut(&event_type) {
+                Some(existing) => existing.absorb(event_metrics),
+                None => {
+                    metrics.by_event_type.insert(event_type, event_metrics);
+                }
+            }
+        }
+        for (hook_id, hook_metrics) in loaded.by_hook_id {
+            match metrics.by_hook_id.get_mut(&hook_id) {
+                Some(existing) => existing.absorb(hook_metrics),
+                None => {
+                    metrics.by_hook_id.insert(hook_id, hook_metrics);
+                }
+            }
+        }
+        self.evict_least_recent_hooks(metrics, self.config.max_tracked_hooks.max(1));
+
+        let mut recent_executions = loaded.recent_executions;
+        recent_executions.append(&mut metrics.recent_executions);
+        metrics.recent_executions = recent_executions;
+        keep_newest(&mut metrics.recent_executions, self.config.max_recent_executions);
+
+        let error_stats = &mut metrics.error_stats;
+        error_stats.total_errors += loaded.error_stats.total_errors;
+        for (error_type, count) in loaded.error_stats.error_count_by_type {
+            *error_stats.error_count_by_type.entry(error_type).or_insert(0) += count;
+        }
+        error_stats.most_common_error = error_stats.error_count_by_type
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(error_type, _)| error_type.clone());
+        let mut recent_errors = loaded.error_stats.recent_errors;
+        recent_errors.append(&mut error_stats.recent_errors);
+        error_stats.recent_errors = recent_errors;
+        keep_newest(&mut error_stats.recent_errors, self.config.max_recent_errors);
+
+        for (reason, count) in loaded.skipped_executions {
+            *metrics.skipped_executions.entry(reason).or_insert(0) += count;
+        }
+        for (hook_id, skips) in loaded.skipped_by_hook_id {
+            let existing = metrics.skipped_by_hook_id.entry(hook_id).or_default();
+            for (reason, count) in skips {
+                *existing.entry(reason).or_insert(0) += count;
+            }
+        }
+        metrics.duration_histogram.absorb(loaded.duration_histogram);
+    }
+
+    /// Spawn a background thread that applies queued records periodically.
+    ///
+    /// The thread holds only weak references and exits once the collector is dropped.
+    fn spawn_batch_drainer(&self, interval: Duration, receiver: Arc<Mutex<Receiver<ExecutionRecord>>>) {
+        let metrics = Arc::downgrade(&self.metrics);
+        let execution_times = Arc::downgrade(&self.execution_times);
+        let config = self.config.clone();
+
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+
+                let (Some(metrics), Some(execution_times)) = (metrics.upgrade(), execution_times.upgrade()) else {
+                    break;
+                };
+                let collector = MetricsCollector {
+                    config: config.clone(),
+                    metrics,
+                    execution_times,
+                    batch: None,
+                };
+                collector.drain(&receiver);
+            }
+        });
+    }
+
+    /// Apply every queued record now. A no-op when batching is disabled.
+    pub fn flush(&self) {
+        if let Some(batch) = &self.batch {
+            self.drain(&batch.receiver);
+        }
+    }
+
+    /// Apply all records currently waiting in `receiver`.
+    fn drain(&self, receiver: &Mutex<Receiver<ExecutionRecord>>) {
+        let records: Vec<ExecutionRecord> = match receiver.lock() {
+            Ok(receiver) => receiver.try_iter().collect(),
+            Err(_) => return,
+        };
+        self.apply_records(&records);
     }
 
     /// Record a hook execution.
@@ -177,70 +742,123 @@ impl MetricsCollector {
             started_at: now,
             duration,
             success,
-            error_message: error_message.clone(),
+            error_message,
             retry_count,
         };
 
-        // Update metrics
+        match &self.batch {
+            Some(batch) => {
+                if let Err(e) = batch.sender.send(execution_record) {
+                    // The receiver lives as long as the collector, so this cannot happen in practice
+                    self.apply_records(std::slice::from_ref(&e.0));
+                }
+            }
+            None => self.apply_records(std::slice::from_ref(&execution_record)),
+        }
+    }
+
+    /// Record that a lifecycle event fired, so executions can be averaged per occurrence.
+    pub fn record_event(&self, event_type: LifecycleEventType) {
+        if !self.config.enabled {
+            return;
+        }
+
         if let Ok(mut metrics) = self.metrics.write() {
-            self.update_metrics(&mut metrics, &execution_record);
+            metrics.by_event_type.entry(event_type).or_insert_with(EventTypeMetrics::empty).occurrences += 1;
+        }
+    }
+
+    /// Record a hook that was skipped instead of executed.
+    pub fn record_skip(&self, hook_id: &str, reason: SkipReason) {
+        if !self.config.enabled {
+            return;
         }
 
-        // Store execution time for percentile calculations
+        if let Ok(mut metrics) = self.metrics.write() {
+            *metrics.skipped_executions.entry(reason).or_insert(0) += 1;
+            *metrics
+                .skipped_by_hook_id
+                .entry(hook_id.to_string())
+                .or_default()
+                .entry(reason)
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Record a send that a hook suppressed as a duplicate of a recent identical payload.
+    pub fn record_deduplicated(&self, hook_id: &str) {
+        if !self.config.enabled {
+            return;
+        }
+
+        if let Ok(mut metrics) = self.metrics.write() {
+            metrics.deduplicated_sends += 1;
+            *metrics.deduplicated_by_hook_id.entry(hook_id.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Apply execution records under a single metrics lock.
+    fn apply_records(&self, records: &[ExecutionRecord]) {
+        if records.is_empty() {
+            return;
+        }
+
+        // Store execution times for percentile calculations
         if self.config.collect_percentiles {
             if let Ok(mut times) = self.execution_times.write() {
-                times.push(duration);
+                times.extend(records.iter().map(|record| record.duration));
                 // Keep only recent times to prevent unbounded growth
                 if times.len() > self.config.max_recent_executions {
-                    times.drain(0..times.len() - self.config.max_recent_executions);
+                    let excess = times.len() - self.config.max_recent_executions;
+                    times.drain(0..excess);
                 }
             }
         }
 
-        // Record error if applicable
-        if !success {
-            if let Some(error_msg) = error_message {
-                self.record_error(hook_id, event_type, &error_msg, retry_count);
+        if let Ok(mut metrics) = self.metrics.write() {
+            for record in records {
+                self.update_metrics(&mut metrics, record);
+
+                // Record error if applicable
+                if !record.success {
+                    if let Some(error_message) = &record.error_message {
+                        self.record_error(&mut metrics, record, error_message);
+                    }
+                }
+            }
+
+            if self.config.collect_percentiles {
+                metrics.performance_percentiles = self.calculate_percentiles();
             }
         }
     }
 
     /// Record an error occurrence.
-    fn record_error(
-        &self,
-        hook_id: &str,
-        event_type: LifecycleEventType,
-        error_message: &str,
-        retry_count: u32,
-    ) {
-        let error_type = self.classify_error(error_message);
+    fn record_error(&self, metrics: &mut HookMetrics, record: &ExecutionRecord, error_message: &str) {
+        let error_type = self.classify_error(error_message).as_str().to_string();
         let error_record = ErrorRecord {
-            hook_id: hook_id.to_string(),
-            event_type,
+            hook_id: record.hook_id.clone(),
+            event_type: record.event_type,
             error_type: error_type.clone(),
             error_message: error_message.to_string(),
-            occurred_at: SystemTime::now(),
-            retry_count,
+            occurred_at: record.started_at,
+            retry_count: record.retry_count,
         };
 
-        if let Ok(mut metrics) = self.metrics.write() {
-            // Update error statistics
-            metrics.error_stats.total_errors += 1;
-            *metrics.error_stats.error_count_by_type.entry(error_type).or_insert(0) += 1;
-
-            // Update most common error
-            let most_common = metrics.error_stats.error_count_by_type
-                .iter()
-                .max_by_key(|(_, count)| *count)
-                .map(|(error_type, _)| error_type.clone());
-            metrics.error_stats.most_common_error = most_common;
-
-            // Add to recent errors
-            metrics.error_stats.recent_errors.push(error_record);
-            if metrics.error_stats.recent_errors.len() > self.config.max_recent_errors {
-                metrics.error_stats.recent_errors.remove(0);
-            }
-        }
+        // Update error statistics
+        metrics.error_stats.total_errors += 1;
+        *metrics.error_stats.error_count_by_type.entry(error_type).or_insert(0) += 1;
+
+        // Update most common error
+        let most_common = metrics.error_stats.error_count_by_type
+            .iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(error_type, _)| error_type.clone());
+        metrics.error_stats.most_common_error = most_common;
+
+        // Add to recent errors
+        metrics.error_stats.recent_errors.push(error_record);
+        keep_newest(&mut metrics.error_stats.recent_errors, self.config.max_recent_errors);
     }
 
     /// Update the main metrics with a new execution record.
@@ -264,15 +882,10 @@ impl MetricsCollector {
         }
 
         metrics.success_rate = metrics.successful_executions as f64 / metrics.total_executions as f64;
+        metrics.duration_histogram.observe(&self.config.histogram_buckets, record.duration);
 
         // Update event type metrics
-        let event_metrics = metrics.by_event_type.entry(record.event_type).or_insert_with(|| EventTypeMetrics {
-            total_executions: 0,
-            successful_executions: 0,
-            failed_executions: 0,
-            average_execution_time: Duration::ZERO,
-            success_rate: 0.0,
-        });
+        let event_metrics = metrics.by_event_type.entry(record.event_type).or_insert_with(EventTypeMetrics::empty);
 
         event_metrics.total_executions += 1;
         if record.success {
@@ -283,16 +896,10 @@ impl MetricsCollector {
         event_metrics.success_rate = event_metrics.successful_executions as f64 / event_metrics.total_executions as f64;
 
         // Update hook ID metrics
-        let hook_metrics = metrics.by_hook_id.entry(record.hook_id.clone()).or_insert_with(|| HookIdMetrics {
-            hook_id: record.hook_id.clone(),
-            total_executions: 0,
-            successful_executions: 0,
-            failed_executions: 0,
-            average_execution_time: Duration::ZERO,
-            last_execution: None,
-            success_rate: 0.0,
-            error_count_by_type: HashMap::new(),
-        });
+        if !metrics.by_hook_id.contains_key(&record.hook_id) {
+            self.evict_least_recent_hooks(metrics, self.config.max_tracked_hooks.max(1) - 1);
+        }
+        let hook_metrics = metrics.by_hook_id.entry(record.hook_id.clone()).or_insert_with(|| HookIdMetrics::empty(&record.hook_id));
 
         hook_metrics.total_executions += 1;
         if record.success {
@@ -301,73 +908,81 @@ impl MetricsCollector {
             hook_metrics.failed_executions += 1;
         }
         hook_metrics.last_execution = Some(record.started_at);
+        let previous_total = hook_metrics.average_execution_time.as_secs_f64() * (hook_metrics.total_executions - 1) as f64;
+        hook_metrics.average_execution_time = Duration::from_secs_f64(
+            (previous_total + record.duration.as_secs_f64()) / hook_metrics.total_executions as f64,
+        );
         hook_metrics.success_rate = hook_metrics.successful_executions as f64 / hook_metrics.total_executions as f64;
+        hook_metrics.recent_success_rate = update_success_ewma(
+            hook_metrics.recent_success_rate,
+            record.success,
+            self.config.success_rate_alpha,
+            hook_metrics.total_executions == 1,
+        );
+        let outcomes = hook_metrics.outcomes_by_event_type.entry(record.event_type).or_default();
+        if record.success {
+            outcomes.successful += 1;
+        } else {
+            outcomes.failed += 1;
+        }
 
         // Add to recent executions
         metrics.recent_executions.push(record.clone());
-        if metrics.recent_executions.len() > self.config.max_recent_executions {
-            metrics.recent_executions.remove(0);
-        }
-
-        // Update performance percentiles
-        if self.config.collect_percentiles {
-            metrics.performance_percentiles = self.calculate_percentiles();
-        }
+        keep_newest(&mut metrics.recent_executions, self.config.max_recent_executions);
     }
 
-    /// Calculate performance percentiles from execution times.
-    fn calculate_percentiles(&self) -> PerformancePercentiles {
-        if let Ok(times) = self.execution_times.read() {
-            if times.is_empty() {
-                return PerformancePercentiles {
-                    p50: Duration::ZERO,
-                    p90: Duration::ZERO,
-                    p95: Duration::ZERO,
-                    p99: Duration::ZERO,
-                };
+    /// Fold the least recently executed hooks into [`EVICTED_HOOKS_BUCKET`] until at
+    /// most `keep` hooks are tracked individually.
+    fn evict_least_recent_hooks(&self, metrics: &mut HookMetrics, keep: usize) {
+        loop {
+            let tracked = metrics.by_hook_id.len() - usize::from(metrics.by_hook_id.contains_key(EVICTED_HOOKS_BUCKET));
+            if tracked <= keep {
+                return;
             }
 
-            let mut sorted_times = times.clone();
-            sorted_times.sort();
+            let Some(evicted_id) = metrics
+                .by_hook_id
+                .values()
+                .filter(|hook_metrics| hook_metrics.hook_id != EVICTED_HOOKS_BUCKET)
+                .min_by_key(|hook_metrics| hook_metrics.last_execution)
+                .map(|hook_metrics| hook_metrics.hook_id.clone())
+            else {
+                return;
+            };
+            let Some(evicted) = metrics.by_hook_id.remove(&evicted_id) else {
+                return;
+            };
 
-            let len = sorted_times.len();
-            PerformancePercentiles {
-                p50: sorted_times[len * 50 / 100],
-                p90: sorted_times[len * 90 / 100],
-                p95: sorted_times[len * 95 / 100],
-                p99: sorted_times[len * 99 / 100],
-            }
-        } else {
-            PerformancePercentiles {
-                p50: Duration::ZERO,
-                p90: Duration::ZERO,
-                p95: Duration::ZERO,
-                p99: Duration::ZERO,
-            }
+            metrics
+                .by_hook_id
+                .entry(EVICTED_HOOKS_BUCKET.to_string())
+                .or_insert_with(|| HookIdMetrics::empty(EVICTED_HOOKS_BUCKET))
+                .absorb(evicted);
         }
     }
 
-    /// Classify an error message into a type.
-    fn classify_error(&self, error_message: &str) -> String {
-        let error_lower = error_message.to_lowercase();
-        
-        if error_lower.contains("timeout") || error_lower.contains("timed out") {
-            "timeout".to_string()
-        } else if error_lower.contains("permission") || error_lower.contains("access denied") {
-            "permission".to_string()
-        } else if error_lower.contains("network") || error_lower.contains("connection") {
-            "network".to_string()
-        } else if error_lower.contains("not found") || error_lower.contains("404") {
-            "not_found".to_string()
-        } else if error_lower.contains("configuration") || error_lower.contains("config") {
-            "configuration".to_string()
-        } else if error_lower.contains("validation") || error_lower.contains("invalid") {
-            "validation".to_string()
-        } else {
-            "unknown".to_string()
+    /// Calculate interpolated performance percentiles from the recent execution times.
+    fn calculate_percentiles(&self) -> PerformancePercentiles {
+        let Ok(times) = self.execution_times.read() else {
+            return PerformancePercentiles::default();
+        };
+        let mut sorted_times = times.clone();
+        drop(times);
+        sorted_times.sort();
+
+        PerformancePercentiles {
+            p50: interpolated_percentile(&sorted_times, 50.0),
+            p90: interpolated_percentile(&sorted_times, 90.0),
+            p95: interpolated_percentile(&sorted_times, 95.0),
+            p99: interpolated_percentile(&sorted_times, 99.0),
         }
     }
 
+    /// Classify an error message into a category.
+    fn classify_error(&self, error_message: &str) -> ErrorCategory {
+        ErrorCategory::classify(error_message)
+    }
+
     /// Get current metrics snapshot.
     pub fn get_metrics(&self) -> Result<HookMetrics, HookError> {
         self.metrics.read()
@@ -389,8 +1004,50 @@ impl MetricsCollector {
             .map_err(|e| HookError::Execution(format!("Failed to read event type metrics: {}", e)))
     }
 
-    /// Reset all metrics.
+    /// Capture the counters needed to compute deltas between two points in time.
+    pub fn snapshot(&self) -> Result<HookMetricsSnapshot, HookError> {
+        self.flush();
+        let metrics = self.get_metrics()?;
+
+        let by_hook_id = metrics
+            .by_hook_id
+            .iter()
+            .map(|(hook_id, hook_metrics)| {
+                let counters = MetricsCounters {
+                    total_executions: hook_metrics.total_executions,
+                    successful_executions: hook_metrics.successful_executions,
+                    failed_executions: hook_metrics.failed_executions,
+                    total_execution_time: hook_metrics
+                        .average_execution_time
+                        .mul_f64(hook_metrics.total_executions as f64),
+                    average_execution_time: hook_metrics.average_execution_time,
+                    success_rate: hook_metrics.success_rate,
+                };
+                (hook_id.clone(), counters)
+            })
+            .collect();
+
+        Ok(HookMetricsSnapshot {
+            taken_at: SystemTime::now(),
+            overall: MetricsCounters {
+                total_executions: metrics.total_executions,
+                successful_executions: metrics.successful_executions,
+                failed_executions: metrics.failed_executions,
+                total_execution_time: metrics.total_execution_time,
+                average_execution_time: metrics.average_execution_time,
+                success_rate: metrics.success_rate,
+            },
+            by_hook_id,
+        })
+    }
+
+    /// Reset all metrics, discarding any queued records.
     pub fn reset_metrics(&self) -> Result<(), HookError> {
+        if let Some(batch) = &self.batch {
+            if let Ok(receiver) = batch.receiver.lock() {
+                receiver.try_iter().for_each(drop);
+            }
+        }
         if let Ok(mut metrics) = self.metrics.write() {
             *metrics = HookMetrics::default();
         }
@@ -407,6 +1064,59 @@ impl MetricsCollector {
             .map_err(|e| HookError::Execution(format!("Failed to serialize metrics: {}", e)))
     }
 
+    /// Render metrics in the Prometheus text exposition format.
+    pub fn export_prometheus(&self) -> Result<String, HookError> {
+        self.flush();
+        let metrics = self.get_metrics()?;
+        let mut hook_ids: Vec<&String> = metrics.by_hook_id.keys().collect();
+        hook_ids.sort();
+        let mut out = String::new();
+
+        out.push_str("# HELP codex_hook_executions_total Hook executions by hook, event type and result.\n");
+        out.push_str("# TYPE codex_hook_executions_total counter\n");
+        for hook_id in &hook_ids {
+            let mut outcomes: Vec<_> = metrics.by_hook_id[*hook_id].outcomes_by_event_type.iter().collect();
+            outcomes.sort_by_key(|(event_type, _)| event_type.to_string());
+            for (event_type, counts) in outcomes {
+                for (result, count) in [("success", counts.successful), ("failure", counts.failed)] {
+                    let _ = writeln!(
+                        out,
+                        "codex_hook_executions_total{{hook_id=\"{}\",event_type=\"{}\",result=\"{}\"}} {}",
+                        escape_label_value(hook_id),
+                        event_type,
+                        result,
+                        count
+                    );
+                }
+            }
+        }
+
+        let histogram = &metrics.duration_histogram;
+        out.push_str("# HELP codex_hook_duration_seconds Hook execution time.\n");
+        out.push_str("# TYPE codex_hook_duration_seconds histogram\n");
+        let mut cumulative = 0;
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+            cumulative += count;
+            let _ = writeln!(out, "codex_hook_duration_seconds_bucket{{le=\"{}\"}} {}", bound, cumulative);
+        }
+        let _ = writeln!(out, "codex_hook_duration_seconds_bucket{{le=\"+Inf\"}} {}", histogram.count);
+        let _ = writeln!(out, "codex_hook_duration_seconds_sum {}", histogram.sum.as_secs_f64());
+        let _ = writeln!(out, "codex_hook_duration_seconds_count {}", histogram.count);
+
+        out.push_str("# HELP codex_hook_success_rate Fraction of successful executions per hook.\n");
+        out.push_str("# TYPE codex_hook_success_rate gauge\n");
+        for hook_id in &hook_ids {
+            let _ = writeln!(
+                out,
+                "codex_hook_success_rate{{hook_id=\"{}\"}} {}",
+                escape_label_value(hook_id),
+                metrics.by_hook_id[*hook_id].success_rate
+            );
+        }
+
+        Ok(out)
+    }
+
     /// Get performance summary.
     pub fn get_performance_summary(&self) -> Result<PerformanceSummary, HookError> {
         let metrics = self.get_metrics()?;
@@ -416,9 +1126,9 @@ impl MetricsCollector {
             success_rate: metrics.success_rate,
             average_execution_time: metrics.average_execution_time,
             p95_execution_time: metrics.performance_percentiles.p95,
-            most_common_error: metrics.error_stats.most_common_error,
             slowest_hook: self.find_slowest_hook(&metrics),
             most_reliable_hook: self.find_most_reliable_hook(&metrics),
+            most_common_error: metrics.error_stats.most_common_error,
         })
     }
 
@@ -452,6 +1162,103 @@ pub struct PerformanceSummary {
     pub most_reliable_hook: Option<String>,
 }
 
+/// Point-in-time copy of the cumulative metrics counters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookMetricsSnapshot {
+    pub taken_at: SystemTime,
+    pub overall: MetricsCounters,
+    pub by_hook_id: HashMap<String, MetricsCounters>,
+}
+
+/// Cumulative counters for all hooks or a single hook.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MetricsCounters {
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub failed_executions: u64,
+    pub total_execution_time: Duration,
+    pub average_execution_time: Duration,
+    pub success_rate: f64,
+}
+
+/// Change in metrics between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookMetricsDelta {
+    /// Time between the two snapshots.
+    pub elapsed: Duration,
+    pub overall: MetricsCountersDelta,
+    /// Deltas for hooks that executed between the two snapshots.
+    pub by_hook_id: HashMap<String, MetricsCountersDelta>,
+}
+
+/// Change in one set of counters between two snapshots.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MetricsCountersDelta {
+    pub executions_added: u64,
+    pub successes_added: u64,
+    pub failures_added: u64,
+    /// Success rate of the executions added in the window (0.0 when none were added).
+    pub window_success_rate: f64,
+    /// Change in the cumulative success rate.
+    pub success_rate_change: f64,
+    /// Average duration of the executions added in the window.
+    pub window_average_execution_time: Duration,
+    /// Change in the cumulative average duration, in milliseconds.
+    pub average_execution_time_change_ms: f64,
+}
+
+impl MetricsCounters {
+    /// Changes from `self` to `later`. Counters that went backwards (after a reset) count as zero.
+    fn delta(&self, later: &MetricsCounters) -> MetricsCountersDelta {
+        let executions_added = later.total_executions.saturating_sub(self.total_executions);
+        let successes_added = later.successful_executions.saturating_sub(self.successful_executions);
+        let failures_added = later.failed_executions.saturating_sub(self.failed_executions);
+        let added_time = later.total_execution_time.saturating_sub(self.total_execution_time);
+
+        let (window_success_rate, window_average_execution_time) = if executions_added > 0 {
+            (
+                successes_added as f64 / executions_added as f64,
+                added_time.div_f64(executions_added as f64),
+            )
+        } else {
+            (0.0, Duration::ZERO)
+        };
+
+        MetricsCountersDelta {
+            executions_added,
+            successes_added,
+            failures_added,
+            window_success_rate,
+            success_rate_change: later.success_rate - self.success_rate,
+            window_average_execution_time,
+            average_execution_time_change_ms: later.average_execution_time.as_secs_f64() * 1000.0
+                - self.average_execution_time.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+impl HookMetricsSnapshot {
+    /// Compute the change from this snapshot to a `later` one.
+    pub fn diff(&self, later: &HookMetricsSnapshot) -> HookMetricsDelta {
+        let empty = MetricsCounters::default();
+        let by_hook_id = later
+            .by_hook_id
+            .iter()
+            .filter_map(|(hook_id, after)| {
+                let before = self.by_hook_id.get(hook_id).unwrap_or(&empty);
+                let delta = before.delta(after);
+                (delta.executions_added > 0).then(|| (hook_id.clone(), delta))
+            })
+            .collect();
+
+        HookMetricsDelta {
+            elapsed: later.taken_at.duration_since(self.taken_at).unwrap_or_default(),
+            overall: self.overall.delta(&later.overall),
+            by_hook_id,
+        }
+    }
+}
+
 impl Default for HookMetrics {
     fn default() -> Self {
         Self {
@@ -468,25 +1275,27 @@ impl Default for HookMetrics {
             by_event_type: HashMap::new(),
             by_hook_id: HashMap::new(),
             recent_executions: Vec::new(),
-            performance_percentiles: PerformancePercentiles {
-                p50: Duration::ZERO,
-                p90: Duration::ZERO,
-                p95: Duration::ZERO,
-                p99: Duration::ZERO,
-            },
+            performance_percentiles: PerformancePercentiles::default(),
             error_stats: ErrorStatistics {
                 total_errors: 0,
                 error_count_by_type: HashMap::new(),
                 most_common_error: None,
                 recent_errors: Vec::new(),
             },
+            skipped_executions: HashMap::new(),
+            skipped_by_hook_id: HashMap::new(),
+            duration_histogram: DurationHistogram::default(),
+            deduplicated_sends: 0,
+            deduplicated_by_hook_id: HashMap::new(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
+    use std::time::Instant;
 
     #[test]
     fn test_metrics_collector_creation() {
@@ -574,12 +1383,270 @@ mod tests {
     fn test_error_classification() {
         let collector = MetricsCollector::default();
         
-        assert_eq!(collector.classify_error("Connection timeout"), "timeout");
-        assert_eq!(collector.classify_error("Permission denied"), "permission");
-        assert_eq!(collector.classify_error("Network error"), "network");
-        assert_eq!(collector.classify_error("File not found"), "not_found");
-        assert_eq!(collector.classify_error("Invalid configuration"), "configuration");
-        assert_eq!(collector.classify_error("Some other error"), "unknown");
+        assert_eq!(collector.classify_error("Connection timeout"), ErrorCategory::Timeout);
+        assert_eq!(collector.classify_error("Permission denied"), ErrorCategory::Permission);
+        assert_eq!(collector.classify_error("Network error"), ErrorCategory::Connection);
+        assert_eq!(collector.classify_error("File not found"), ErrorCategory::NotFound);
+        assert_eq!(collector.classify_error("Invalid configuration"), ErrorCategory::Configuration);
+        assert_eq!(collector.classify_error("Some other error"), ErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn test_error_classification_is_bounded() {
+        let collector = MetricsCollector::default();
+        for (message, category) in [
+            ("Hook execution timed out after 30s", ErrorCategory::Timeout),
+            ("Hook execution timed out after 45s", ErrorCategory::Timeout),
+            ("Hook execution timed out after 60s", ErrorCategory::Timeout),
+            ("operation timed out: deadline exceeded", ErrorCategory::Timeout),
+            ("Failed to connect to 10.0.0.1:5672: Connection refused (os error 111)", ErrorCategory::Connection),
+            ("Webhook to https://example.com/hook not sent: circuit open", ErrorCategory::Connection),
+            ("dns error: failed to lookup address", ErrorCategory::Connection),
+            ("Webhook returned HTTP 403 Forbidden", ErrorCategory::Permission),
+            ("EACCES: operation not permitted", ErrorCategory::Permission),
+            ("Script /tmp/hook.sh does not exist", ErrorCategory::NotFound),
+            ("HTTP 404 from https://example.com/missing", ErrorCategory::NotFound),
+            ("Webhook signing is not configured", ErrorCategory::Configuration),
+            ("missing required field `url`", ErrorCategory::Configuration),
+            ("exit status 2", ErrorCategory::Unknown),
+        ] {
+            assert_eq!(collector.classify_error(message), category, "{}", message);
+            collector.record_execution("classified", LifecycleEventType::TaskComplete, Duration::from_millis(1), false, Some(message.to_string()), 0);
+        }
+
+        let error_stats = collector.get_metrics().unwrap().error_stats;
+        assert_eq!(error_stats.error_count_by_type.len(), 6);
+        assert_eq!(error_stats.error_count_by_type["timeout"], 4);
+        assert_eq!(error_stats.most_common_error.as_deref(), Some("timeout"));
+    }
+
+    #[test]
+    fn test_recent_records_are_capped() {
+        let collector = MetricsCollector::new(MetricsConfig {
+            max_recent_executions: 5,
+            max_recent_errors: 3,
+            ..MetricsConfig::default()
+        });
+        for i in 0..20 {
+            collector.record_execution(
+                &format!("hook_{}", i),
+                LifecycleEventType::TaskComplete,
+                Duration::from_millis(i),
+                i % 2 == 0,
+                (i % 2 == 1).then(|| format!("failure {}", i)),
+                0,
+            );
+        }
+
+        let metrics = collector.get_metrics().unwrap();
+        let recent: Vec<&str> = metrics.recent_executions.iter().map(|r| r.hook_id.as_str()).collect();
+        assert_eq!(recent, ["hook_15", "hook_16", "hook_17", "hook_18", "hook_19"]);
+        let errors: Vec<&str> = metrics.error_stats.recent_errors.iter().map(|r| r.hook_id.as_str()).collect();
+        assert_eq!(errors, ["hook_15", "hook_17", "hook_19"]);
+        assert_eq!(metrics.total_executions, 20);
+    }
+
+    #[test]
+    fn test_performance_percentiles_interpolate() {
+        let record_all = |collector: &MetricsCollector| {
+            for ms in [40, 10, 100, 70, 20, 90, 30, 60, 80, 50] {
+                collector.record_execution("ranked", LifecycleEventType::TaskComplete, Duration::from_millis(ms), true, None, 0);
+            }
+        };
+
+        let collector = MetricsCollector::default();
+        record_all(&collector);
+        let percentiles = collector.get_metrics().unwrap().performance_percentiles;
+        for (actual, expected_ms) in [
+            (percentiles.p50, 55.0),
+            (percentiles.p90, 91.0),
+            (percentiles.p95, 95.5),
+            (percentiles.p99, 99.1),
+        ] {
+            assert!((actual.as_secs_f64() * 1000.0 - expected_ms).abs() < 1e-6, "expected {}ms, got {:?}", expected_ms, actual);
+        }
+
+        let disabled = MetricsCollector::new(MetricsConfig {
+            collect_percentiles: false,
+            ..MetricsConfig::default()
+        });
+        record_all(&disabled);
+        let percentiles = disabled.get_metrics().unwrap().performance_percentiles;
+        assert_eq!(percentiles.p50, Duration::ZERO);
+        assert_eq!(percentiles.p99, Duration::ZERO);
+    }
+
+    fn record_mixed(collector: &MetricsCollector, hook_id: &str, count: u64) {
+        for i in 0..count {
+            let success = i % 3 != 0;
+            collector.record_execution(
+                hook_id,
+                LifecycleEventType::TaskComplete,
+                Duration::from_millis(10 * (i + 1)),
+                success,
+                (!success).then(|| "Connection refused".to_string()),
+                0,
+            );
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics.json");
+
+        let original = MetricsCollector::default();
+        record_mixed(&original, "lint", 6);
+        record_mixed(&original, "format", 3);
+        original.record_skip("lint", SkipReason::ConditionNotMet);
+        original.save_snapshot(&path).unwrap();
+
+        let restored = MetricsCollector::default();
+        restored.load_snapshot(&path).unwrap();
+        let before = original.get_metrics().unwrap();
+        let after = restored.get_metrics().unwrap();
+        assert_eq!(after.total_executions, 9);
+        assert_eq!(after.successful_executions, before.successful_executions);
+        assert_eq!(after.failed_executions, before.failed_executions);
+        assert_eq!(after.total_execution_time, before.total_execution_time);
+        assert_eq!(after.by_hook_id["lint"].total_executions, 6);
+        assert_eq!(after.error_stats.error_count_by_type["connection"], 3);
+        assert_eq!(after.skipped_executions[&SkipReason::ConditionNotMet], 1);
+        assert_eq!(after.duration_histogram, before.duration_histogram);
+        assert_eq!(after.performance_percentiles.p99, before.performance_percentiles.p99);
+    }
+
+    #[test]
+    fn test_load_snapshot_merges_with_existing_metrics() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics.json");
+
+        let previous = MetricsCollector::default();
+        record_mixed(&previous, "lint", 6);
+        previous.save_snapshot(&path).unwrap();
+
+        let current = MetricsCollector::default();
+        record_mixed(&current, "lint", 3);
+        record_mixed(&current, "test", 3);
+        current.load_snapshot(&path).unwrap();
+
+        let metrics = current.get_metrics().unwrap();
+        assert_eq!(metrics.total_executions, 12);
+        assert_eq!(metrics.by_hook_id["lint"].total_executions, 9);
+        assert_eq!(metrics.by_hook_id["test"].total_executions, 3);
+        assert_eq!(metrics.by_event_type[&LifecycleEventType::TaskComplete].total_executions, 12);
+        assert_eq!(metrics.error_stats.total_errors, 4);
+        assert_eq!(metrics.recent_executions.len(), 12);
+        // Snapshot records are older than the ones collected since startup
+        assert_eq!(metrics.recent_executions.last().unwrap().hook_id, "test");
+        assert_eq!(metrics.duration_histogram.count, 12);
+    }
+
+    #[test]
+    fn test_corrupt_snapshot_starts_fresh() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics.json");
+        std::fs::write(&path, br#"{"total_executions": 12, "successful_exec"#).unwrap();
+
+        let collector = MetricsCollector::new(MetricsConfig {
+            snapshot_path: Some(path.clone()),
+            ..MetricsConfig::default()
+        });
+        assert_eq!(collector.get_metrics().unwrap().total_executions, 0);
+
+        collector.load_snapshot(&temp_dir.path().join("missing.json")).unwrap();
+        assert_eq!(collector.get_metrics().unwrap().total_executions, 0);
+    }
+
+    #[test]
+    fn test_auto_save_persists_across_restart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics.json");
+        let config = MetricsConfig {
+            snapshot_path: Some(path.clone()),
+            auto_save_interval: Some(Duration::from_millis(10)),
+            ..MetricsConfig::default()
+        };
+
+        let collector = MetricsCollector::new(config.clone());
+        record_mixed(&collector, "lint", 4);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let saved_total = || {
+            std::fs::read(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_slice::<HookMetrics>(&contents).ok())
+                .map(|metrics| metrics.total_executions)
+        };
+        while saved_total() != Some(4) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(collector);
+
+        let restarted = MetricsCollector::new(config);
+        assert_eq!(restarted.get_metrics().unwrap().total_executions, 4);
+    }
+
+    #[test]
+    fn test_auto_save_applies_queued_records_first() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics.json");
+        // The batch would not drain on its own during the test
+        let collector = MetricsCollector::new(MetricsConfig {
+            snapshot_path: Some(path.clone()),
+            auto_save_interval: Some(Duration::from_millis(10)),
+            batch_interval: Some(Duration::from_secs(3600)),
+            ..MetricsConfig::default()
+        });
+        record_mixed(&collector, "lint", 3);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let saved_total = || {
+            std::fs::read(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_slice::<HookMetrics>(&contents).ok())
+                .map(|metrics| metrics.total_executions)
+        };
+        while saved_total() != Some(3) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(saved_total(), Some(3));
+    }
+
+    #[test]
+    fn test_export_prometheus() {
+        let collector = MetricsCollector::new(MetricsConfig {
+            histogram_buckets: vec![0.05, 0.5],
+            ..MetricsConfig::default()
+        });
+        collector.record_execution("lint", LifecycleEventType::TaskComplete, Duration::from_millis(20), true, None, 0);
+        collector.record_execution("lint", LifecycleEventType::TaskComplete, Duration::from_millis(200), true, None, 0);
+        collector.record_execution("lint", LifecycleEventType::TaskComplete, Duration::from_millis(800), false, Some("boom".to_string()), 0);
+        collector.record_execution("say \"hi\"\\\n", LifecycleEventType::SessionStart, Duration::from_millis(10), true, None, 0);
+
+        let text = collector.export_prometheus().unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        let expected = [
+            "# TYPE codex_hook_executions_total counter",
+            "codex_hook_executions_total{hook_id=\"lint\",event_type=\"task_complete\",result=\"success\"} 2",
+            "codex_hook_executions_total{hook_id=\"lint\",event_type=\"task_complete\",result=\"failure\"} 1",
+            "codex_hook_executions_total{hook_id=\"say \\\"hi\\\"\\\\\\n\",event_type=\"session_start\",result=\"success\"} 1",
+            "# TYPE codex_hook_duration_seconds histogram",
+            "codex_hook_duration_seconds_bucket{le=\"0.05\"} 2",
+            "codex_hook_duration_seconds_bucket{le=\"0.5\"} 3",
+            "codex_hook_duration_seconds_bucket{le=\"+Inf\"} 4",
+            "codex_hook_duration_seconds_sum 1.03",
+            "codex_hook_duration_seconds_count 4",
+            "# TYPE codex_hook_success_rate gauge",
+        ];
+        for line in expected {
+            assert!(lines.contains(&line), "missing {:?} in:\n{}", line, text);
+        }
+
+        let lint_rate = lines
+            .iter()
+            .find_map(|line| line.strip_prefix("codex_hook_success_rate{hook_id=\"lint\"} "))
+            .unwrap();
+        assert!((lint_rate.parse::<f64>().unwrap() - 2.0 / 3.0).abs() < 1e-9);
     }
 
     #[test]
@@ -597,9 +1664,207 @@ mod tests {
         }
 
         let summary = collector.get_performance_summary().unwrap();
-        assert_eq!(summary.total_executions, 7);
+        assert_eq!(summary.total_executions, 8);
         assert_eq!(summary.success_rate, 1.0);
         assert_eq!(summary.slowest_hook, Some("slow_hook".to_string()));
         assert_eq!(summary.most_reliable_hook, Some("reliable_hook".to_string()));
     }
+
+    fn create_batched_collector(interval: Duration) -> Arc<MetricsCollector> {
+        Arc::new(MetricsCollector::new(MetricsConfig {
+            batch_interval: Some(interval),
+            ..MetricsConfig::default()
+        }))
+    }
+
+    fn record_concurrently(collector: &Arc<MetricsCollector>, threads: usize, per_thread: usize) {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let collector = collector.clone();
+                std::thread::spawn(move || {
+                    for i in 0..per_thread {
+                        let success = i % 10 != 0;
+                        collector.record_execution(
+                            &format!("hook_{}", t),
+                            LifecycleEventType::TaskComplete,
+                            Duration::from_millis(i as u64 % 50),
+                            success,
+                            (!success).then(|| "Connection timeout".to_string()),
+                            0,
+                        );
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_batched_metrics_consistent_after_flush() {
+        // Long interval so only the explicit flush applies records
+        let collector = create_batched_collector(Duration::from_secs(3600));
+        record_concurrently(&collector, 8, 250);
+
+        assert_eq!(collector.get_metrics().unwrap().total_executions, 0);
+
+        collector.flush();
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.total_executions, 2000);
+        assert_eq!(metrics.successful_executions, 1800);
+        assert_eq!(metrics.failed_executions, 200);
+        assert_eq!(metrics.error_stats.total_errors, 200);
+        assert_eq!(metrics.by_hook_id.len(), 8);
+        assert_eq!(metrics.by_hook_id["hook_3"].total_executions, 250);
+        assert!(metrics.performance_percentiles.p99 > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_batched_metrics_drained_in_background() {
+        let collector = create_batched_collector(Duration::from_millis(10));
+        record_concurrently(&collector, 2, 50);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while collector.get_metrics().unwrap().total_executions < 100 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(collector.get_metrics().unwrap().total_executions, 100);
+    }
+
+    #[test]
+    #[ignore = "timing-sensitive benchmark"]
+    fn bench_batched_recording_reduces_contention() {
+        let direct = Arc::new(MetricsCollector::new(MetricsConfig::default()));
+        let start = Instant::now();
+        record_concurrently(&direct, 16, 2000);
+        let direct_elapsed = start.elapsed();
+
+        let batched = create_batched_collector(Duration::from_millis(5));
+        let start = Instant::now();
+        record_concurrently(&batched, 16, 2000);
+        let batched_elapsed = start.elapsed();
+        batched.flush();
+
+        assert_eq!(batched.get_metrics().unwrap().total_executions, 32_000);
+        assert!(
+            batched_elapsed < direct_elapsed,
+            "batched recording ({:?}) was not faster than direct recording ({:?})",
+            batched_elapsed,
+            direct_elapsed
+        );
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let collector = MetricsCollector::default();
+        collector.record_execution("steady", LifecycleEventType::SessionStart, Duration::from_millis(100), true, None, 0);
+        collector.record_execution("steady", LifecycleEventType::SessionStart, Duration::from_millis(100), true, None, 0);
+
+        let before = collector.snapshot().unwrap();
+
+        collector.record_execution("steady", LifecycleEventType::SessionStart, Duration::from_millis(400), false, Some("boom".to_string()), 0);
+        collector.record_execution("new_hook", LifecycleEventType::TaskComplete, Duration::from_millis(50), true, None, 0);
+
+        let after = collector.snapshot().unwrap();
+        let delta = before.diff(&after);
+
+        assert_eq!(delta.overall.executions_added, 2);
+        assert_eq!(delta.overall.successes_added, 1);
+        assert_eq!(delta.overall.failures_added, 1);
+        assert_eq!(delta.overall.window_success_rate, 0.5);
+        assert!((delta.overall.success_rate_change - (0.75 - 1.0)).abs() < 1e-9);
+        assert_eq!(delta.overall.window_average_execution_time, Duration::from_millis(225));
+
+        let steady = &delta.by_hook_id["steady"];
+        assert_eq!(steady.executions_added, 1);
+        assert_eq!(steady.failures_added, 1);
+        assert!((steady.success_rate_change - (2.0 / 3.0 - 1.0)).abs() < 1e-9);
+        assert!((steady.window_average_execution_time.as_secs_f64() - 0.4).abs() < 1e-6);
+        assert!((steady.average_execution_time_change_ms - 100.0).abs() < 1e-3);
+
+        let new_hook = &delta.by_hook_id["new_hook"];
+        assert_eq!(new_hook.executions_added, 1);
+        assert_eq!(new_hook.success_rate_change, 1.0);
+
+        // A snapshot diffed against itself has no per-hook changes
+        let unchanged = after.diff(&after);
+        assert_eq!(unchanged.overall.executions_added, 0);
+        assert!(unchanged.by_hook_id.is_empty());
+    }
+
+    #[test]
+    fn test_record_skip_is_not_an_execution() {
+        let collector = MetricsCollector::default();
+        collector.record_skip("hook1", SkipReason::ConditionNotMet);
+        collector.record_skip("hook1", SkipReason::ConditionNotMet);
+        collector.record_skip("hook2", SkipReason::CircuitOpen);
+
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.total_executions, 0);
+        assert_eq!(metrics.failed_executions, 0);
+        assert_eq!(metrics.skipped_executions[&SkipReason::ConditionNotMet], 2);
+        assert_eq!(metrics.skipped_executions[&SkipReason::CircuitOpen], 1);
+        assert_eq!(metrics.skipped_by_hook_id["hook1"][&SkipReason::ConditionNotMet], 2);
+        assert!(!metrics.by_hook_id.contains_key("hook1"));
+    }
+
+    #[test]
+    fn test_record_deduplicated() {
+        let collector = MetricsCollector::default();
+        collector.record_deduplicated("notify");
+        collector.record_deduplicated("notify");
+        collector.record_deduplicated("publish");
+
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.deduplicated_sends, 3);
+        assert_eq!(metrics.deduplicated_by_hook_id["notify"], 2);
+        assert_eq!(metrics.deduplicated_by_hook_id["publish"], 1);
+    }
+
+    #[test]
+    fn test_hook_metrics_evict_least_recently_used() {
+        let collector = MetricsCollector::new(MetricsConfig {
+            max_tracked_hooks: 3,
+            ..MetricsConfig::default()
+        });
+        let record = |hook_id: &str, success: bool| {
+            collector.record_execution(
+                hook_id,
+                LifecycleEventType::TaskComplete,
+                Duration::from_millis(10),
+                success,
+                (!success).then(|| "timeout".to_string()),
+                0,
+            );
+        };
+
+        record("hook_0", true);
+        record("hook_1", false);
+        record("hook_2", true);
+        // Touch hook_0 so hook_1 becomes the least recently used
+        record("hook_0", true);
+        record("hook_3", true);
+
+        let metrics = collector.get_metrics().unwrap();
+        assert!(!metrics.by_hook_id.contains_key("hook_1"));
+        assert!(metrics.by_hook_id.contains_key("hook_0"));
+        let other = &metrics.by_hook_id[EVICTED_HOOKS_BUCKET];
+        assert_eq!(other.total_executions, 1);
+        assert_eq!(other.failed_executions, 1);
+
+        for i in 4..50 {
+            record(&format!("hook_{}", i), i % 2 == 0);
+        }
+
+        let metrics = collector.get_metrics().unwrap();
+        assert_eq!(metrics.by_hook_id.len(), 4);
+        assert!(metrics.by_hook_id.contains_key("hook_49"));
+
+        let tracked_total: u64 = metrics.by_hook_id.values().map(|m| m.total_executions).sum();
+        let tracked_successes: u64 = metrics.by_hook_id.values().map(|m| m.successful_executions).sum();
+        assert_eq!(tracked_total, metrics.total_executions);
+        assert_eq!(tracked_successes, metrics.successful_executions);
+        assert_eq!(metrics.total_executions, 51);
+    }
 }
@@ -36,18 +36,23 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,no_run
 //! use codex_core::hooks::{HookManager, LifecycleEvent};
 //!
+//! # async fn example(config: codex_core::config::Config) -> codex_core::hooks::Result<()> {
 //! // Initialize the hook manager with configuration
 //! let hook_manager = HookManager::new(config.hooks).await?;
 //!
 //! // Trigger a lifecycle event
 //! let event = LifecycleEvent::TaskStart {
 //!     task_id: "task_123".to_string(),
+//!     session_id: "session_456".to_string(),
 //!     prompt: "Create a new file".to_string(),
+//!     timestamp: chrono::Utc::now(),
 //! };
 //! hook_manager.trigger_event(event).await?;
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! # Configuration
@@ -65,17 +70,31 @@
 //! command = ["./scripts/log-task-start.sh"]
 //! ```
 
+#![allow(clippy::type_complexity)]
+#![allow(clippy::too_many_arguments)]
+
+pub mod alerting;
+pub mod api;
 pub mod chaining;
+pub mod circuit_breaker;
+pub mod clock;
+pub mod condition;
 pub mod config;
 pub mod context;
+pub mod dashboard;
+pub mod dedup;
 pub mod dependency;
 pub mod executor;
 pub mod executors;
+pub mod health;
 pub mod history;
 pub mod manager;
 pub mod metrics;
+pub mod performance;
 pub mod protocol_integration;
+pub mod proxy;
 pub mod registry;
+pub mod secrets;
 pub mod types;
 
 #[cfg(test)]
@@ -1,13 +1,63 @@
 //! Advanced hook performance metrics collection and analysis.
 
 use std::collections::{HashMap, VecDeque};
-use std::sync::{Arc, RwLock};
-use std::time::{Duration, Instant, SystemTime};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc};
+#[cfg(feature = "system-metrics")]
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::hooks::clock::{Clock, SystemClock};
+use crate::hooks::executor::ExecutionResult;
+use crate::hooks::metrics::{
+    default_max_tracked_hooks, interpolated_percentile, update_success_ewma, DEFAULT_MAX_TRACKED_HOOKS, DEFAULT_SUCCESS_RATE_ALPHA,
+    EVICTED_HOOKS_BUCKET,
+};
+use crate::hooks::types::{ExportFormat, HookError, LifecycleEventType};
+
+/// Number of most recent samples used for the current response time and error rate.
+const RECENT_WINDOW: usize = 100;
+
+/// Shortest interval between background resource samples, whatever `sampling_interval` says.
+const MIN_RESOURCE_SAMPLING_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Number of hourly buckets kept in [`HistoricalData`].
+const MAX_HOURLY_BUCKETS: usize = 48;
+/// Number of daily buckets kept in [`HistoricalData`].
+const MAX_DAILY_BUCKETS: usize = 30;
+/// Number of weekly buckets kept in [`HistoricalData`].
+const MAX_WEEKLY_BUCKETS: usize = 26;
+
+/// Median and population standard deviation of a window of durations, using
+/// Welford's online algorithm for the variance.
+fn median_and_std_dev(durations: &VecDeque<Duration>) -> (Duration, Duration) {
+    if durations.is_empty() {
+        return (Duration::ZERO, Duration::ZERO);
+    }
 
-use crate::hooks::types::{HookError, LifecycleEventType};
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for (i, duration) in durations.iter().enumerate() {
+        let value = duration.as_secs_f64();
+        let delta = value - mean;
+        mean += delta / (i + 1) as f64;
+        m2 += delta * (value - mean);
+    }
+    let std_dev = (m2 / durations.len() as f64).sqrt();
+
+    let mut sorted: Vec<Duration> = durations.iter().copied().collect();
+    sorted.sort();
+    let mid = sorted.len() / 2;
+    let median = if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    };
+
+    (median, Duration::from_secs_f64(std_dev))
+}
 
 /// Advanced performance metrics collector with detailed analytics.
 #[derive(Debug)]
@@ -18,6 +68,205 @@ pub struct PerformanceCollector {
     performance_data: Arc<RwLock<PerformanceData>>,
     /// Historical performance trends.
     historical_data: Arc<RwLock<HistoricalData>>,
+    /// Time source for update times and the throughput window.
+    clock: Arc<dyn Clock>,
+}
+
+/// Whether this build reads resource usage through `sysinfo`: it needs the `system-metrics`
+/// feature and a platform `sysinfo` supports.
+#[cfg(all(test, feature = "system-metrics"))]
+pub(crate) const SYSTEM_METRICS_SUPPORTED: bool = sysinfo::IS_SUPPORTED_SYSTEM;
+#[cfg(all(test, not(feature = "system-metrics")))]
+pub(crate) const SYSTEM_METRICS_SUPPORTED: bool = false;
+
+/// Reads process and system resource usage through `sysinfo`.
+///
+/// Unless [`SYSTEM_METRICS_SUPPORTED`], every reading except open file descriptors stays at
+/// its zero default. Disk usage and network throughput are not sampled.
+#[derive(Debug)]
+struct ResourceSampler {
+    #[cfg(feature = "system-metrics")]
+    system: System,
+    #[cfg(feature = "system-metrics")]
+    pid: Option<Pid>,
+}
+
+impl ResourceSampler {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "system-metrics")]
+            system: System::new(),
+            #[cfg(feature = "system-metrics")]
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+
+    /// Take a reading of open file descriptors only; this build has no `sysinfo`.
+    #[cfg(not(feature = "system-metrics"))]
+    fn sample(&mut self) -> ResourceMetrics {
+        ResourceMetrics {
+            open_file_descriptors: open_file_descriptors(),
+            ..ResourceMetrics::default()
+        }
+    }
+
+    /// Take a reading. CPU usage is measured since the previous reading, so the
+    /// first one reports zero.
+    #[cfg(feature = "system-metrics")]
+    fn sample(&mut self) -> ResourceMetrics {
+        let mut metrics = ResourceMetrics::default();
+        if !sysinfo::IS_SUPPORTED_SYSTEM {
+            return metrics;
+        }
+
+        self.system.refresh_memory();
+        let total_memory = self.system.total_memory();
+        metrics.available_memory = self.system.available_memory();
+        metrics.load_average = System::load_average().one;
+
+        if let Some(pid) = self.pid {
+            self.system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+            if let Some(process) = self.system.process(pid) {
+                metrics.cpu_usage = f64::from(process.cpu_usage());
+                if total_memory > 0 {
+                    metrics.memory_usage = process.memory() as f64 / total_memory as f64 * 100.0;
+                }
+            }
+        }
+        metrics.open_file_descriptors = open_file_descriptors();
+
+        metrics
+    }
+}
+
+/// A collector fed by the shared resource sampling task.
+struct ResourceSubscriber {
+    performance_data: Weak<RwLock<PerformanceData>>,
+    clock: Arc<dyn Clock>,
+    interval: Duration,
+    next_due: Instant,
+}
+
+/// Subscribers of the shared sampler and whether its task is running.
+#[derive(Default)]
+struct SharedSamplerState {
+    subscribers: Vec<ResourceSubscriber>,
+    running: bool,
+}
+
+/// Resource sampling shared by every collector in the process.
+///
+/// Readings describe the whole process, so a single tokio task takes them for all
+/// collectors instead of each one running its own thread. The task starts with the
+/// first subscriber and exits once every subscribed collector has been dropped.
+struct SharedResourceSampler {
+    sampler: Arc<Mutex<ResourceSampler>>,
+    state: Mutex<SharedSamplerState>,
+}
+
+/// Marks the shared sampler as stopped when its task ends, including when the
+/// runtime it was spawned on shuts down.
+struct SamplerTaskGuard(&'static SharedResourceSampler);
+
+impl Drop for SamplerTaskGuard {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.0.state.lock() {
+            state.running = false;
+        }
+    }
+}
+
+impl SharedResourceSampler {
+    /// The process-wide sampler.
+    fn get() -> &'static Self {
+        static SHARED: OnceLock<SharedResourceSampler> = OnceLock::new();
+        SHARED.get_or_init(|| Self {
+            sampler: Arc::new(Mutex::new(ResourceSampler::new())),
+            state: Mutex::new(SharedSamplerState::default()),
+        })
+    }
+
+    /// Feed `subscriber` a reading every `interval`, starting the sampling task if needed.
+    ///
+    /// Outside a tokio runtime nothing is sampled in the background; readings can still
+    /// be taken with [`PerformanceCollector::sample_resource_metrics`].
+    fn subscribe(&'static self, subscriber: ResourceSubscriber) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!("No tokio runtime; resource metrics are only sampled on demand");
+            return;
+        };
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.subscribers.push(subscriber);
+        if !state.running {
+            state.running = true;
+            handle.spawn(self.run());
+        }
+    }
+
+    /// Sample whenever a subscriber is due, until no subscribed collector is left.
+    async fn run(&'static self) {
+        let _guard = SamplerTaskGuard(self);
+        let mut ticker = tokio::time::interval(MIN_RESOURCE_SAMPLING_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            let now = Instant::now();
+            let any_due = {
+                let Ok(mut state) = self.state.lock() else {
+                    return;
+                };
+                state.subscribers.retain(|subscriber| subscriber.performance_data.strong_count() > 0);
+                if state.subscribers.is_empty() {
+                    return;
+                }
+                state.subscribers.iter().any(|subscriber| subscriber.next_due <= now)
+            };
+            if !any_due {
+                continue;
+            }
+
+            // Refreshing sysinfo blocks, so it runs off the async worker threads
+            let sampler = Arc::clone(&self.sampler);
+            let Ok(Some(metrics)) =
+                tokio::task::spawn_blocking(move || sampler.lock().ok().map(|mut sampler| sampler.sample())).await
+            else {
+                continue;
+            };
+
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            for subscriber in state.subscribers.iter_mut().filter(|subscriber| subscriber.next_due <= now) {
+                subscriber.next_due = now + subscriber.interval;
+                let Some(performance_data) = subscriber.performance_data.upgrade() else {
+                    continue;
+                };
+                if let Ok(mut data) = performance_data.write() {
+                    data.resource_metrics = metrics.clone();
+                    data.last_updated = subscriber.clock.now();
+                }
+            }
+        }
+    }
+}
+
+/// Number of file descriptors open in this process.
+#[cfg(target_os = "linux")]
+fn open_file_descriptors() -> u32 {
+    std::fs::read_dir("/proc/self/fd").map(|entries| entries.count() as u32).unwrap_or(0)
+}
+
+/// Number of file descriptors open in this process; not available on this platform.
+#[cfg(not(target_os = "linux"))]
+fn open_file_descriptors() -> u32 {
+    0
 }
 
 /// Configuration for performance metrics collection.
@@ -25,7 +274,8 @@ pub struct PerformanceCollector {
 pub struct PerformanceConfig {
     /// Whether performance collection is enabled.
     pub enabled: bool,
-    /// Sampling interval for metrics collection.
+    /// Minimum spacing between retained samples of the same hook; samples arriving
+    /// sooner still count toward aggregate totals but are not kept.
     pub sampling_interval: Duration,
     /// Maximum number of samples to keep in memory.
     pub max_samples: usize,
@@ -37,6 +287,21 @@ pub struct PerformanceConfig {
     pub collect_concurrency_metrics: bool,
     /// Percentiles to calculate for performance analysis.
     pub percentiles: Vec<f64>,
+    /// Weight of the newest sample in the recent success rate (0.0 to 1.0).
+    #[serde(default = "default_success_rate_alpha")]
+    pub success_rate_alpha: f64,
+    /// Maximum number of hook IDs tracked individually; the least recently executed
+    /// are folded into [`EVICTED_HOOKS_BUCKET`].
+    #[serde(default = "default_max_tracked_hooks")]
+    pub max_tracked_hooks: usize,
+    /// Whether trend analysis compares hooks against the rolled-up baselines instead
+    /// of against their own earlier samples.
+    #[serde(default)]
+    pub compare_to_baseline: bool,
+}
+
+fn default_success_rate_alpha() -> f64 {
+    DEFAULT_SUCCESS_RATE_ALPHA
 }
 
 impl Default for PerformanceConfig {
@@ -49,6 +314,9 @@ impl Default for PerformanceConfig {
             collect_resource_metrics: true,
             collect_concurrency_metrics: true,
             percentiles: vec![50.0, 75.0, 90.0, 95.0, 99.0, 99.9],
+            success_rate_alpha: DEFAULT_SUCCESS_RATE_ALPHA,
+            max_tracked_hooks: DEFAULT_MAX_TRACKED_HOOKS,
+            compare_to_baseline: false,
         }
     }
 }
@@ -70,6 +338,9 @@ pub struct PerformanceData {
     pub concurrency_metrics: ConcurrencyMetrics,
     /// Last update timestamp.
     pub last_updated: DateTime<Utc>,
+    /// Timestamp of the last retained sample per hook.
+    #[serde(skip)]
+    pub last_sampled: HashMap<String, DateTime<Utc>>,
 }
 
 /// Current real-time performance metrics.
@@ -108,6 +379,27 @@ pub struct PerformanceSample {
     pub concurrency_level: u32,
 }
 
+impl PerformanceSample {
+    /// Build a sample from a coordinated execution, carrying over its timing breakdown.
+    pub fn from_execution(
+        hook_id: impl Into<String>,
+        event_type: LifecycleEventType,
+        result: &ExecutionResult,
+        concurrency_level: u32,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            hook_id: hook_id.into(),
+            event_type,
+            duration: result.duration,
+            success: result.result.success,
+            timing_breakdown: result.timing_breakdown.clone(),
+            resource_usage: None,
+            concurrency_level,
+        }
+    }
+}
+
 /// Detailed timing breakdown for hook execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimingBreakdown {
@@ -123,6 +415,13 @@ pub struct TimingBreakdown {
     pub serialization_time: Duration,
 }
 
+impl TimingBreakdown {
+    /// Sum of all phases.
+    pub fn total(&self) -> Duration {
+        self.setup_time + self.execution_time + self.cleanup_time + self.wait_time + self.serialization_time
+    }
+}
+
 /// Resource usage metrics during execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceUsage {
@@ -155,6 +454,66 @@ pub struct HookPerformanceData {
     pub percentiles: HashMap<String, Duration>,
     /// Last execution timestamp.
     pub last_execution: Option<DateTime<Utc>>,
+    /// Most recent execution durations, bounded by `max_samples`.
+    #[serde(skip)]
+    pub recent_durations: VecDeque<Duration>,
+}
+
+impl HookPerformanceData {
+    /// Performance data for a hook that has not executed yet.
+    fn empty(hook_id: &str) -> Self {
+        Self {
+            hook_id: hook_id.to_string(),
+            total_executions: 0,
+            successful_executions: 0,
+            statistics: PerformanceStatistics {
+                mean: Duration::ZERO,
+                median: Duration::ZERO,
+                std_dev: Duration::ZERO,
+                min: Duration::MAX,
+                max: Duration::ZERO,
+                success_rate: 0.0,
+                recent_success_rate: 0.0,
+                throughput: 0.0,
+            },
+            trend: PerformanceTrend {
+                direction: TrendDirection::Unknown,
+                strength: 0.0,
+                change_percentage: 0.0,
+                analysis_period: Duration::from_hours(1),
+            },
+            percentiles: HashMap::new(),
+            last_execution: None,
+            recent_durations: VecDeque::new(),
+        }
+    }
+
+    /// Fold another hook's data into this one, weighting averages by execution count.
+    ///
+    /// Percentiles cannot be combined from summaries and are cleared; duration windows
+    /// are concatenated so median and standard deviation recover on the next sample.
+    fn absorb(&mut self, other: HookPerformanceData) {
+        let total = self.total_executions + other.total_executions;
+        if total > 0 {
+            let weight = |count: u64| count as f64 / total as f64;
+            let stats = &mut self.statistics;
+            stats.mean = Duration::from_secs_f64(
+                stats.mean.as_secs_f64() * weight(self.total_executions)
+                    + other.statistics.mean.as_secs_f64() * weight(other.total_executions),
+            );
+            stats.recent_success_rate = stats.recent_success_rate * weight(self.total_executions)
+                + other.statistics.recent_success_rate * weight(other.total_executions);
+            stats.success_rate = (self.successful_executions + other.successful_executions) as f64 / total as f64;
+        }
+
+        self.statistics.min = self.statistics.min.min(other.statistics.min);
+        self.statistics.max = self.statistics.max.max(other.statistics.max);
+        self.total_executions = total;
+        self.successful_executions += other.successful_executions;
+        self.percentiles.clear();
+        self.last_execution = self.last_execution.max(other.last_execution);
+        self.recent_durations.extend(other.recent_durations);
+    }
 }
 
 /// Performance data for a specific event type.
@@ -170,6 +529,9 @@ pub struct EventPerformanceData {
     pub statistics: PerformanceStatistics,
     /// Event processing trend.
     pub trend: PerformanceTrend,
+    /// Most recent execution durations, bounded by `max_samples`.
+    #[serde(skip)]
+    pub recent_durations: VecDeque<Duration>,
 }
 
 /// Statistical performance metrics.
@@ -187,6 +549,9 @@ pub struct PerformanceStatistics {
     pub max: Duration,
     /// Success rate.
     pub success_rate: f64,
+    /// Exponentially-weighted success rate that tracks recent behavior.
+    #[serde(default)]
+    pub recent_success_rate: f64,
     /// Throughput (executions per second).
     pub throughput: f64,
 }
@@ -218,11 +583,11 @@ pub enum TrendDirection {
 }
 
 /// System resource metrics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResourceMetrics {
-    /// Overall CPU usage percentage.
+    /// CPU usage percentage of this process.
     pub cpu_usage: f64,
-    /// Overall memory usage percentage.
+    /// Resident memory of this process as a percentage of total memory.
     pub memory_usage: f64,
     /// Available memory in bytes.
     pub available_memory: u64,
@@ -277,6 +642,45 @@ pub struct HistoricalData {
     pub weekly_metrics: VecDeque<AggregatedMetrics>,
     /// Performance baselines.
     pub baselines: PerformanceBaselines,
+    /// Timestamp of the newest sample folded into the aggregates.
+    #[serde(default)]
+    pub rolled_up_through: Option<DateTime<Utc>>,
+}
+
+/// Granularity of a historical aggregate bucket.
+#[derive(Debug, Clone, Copy)]
+enum RollUpPeriod {
+    Hour,
+    Day,
+    Week,
+}
+
+impl RollUpPeriod {
+    /// Start and end of the bucket containing `timestamp`; weeks start on Monday.
+    fn bucket(self, timestamp: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let day_start = timestamp.date_naive().and_time(NaiveTime::MIN).and_utc();
+        match self {
+            RollUpPeriod::Hour => {
+                let start = day_start + chrono::Duration::hours(i64::from(timestamp.hour()));
+                (start, start + chrono::Duration::hours(1))
+            }
+            RollUpPeriod::Day => (day_start, day_start + chrono::Duration::days(1)),
+            RollUpPeriod::Week => {
+                let start = day_start
+                    - chrono::Duration::days(i64::from(timestamp.weekday().num_days_from_monday()));
+                (start, start + chrono::Duration::weeks(1))
+            }
+        }
+    }
+
+    /// Number of buckets of this granularity to keep.
+    fn retention(self) -> usize {
+        match self {
+            RollUpPeriod::Hour => MAX_HOURLY_BUCKETS,
+            RollUpPeriod::Day => MAX_DAILY_BUCKETS,
+            RollUpPeriod::Week => MAX_WEEKLY_BUCKETS,
+        }
+    }
 }
 
 /// Aggregated metrics for a time period.
@@ -296,6 +700,109 @@ pub struct AggregatedMetrics {
     pub resource_summary: ResourceSummary,
 }
 
+impl AggregatedMetrics {
+    /// Aggregate the samples that fall into one bucket.
+    fn from_samples(period_start: DateTime<Utc>, period_end: DateTime<Utc>, samples: &[&PerformanceSample]) -> Self {
+        let total = samples.len() as u64;
+        let successful = samples.iter().filter(|s| s.success).count() as u64;
+        let durations: VecDeque<Duration> = samples.iter().map(|s| s.duration).collect();
+        let (median, std_dev) = median_and_std_dev(&durations);
+        let mean = if durations.is_empty() {
+            Duration::ZERO
+        } else {
+            durations.iter().sum::<Duration>() / durations.len() as u32
+        };
+        let success_rate = if total > 0 { successful as f64 / total as f64 } else { 0.0 };
+
+        let usages: Vec<&ResourceUsage> = samples.iter().filter_map(|s| s.resource_usage.as_ref()).collect();
+        let average = |value: fn(&ResourceUsage) -> f64| {
+            if usages.is_empty() {
+                0.0
+            } else {
+                usages.iter().map(|u| value(u)).sum::<f64>() / usages.len() as f64
+            }
+        };
+        let peak = |value: fn(&ResourceUsage) -> f64| usages.iter().map(|u| value(u)).fold(0.0, f64::max);
+
+        Self {
+            period_start,
+            period_end,
+            total_executions: total,
+            successful_executions: successful,
+            statistics: PerformanceStatistics {
+                mean,
+                median,
+                std_dev,
+                min: durations.iter().min().copied().unwrap_or(Duration::ZERO),
+                max: durations.iter().max().copied().unwrap_or(Duration::ZERO),
+                success_rate,
+                recent_success_rate: success_rate,
+                throughput: Self::throughput(total, period_start, period_end),
+            },
+            resource_summary: ResourceSummary {
+                avg_cpu_usage: average(|u| u.cpu_usage),
+                peak_cpu_usage: peak(|u| u.cpu_usage),
+                avg_memory_usage: average(|u| u.memory_usage as f64),
+                peak_memory_usage: peak(|u| u.memory_usage as f64),
+                total_network_io: usages.iter().map(|u| u.network_io).sum(),
+                total_disk_io: usages.iter().map(|u| u.disk_io).sum(),
+            },
+        }
+    }
+
+    /// Fold a later roll-up of the same bucket into this one.
+    ///
+    /// Mean and standard deviation are pooled exactly; the median and resource averages
+    /// are weighted by execution count.
+    fn merge(&mut self, other: AggregatedMetrics) {
+        let total = self.total_executions + other.total_executions;
+        if total == 0 {
+            return;
+        }
+        let (n_a, n_b, n) = (self.total_executions as f64, other.total_executions as f64, total as f64);
+        let weighted = |a: f64, b: f64| (a * n_a + b * n_b) / n;
+
+        let stats = &mut self.statistics;
+        let other_stats = &other.statistics;
+        let (mean_a, mean_b) = (stats.mean.as_secs_f64(), other_stats.mean.as_secs_f64());
+        let delta = mean_b - mean_a;
+        let m2 = stats.std_dev.as_secs_f64().powi(2) * n_a
+            + other_stats.std_dev.as_secs_f64().powi(2) * n_b
+            + delta * delta * n_a * n_b / n;
+
+        stats.mean = Duration::from_secs_f64(weighted(mean_a, mean_b));
+        stats.std_dev = Duration::from_secs_f64((m2 / n).sqrt());
+        stats.median = Duration::from_secs_f64(weighted(stats.median.as_secs_f64(), other_stats.median.as_secs_f64()));
+        if self.total_executions == 0 {
+            stats.min = other_stats.min;
+        } else if other.total_executions > 0 {
+            stats.min = stats.min.min(other_stats.min);
+        }
+        stats.max = stats.max.max(other_stats.max);
+
+        self.total_executions = total;
+        self.successful_executions += other.successful_executions;
+        stats.success_rate = self.successful_executions as f64 / n;
+        stats.recent_success_rate = stats.success_rate;
+        stats.throughput = Self::throughput(total, self.period_start, self.period_end);
+
+        let summary = &mut self.resource_summary;
+        let other_summary = &other.resource_summary;
+        summary.avg_cpu_usage = weighted(summary.avg_cpu_usage, other_summary.avg_cpu_usage);
+        summary.peak_cpu_usage = summary.peak_cpu_usage.max(other_summary.peak_cpu_usage);
+        summary.avg_memory_usage = weighted(summary.avg_memory_usage, other_summary.avg_memory_usage);
+        summary.peak_memory_usage = summary.peak_memory_usage.max(other_summary.peak_memory_usage);
+        summary.total_network_io += other_summary.total_network_io;
+        summary.total_disk_io += other_summary.total_disk_io;
+    }
+
+    /// Executions per second over the whole bucket.
+    fn throughput(total: u64, period_start: DateTime<Utc>, period_end: DateTime<Utc>) -> f64 {
+        let seconds = (period_end - period_start).num_milliseconds() as f64 / 1000.0;
+        if seconds > 0.0 { total as f64 / seconds } else { 0.0 }
+    }
+}
+
 /// Summary of resource usage over a period.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceSummary {
@@ -328,10 +835,27 @@ pub struct PerformanceBaselines {
     pub established_at: DateTime<Utc>,
 }
 
+impl Default for PerformanceCollector {
+    fn default() -> Self {
+        Self::new(PerformanceConfig::default())
+    }
+}
+
 impl PerformanceCollector {
     /// Create a new performance collector.
+    ///
+    /// When resource metrics are enabled and a tokio runtime is running, the shared
+    /// sampling task records process resource usage every `sampling_interval` until
+    /// the collector is dropped.
     pub fn new(config: PerformanceConfig) -> Self {
-        Self {
+        Self::new_with_clock(config, SystemClock::shared())
+    }
+
+    /// Create a new performance collector that reads time from `clock`.
+    pub fn new_with_clock(config: PerformanceConfig, clock: Arc<dyn Clock>) -> Self {
+        let sample_resources = config.enabled && config.collect_resource_metrics;
+        let resource_interval = config.sampling_interval.max(MIN_RESOURCE_SAMPLING_INTERVAL);
+        let collector = Self {
             config,
             performance_data: Arc::new(RwLock::new(PerformanceData {
                 current_metrics: CurrentMetrics {
@@ -344,15 +868,7 @@ impl PerformanceCollector {
                 recent_samples: VecDeque::new(),
                 hook_performance: HashMap::new(),
                 event_performance: HashMap::new(),
-                resource_metrics: ResourceMetrics {
-                    cpu_usage: 0.0,
-                    memory_usage: 0.0,
-                    available_memory: 0,
-                    disk_usage: 0.0,
-                    network_throughput: 0,
-                    open_file_descriptors: 0,
-                    load_average: 0.0,
-                },
+                resource_metrics: ResourceMetrics::default(),
                 concurrency_metrics: ConcurrencyMetrics {
                     current_concurrent: 0,
                     max_concurrent: 0,
@@ -366,7 +882,8 @@ impl PerformanceCollector {
                         throughput: 0.0,
                     },
                 },
-                last_updated: Utc::now(),
+                last_updated: clock.now(),
+                last_sampled: HashMap::new(),
             })),
             historical_data: Arc::new(RwLock::new(HistoricalData {
                 hourly_metrics: VecDeque::new(),
@@ -383,15 +900,33 @@ impl PerformanceCollector {
                         disk_io: 0,
                         file_descriptors: 10,
                     },
-                    established_at: Utc::now(),
+                    established_at: clock.now(),
                 },
+                rolled_up_through: None,
             })),
+            clock,
+        };
+        if sample_resources {
+            SharedResourceSampler::get().subscribe(ResourceSubscriber {
+                performance_data: Arc::downgrade(&collector.performance_data),
+                clock: Arc::clone(&collector.clock),
+                interval: resource_interval,
+                next_due: Instant::now() + resource_interval,
+            });
         }
+        collector
     }
 
-    /// Create a performance collector with default configuration.
-    pub fn default() -> Self {
-        Self::new(PerformanceConfig::default())
+    /// Sample process resource usage now instead of waiting for the next tick.
+    pub fn sample_resource_metrics(&self) -> Result<(), HookError> {
+        if !self.config.collect_resource_metrics {
+            return Ok(());
+        }
+
+        let metrics = SharedResourceSampler::get().sampler.lock()
+            .map_err(|e| HookError::Execution(format!("Failed to sample resource metrics: {}", e)))?
+            .sample();
+        self.update_resource_metrics(metrics)
     }
 
     /// Record a performance sample.
@@ -401,12 +936,14 @@ impl PerformanceCollector {
         }
 
         if let Ok(mut data) = self.performance_data.write() {
-            // Add to recent samples
-            data.recent_samples.push_back(sample.clone());
-            
-            // Maintain size limit
-            if data.recent_samples.len() > self.config.max_samples {
-                data.recent_samples.pop_front();
+            if self.should_retain(&data, &sample) {
+                data.last_sampled.insert(sample.hook_id.clone(), sample.timestamp);
+                data.recent_samples.push_back(sample.clone());
+
+                // Maintain size limit
+                while data.recent_samples.len() > self.config.max_samples {
+                    data.recent_samples.pop_front();
+                }
             }
 
             // Update hook performance data
@@ -418,38 +955,30 @@ impl PerformanceCollector {
             // Update current metrics
             self.update_current_metrics(&mut data);
 
-            data.last_updated = Utc::now();
+            data.last_updated = self.clock.now();
         }
 
         Ok(())
     }
 
+    /// Whether a sample is far enough from the hook's last retained sample to be kept.
+    fn should_retain(&self, data: &PerformanceData, sample: &PerformanceSample) -> bool {
+        let Some(last) = data.last_sampled.get(&sample.hook_id) else {
+            return true;
+        };
+        let elapsed = sample.timestamp.signed_duration_since(*last);
+        elapsed.to_std().is_ok_and(|elapsed| elapsed >= self.config.sampling_interval)
+    }
+
     /// Update hook-specific performance data.
     fn update_hook_performance(&self, data: &mut PerformanceData, sample: &PerformanceSample) {
+        if !data.hook_performance.contains_key(&sample.hook_id) {
+            self.evict_least_recent_hooks(data);
+        }
+        let percentiles = self.calculate_percentiles_for_hook(&sample.hook_id, data);
         let hook_perf = data.hook_performance
             .entry(sample.hook_id.clone())
-            .or_insert_with(|| HookPerformanceData {
-                hook_id: sample.hook_id.clone(),
-                total_executions: 0,
-                successful_executions: 0,
-                statistics: PerformanceStatistics {
-                    mean: Duration::ZERO,
-                    median: Duration::ZERO,
-                    std_dev: Duration::ZERO,
-                    min: Duration::MAX,
-                    max: Duration::ZERO,
-                    success_rate: 0.0,
-                    throughput: 0.0,
-                },
-                trend: PerformanceTrend {
-                    direction: TrendDirection::Unknown,
-                    strength: 0.0,
-                    change_percentage: 0.0,
-                    analysis_period: Duration::from_hours(1),
-                },
-                percentiles: HashMap::new(),
-                last_execution: None,
-            });
+            .or_insert_with(|| HookPerformanceData::empty(&sample.hook_id));
 
         hook_perf.total_executions += 1;
         if sample.success {
@@ -458,10 +987,49 @@ impl PerformanceCollector {
         hook_perf.last_execution = Some(sample.timestamp);
 
         // Update statistics
-        self.update_statistics(&mut hook_perf.statistics, sample, hook_perf.total_executions);
+        self.update_statistics(
+            &mut hook_perf.statistics,
+            &mut hook_perf.recent_durations,
+            sample,
+            hook_perf.total_executions,
+        );
+
+        hook_perf.percentiles = percentiles;
+    }
+
+    /// Make room for one more hook by folding the least recently executed hooks
+    /// into [`EVICTED_HOOKS_BUCKET`].
+    fn evict_least_recent_hooks(&self, data: &mut PerformanceData) {
+        let max_tracked = self.config.max_tracked_hooks.max(1);
+        loop {
+            let tracked = data.hook_performance.len()
+                - usize::from(data.hook_performance.contains_key(EVICTED_HOOKS_BUCKET));
+            if tracked < max_tracked {
+                return;
+            }
 
-        // Calculate percentiles
-        hook_perf.percentiles = self.calculate_percentiles_for_hook(&sample.hook_id, data);
+            let Some(evicted_id) = data
+                .hook_performance
+                .values()
+                .filter(|hook_perf| hook_perf.hook_id != EVICTED_HOOKS_BUCKET)
+                .min_by_key(|hook_perf| hook_perf.last_execution)
+                .map(|hook_perf| hook_perf.hook_id.clone())
+            else {
+                return;
+            };
+            let Some(evicted) = data.hook_performance.remove(&evicted_id) else {
+                return;
+            };
+            data.last_sampled.remove(&evicted_id);
+
+            let bucket = data
+                .hook_performance
+                .entry(EVICTED_HOOKS_BUCKET.to_string())
+                .or_insert_with(|| HookPerformanceData::empty(EVICTED_HOOKS_BUCKET));
+            bucket.absorb(evicted);
+            let excess = bucket.recent_durations.len().saturating_sub(self.config.max_samples.max(1));
+            bucket.recent_durations.drain(..excess);
+        }
     }
 
     /// Update event-specific performance data.
@@ -479,6 +1047,7 @@ impl PerformanceCollector {
                     min: Duration::MAX,
                     max: Duration::ZERO,
                     success_rate: 0.0,
+                    recent_success_rate: 0.0,
                     throughput: 0.0,
                 },
                 trend: PerformanceTrend {
@@ -487,14 +1056,26 @@ impl PerformanceCollector {
                     change_percentage: 0.0,
                     analysis_period: Duration::from_hours(1),
                 },
+                recent_durations: VecDeque::new(),
             });
 
         event_perf.total_events += 1;
-        self.update_statistics(&mut event_perf.statistics, sample, event_perf.total_events);
+        self.update_statistics(
+            &mut event_perf.statistics,
+            &mut event_perf.recent_durations,
+            sample,
+            event_perf.total_events,
+        );
     }
 
     /// Update performance statistics.
-    fn update_statistics(&self, stats: &mut PerformanceStatistics, sample: &PerformanceSample, total_count: u64) {
+    fn update_statistics(
+        &self,
+        stats: &mut PerformanceStatistics,
+        recent_durations: &mut VecDeque<Duration>,
+        sample: &PerformanceSample,
+        total_count: u64,
+    ) {
         // Update min/max
         if sample.duration < stats.min {
             stats.min = sample.duration;
@@ -507,12 +1088,25 @@ impl PerformanceCollector {
         let new_mean_ms = (stats.mean.as_millis() as f64 * (total_count - 1) as f64 + sample.duration.as_millis() as f64) / total_count as f64;
         stats.mean = Duration::from_millis(new_mean_ms as u64);
 
+        // Update median and standard deviation over the bounded window
+        recent_durations.push_back(sample.duration);
+        while recent_durations.len() > self.config.max_samples.max(1) {
+            recent_durations.pop_front();
+        }
+        (stats.median, stats.std_dev) = median_and_std_dev(recent_durations);
+
         // Update success rate
         stats.success_rate = if sample.success {
             (stats.success_rate * (total_count - 1) as f64 + 1.0) / total_count as f64
         } else {
             (stats.success_rate * (total_count - 1) as f64) / total_count as f64
         };
+        stats.recent_success_rate = update_success_ewma(
+            stats.recent_success_rate,
+            sample.success,
+            self.config.success_rate_alpha,
+            total_count == 1,
+        );
     }
 
     /// Update current real-time metrics.
@@ -522,34 +1116,28 @@ impl PerformanceCollector {
         }
 
         // Calculate throughput over last minute
-        let one_minute_ago = Utc::now() - chrono::Duration::minutes(1);
+        let one_minute_ago = self.clock.now() - chrono::Duration::minutes(1);
         let recent_count = data.recent_samples
             .iter()
             .filter(|s| s.timestamp > one_minute_ago)
             .count();
         data.current_metrics.throughput = recent_count as f64 / 60.0;
 
-        // Calculate average response time over recent samples
-        let recent_durations: Vec<Duration> = data.recent_samples
+        // Response time and error rate share the same window of recent samples
+        let window: Vec<&PerformanceSample> = data.recent_samples
             .iter()
             .rev()
-            .take(100) // Last 100 samples
-            .map(|s| s.duration)
+            .take(RECENT_WINDOW)
             .collect();
-
-        if !recent_durations.is_empty() {
-            let total_ms: u64 = recent_durations.iter().map(|d| d.as_millis() as u64).sum();
-            data.current_metrics.average_response_time = Duration::from_millis(total_ms / recent_durations.len() as u64);
+        if window.is_empty() {
+            return;
         }
 
-        // Calculate error rate over recent samples
-        let recent_errors = data.recent_samples
-            .iter()
-            .rev()
-            .take(100)
-            .filter(|s| !s.success)
-            .count();
-        data.current_metrics.error_rate = recent_errors as f64 / 100.0.min(data.recent_samples.len() as f64);
+        let total_ms: u64 = window.iter().map(|s| s.duration.as_millis() as u64).sum();
+        data.current_metrics.average_response_time = Duration::from_millis(total_ms / window.len() as u64);
+
+        let errors = window.iter().filter(|s| !s.success).count();
+        data.current_metrics.error_rate = (errors as f64 / window.len() as f64).clamp(0.0, 1.0);
     }
 
     /// Calculate percentiles for a specific hook.
@@ -565,15 +1153,10 @@ impl PerformanceCollector {
         }
 
         durations.sort();
-        let mut percentiles = HashMap::new();
-
-        for &percentile in &self.config.percentiles {
-            let index = ((percentile / 100.0) * (durations.len() - 1) as f64) as usize;
-            let duration = durations.get(index).copied().unwrap_or(Duration::ZERO);
-            percentiles.insert(format!("p{}", percentile), duration);
-        }
-
-        percentiles
+        self.config.percentiles
+            .iter()
+            .map(|&percentile| (format!("p{}", percentile), interpolated_percentile(&durations, percentile)))
+            .collect()
     }
 
     /// Get current performance data.
@@ -598,7 +1181,7 @@ impl PerformanceCollector {
 
         if let Ok(mut data) = self.performance_data.write() {
             data.resource_metrics = metrics;
-            data.last_updated = Utc::now();
+            data.last_updated = self.clock.now();
         }
 
         Ok(())
@@ -612,19 +1195,101 @@ impl PerformanceCollector {
 
         if let Ok(mut data) = self.performance_data.write() {
             data.concurrency_metrics = metrics;
-            data.last_updated = Utc::now();
+            data.last_updated = self.clock.now();
         }
 
         Ok(())
     }
 
+    /// Get historical aggregates and baselines.
+    pub fn get_historical_data(&self) -> Result<HistoricalData, HookError> {
+        self.historical_data.read()
+            .map(|data| data.clone())
+            .map_err(|e| HookError::Execution(format!("Failed to read historical data: {}", e)))
+    }
+
+    /// Fold samples recorded since the last roll-up into the hourly, daily and weekly
+    /// aggregates, prune old buckets and refresh the baselines from the hourly history.
+    ///
+    /// Intended to be called periodically; calling it again without new samples is a no-op.
+    pub fn roll_up(&self) -> Result<(), HookError> {
+        let watermark = self.get_historical_data()?.rolled_up_through;
+        let samples: Vec<PerformanceSample> = self.performance_data.read()
+            .map_err(|e| HookError::Execution(format!("Failed to read performance data: {}", e)))?
+            .recent_samples
+            .iter()
+            .filter(|s| watermark.is_none_or(|watermark| s.timestamp > watermark))
+            .cloned()
+            .collect();
+        let Some(newest) = samples.iter().map(|s| s.timestamp).max() else {
+            return Ok(());
+        };
+
+        let mut history = self.historical_data.write()
+            .map_err(|e| HookError::Execution(format!("Failed to write historical data: {}", e)))?;
+        for period in [RollUpPeriod::Hour, RollUpPeriod::Day, RollUpPeriod::Week] {
+            let mut buckets: HashMap<DateTime<Utc>, (DateTime<Utc>, Vec<&PerformanceSample>)> = HashMap::new();
+            for sample in &samples {
+                let (start, end) = period.bucket(sample.timestamp);
+                buckets.entry(start).or_insert_with(|| (end, Vec::new())).1.push(sample);
+            }
+
+            let aggregates = match period {
+                RollUpPeriod::Hour => &mut history.hourly_metrics,
+                RollUpPeriod::Day => &mut history.daily_metrics,
+                RollUpPeriod::Week => &mut history.weekly_metrics,
+            };
+            for (start, (end, bucket_samples)) in buckets {
+                let aggregate = AggregatedMetrics::from_samples(start, end, &bucket_samples);
+                match aggregates.iter_mut().find(|existing| existing.period_start == start) {
+                    Some(existing) => existing.merge(aggregate),
+                    None => aggregates.push_back(aggregate),
+                }
+            }
+            aggregates.make_contiguous().sort_by_key(|aggregate| aggregate.period_start);
+            while aggregates.len() > period.retention() {
+                aggregates.pop_front();
+            }
+        }
+
+        let total: u64 = history.hourly_metrics.iter().map(|m| m.total_executions).sum();
+        if total > 0 {
+            let successful: u64 = history.hourly_metrics.iter().map(|m| m.successful_executions).sum();
+            let mean_secs = history.hourly_metrics.iter()
+                .map(|m| m.statistics.mean.as_secs_f64() * m.total_executions as f64)
+                .sum::<f64>() / total as f64;
+            let throughput = history.hourly_metrics.iter()
+                .map(|m| m.statistics.throughput)
+                .sum::<f64>() / history.hourly_metrics.len() as f64;
+
+            let baselines = &mut history.baselines;
+            baselines.baseline_response_time = Duration::from_secs_f64(mean_secs);
+            baselines.baseline_throughput = throughput;
+            baselines.baseline_error_rate = 1.0 - successful as f64 / total as f64;
+            baselines.established_at = self.clock.now();
+        }
+        history.rolled_up_through = Some(newest);
+
+        Ok(())
+    }
+
     /// Analyze performance trends.
+    ///
+    /// With `compare_to_baseline` set and at least one roll-up done, each hook's recent
+    /// mean is compared with the baseline response time; otherwise its older and newer
+    /// samples are compared with each other.
     pub fn analyze_trends(&self) -> Result<HashMap<String, PerformanceTrend>, HookError> {
         let data = self.get_performance_data()?;
+        let baseline = if self.config.compare_to_baseline {
+            let history = self.get_historical_data()?;
+            history.rolled_up_through.map(|_| history.baselines.baseline_response_time)
+        } else {
+            None
+        };
         let mut trends = HashMap::new();
 
-        for (hook_id, hook_perf) in &data.hook_performance {
-            let trend = self.calculate_trend_for_hook(hook_id, &data)?;
+        for hook_id in data.hook_performance.keys() {
+            let trend = self.calculate_trend_for_hook(hook_id, &data, baseline)?;
             trends.insert(hook_id.clone(), trend);
         }
 
@@ -632,7 +1297,12 @@ impl PerformanceCollector {
     }
 
     /// Calculate performance trend for a specific hook.
-    fn calculate_trend_for_hook(&self, hook_id: &str, data: &PerformanceData) -> Result<PerformanceTrend, HookError> {
+    fn calculate_trend_for_hook(
+        &self,
+        hook_id: &str,
+        data: &PerformanceData,
+        baseline: Option<Duration>,
+    ) -> Result<PerformanceTrend, HookError> {
         let recent_samples: Vec<&PerformanceSample> = data.recent_samples
             .iter()
             .filter(|s| s.hook_id == hook_id)
@@ -647,20 +1317,23 @@ impl PerformanceCollector {
             });
         }
 
-        // Split samples into two halves for comparison
-        let mid_point = recent_samples.len() / 2;
-        let first_half = &recent_samples[..mid_point];
-        let second_half = &recent_samples[mid_point..];
-
-        let first_avg = first_half.iter()
-            .map(|s| s.duration.as_millis() as f64)
-            .sum::<f64>() / first_half.len() as f64;
-
-        let second_avg = second_half.iter()
-            .map(|s| s.duration.as_millis() as f64)
-            .sum::<f64>() / second_half.len() as f64;
+        let average_ms = |samples: &[&PerformanceSample]| {
+            samples.iter().map(|s| s.duration.as_secs_f64() * 1000.0).sum::<f64>() / samples.len() as f64
+        };
+        let (reference_avg, current_avg) = match baseline {
+            Some(baseline) => (baseline.as_secs_f64() * 1000.0, average_ms(&recent_samples)),
+            None => {
+                // Split samples into two halves for comparison
+                let mid_point = recent_samples.len() / 2;
+                (average_ms(&recent_samples[..mid_point]), average_ms(&recent_samples[mid_point..]))
+            }
+        };
 
-        let change_percentage = ((second_avg - first_avg) / first_avg) * 100.0;
+        let change_percentage = if reference_avg > 0.0 {
+            ((current_avg - reference_avg) / reference_avg) * 100.0
+        } else {
+            0.0
+        };
         let direction = if change_percentage < -5.0 {
             TrendDirection::Improving
         } else if change_percentage > 5.0 {
@@ -685,11 +1358,18 @@ impl PerformanceCollector {
         serde_json::to_string_pretty(&data)
             .map_err(|e| HookError::Execution(format!("Failed to serialize performance data: {}", e)))
     }
+
+    /// Export performance data in the given format.
+    pub fn export_performance_data_as(&self, format: ExportFormat) -> Result<Vec<u8>, HookError> {
+        format.encode(&self.get_performance_data()?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
+    use crate::hooks::clock::MockClock;
 
     #[test]
     fn test_performance_collector_creation() {
@@ -719,6 +1399,118 @@ mod tests {
         assert!(data.hook_performance.contains_key("test_hook"));
     }
 
+    #[test]
+    fn test_throughput_counts_samples_from_the_last_minute() {
+        let clock = Arc::new(MockClock::new());
+        let config = PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            ..PerformanceConfig::default()
+        };
+        let collector = PerformanceCollector::new_with_clock(config, clock.clone());
+        let sample = |timestamp| PerformanceSample {
+            timestamp,
+            hook_id: "test_hook".to_string(),
+            event_type: LifecycleEventType::SessionStart,
+            duration: Duration::from_millis(100),
+            success: true,
+            timing_breakdown: None,
+            resource_usage: None,
+            concurrency_level: 1,
+        };
+
+        for _ in 0..6 {
+            collector.record_sample(sample(clock.now())).unwrap();
+        }
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.current_metrics.throughput, 0.1);
+        assert_eq!(data.last_updated, clock.now());
+
+        // Two minutes later the earlier samples have left the throughput window
+        clock.advance(Duration::from_secs(120));
+        collector.record_sample(sample(clock.now())).unwrap();
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.current_metrics.throughput, 1.0 / 60.0);
+        assert_eq!(data.last_updated, clock.now());
+    }
+
+    #[test]
+    fn test_resource_sampler_reports_memory_usage() {
+        if !SYSTEM_METRICS_SUPPORTED {
+            return;
+        }
+        let collector = PerformanceCollector::default();
+        let buffer = vec![1u8; 64 * 1024 * 1024];
+
+        collector.sample_resource_metrics().unwrap();
+
+        let metrics = collector.get_performance_data().unwrap().resource_metrics;
+        assert!(buffer.iter().all(|&b| b == 1));
+        assert!(metrics.memory_usage > 0.0, "memory usage was {}", metrics.memory_usage);
+        assert!(metrics.available_memory > 0);
+        if cfg!(target_os = "linux") {
+            assert!(metrics.open_file_descriptors > 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collectors_share_one_sampling_task() {
+        if !SYSTEM_METRICS_SUPPORTED {
+            return;
+        }
+        let first = PerformanceCollector::default();
+        let second = PerformanceCollector::default();
+        {
+            let state = SharedResourceSampler::get().state.lock().unwrap();
+            assert!(state.running);
+            assert!(state.subscribers.len() >= 2);
+        }
+
+        let sampled = |collector: &PerformanceCollector| {
+            collector.get_performance_data().unwrap().resource_metrics.available_memory > 0
+        };
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !(sampled(&first) && sampled(&second)) && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(sampled(&first) && sampled(&second));
+    }
+
+    #[test]
+    fn test_percentiles_interpolate_between_ranks() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            percentiles: vec![0.0, 50.0, 90.0, 99.0, 100.0],
+            ..PerformanceConfig::default()
+        });
+        for ms in [40, 10, 100, 70, 20, 90, 30, 60, 80, 50] {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: Utc::now(),
+                    hook_id: "ranked_hook".to_string(),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(ms),
+                    success: true,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let percentiles = collector.get_hook_performance("ranked_hook").unwrap().unwrap().percentiles;
+        for (key, expected_ms) in [("p0", 10.0), ("p50", 55.0), ("p90", 91.0), ("p99", 99.1), ("p100", 100.0)] {
+            let actual_ms = percentiles[key].as_secs_f64() * 1000.0;
+            assert!((actual_ms - expected_ms).abs() < 1e-6, "{} was {}ms", key, actual_ms);
+        }
+    }
+
+    #[test]
+    fn test_percentiles_of_single_sample() {
+        let single = [Duration::from_millis(42)];
+        assert_eq!(interpolated_percentile(&single, 99.0), Duration::from_millis(42));
+        assert_eq!(interpolated_percentile(&[], 50.0), Duration::ZERO);
+    }
+
     #[test]
     fn test_trend_direction_serialization() {
         let direction = TrendDirection::Improving;
@@ -751,4 +1543,365 @@ mod tests {
         assert_eq!(hook_perf.successful_executions, 5);
         assert_eq!(hook_perf.statistics.success_rate, 0.5);
     }
+
+    #[test]
+    fn test_median_and_std_dev_track_distribution() {
+        let collector = PerformanceCollector::default();
+        // 2, 4, 4, 4, 5, 5, 7, 9 ms: mean 5, population std dev 2, median 4.5.
+        for (i, ms) in [2, 4, 4, 4, 5, 5, 7, 9].into_iter().enumerate() {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: Utc::now() + chrono::Duration::seconds(i as i64 * 2),
+                    hook_id: "known_hook".to_string(),
+                    event_type: LifecycleEventType::SessionStart,
+                    duration: Duration::from_millis(ms),
+                    success: true,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let data = collector.get_performance_data().unwrap();
+        for stats in [
+            &data.hook_performance["known_hook"].statistics,
+            &data.event_performance[&LifecycleEventType::SessionStart].statistics,
+        ] {
+            assert!((stats.median.as_secs_f64() - 0.0045).abs() < 1e-9, "median was {:?}", stats.median);
+            assert!((stats.std_dev.as_secs_f64() - 0.002).abs() < 1e-9, "std_dev was {:?}", stats.std_dev);
+        }
+    }
+
+    #[test]
+    fn test_statistics_window_respects_max_samples() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            max_samples: 50,
+            ..PerformanceConfig::default()
+        });
+        for i in 0..200 {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: Utc::now(),
+                    hook_id: "shifting_hook".to_string(),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(if i < 150 { 1000 } else { 10 }),
+                    success: true,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let hook_perf = collector.get_hook_performance("shifting_hook").unwrap().unwrap();
+        assert_eq!(hook_perf.recent_durations.len(), 50);
+        assert_eq!(hook_perf.statistics.median, Duration::from_millis(10));
+        assert_eq!(hook_perf.statistics.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_recent_success_rate_reacts_to_outage() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            success_rate_alpha: 0.2,
+            ..PerformanceConfig::default()
+        });
+        let sample = |success: bool| PerformanceSample {
+            timestamp: Utc::now(),
+            hook_id: "steady_hook".to_string(),
+            event_type: LifecycleEventType::TaskComplete,
+            duration: Duration::from_millis(5),
+            success,
+            timing_breakdown: None,
+            resource_usage: None,
+            concurrency_level: 1,
+        };
+
+        for _ in 0..1000 {
+            collector.record_sample(sample(true)).unwrap();
+        }
+        for _ in 0..10 {
+            collector.record_sample(sample(false)).unwrap();
+        }
+
+        let stats = collector.get_hook_performance("steady_hook").unwrap().unwrap().statistics;
+        assert!(stats.success_rate > 0.98, "cumulative rate was {}", stats.success_rate);
+        assert!(stats.recent_success_rate < 0.2, "recent rate was {}", stats.recent_success_rate);
+    }
+
+    #[test]
+    fn test_hook_performance_map_is_bounded() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            max_tracked_hooks: 5,
+            ..PerformanceConfig::default()
+        });
+        let start = Utc::now();
+        for i in 0..40 {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: start + chrono::Duration::milliseconds(i),
+                    hook_id: format!("request_{}", i),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(10),
+                    success: i % 4 != 0,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.hook_performance.len(), 6);
+        assert!(data.hook_performance.contains_key("request_39"));
+        assert!(!data.hook_performance.contains_key("request_0"));
+
+        let other = &data.hook_performance[EVICTED_HOOKS_BUCKET];
+        assert_eq!(other.total_executions, 35);
+        let total: u64 = data.hook_performance.values().map(|h| h.total_executions).sum();
+        let successes: u64 = data.hook_performance.values().map(|h| h.successful_executions).sum();
+        assert_eq!(total, 40);
+        assert_eq!(successes, 30);
+        assert_eq!(other.statistics.mean, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_sampling_interval_limits_retained_samples() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::from_millis(100),
+            ..PerformanceConfig::default()
+        });
+        let start = Utc::now();
+        for i in 0..1000 {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: start + chrono::Duration::milliseconds(i),
+                    hook_id: "chatty_hook".to_string(),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(if i % 100 == 0 { 50 } else { 500 }),
+                    success: true,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.recent_samples.len(), 10);
+        assert!(data.recent_samples.iter().all(|s| s.duration == Duration::from_millis(50)));
+
+        let hook_perf = &data.hook_performance["chatty_hook"];
+        assert_eq!(hook_perf.total_executions, 1000);
+        assert_eq!(hook_perf.statistics.max, Duration::from_millis(500));
+        assert_eq!(hook_perf.percentiles["p99"], Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_error_rate_uses_recent_window() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            ..PerformanceConfig::default()
+        });
+        let sample = |success: bool| PerformanceSample {
+            timestamp: Utc::now(),
+            hook_id: "flaky_hook".to_string(),
+            event_type: LifecycleEventType::TaskComplete,
+            duration: Duration::from_millis(10),
+            success,
+            timing_breakdown: None,
+            resource_usage: None,
+            concurrency_level: 1,
+        };
+
+        for i in 0..10 {
+            collector.record_sample(sample(i >= 3)).unwrap();
+        }
+        let data = collector.get_performance_data().unwrap();
+        assert!((data.current_metrics.error_rate - 0.3).abs() < f64::EPSILON);
+
+        // Older failures fall out of the window once enough newer samples arrive.
+        for _ in 0..RECENT_WINDOW {
+            collector.record_sample(sample(true)).unwrap();
+        }
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.current_metrics.error_rate, 0.0);
+    }
+
+    #[test]
+    fn test_max_samples_bounds_retained_samples() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            max_samples: 250,
+            ..PerformanceConfig::default()
+        });
+        for i in 0..1000 {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: Utc::now(),
+                    hook_id: format!("hook_{}", i % 3),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(10),
+                    success: true,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let data = collector.get_performance_data().unwrap();
+        assert_eq!(data.recent_samples.len(), 250);
+        let total: u64 = data.hook_performance.values().map(|h| h.total_executions).sum();
+        assert_eq!(total, 1000);
+    }
+
+    fn sample_at(hook_id: &str, timestamp: &str, millis: u64, success: bool) -> PerformanceSample {
+        PerformanceSample {
+            timestamp: DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc),
+            hook_id: hook_id.to_string(),
+            event_type: LifecycleEventType::TaskComplete,
+            duration: Duration::from_millis(millis),
+            success,
+            timing_breakdown: None,
+            resource_usage: None,
+            concurrency_level: 1,
+        }
+    }
+
+    fn assert_millis(actual: Duration, expected: f64) {
+        assert!((actual.as_secs_f64() * 1000.0 - expected).abs() < 1e-6, "expected {}ms, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn test_roll_up_buckets_by_hour_day_and_week() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            ..PerformanceConfig::default()
+        });
+        // Sunday evening through the first minutes of Monday.
+        for (timestamp, millis, success) in [
+            ("2026-03-01T22:30:00Z", 100, true),
+            ("2026-03-01T22:45:00Z", 300, false),
+            ("2026-03-01T23:10:00Z", 200, true),
+            ("2026-03-02T00:05:00Z", 400, true),
+        ] {
+            collector.record_sample(sample_at("rollup_hook", timestamp, millis, success)).unwrap();
+        }
+
+        collector.roll_up().unwrap();
+        collector.roll_up().unwrap();
+        let history = collector.get_historical_data().unwrap();
+
+        let counts = |metrics: &VecDeque<AggregatedMetrics>| -> Vec<(String, u64)> {
+            metrics.iter().map(|m| (m.period_start.to_rfc3339(), m.total_executions)).collect()
+        };
+        assert_eq!(
+            counts(&history.hourly_metrics),
+            vec![
+                ("2026-03-01T22:00:00+00:00".to_string(), 2),
+                ("2026-03-01T23:00:00+00:00".to_string(), 1),
+                ("2026-03-02T00:00:00+00:00".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            counts(&history.daily_metrics),
+            vec![("2026-03-01T00:00:00+00:00".to_string(), 3), ("2026-03-02T00:00:00+00:00".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&history.weekly_metrics),
+            vec![("2026-02-23T00:00:00+00:00".to_string(), 3), ("2026-03-02T00:00:00+00:00".to_string(), 1)]
+        );
+
+        let first_hour = &history.hourly_metrics[0];
+        assert_eq!(first_hour.successful_executions, 1);
+        assert_eq!(first_hour.statistics.mean, Duration::from_millis(200));
+        assert_eq!(first_hour.period_end.to_rfc3339(), "2026-03-01T23:00:00+00:00");
+        assert_millis(history.baselines.baseline_response_time, 250.0);
+        assert!((history.baselines.baseline_error_rate - 0.25).abs() < 1e-9);
+
+        // A later roll-up merges into the bucket that is still open.
+        collector.record_sample(sample_at("rollup_hook", "2026-03-02T00:40:00Z", 200, true)).unwrap();
+        collector.roll_up().unwrap();
+        let history = collector.get_historical_data().unwrap();
+        let last_hour = history.hourly_metrics.back().unwrap();
+        assert_eq!(history.hourly_metrics.len(), 3);
+        assert_eq!(last_hour.total_executions, 2);
+        assert_millis(last_hour.statistics.mean, 300.0);
+        assert_millis(last_hour.statistics.std_dev, 100.0);
+        assert_eq!(last_hour.statistics.min, Duration::from_millis(200));
+        assert_eq!(history.daily_metrics.back().unwrap().total_executions, 2);
+    }
+
+    #[test]
+    fn test_roll_up_prunes_old_hourly_buckets() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            ..PerformanceConfig::default()
+        });
+        let start = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        for hour in 0..(MAX_HOURLY_BUCKETS as i64 + 12) {
+            let timestamp = (start + chrono::Duration::hours(hour)).to_rfc3339();
+            collector.record_sample(sample_at("hourly_hook", &timestamp, 10, true)).unwrap();
+        }
+
+        collector.roll_up().unwrap();
+        let history = collector.get_historical_data().unwrap();
+        assert_eq!(history.hourly_metrics.len(), MAX_HOURLY_BUCKETS);
+        assert_eq!(history.hourly_metrics[0].period_start, start + chrono::Duration::hours(12));
+        assert_eq!(history.daily_metrics.len(), 3);
+    }
+
+    #[test]
+    fn test_analyze_trends_compares_against_baseline() {
+        let collector = PerformanceCollector::new(PerformanceConfig {
+            sampling_interval: Duration::ZERO,
+            compare_to_baseline: true,
+            ..PerformanceConfig::default()
+        });
+        for minute in 0..20 {
+            let timestamp = format!("2026-03-01T10:{:02}:00Z", minute);
+            collector.record_sample(sample_at("baseline_hook", &timestamp, 100, true)).unwrap();
+        }
+        collector.roll_up().unwrap();
+
+        for minute in 0..10 {
+            let timestamp = format!("2026-03-01T11:{:02}:00Z", minute);
+            collector.record_sample(sample_at("slow_hook", &timestamp, 200, true)).unwrap();
+        }
+
+        let trends = collector.analyze_trends().unwrap();
+        assert_eq!(trends["slow_hook"].direction, TrendDirection::Degrading);
+        assert!((trends["slow_hook"].change_percentage - 100.0).abs() < 1e-6);
+        assert_eq!(trends["baseline_hook"].direction, TrendDirection::Stable);
+    }
+
+    #[test]
+    fn test_export_performance_data_message_pack_round_trip() {
+        let collector = PerformanceCollector::default();
+        for i in 0..20 {
+            collector
+                .record_sample(PerformanceSample {
+                    timestamp: Utc::now(),
+                    hook_id: format!("hook_{}", i % 4),
+                    event_type: LifecycleEventType::TaskComplete,
+                    duration: Duration::from_millis(10 * i),
+                    success: i % 5 != 0,
+                    timing_breakdown: None,
+                    resource_usage: None,
+                    concurrency_level: 1,
+                })
+                .unwrap();
+        }
+
+        let json = collector.export_performance_data_as(ExportFormat::Json).unwrap();
+        let packed = collector.export_performance_data_as(ExportFormat::MessagePack).unwrap();
+        assert!(packed.len() < json.len());
+
+        let decoded: PerformanceData = ExportFormat::MessagePack.decode(&packed).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(collector.get_performance_data().unwrap()).unwrap()
+        );
+    }
 }
@@ -148,22 +148,27 @@ impl MockProtocolEventEmitter {
     }
 
     pub fn get_events(&self) -> Vec<Event> {
-        self.events.lock().unwrap().clone()
+        self.events.lock().map(|events| events.clone()).unwrap_or_default()
     }
 
     pub fn clear_events(&self) {
-        self.events.lock().unwrap().clear();
+        if let Ok(mut events) = self.events.lock() {
+            events.clear();
+        }
     }
 }
 
 impl ProtocolEventEmitter for MockProtocolEventEmitter {
     fn emit_event(&self, event: Event) {
-        self.events.lock().unwrap().push(event);
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
 
     use std::path::PathBuf;
@@ -0,0 +1,122 @@
+//! Outbound HTTP proxy configuration shared by webhook hooks and notification channels.
+
+use std::time::Duration;
+
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+
+use crate::hooks::secrets::{EnvSecretProvider, SecretProvider};
+use crate::hooks::types::HookError;
+
+/// URL schemes accepted for an explicit proxy.
+const SUPPORTED_PROXY_SCHEMES: &[&str] = &["http", "https", "socks5", "socks5h"];
+
+/// How outbound HTTP requests are routed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ProxySettings {
+    /// Honor the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables.
+    #[default]
+    System,
+    /// Connect directly, ignoring proxy environment variables.
+    Disabled,
+    /// Send every request through the given proxy.
+    Custom(ProxyConfig),
+}
+
+/// An explicit HTTP, HTTPS or SOCKS5 proxy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProxyConfig {
+    /// Proxy URL, e.g. `http://proxy.corp:3128` or `socks5://proxy.corp:1080`.
+    pub url: String,
+    /// Username for proxy authentication.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password for proxy authentication, or a secret reference such as `env:PROXY_PASSWORD`.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Hosts that bypass the proxy; falls back to `NO_PROXY` when empty.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+impl ProxySettings {
+    /// Apply these settings to a client builder.
+    pub fn apply(&self, builder: ClientBuilder) -> Result<ClientBuilder, HookError> {
+        match self {
+            ProxySettings::System => Ok(builder),
+            ProxySettings::Disabled => Ok(builder.no_proxy()),
+            ProxySettings::Custom(config) => Ok(builder.proxy(config.to_proxy()?)),
+        }
+    }
+
+    /// Build an HTTP client with these settings.
+    pub fn build_client(&self, timeout: Duration, user_agent: &str) -> Result<Client, HookError> {
+        self.apply(Client::builder().timeout(timeout).user_agent(user_agent))?
+            .build()
+            .map_err(|e| HookError::Configuration(format!("Failed to create HTTP client: {}", e)))
+    }
+}
+
+impl ProxyConfig {
+    /// Build the `reqwest` proxy, resolving the password if it is a secret reference.
+    pub fn to_proxy(&self) -> Result<Proxy, HookError> {
+        let scheme = self.url.split_once("://").map(|(scheme, _)| scheme.to_ascii_lowercase());
+        if !scheme.as_deref().is_some_and(|s| SUPPORTED_PROXY_SCHEMES.contains(&s)) {
+            return Err(HookError::Configuration(format!(
+                "Unsupported proxy URL {}: expected one of {} schemes",
+                self.url,
+                SUPPORTED_PROXY_SCHEMES.join(", ")
+            )));
+        }
+
+        let mut proxy = Proxy::all(&self.url)
+            .map_err(|e| HookError::Configuration(format!("Invalid proxy URL {}: {}", self.url, e)))?;
+
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => {
+                let password = EnvSecretProvider.resolve(password)?;
+                proxy = proxy.basic_auth(username, &password);
+            }
+            (Some(username), None) => proxy = proxy.basic_auth(username, ""),
+            (None, Some(_)) => {
+                return Err(HookError::Configuration("Proxy password set without a username".to_string()));
+            }
+            (None, None) => {}
+        }
+
+        let no_proxy = if self.no_proxy.is_empty() {
+            NoProxy::from_env()
+        } else {
+            NoProxy::from_string(&self.no_proxy.join(","))
+        };
+        Ok(proxy.no_proxy(no_proxy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn proxy(url: &str) -> ProxyConfig {
+        ProxyConfig {
+            url: url.to_string(),
+            username: None,
+            password: None,
+            no_proxy: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_proxy_url_validation() {
+        assert!(proxy("http://proxy.corp:3128").to_proxy().is_ok());
+        assert!(proxy("socks5://proxy.corp:1080").to_proxy().is_ok());
+        assert!(matches!(proxy("ftp://proxy.corp").to_proxy(), Err(HookError::Configuration(_))));
+        assert!(matches!(proxy("proxy.corp:3128").to_proxy(), Err(HookError::Configuration(_))));
+
+        let mut missing_user = proxy("http://proxy.corp:3128");
+        missing_user.password = Some("secret".to_string());
+        assert!(matches!(missing_user.to_proxy(), Err(HookError::Configuration(_))));
+    }
+}
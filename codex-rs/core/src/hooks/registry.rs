@@ -2,14 +2,18 @@
 
 use std::collections::HashMap;
 
-use crate::hooks::config::{HooksConfig, HookConfig};
+use crate::hooks::condition::{event_command, CommandPattern, ConditionEvaluator};
+use crate::hooks::config::{validate_hook_set, HooksConfig, HookConfig, HookWarning};
 use crate::hooks::context::HookContext;
-use crate::hooks::types::{HookError, LifecycleEvent, LifecycleEventType, HookPriority};
+use crate::hooks::executor::SkippedHook;
+use crate::hooks::types::{HookError, LifecycleEvent, LifecycleEventType, HookPriority, SkipReason};
 
 /// Registry for managing hook definitions and event routing.
 pub struct HookRegistry {
     hooks_by_event: HashMap<LifecycleEventType, Vec<HookConfig>>,
     config: HooksConfig,
+    /// Conflicts found across the configured hooks when they were loaded.
+    warnings: Vec<HookWarning>,
 }
 
 impl HookRegistry {
@@ -18,6 +22,7 @@ impl HookRegistry {
         let mut registry = Self {
             hooks_by_event: HashMap::new(),
             config: config.clone(),
+            warnings: Vec::new(),
         };
 
         // Populate hooks from configuration
@@ -46,6 +51,13 @@ impl HookRegistry {
             }
         }
 
+        // Flag conflicts between hooks that are each valid on their own
+        let hooks: Vec<HookConfig> = all_hook_groups.iter().flat_map(|(group, _)| group.iter().cloned()).collect();
+        self.warnings = validate_hook_set(&hooks);
+        for warning in &self.warnings {
+            tracing::warn!("Hook configuration conflict: {}", warning);
+        }
+
         // Sort hooks by priority within each event type
         self.sort_hooks_by_priority();
 
@@ -62,7 +74,7 @@ impl HookRegistry {
         // Add the hook to the appropriate event type
         self.hooks_by_event
             .entry(hook.event)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(hook);
 
         Ok(())
@@ -75,6 +87,11 @@ impl HookRegistry {
         }
     }
 
+    /// Conflicts between configured hooks found at load time.
+    pub fn warnings(&self) -> &[HookWarning] {
+        &self.warnings
+    }
+
     /// Get all hooks for a specific event type, sorted by priority.
     pub fn get_hooks_for_event(&self, event_type: LifecycleEventType) -> Vec<&HookConfig> {
         self.hooks_by_event
@@ -83,24 +100,71 @@ impl HookRegistry {
             .unwrap_or_default()
     }
 
+    /// Find a hook by its ID.
+    pub fn get_hook(&self, hook_id: &str) -> Option<&HookConfig> {
+        self.hooks_by_event
+            .values()
+            .flatten()
+            .find(|hook| hook.get_id() == hook_id)
+    }
+
     /// Get hooks for a specific event type that match the given condition.
     pub fn get_matching_hooks(
         &self,
         event: &LifecycleEvent,
         context: &HookContext,
     ) -> Result<Vec<&HookConfig>, HookError> {
+        self.get_matching_hooks_with_skips(event, context).map(|(matching, _)| matching)
+    }
+
+    /// Get hooks matching an event along with the hooks whose condition excluded them.
+    pub fn get_matching_hooks_with_skips(
+        &self,
+        event: &LifecycleEvent,
+        context: &HookContext,
+    ) -> Result<(Vec<&HookConfig>, Vec<SkippedHook>), HookError> {
         let event_type = event.event_type();
         let all_hooks = self.get_hooks_for_event(event_type);
 
         let mut matching_hooks = Vec::new();
+        let mut skipped = Vec::new();
 
         for hook in all_hooks {
-            if self.evaluate_hook_condition(hook, event, context)? {
-                matching_hooks.push(hook);
+            if let Some(detail) = Self::command_mismatch(hook, event) {
+                skipped.push(SkippedHook::new(hook.get_id(), SkipReason::CommandNotMatched, Some(detail)));
+                continue;
+            }
+
+            match self.evaluate_hook_condition(hook, event, context) {
+                Ok(true) => matching_hooks.push(hook),
+                Ok(false) => {
+                    skipped.push(SkippedHook::new(hook.get_id(), SkipReason::ConditionNotMet, hook.condition.clone()));
+                }
+                // A condition that cannot be parsed never lets its hook run
+                Err(e) => {
+                    tracing::warn!("Skipping hook {} with an invalid condition: {}", hook.get_id(), e);
+                    skipped.push(SkippedHook::new(hook.get_id(), SkipReason::ConditionNotMet, Some(e.to_string())));
+                }
             }
         }
 
-        Ok(matching_hooks)
+        Ok((matching_hooks, skipped))
+    }
+
+    /// Why the event's command fails the hook's `command_pattern`, if it does.
+    ///
+    /// An invalid pattern matches nothing, so its hook never runs.
+    fn command_mismatch(hook: &HookConfig, event: &LifecycleEvent) -> Option<String> {
+        let pattern = hook.command_pattern.as_ref()?;
+        let command = event_command(event).unwrap_or_default();
+        match CommandPattern::parse(pattern) {
+            Ok(compiled) if compiled.matches(&command) => None,
+            Ok(_) => Some(format!("Command '{}' does not match '{}'", command, pattern)),
+            Err(e) => {
+                tracing::warn!("Skipping hook {} with an invalid command pattern: {}", hook.get_id(), e);
+                Some(e.to_string())
+            }
+        }
     }
 
     /// Evaluate whether a hook's condition is met for the given event and context.
@@ -119,150 +183,14 @@ impl HookRegistry {
         self.evaluate_condition_expression(condition, event, context)
     }
 
-    /// Evaluate a condition expression.
-    /// This is a basic implementation - in the future this could be extended
-    /// with a proper expression parser.
+    /// Parse and evaluate a condition expression.
     fn evaluate_condition_expression(
         &self,
         condition: &str,
         event: &LifecycleEvent,
         context: &HookContext,
     ) -> Result<bool, HookError> {
-        // Basic condition evaluation
-        // For now, we support simple conditions like:
-        // - "success == true"
-        // - "exit_code == 0"
-        // - "message.contains('ERROR')"
-        // - "task_id == 'specific_task'"
-
-        let condition = condition.trim();
-
-        // Handle boolean conditions
-        if condition == "true" {
-            return Ok(true);
-        }
-        if condition == "false" {
-            return Ok(false);
-        }
-
-        // Handle equality conditions
-        if let Some((left, right)) = condition.split_once("==") {
-            let left = left.trim();
-            let right = right.trim().trim_matches('"').trim_matches('\'');
-
-            return self.evaluate_equality_condition(left, right, event, context);
-        }
-
-        // Handle contains conditions
-        if condition.contains(".contains(") {
-            return self.evaluate_contains_condition(condition, event, context);
-        }
-
-        // Handle not equals conditions
-        if let Some((left, right)) = condition.split_once("!=") {
-            let left = left.trim();
-            let right = right.trim().trim_matches('"').trim_matches('\'');
-
-            return Ok(!self.evaluate_equality_condition(left, right, event, context)?);
-        }
-
-        // If we can't parse the condition, log a warning and return true
-        tracing::warn!("Unknown condition format: '{}', defaulting to true", condition);
-        Ok(true)
-    }
-
-    /// Evaluate an equality condition.
-    fn evaluate_equality_condition(
-        &self,
-        left: &str,
-        right: &str,
-        event: &LifecycleEvent,
-        context: &HookContext,
-    ) -> Result<bool, HookError> {
-        let left_value = self.get_condition_value(left, event, context)?;
-        Ok(left_value == right)
-    }
-
-    /// Evaluate a contains condition.
-    fn evaluate_contains_condition(
-        &self,
-        condition: &str,
-        event: &LifecycleEvent,
-        context: &HookContext,
-    ) -> Result<bool, HookError> {
-        // Parse "field.contains('value')" format
-        if let Some(start) = condition.find(".contains(") {
-            let field = condition[..start].trim();
-            let rest = &condition[start + 10..]; // Skip ".contains("
-
-            if let Some(end) = rest.find(')') {
-                let value = rest[..end].trim().trim_matches('"').trim_matches('\'');
-                let field_value = self.get_condition_value(field, event, context)?;
-                return Ok(field_value.contains(value));
-            }
-        }
-
-        Err(HookError::Registry(format!(
-            "Invalid contains condition format: '{}'",
-            condition
-        )))
-    }
-
-    /// Get the value of a field for condition evaluation.
-    fn get_condition_value(
-        &self,
-        field: &str,
-        event: &LifecycleEvent,
-        context: &HookContext,
-    ) -> Result<String, HookError> {
-        match field {
-            "success" => match event {
-                LifecycleEvent::TaskComplete { success, .. }
-                | LifecycleEvent::PatchAfter { success, .. }
-                | LifecycleEvent::McpToolAfter { success, .. } => Ok(success.to_string()),
-                LifecycleEvent::ExecAfter { exit_code, .. } => Ok((exit_code == &0).to_string()),
-                _ => Ok("false".to_string()),
-            },
-            "exit_code" => match event {
-                LifecycleEvent::ExecAfter { exit_code, .. } => Ok(exit_code.to_string()),
-                _ => Ok("0".to_string()),
-            },
-            "message" => match event {
-                LifecycleEvent::AgentMessage { message, .. } => Ok(message.clone()),
-                _ => Ok(String::new()),
-            },
-            "task_id" => Ok(event.task_id().unwrap_or("").to_string()),
-            "session_id" => match event {
-                LifecycleEvent::SessionStart { session_id, .. }
-                | LifecycleEvent::SessionEnd { session_id, .. }
-                | LifecycleEvent::TaskStart { session_id, .. }
-                | LifecycleEvent::TaskComplete { session_id, .. } => Ok(session_id.clone()),
-                _ => Ok(String::new()),
-            },
-            "model" => match event {
-                LifecycleEvent::SessionStart { model, .. } => Ok(model.clone()),
-                _ => Ok(String::new()),
-            },
-            "server" => match event {
-                LifecycleEvent::McpToolBefore { server, .. }
-                | LifecycleEvent::McpToolAfter { server, .. } => Ok(server.clone()),
-                _ => Ok(String::new()),
-            },
-            "tool" => match event {
-                LifecycleEvent::McpToolBefore { tool, .. }
-                | LifecycleEvent::McpToolAfter { tool, .. } => Ok(tool.clone()),
-                _ => Ok(String::new()),
-            },
-            // Check environment variables
-            field if field.starts_with("env.") => {
-                let env_var = &field[4..]; // Remove "env." prefix
-                Ok(context.get_env(env_var).cloned().unwrap_or_default())
-            },
-            _ => {
-                tracing::warn!("Unknown condition field: '{}'", field);
-                Ok(String::new())
-            }
-        }
+        Ok(ConditionEvaluator::parse(condition)?.evaluate(event, context))
     }
 
     /// Register a new hook at runtime.
@@ -367,6 +295,7 @@ pub struct HookRegistryStatistics {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
     use crate::hooks::config::{GlobalHooksConfig, HooksConfig};
     use crate::hooks::types::{HookType, HookExecutionMode};
@@ -375,12 +304,14 @@ mod tests {
 
     fn create_test_hook(event: LifecycleEventType, priority: HookPriority) -> HookConfig {
         HookConfig {
+            id: None,
             event,
             hook_type: HookType::Script {
                 command: vec!["echo".to_string(), "test".to_string()],
                 cwd: None,
                 environment: HashMap::new(),
                 timeout: None,
+                resource_limits: None,
             },
             mode: HookExecutionMode::Async,
             priority,
@@ -389,6 +320,12 @@ mod tests {
             required: false,
             tags: Vec::new(),
             description: None,
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            timeout: None,
+            fallback: None,
+            command_pattern: None,
         }
     }
 
@@ -1,19 +1,19 @@
 //! Comprehensive unit tests for the hooks system.
 
+#![allow(clippy::unwrap_used)]
+
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::time::Duration;
 
 use tempfile::TempDir;
-use tokio::time::sleep;
 
 use crate::hooks::config::{HookConfig, HooksConfig, GlobalHooksConfig};
-use crate::hooks::context::{HookContext, HookExecutionContext};
-use crate::hooks::executor::{HookExecutor, HookExecutorResult, ExecutionResult, HookExecutionResults};
+use crate::hooks::context::HookContext;
+use crate::hooks::executor::{AggregatedResults, ExecutionConfig, ExecutionContext, ExecutionResult, HookExecutor};
 use crate::hooks::manager::HookManager;
 use crate::hooks::types::{
-    HookError, HookResult, HookType, LifecycleEvent, HookExecutionMode, HookPriority,
+    HookError, HookResult, HookType, LifecycleEvent, LifecycleEventType, HookExecutionMode, HookPriority,
 };
 
 /// Helper function to create a test configuration.
@@ -31,6 +31,9 @@ fn create_test_config() -> HooksConfig {
             agent: Vec::new(),
             error: Vec::new(),
             integration: Vec::new(),
+            proxy: Default::default(),
+            message_queue: Default::default(),
+            payload_dedup_window_seconds: None,
         },
     }
 }
@@ -44,20 +47,28 @@ fn create_test_config_with_hooks() -> HooksConfig {
             parallel_execution: true,
             session: vec![
                 HookConfig {
-                    event: "session.start".to_string(),
+                    id: None,
+                    event: LifecycleEventType::SessionStart,
                     hook_type: HookType::Script {
                         command: vec!["echo".to_string(), "session started".to_string()],
                         cwd: None,
                         environment: HashMap::new(),
                         timeout: Some(Duration::from_secs(5)),
+                        resource_limits: None,
                     },
                     description: Some("Test session start hook".to_string()),
-                    enabled: true,
                     required: false,
                     timeout: Some(Duration::from_secs(10)),
                     mode: HookExecutionMode::Async,
                     priority: HookPriority::NORMAL,
-                    conditions: HashMap::new(),
+                    condition: None,
+                    command_pattern: None,
+                    blocking: false,
+                    tags: Vec::new(),
+                    depends_on: Vec::new(),
+                    parallel: true,
+                    max_retries: 0,
+                    fallback: None,
                 },
             ],
             task: Vec::new(),
@@ -67,6 +78,9 @@ fn create_test_config_with_hooks() -> HooksConfig {
             agent: Vec::new(),
             error: Vec::new(),
             integration: Vec::new(),
+            proxy: Default::default(),
+            message_queue: Default::default(),
+            payload_dedup_window_seconds: None,
         },
     }
 }
@@ -75,7 +89,9 @@ fn create_test_config_with_hooks() -> HooksConfig {
 fn create_test_event() -> LifecycleEvent {
     LifecycleEvent::SessionStart {
         session_id: "test_session_123".to_string(),
-        user_id: Some("test_user".to_string()),
+        model: "test-model".to_string(),
+        cwd: PathBuf::from("/tmp"),
+        timestamp: chrono::Utc::now(),
     }
 }
 
@@ -84,32 +100,13 @@ fn create_test_context() -> HookContext {
     let event = create_test_event();
     let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp"));
     
-    HookContext::new(
-        event,
-        working_dir,
-        HookType::Script {
-            command: vec!["echo".to_string(), "test".to_string()],
-            cwd: None,
-            environment: HashMap::new(),
-            timeout: Some(Duration::from_secs(5)),
-        },
-        HookConfig {
-            event: "session.start".to_string(),
-            hook_type: HookType::Script {
-                command: vec!["echo".to_string(), "test".to_string()],
-                cwd: None,
-                environment: HashMap::new(),
-                timeout: Some(Duration::from_secs(5)),
-            },
-            description: Some("Test hook".to_string()),
-            enabled: true,
-            required: false,
-            timeout: Some(Duration::from_secs(10)),
-            mode: HookExecutionMode::Async,
-            priority: HookPriority::NORMAL,
-            conditions: HashMap::new(),
-        },
-    )
+    HookContext::new(event, working_dir).with_hook_type(HookType::Script {
+        command: vec!["echo".to_string(), "test".to_string()],
+        cwd: None,
+        environment: HashMap::new(),
+        timeout: Some(Duration::from_secs(5)),
+        resource_limits: None,
+    })
 }
 
 mod manager_tests {
@@ -121,7 +118,15 @@ mod manager_tests {
         let manager = HookManager::new(config).await.unwrap();
 
         assert!(manager.is_enabled());
-        assert_eq!(manager.executors.len(), 4); // script, webhook, mcp_tool, executable
+        let executable = HookType::Executable {
+            path: PathBuf::from("/bin/true"),
+            args: Vec::new(),
+            cwd: None,
+            environment: HashMap::new(),
+            timeout: None,
+            resource_limits: None,
+        };
+        assert_eq!(manager.get_executor_for_hook(&executable).unwrap().executor_type(), "executable");
     }
 
     #[tokio::test]
@@ -135,7 +140,7 @@ mod manager_tests {
         ).await.unwrap();
 
         assert!(manager.is_enabled());
-        assert_eq!(manager.working_directory, temp_dir.path());
+        assert_eq!(manager.working_directory(), temp_dir.path());
     }
 
     #[tokio::test]
@@ -181,6 +186,7 @@ mod manager_tests {
             cwd: None,
             environment: HashMap::new(),
             timeout: Some(Duration::from_secs(5)),
+            resource_limits: None,
         };
         
         let executor = manager.get_executor_for_hook(&hook_type);
@@ -199,6 +205,12 @@ mod manager_tests {
             headers: HashMap::new(),
             timeout: Some(Duration::from_secs(10)),
             retry_count: Some(3),
+            payload_mode: crate::hooks::types::WebhookPayloadMode::Raw,
+            success_criteria: None,
+            signing: None,
+            success_when: None,
+            capture: None,
+            body: None,
         };
         
         let executor = manager.get_executor_for_hook(&hook_type);
@@ -247,6 +259,7 @@ mod executor_tests {
             cwd: None,
             environment: HashMap::new(),
             timeout: Some(Duration::from_secs(5)),
+            resource_limits: None,
         };
         
         let result = executor.execute(&context).await;
@@ -269,6 +282,7 @@ mod executor_tests {
             cwd: None,
             environment: HashMap::new(),
             timeout: Some(Duration::from_secs(5)),
+            resource_limits: None,
         };
         
         let result = executor.execute(&context).await;
@@ -290,6 +304,12 @@ mod executor_tests {
             headers: HashMap::new(),
             timeout: Some(Duration::from_secs(10)),
             retry_count: Some(3),
+            payload_mode: crate::hooks::types::WebhookPayloadMode::Raw,
+            success_criteria: None,
+            signing: None,
+            success_when: None,
+            capture: None,
+            body: None,
         };
         
         assert!(executor.can_execute(&context));
@@ -325,7 +345,7 @@ mod executor_tests {
         
         context.hook_type = HookType::McpTool {
             server: "test_server".to_string(),
-            tool: "test_tool".to_string(),
+            tool: "log_event".to_string(),
             timeout: Some(Duration::from_secs(15)),
         };
         
@@ -353,17 +373,24 @@ mod timeout_and_error_tests {
             cwd: None,
             environment: HashMap::new(),
             timeout: Some(Duration::from_secs(1)), // But timeout after 1 second
+            resource_limits: None,
+        };
+        
+        // The timeout is enforced by the execution layer around the executor
+        let config = ExecutionConfig {
+            timeout: Duration::from_secs(1),
+            max_retries: 0,
+            ..ExecutionConfig::default()
         };
+        let exec_context = ExecutionContext::new(context, config);
         
         let start = std::time::Instant::now();
-        let result = executor.execute(&context).await;
+        let result = executor.execute_with_context(&exec_context).await;
         let duration = start.elapsed();
         
-        assert!(result.is_ok());
-        let hook_result = result.unwrap();
-        
         // Should fail due to timeout
-        assert!(!hook_result.success);
+        assert!(!result.result.success);
+        assert!(result.error_details.unwrap().contains("timed out"));
         // Should not take much longer than the timeout
         assert!(duration < Duration::from_secs(3));
     }
@@ -410,7 +437,6 @@ mod timeout_and_error_tests {
 
 mod execution_results_tests {
     use super::*;
-    use crate::hooks::config::HookConfig;
 
     fn create_test_execution_result(success: bool, required: bool) -> ExecutionResult {
         ExecutionResult {
@@ -420,26 +446,17 @@ mod execution_results_tests {
             } else {
                 HookResult::failure("failure".to_string(), Duration::from_millis(100))
             },
-            config: HookConfig {
-                event: "test.event".to_string(),
-                hook_type: HookType::Script {
-                    command: vec!["echo".to_string(), "test".to_string()],
-                    cwd: None,
-                    environment: HashMap::new(),
-                    timeout: Some(Duration::from_secs(5)),
-                },
-                description: Some("Test hook".to_string()),
-                enabled: true,
+            config: ExecutionConfig {
                 required,
-                timeout: Some(Duration::from_secs(10)),
-                mode: HookExecutionMode::Async,
-                priority: HookPriority::NORMAL,
-                conditions: HashMap::new(),
+                timeout: Duration::from_secs(10),
+                ..ExecutionConfig::default()
             },
             duration: Duration::from_millis(100),
             retry_attempts: 0,
             cancelled: false,
+            cancel_reason: None,
             error_details: if success { None } else { Some("failure".to_string()) },
+            timing_breakdown: None,
         }
     }
 
@@ -450,7 +467,7 @@ mod execution_results_tests {
             create_test_execution_result(true, true),
         ];
         
-        let execution_results = HookExecutionResults::new(results);
+        let execution_results = AggregatedResults::from_results(results);
         
         assert_eq!(execution_results.successful.len(), 2);
         assert_eq!(execution_results.failed.len(), 0);
@@ -467,7 +484,7 @@ mod execution_results_tests {
             create_test_execution_result(false, true), // Critical failure
         ];
         
-        let execution_results = HookExecutionResults::new(results);
+        let execution_results = AggregatedResults::from_results(results);
         
         assert_eq!(execution_results.successful.len(), 1);
         assert_eq!(execution_results.failed.len(), 2);
@@ -483,7 +500,7 @@ mod execution_results_tests {
             create_test_execution_result(false, false),
         ];
         
-        let execution_results = HookExecutionResults::new(results);
+        let execution_results = AggregatedResults::from_results(results);
         let summary = execution_results.summary();
         
         assert!(summary.contains("Executed 2 hooks"));
@@ -505,7 +522,9 @@ mod integration_tests {
         
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session_123".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
         
         let result = manager.trigger_event(event).await;
@@ -521,27 +540,37 @@ mod integration_tests {
         env.insert("TEST_VAR".to_string(), "test_value".to_string());
         
         config.hooks.session.push(HookConfig {
-            event: "session.start".to_string(),
+            id: None,
+            event: LifecycleEventType::SessionStart,
             hook_type: HookType::Script {
                 command: vec!["echo".to_string(), "$TEST_VAR".to_string()],
                 cwd: None,
                 environment: env,
                 timeout: Some(Duration::from_secs(5)),
+                resource_limits: None,
             },
             description: Some("Test environment hook".to_string()),
-            enabled: true,
             required: false,
             timeout: Some(Duration::from_secs(10)),
             mode: HookExecutionMode::Async,
             priority: HookPriority::NORMAL,
-            conditions: HashMap::new(),
+            condition: None,
+            command_pattern: None,
+            blocking: false,
+            tags: Vec::new(),
+            depends_on: Vec::new(),
+            parallel: true,
+            max_retries: 0,
+            fallback: None,
         });
         
         let manager = HookManager::new(config).await.unwrap();
         
         let event = LifecycleEvent::SessionStart {
             session_id: "test_session_123".to_string(),
-            user_id: Some("test_user".to_string()),
+            model: "test-model".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            timestamp: chrono::Utc::now(),
         };
         
         let result = manager.trigger_event(event).await;
@@ -193,6 +193,119 @@ impl LifecycleEvent {
         }
     }
 
+    /// Placeholder event of the given type, used to test-fire hooks.
+    pub fn synthetic(event_type: LifecycleEventType) -> Self {
+        let timestamp = chrono::Utc::now();
+        let session_id = "test-session".to_string();
+        let task_id = "test-task".to_string();
+        let call_id = "test-call".to_string();
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp"));
+
+        match event_type {
+            LifecycleEventType::SessionStart => LifecycleEvent::SessionStart {
+                session_id,
+                model: "test-model".to_string(),
+                cwd,
+                timestamp,
+            },
+            LifecycleEventType::SessionEnd => LifecycleEvent::SessionEnd {
+                session_id,
+                duration: Duration::ZERO,
+                timestamp,
+            },
+            LifecycleEventType::TaskStart => LifecycleEvent::TaskStart {
+                task_id,
+                session_id,
+                prompt: "Test prompt".to_string(),
+                timestamp,
+            },
+            LifecycleEventType::TaskComplete => LifecycleEvent::TaskComplete {
+                task_id,
+                session_id,
+                success: true,
+                output: None,
+                duration: Duration::ZERO,
+                timestamp,
+            },
+            LifecycleEventType::ExecBefore => LifecycleEvent::ExecBefore {
+                call_id,
+                task_id,
+                command: vec!["true".to_string()],
+                cwd,
+                timestamp,
+            },
+            LifecycleEventType::ExecAfter => LifecycleEvent::ExecAfter {
+                call_id,
+                task_id,
+                command: vec!["true".to_string()],
+                exit_code: 0,
+                stdout: String::new(),
+                stderr: String::new(),
+                duration: Duration::ZERO,
+                timestamp,
+            },
+            LifecycleEventType::PatchBefore => LifecycleEvent::PatchBefore {
+                call_id,
+                task_id,
+                changes: HashMap::new(),
+                timestamp,
+            },
+            LifecycleEventType::PatchAfter => LifecycleEvent::PatchAfter {
+                call_id,
+                task_id,
+                success: true,
+                applied_files: Vec::new(),
+                duration: Duration::ZERO,
+                timestamp,
+            },
+            LifecycleEventType::McpToolBefore => LifecycleEvent::McpToolBefore {
+                call_id,
+                task_id,
+                server: "test-server".to_string(),
+                tool: "test-tool".to_string(),
+                arguments: None,
+                timestamp,
+            },
+            LifecycleEventType::McpToolAfter => LifecycleEvent::McpToolAfter {
+                call_id,
+                task_id,
+                server: "test-server".to_string(),
+                tool: "test-tool".to_string(),
+                success: true,
+                result: None,
+                duration: Duration::ZERO,
+                timestamp,
+            },
+            LifecycleEventType::AgentMessage => LifecycleEvent::AgentMessage {
+                task_id,
+                message: "Test message".to_string(),
+                reasoning: None,
+                timestamp,
+            },
+            LifecycleEventType::ErrorOccurred => LifecycleEvent::ErrorOccurred {
+                task_id: Some(task_id),
+                error: "Test error".to_string(),
+                context: ErrorContext {
+                    component: "test".to_string(),
+                    operation: None,
+                    details: HashMap::new(),
+                },
+                timestamp,
+            },
+        }
+    }
+
+    /// Get the session ID associated with this event, if any.
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            LifecycleEvent::SessionStart { session_id, .. }
+            | LifecycleEvent::SessionEnd { session_id, .. }
+            | LifecycleEvent::TaskStart { session_id, .. }
+            | LifecycleEvent::TaskComplete { session_id, .. } => Some(session_id),
+            _ => None,
+        }
+    }
+
     /// Get the timestamp for this event.
     pub fn timestamp(&self) -> chrono::DateTime<chrono::Utc> {
         match self {
@@ -222,6 +335,9 @@ pub enum HookType {
         cwd: Option<PathBuf>,
         environment: HashMap<String, String>,
         timeout: Option<Duration>,
+        /// Memory and CPU limits for the spawned process.
+        #[serde(default)]
+        resource_limits: Option<ResourceLimits>,
     },
     /// Send an HTTP request to a webhook URL.
     Webhook {
@@ -230,6 +346,27 @@ pub enum HookType {
         headers: HashMap<String, String>,
         timeout: Option<Duration>,
         retry_count: Option<u32>,
+        /// Shape of the request body sent to the webhook.
+        #[serde(default)]
+        payload_mode: WebhookPayloadMode,
+        /// Rules deciding success from the response instead of the HTTP status alone.
+        #[serde(default)]
+        success_criteria: Option<WebhookSuccessCriteria>,
+        /// HMAC signing of the request body, so receivers can verify the sender.
+        #[serde(default)]
+        signing: Option<WebhookSigning>,
+        /// Expression over the JSON response that must hold for success, e.g. `$.ok == true`.
+        ///
+        /// A JSONPath (`$.a.b[0]` subset), optionally compared with a JSON literal using
+        /// `==`, `!=`, `>`, `>=`, `<` or `<=`; a bare path must be non-null and not `false`.
+        #[serde(default)]
+        success_when: Option<String>,
+        /// JSONPath of a response value to store in the result metadata for downstream hooks.
+        #[serde(default)]
+        capture: Option<String>,
+        /// JSON body sent instead of the generated payload; its strings may use `${...}` templates.
+        #[serde(default)]
+        body: Option<serde_json::Value>,
     },
     /// Call an MCP tool.
     McpTool {
@@ -244,6 +381,9 @@ pub enum HookType {
         cwd: Option<PathBuf>,
         environment: HashMap<String, String>,
         timeout: Option<Duration>,
+        /// Memory and CPU limits for the spawned process.
+        #[serde(default)]
+        resource_limits: Option<ResourceLimits>,
     },
     /// Execute a database operation.
     Database {
@@ -261,6 +401,18 @@ pub enum HookType {
         timeout: Option<Duration>,
         routing_key: Option<String>,
         headers: HashMap<String, String>,
+        /// Deduplication key that makes retried sends safe.
+        ///
+        /// `${...}` templates are rendered per event; a key without templates applies to
+        /// retries of a single event.
+        #[serde(default)]
+        idempotency_key: Option<String>,
+        /// How hook metadata is attached to the message.
+        #[serde(default)]
+        payload_mode: MessagePayloadMode,
+        /// SASL credentials and TLS settings for authenticated brokers.
+        #[serde(default)]
+        credentials: Option<QueueCredentials>,
     },
     /// Perform file system operations.
     FileSystem {
@@ -270,6 +422,12 @@ pub enum HookType {
         content: Option<String>,
         timeout: Option<Duration>,
         permissions: Option<u32>,
+        /// For `Watch`, stop once this many change events were observed.
+        #[serde(default)]
+        max_events: Option<usize>,
+        /// For `Write`, replace the file via a temp file and rename so readers never see a partial file.
+        #[serde(default)]
+        atomic: bool,
     },
     /// Execute a custom plugin.
     CustomPlugin {
@@ -277,6 +435,25 @@ pub enum HookType {
         plugin_config: HashMap<String, serde_json::Value>,
         timeout: Option<Duration>,
         plugin_path: Option<PathBuf>,
+        /// Wire format for the plugin's stdin/stdout.
+        #[serde(default)]
+        plugin_protocol: PluginProtocol,
+        /// Memory and CPU limits for the spawned process.
+        #[serde(default)]
+        resource_limits: Option<ResourceLimits>,
+    },
+    /// Compute structured statistics for the patch applied by a `PatchAfter` event.
+    PatchAnalysis {
+        timeout: Option<Duration>,
+    },
+    /// Show an OS desktop notification with a templated title and body.
+    DesktopNotification {
+        title: String,
+        body: String,
+        #[serde(default)]
+        urgency: NotificationUrgency,
+        #[serde(default)]
+        timeout: Option<Duration>,
     },
 }
 
@@ -291,6 +468,50 @@ pub enum HttpMethod {
     Delete,
 }
 
+/// Request body format for webhook hooks.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookPayloadMode {
+    /// Legacy ad-hoc payload with event fields and hook environment.
+    #[default]
+    Raw,
+    /// Versioned envelope with an event-specific `data` schema.
+    Structured,
+}
+
+/// Response-based success rules for webhook hooks; every configured rule must hold.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WebhookSuccessCriteria {
+    /// HTTP status codes treated as success (any 2xx when empty).
+    #[serde(default)]
+    pub status_codes: Vec<u16>,
+    /// Regex the response body must match.
+    #[serde(default)]
+    pub body_regex: Option<String>,
+    /// JSONPath (`$.a.b[0]` subset) that must resolve in the JSON response body.
+    #[serde(default)]
+    pub json_path: Option<String>,
+    /// Value the `json_path` result must equal; any non-null, non-false value passes when unset.
+    #[serde(default)]
+    pub json_equals: Option<serde_json::Value>,
+}
+
+/// HMAC-SHA256 signing of webhook requests.
+///
+/// The signature covers `<timestamp>.<body>`, so receivers can reject replayed requests by
+/// checking the timestamp header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookSigning {
+    /// Secret reference such as `env:CODEX_WEBHOOK_SECRET`, resolved by the executor's secret provider.
+    pub secret: String,
+    /// Header carrying the `sha256=<hex>` signature (`X-Codex-Signature` when unset).
+    #[serde(default)]
+    pub signature_header: Option<String>,
+    /// Header carrying the signed Unix timestamp in seconds (`X-Codex-Timestamp` when unset).
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+}
+
 /// Supported database types for database hooks.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -308,10 +529,11 @@ pub enum DatabaseType {
 }
 
 /// Supported message queue types.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum MessageQueueType {
     /// RabbitMQ message queue.
+    #[serde(rename = "rabbit_mq")]
     RabbitMQ,
     /// Apache Kafka.
     Kafka,
@@ -325,6 +547,72 @@ pub enum MessageQueueType {
     AzureServiceBus,
 }
 
+/// SASL mechanisms for authenticating to a message queue broker.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SaslMechanism {
+    /// Username and password sent in the clear (use with TLS).
+    #[serde(rename = "PLAIN")]
+    Plain,
+    /// Salted challenge-response with SHA-256.
+    #[serde(rename = "SCRAM-SHA-256")]
+    ScramSha256,
+    /// Salted challenge-response with SHA-512.
+    #[serde(rename = "SCRAM-SHA-512")]
+    ScramSha512,
+}
+
+impl SaslMechanism {
+    /// Mechanism name as used by broker clients.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SaslMechanism::Plain => "PLAIN",
+            SaslMechanism::ScramSha256 => "SCRAM-SHA-256",
+            SaslMechanism::ScramSha512 => "SCRAM-SHA-512",
+        }
+    }
+}
+
+/// Credentials for an authenticated message queue broker.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueueCredentials {
+    /// SASL mechanism used to authenticate.
+    pub mechanism: SaslMechanism,
+    /// SASL username.
+    pub username: Option<String>,
+    /// SASL password or a secret reference such as `env:KAFKA_PASSWORD`.
+    pub password: Option<String>,
+    /// TLS settings; the connection is unencrypted when unset.
+    #[serde(default)]
+    pub tls: Option<QueueTlsConfig>,
+}
+
+/// TLS settings for a message queue connection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct QueueTlsConfig {
+    /// CA certificate used to verify the broker.
+    pub ca_cert_path: Option<PathBuf>,
+    /// Client certificate for mutual TLS; a PKCS#12 bundle with its key for RabbitMQ.
+    pub client_cert_path: Option<PathBuf>,
+    /// Client private key for mutual TLS (Kafka only).
+    pub client_key_path: Option<PathBuf>,
+    /// Skip broker certificate verification (Kafka only).
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+}
+
+/// How message queue hooks attach hook metadata to the outgoing message.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessagePayloadMode {
+    /// Merge `_hook_metadata` into JSON objects and send any other message unchanged.
+    #[default]
+    Passthrough,
+    /// Wrap every message as `{"payload": ..., "_hook_metadata": ...}`.
+    AlwaysWrap,
+    /// Merge `_hook_metadata` into JSON objects and fail for any other message.
+    JsonMergeOrFail,
+}
+
 /// File system operations for file system hooks.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -350,10 +638,11 @@ pub enum FileSystemOperation {
 }
 
 /// Hook execution modes.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum HookExecutionMode {
     /// Execute the hook asynchronously without blocking.
+    #[default]
     Async,
     /// Execute the hook synchronously and wait for completion.
     Blocking,
@@ -361,9 +650,73 @@ pub enum HookExecutionMode {
     FireAndForget,
 }
 
-impl Default for HookExecutionMode {
-    fn default() -> Self {
-        HookExecutionMode::Async
+/// Why a hook registered for an event did not run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The hooks system is disabled.
+    Disabled,
+    /// The hook's condition evaluated to false.
+    ConditionNotMet,
+    /// The event's command did not match the hook's `command_pattern`.
+    CommandNotMatched,
+    /// No executor is registered for the hook type, or the executor declined the hook.
+    NoMatchingExecutor,
+    /// The hook's circuit breaker is open.
+    CircuitOpen,
+    /// The event's time budget ran out before the hook started.
+    BudgetExhausted,
+    /// A hook listed in `depends_on` failed or did not run.
+    DependencyFailed,
+    /// The hook's `depends_on` forms a cycle with other hooks for the event.
+    DependencyCycle,
+    /// The hook depends on a hook whose execution mode runs after it or is never awaited.
+    DependencyModeMismatch,
+    /// A required blocking hook for the event failed first.
+    RequiredHookFailed,
+}
+
+impl SkipReason {
+    /// Stable name used in logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Disabled => "disabled",
+            SkipReason::ConditionNotMet => "condition_not_met",
+            SkipReason::CommandNotMatched => "command_not_matched",
+            SkipReason::NoMatchingExecutor => "no_matching_executor",
+            SkipReason::CircuitOpen => "circuit_open",
+            SkipReason::BudgetExhausted => "budget_exhausted",
+            SkipReason::DependencyFailed => "dependency_failed",
+            SkipReason::DependencyCycle => "dependency_cycle",
+            SkipReason::DependencyModeMismatch => "dependency_mode_mismatch",
+            SkipReason::RequiredHookFailed => "required_hook_failed",
+        }
+    }
+}
+
+/// Why a running hook execution was cancelled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelReason {
+    /// A user asked for the execution to stop.
+    User,
+    /// Executions were drained because the hooks system is shutting down.
+    Shutdown,
+    /// The event's time budget ran out while the hook was running.
+    BudgetExhausted,
+    /// The execution was cancelled by ID through the coordinator.
+    ById,
+}
+
+impl CancelReason {
+    /// Stable name used in logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CancelReason::User => "user",
+            CancelReason::Shutdown => "shutdown",
+            CancelReason::BudgetExhausted => "budget_exhausted",
+            CancelReason::ById => "by_id",
+        }
     }
 }
 
@@ -439,6 +792,137 @@ pub enum HookError {
 
     #[error("MCP error: {0}")]
     Mcp(String),
+
+    #[error("Hook panicked: {0}")]
+    Panic(String),
+}
+
+/// Serialization format for exported history, metrics and dashboard data.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// MessagePack with named fields; compact and self-describing.
+    MessagePack,
+}
+
+impl ExportFormat {
+    /// Serialize a value in this format.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, HookError> {
+        match self {
+            ExportFormat::Json => serde_json::to_vec_pretty(value).map_err(HookError::from),
+            ExportFormat::MessagePack => rmp_serde::to_vec_named(value)
+                .map_err(|e| HookError::Execution(format!("Failed to encode MessagePack: {}", e))),
+        }
+    }
+
+    /// Deserialize a value previously produced by [`ExportFormat::encode`].
+    pub fn decode<T: serde::de::DeserializeOwned>(self, bytes: &[u8]) -> Result<T, HookError> {
+        match self {
+            ExportFormat::Json => serde_json::from_slice(bytes).map_err(HookError::from),
+            ExportFormat::MessagePack => rmp_serde::from_slice(bytes)
+                .map_err(|e| HookError::Execution(format!("Failed to decode MessagePack: {}", e))),
+        }
+    }
+}
+
+/// Urgency of a desktop notification.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationUrgency {
+    /// Informational; may be shown quietly.
+    Low,
+    /// Regular notification.
+    #[default]
+    Normal,
+    /// Stays on screen until dismissed where the platform supports it.
+    Critical,
+}
+
+/// Wire format used to exchange data with custom plugins over stdin/stdout.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginProtocol {
+    /// A JSON document in each direction.
+    #[default]
+    JsonStdio,
+    /// A single MessagePack frame in each direction.
+    MsgPackStdio,
+    /// A single CBOR frame in each direction.
+    CborStdio,
+}
+
+impl PluginProtocol {
+    /// Stable name passed to plugins in `CODEX_PLUGIN_PROTOCOL`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluginProtocol::JsonStdio => "json",
+            PluginProtocol::MsgPackStdio => "msgpack",
+            PluginProtocol::CborStdio => "cbor",
+        }
+    }
+
+    /// Encode a frame to send to a plugin.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, HookError> {
+        match self {
+            PluginProtocol::JsonStdio => serde_json::to_vec_pretty(value).map_err(HookError::from),
+            PluginProtocol::MsgPackStdio => rmp_serde::to_vec_named(value)
+                .map_err(|e| HookError::Execution(format!("Failed to encode MessagePack: {}", e))),
+            PluginProtocol::CborStdio => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(value, &mut buf)
+                    .map_err(|e| HookError::Execution(format!("Failed to encode CBOR: {}", e)))?;
+                Ok(buf)
+            }
+        }
+    }
+
+    /// Decode a frame written by a plugin.
+    pub fn decode<T: serde::de::DeserializeOwned>(self, bytes: &[u8]) -> Result<T, HookError> {
+        match self {
+            PluginProtocol::JsonStdio => serde_json::from_slice(bytes).map_err(HookError::from),
+            PluginProtocol::MsgPackStdio => rmp_serde::from_slice(bytes)
+                .map_err(|e| HookError::Execution(format!("Failed to decode MessagePack: {}", e))),
+            PluginProtocol::CborStdio => ciborium::from_reader(bytes)
+                .map_err(|e| HookError::Execution(format!("Failed to decode CBOR: {}", e))),
+        }
+    }
+}
+
+/// Limits on the resources a spawned hook process may use.
+///
+/// Enforced on Linux; other platforms rely on the hook timeout alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum address space, in bytes.
+    #[serde(default)]
+    pub max_memory_bytes: Option<u64>,
+    /// Maximum CPU time, in seconds.
+    #[serde(default)]
+    pub max_cpu_seconds: Option<u64>,
+    /// Existing cgroup v2 directory the process is moved into before it starts.
+    #[serde(default)]
+    pub cgroup: Option<PathBuf>,
+}
+
+impl ResourceLimits {
+    /// Whether no limit is set.
+    pub fn is_empty(&self) -> bool {
+        self.max_memory_bytes.is_none() && self.max_cpu_seconds.is_none() && self.cgroup.is_none()
+    }
+
+    /// Reject limits no process could run under.
+    pub fn validate(&self) -> Result<(), HookError> {
+        if self.max_memory_bytes == Some(0) {
+            return Err(HookError::Configuration("Memory limit must be greater than zero".to_string()));
+        }
+        if self.max_cpu_seconds == Some(0) {
+            return Err(HookError::Configuration("CPU time limit must be greater than zero".to_string()));
+        }
+        Ok(())
+    }
 }
 
 /// Hook execution priority.
@@ -466,6 +950,7 @@ impl HookPriority {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
 
     #[test]
@@ -491,6 +976,35 @@ mod tests {
         assert_eq!(result.error, Some("error".to_string()));
     }
 
+    #[test]
+    fn test_desktop_notification_serialization() {
+        let hook_type: HookType = serde_json::from_value(serde_json::json!({
+            "type": "desktop_notification",
+            "title": "Task {task_id} finished",
+            "body": "{output}",
+        }))
+        .unwrap();
+        assert_eq!(
+            hook_type,
+            HookType::DesktopNotification {
+                title: "Task {task_id} finished".to_string(),
+                body: "{output}".to_string(),
+                urgency: NotificationUrgency::Normal,
+                timeout: None,
+            }
+        );
+
+        let critical = HookType::DesktopNotification {
+            title: "Build failed".to_string(),
+            body: String::new(),
+            urgency: NotificationUrgency::Critical,
+            timeout: Some(Duration::from_secs(2)),
+        };
+        let value = serde_json::to_value(&critical).unwrap();
+        assert_eq!(value["urgency"], "critical");
+        assert_eq!(serde_json::from_value::<HookType>(value).unwrap(), critical);
+    }
+
     #[test]
     fn test_hook_priority_ordering() {
         assert!(HookPriority::HIGHEST < HookPriority::HIGH);